@@ -0,0 +1,280 @@
+//! Relative cost baselines for the hot paths of a move.
+//!
+//! Run with `cargo bench --features bench-baseline`. Each benchmark runs over
+//! 1M pseudo-random boards (or seeds) and is timed against a reference loop of
+//! plain integer mixing measured in the same run, so the ratio does not depend
+//! on the machine. The run fails if a ratio grows more than `max_regression`
+//! above the value in `benches/baselines.toml`.
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use game2048::{
+    hash_seed, BoardStatistics, Direction, Game, Moves, SpawnDistribution, BATCH_MOVES_MULTIPLIER,
+    DEFAULT_MAX_MOVES_PER_CALL,
+};
+
+/// The contract's move processor (it lives in the contract binary, which benches cannot link)
+#[allow(dead_code, unused_imports)]
+#[path = "../src/contract_domain/game_logic.rs"]
+mod game_logic;
+
+use game_logic::{GameMoveProcessor, GameMoveResult};
+
+const ITERATIONS: u64 = 1_000_000;
+/// Each benchmark keeps its fastest of this many runs, to damp scheduling noise
+const RUNS: usize = 5;
+const BASELINES: &str = include_str!("baselines.toml");
+
+/// xorshift64, so every run measures the same boards
+fn xorshift(state: &mut u64) -> u64 {
+    *state ^= *state << 13;
+    *state ^= *state >> 7;
+    *state ^= *state << 17;
+    *state
+}
+
+fn boards() -> Vec<u64> {
+    let mut state = 0x2048_2048_2048_2048u64;
+    (0..ITERATIONS)
+        // Keep tiles at 2^11 or below, as in a real game
+        .map(|_| xorshift(&mut state) & 0xBBBB_BBBB_BBBB_BBBB)
+        .collect()
+}
+
+/// Boards per `BatchMakeMoves` call in the batch benchmark
+const BATCH_BOARDS: usize = 8;
+/// Moves per board in each MakeMoves or batch entry
+const MOVES_PER_BOARD: u64 = 4;
+
+/// MakeMoves payloads (JSON arrays of direction, timestamp and beat), one per board id
+fn move_payloads() -> Vec<(String, String)> {
+    (0..64u64)
+        .map(|seed| {
+            let moves: Vec<(Direction, String, u32)> = (0..MOVES_PER_BOARD)
+                .map(|index| {
+                    let direction = Moves::DIRECTIONS[((seed + index) % 4) as usize];
+                    (direction, (1_000_000 + index * 100_000).to_string(), 0)
+                })
+                .collect();
+            (
+                format!("board-{}", seed),
+                serde_json::to_string(&moves).unwrap(),
+            )
+        })
+        .collect()
+}
+
+/// One board of a batch: its id, board and parsed moves
+type BatchEntry<'a> = (&'a str, u64, Vec<(Direction, u64, u32)>);
+
+/// Parse one board's moves the way the MakeMoves handler does
+fn parse_moves(moves: &str) -> Vec<(Direction, u64, u32)> {
+    let moves: Vec<(Direction, String, u32)> = serde_json::from_str(moves).unwrap();
+    moves
+        .into_iter()
+        .map(|(direction, timestamp, beat)| (direction, timestamp.parse().unwrap(), beat))
+        .collect()
+}
+
+fn process_moves(
+    board_id: &str,
+    moves: &[(Direction, u64, u32)],
+    board: u64,
+    spawns: &SpawnDistribution,
+) -> GameMoveResult {
+    GameMoveProcessor::process_moves(
+        board_id, "player", moves, board, 0, None, None, 1.0, spawns, false, 0,
+    )
+}
+
+/// Seconds for `ITERATIONS` calls of `operation` (fastest of `RUNS`)
+fn measure(mut operation: impl FnMut(u64)) -> f64 {
+    (0..RUNS)
+        .map(|_| {
+            let start = Instant::now();
+            for i in 0..ITERATIONS {
+                operation(i);
+            }
+            start.elapsed().as_secs_f64()
+        })
+        .fold(f64::INFINITY, f64::min)
+}
+
+/// `key = value` from baselines.toml (the file is flat enough not to need a TOML parser)
+fn baseline(key: &str) -> f64 {
+    BASELINES
+        .lines()
+        .filter_map(|line| line.split_once('='))
+        .find(|(name, _)| name.trim() == key)
+        .and_then(|(_, value)| value.trim().parse().ok())
+        .unwrap_or_else(|| panic!("Missing baseline for {}", key))
+}
+
+fn main() {
+    let boards = boards();
+    let payloads = move_payloads();
+    let spawns = SpawnDistribution::default();
+
+    // Reference: 16 rounds of xorshift per board, no game code involved
+    let reference = measure(|i| {
+        let mut state = black_box(boards[i as usize]) | 1;
+        for _ in 0..16 {
+            xorshift(&mut state);
+        }
+        black_box(state);
+    });
+
+    let results = [
+        (
+            "apply_left",
+            measure(|i| {
+                black_box(Moves::apply_direction(
+                    black_box(boards[i as usize]),
+                    Direction::Left,
+                ));
+            }),
+        ),
+        (
+            "evaluate",
+            measure(|i| {
+                let board = black_box(boards[i as usize]);
+                black_box((
+                    BoardStatistics::monotonicity(board),
+                    BoardStatistics::smoothness(board),
+                    BoardStatistics::merge_potential(board),
+                    BoardStatistics::empty_count(board),
+                ));
+            }),
+        ),
+        (
+            "move_left",
+            measure(|i| {
+                black_box(Game::move_left(black_box(boards[i as usize])));
+            }),
+        ),
+        (
+            "score",
+            measure(|i| {
+                black_box(Game::score(black_box(boards[i as usize])));
+            }),
+        ),
+        (
+            "count_empty",
+            measure(|i| {
+                black_box(Game::count_empty(black_box(boards[i as usize])));
+            }),
+        ),
+        // apply_all_directions: compare with apply_direction_x4 (the same work, one call per direction)
+        (
+            "apply_all_directions",
+            measure(|i| {
+                black_box(Moves::apply_all_directions(black_box(boards[i as usize])));
+            }),
+        ),
+        (
+            "apply_direction_x4",
+            measure(|i| {
+                let board = black_box(boards[i as usize]);
+                let score = Game::score(board);
+                black_box(Moves::DIRECTIONS.map(|direction| {
+                    let new_board = Moves::apply_direction(board, direction);
+                    (new_board, Game::score(new_board) - score)
+                }));
+            }),
+        ),
+        // has_any_valid_move: compare with all_four_directions (deadlock check without short-circuit)
+        (
+            "has_any_valid_move",
+            measure(|i| {
+                black_box(Moves::has_any_valid_move(black_box(boards[i as usize])));
+            }),
+        ),
+        (
+            "all_four_directions",
+            measure(|i| {
+                let board = black_box(boards[i as usize]);
+                black_box(
+                    Moves::DIRECTIONS
+                        .map(|direction| Moves::apply_direction(board, direction) != board),
+                );
+            }),
+        ),
+        (
+            "hash_seed",
+            measure(|i| {
+                black_box(hash_seed(black_box("board"), black_box("player"), i));
+            }),
+        ),
+        // process_moves_single: one MakeMoves call per board; compare with process_moves_batch
+        // (the same boards, BATCH_BOARDS per BatchMakeMoves call)
+        (
+            "process_moves_single",
+            measure(|i| {
+                let (board_id, moves) = &payloads[i as usize % payloads.len()];
+                let moves = parse_moves(black_box(moves));
+                black_box(process_moves(
+                    board_id,
+                    &moves,
+                    black_box(boards[i as usize]),
+                    &spawns,
+                ));
+            }),
+        ),
+        (
+            "process_moves_batch",
+            measure(|i| {
+                if i as usize % BATCH_BOARDS != 0 {
+                    return;
+                }
+                let batch: Vec<BatchEntry> = (0..BATCH_BOARDS)
+                    .map(|offset| {
+                        let index = i as usize + offset;
+                        let (board_id, moves) = &payloads[index % payloads.len()];
+                        (
+                            board_id.as_str(),
+                            boards[index],
+                            parse_moves(black_box(moves)),
+                        )
+                    })
+                    .collect();
+                let total_moves: usize = batch.iter().map(|(_, _, moves)| moves.len()).sum();
+                assert!(
+                    total_moves
+                        <= DEFAULT_MAX_MOVES_PER_CALL as usize * BATCH_MOVES_MULTIPLIER as usize
+                );
+                for (board_id, board, moves) in &batch {
+                    black_box(process_moves(board_id, moves, black_box(*board), &spawns));
+                }
+            }),
+        ),
+    ];
+
+    println!(
+        "{:<20} {:>8.2} ns/op (reference)",
+        "reference",
+        reference * 1e9 / ITERATIONS as f64
+    );
+    let max_regression = baseline("max_regression");
+    let mut regressions = Vec::new();
+    for (name, seconds) in results {
+        let ratio = seconds / reference;
+        let expected = baseline(name);
+        let change = ratio / expected - 1.0;
+        println!(
+            "{:<20} {:>8.2} x reference (baseline {:>8.2}, {:+.1}%)",
+            name,
+            ratio,
+            expected,
+            change * 100.0
+        );
+        if change > max_regression {
+            regressions.push(name);
+        }
+    }
+
+    if !regressions.is_empty() {
+        eprintln!("Performance regression in: {}", regressions.join(", "));
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,363 @@
+use async_graphql::{InputObject, SimpleObject};
+use serde::{Deserialize, Serialize};
+
+use crate::{Direction, Game, SpawnDistribution};
+
+/// Chance that a spawned tile is a `4` (see `Game::tile`)
+pub const FOUR_TILE_PROBABILITY: f64 = 0.1;
+/// Spawn ratios further than this many standard deviations from `FOUR_TILE_PROBABILITY` are abnormal
+pub const SPAWN_RATIO_MAX_DEVIATIONS: f64 = 3.0;
+/// Fewer spawns than this are too few for the normal approximation
+const MIN_SPAWN_SAMPLE: u32 = 30;
+/// Most moves accepted in one MakeMoves call when the tournament does not set a limit
+pub const DEFAULT_MAX_MOVES_PER_CALL: u16 = 100;
+/// Shortest gap between consecutive moves of a call when the tournament does not set one (50 ms)
+pub const DEFAULT_MIN_MOVE_INTERVAL_MICROS: u64 = 50_000;
+/// Shortest gap between two new boards of a player in one tournament when it does not set one
+pub const DEFAULT_NEW_BOARD_COOLDOWN_SECONDS: u64 = 60;
+
+/// Per-tournament anti-cheat thresholds
+#[derive(Debug, Deserialize, Serialize, InputObject, Clone, PartialEq)]
+#[graphql(input_name = "AntiCheatConfigInput")]
+pub struct AntiCheatConfig {
+    pub max_moves_per_second: f64,
+    /// Shannon entropy of the direction distribution in bits (0.0 - 2.0)
+    pub min_move_entropy: f64,
+    pub max_score_per_move: u64,
+    pub replay_verification_enabled: bool,
+    /// Moves that did not change the board / all submitted moves
+    pub null_move_max_ratio: f64,
+    pub signature_verification_enabled: bool,
+}
+
+impl Default for AntiCheatConfig {
+    fn default() -> Self {
+        Self {
+            max_moves_per_second: 15.0,
+            min_move_entropy: 0.5,
+            max_score_per_move: 131_072,
+            replay_verification_enabled: true,
+            null_move_max_ratio: 0.5,
+            signature_verification_enabled: false,
+        }
+    }
+}
+
+/// Recorded move used as anti-cheat input
+#[derive(Debug, Clone)]
+pub struct MoveSample {
+    pub direction: Direction,
+    pub timestamp: u64, // milliseconds
+    pub board_after: u64,
+    pub score_after: u64,
+}
+
+/// Result of a single anti-cheat check.
+/// `margin` is the distance to the threshold (negative = failed by that much).
+#[derive(Debug, Clone, SimpleObject)]
+pub struct AntiCheatCheck {
+    pub check: String,
+    pub passed: bool,
+    pub skipped: bool, // Disabled or not enough data
+    pub value: f64,
+    pub threshold: f64,
+    pub margin: f64,
+}
+
+impl AntiCheatCheck {
+    fn at_most(check: &str, value: f64, threshold: f64) -> Self {
+        Self {
+            check: check.to_string(),
+            passed: value <= threshold,
+            skipped: false,
+            value,
+            threshold,
+            margin: threshold - value,
+        }
+    }
+
+    fn at_least(check: &str, value: f64, threshold: f64) -> Self {
+        Self {
+            check: check.to_string(),
+            passed: value >= threshold,
+            skipped: false,
+            value,
+            threshold,
+            margin: value - threshold,
+        }
+    }
+
+    fn not_run(check: &str) -> Self {
+        Self {
+            check: check.to_string(),
+            passed: true,
+            skipped: true,
+            value: 0.0,
+            threshold: 0.0,
+            margin: 0.0,
+        }
+    }
+}
+
+/// Anti-cheat checks over a board's recorded moves
+pub struct AntiCheat;
+
+impl AntiCheat {
+    /// Run every check against `config`
+    pub fn analyze(
+        config: &AntiCheatConfig,
+        board_id: &str,
+        username: &str,
+        moves: &[MoveSample],
+        null_moves: u32,
+        spawns: &SpawnDistribution,
+    ) -> Vec<AntiCheatCheck> {
+        vec![
+            Self::check_move_rate(config, moves),
+            Self::check_move_entropy(config, moves),
+            Self::check_score_per_move(config, moves),
+            Self::check_null_moves(config, moves.len(), null_moves),
+            Self::check_replay(config, board_id, username, moves, spawns),
+            // The expected ratio only holds for classic spawns
+            if spawns.is_classic() {
+                Self::check_spawn_ratio(moves)
+            } else {
+                AntiCheatCheck::not_run("spawn_ratio")
+            },
+            // Move signatures are not recorded yet, so this check cannot run
+            AntiCheatCheck::not_run("signature_verification"),
+        ]
+    }
+
+    /// First failed check of a MakeMoves batch (None = the batch passes). The checks that
+    /// need the whole game (replay, spawn ratio) only run in `analyze`.
+    pub fn failed_batch_check(
+        config: &AntiCheatConfig,
+        moves: &[MoveSample],
+        null_moves: u32,
+    ) -> Option<AntiCheatCheck> {
+        [
+            Self::check_move_rate(config, moves),
+            Self::check_move_entropy(config, moves),
+            Self::check_score_per_move(config, moves),
+            Self::check_null_moves(config, moves.len(), null_moves),
+        ]
+        .into_iter()
+        .find(|check| !check.passed)
+    }
+
+    pub fn check_move_rate(config: &AntiCheatConfig, moves: &[MoveSample]) -> AntiCheatCheck {
+        let (Some(first), Some(last)) = (moves.first(), moves.last()) else {
+            return AntiCheatCheck::not_run("move_rate");
+        };
+        let elapsed_ms = last.timestamp.saturating_sub(first.timestamp);
+        if moves.len() < 2 || elapsed_ms == 0 {
+            return AntiCheatCheck::not_run("move_rate");
+        }
+        let rate = (moves.len() - 1) as f64 * 1000.0 / elapsed_ms as f64;
+        AntiCheatCheck::at_most("move_rate", rate, config.max_moves_per_second)
+    }
+
+    pub fn check_move_entropy(config: &AntiCheatConfig, moves: &[MoveSample]) -> AntiCheatCheck {
+        // Too few moves to say anything about the distribution
+        if moves.len() < 20 {
+            return AntiCheatCheck::not_run("move_entropy");
+        }
+        let mut counts = [0usize; 4];
+        for sample in moves {
+            counts[sample.direction as usize] += 1;
+        }
+        let total = moves.len() as f64;
+        let entropy = counts
+            .iter()
+            .filter(|count| **count > 0)
+            .map(|count| {
+                let p = *count as f64 / total;
+                -p * p.log2()
+            })
+            .sum();
+        AntiCheatCheck::at_least("move_entropy", entropy, config.min_move_entropy)
+    }
+
+    pub fn check_score_per_move(config: &AntiCheatConfig, moves: &[MoveSample]) -> AntiCheatCheck {
+        if moves.len() < 2 {
+            return AntiCheatCheck::not_run("score_per_move");
+        }
+        let max_gain = moves
+            .windows(2)
+            .map(|pair| pair[1].score_after.saturating_sub(pair[0].score_after))
+            .max()
+            .unwrap_or(0);
+        AntiCheatCheck::at_most(
+            "score_per_move",
+            max_gain as f64,
+            config.max_score_per_move as f64,
+        )
+    }
+
+    pub fn check_null_moves(
+        config: &AntiCheatConfig,
+        effective_moves: usize,
+        null_moves: u32,
+    ) -> AntiCheatCheck {
+        let total = effective_moves + null_moves as usize;
+        if total == 0 {
+            return AntiCheatCheck::not_run("null_move_ratio");
+        }
+        let ratio = null_moves as f64 / total as f64;
+        AntiCheatCheck::at_most("null_move_ratio", ratio, config.null_move_max_ratio)
+    }
+
+    /// Share of `4` tiles among the spawns between recorded moves
+    pub fn check_spawn_ratio(moves: &[MoveSample]) -> AntiCheatCheck {
+        let (mut spawn_2_count, mut spawn_4_count) = (0, 0);
+        for pair in moves.windows(2) {
+            match Game::spawned_tile(pair[0].board_after, pair[1].direction, pair[1].board_after) {
+                2 => spawn_2_count += 1,
+                4 => spawn_4_count += 1,
+                _ => {}
+            }
+        }
+        match Self::spawn_ratio_deviations(spawn_2_count, spawn_4_count) {
+            Some(deviations) => {
+                AntiCheatCheck::at_most("spawn_ratio", deviations, SPAWN_RATIO_MAX_DEVIATIONS)
+            }
+            None => AntiCheatCheck::not_run("spawn_ratio"),
+        }
+    }
+
+    /// Distance of the observed `4` ratio from `FOUR_TILE_PROBABILITY`, in standard deviations
+    /// for this many spawns (None when there are too few spawns to tell)
+    pub fn spawn_ratio_deviations(spawn_2_count: u32, spawn_4_count: u32) -> Option<f64> {
+        let sample_size = spawn_2_count + spawn_4_count;
+        if sample_size < MIN_SPAWN_SAMPLE {
+            return None;
+        }
+        let actual_ratio = spawn_4_count as f64 / sample_size as f64;
+        let standard_deviation =
+            (FOUR_TILE_PROBABILITY * (1.0 - FOUR_TILE_PROBABILITY) / sample_size as f64).sqrt();
+        Some((actual_ratio - FOUR_TILE_PROBABILITY).abs() / standard_deviation)
+    }
+
+    /// Whether move timestamps (milliseconds) strictly increase, each at least
+    /// `min_interval_micros` after the previous one
+    pub fn move_intervals_valid(timestamps: &[u64], min_interval_micros: u64) -> bool {
+        timestamps.windows(2).all(|pair| {
+            pair[1] > pair[0] && (pair[1] - pair[0]).saturating_mul(1000) >= min_interval_micros
+        })
+    }
+
+    /// Re-execute each recorded move from the previous recorded board and compare.
+    /// Value is the number of mismatching moves.
+    pub fn check_replay(
+        config: &AntiCheatConfig,
+        board_id: &str,
+        username: &str,
+        moves: &[MoveSample],
+        spawns: &SpawnDistribution,
+    ) -> AntiCheatCheck {
+        if !config.replay_verification_enabled || moves.len() < 2 {
+            return AntiCheatCheck::not_run("replay_verification");
+        }
+        let mismatches = moves
+            .windows(2)
+            .filter(|pair| {
+                let mut game = Game {
+                    board: pair[0].board_after,
+                    board_id: board_id.to_string(),
+                    username: username.to_string(),
+                    timestamp: pair[1].timestamp,
+                    spawns: spawns.clone(),
+                };
+                game.execute(pair[1].direction) != pair[1].board_after
+            })
+            .count();
+        AntiCheatCheck::at_most("replay_verification", mismatches as f64, 0.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spawn_state;
+
+    #[test]
+    fn test_replay_and_rate_checks() {
+        let config = AntiCheatConfig::default();
+        let mut board = 0x0001_0000_0000_0001u64;
+        let mut moves = Vec::new();
+        for (i, direction) in [
+            Direction::Left,
+            Direction::Up,
+            Direction::Right,
+            Direction::Down,
+        ]
+        .into_iter()
+        .cycle()
+        .take(8)
+        .enumerate()
+        {
+            let timestamp = 1_000 + i as u64 * 500;
+            let mut game = Game {
+                board,
+                board_id: "board".to_string(),
+                username: "alice".to_string(),
+                timestamp,
+                spawns: SpawnDistribution::default(),
+            };
+            board = game.execute(direction);
+            moves.push(MoveSample {
+                direction,
+                timestamp,
+                board_after: board,
+                score_after: Game::score(board),
+            });
+        }
+
+        let spawns = SpawnDistribution::default();
+        let replay = AntiCheat::check_replay(&config, "board", "alice", &moves, &spawns);
+        assert!(replay.passed && !replay.skipped);
+        let tampered = AntiCheat::check_replay(&config, "board", "mallory", &moves, &spawns);
+        assert!(!tampered.passed);
+
+        let rate = AntiCheat::check_move_rate(&config, &moves);
+        assert_eq!(rate.value, 2.0);
+        assert!(rate.passed);
+        assert!(AntiCheat::check_move_entropy(&config, &moves).skipped);
+
+        let timestamps: Vec<u64> = moves.iter().map(|sample| sample.timestamp).collect();
+        assert!(AntiCheat::move_intervals_valid(&timestamps, 500_000));
+        assert!(!AntiCheat::move_intervals_valid(&timestamps, 500_001));
+        assert!(!AntiCheat::move_intervals_valid(&[1_000, 1_000], 0));
+        assert!(!AntiCheat::move_intervals_valid(&[2_000, 1_000], 0));
+
+        // The same batch is rejected once it is faster than the tournament allows
+        assert!(AntiCheat::failed_batch_check(&config, &moves, 0).is_none());
+        let strict = AntiCheatConfig {
+            max_moves_per_second: 1.0,
+            ..config.clone()
+        };
+        let failed = AntiCheat::failed_batch_check(&strict, &moves, 0).unwrap();
+        assert_eq!(failed.check, "move_rate");
+        let failed = AntiCheat::failed_batch_check(&config, &moves, 10).unwrap();
+        assert_eq!(failed.check, "null_move_ratio");
+    }
+
+    #[test]
+    fn test_spawn_ratio_threshold() {
+        // The game's own spawns stay within the threshold
+        let spawn_4_count = (0..1_000)
+            .filter(|timestamp| {
+                let mut state = spawn_state("board", "alice", *timestamp, 0);
+                Game::tile(&mut state) == 2
+            })
+            .count() as u32;
+        let deviations = AntiCheat::spawn_ratio_deviations(1_000 - spawn_4_count, spawn_4_count);
+        assert!(deviations.unwrap() <= SPAWN_RATIO_MAX_DEVIATIONS);
+
+        // 100 spawns: sd = 0.03, so the limit is 0.19 - 18 fours pass, 20 fours do not
+        assert!(AntiCheat::spawn_ratio_deviations(82, 18).unwrap() <= SPAWN_RATIO_MAX_DEVIATIONS);
+        assert!(AntiCheat::spawn_ratio_deviations(80, 20).unwrap() > SPAWN_RATIO_MAX_DEVIATIONS);
+        assert!(AntiCheat::spawn_ratio_deviations(20, 5).is_none());
+    }
+}
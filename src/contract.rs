@@ -42,7 +42,7 @@ impl Contract for Game2048Contract {
         Game2048Contract { state, runtime }
     }
 
-    async fn instantiate(&mut self, _argument: Self::InstantiationArgument) {
+    async fn instantiate(&mut self, argument: Self::InstantiationArgument) {
         self.runtime.application_parameters();
 
         // Initialize default leaderboard
@@ -60,10 +60,19 @@ impl Contract for Game2048Contract {
         leaderboard.total_players.set(0);
         leaderboard.admin_base_triggerer_count.set(5); // Default to 5 triggerers
 
-        // 🚀 CHAIN POOL: Configure pool thresholds (fixed defaults)
-        // Pool is filled via RefillChainPool operation (admin only)
-        self.state.chain_pool_target_size.set(300); // Target: 300
+        // 🚀 CHAIN POOL: Configure pool thresholds
+        // Pool is filled via RefillChainPool operation (admin only), and topped up to
+        // `min_pool_size` on AggregateScores
+        let target_size = match argument.pool_size {
+            0 => game2048::DEFAULT_CHAIN_POOL_SIZE,
+            size => size,
+        };
+        self.state.chain_pool_target_size.set(target_size);
         self.state.chain_pool_low_threshold.set(50); // Threshold: 50
+        self.state.chain_pool_min_size.set(argument.min_pool_size);
+        self.state
+            .unclaimed_timeout_hours
+            .set(game2048::DEFAULT_UNCLAIMED_TIMEOUT_HOURS);
         // Pool starts empty - admin must call RefillChainPool after deployment
     }
 
@@ -141,13 +150,14 @@ impl Contract for Game2048Contract {
                             {
                                 let my_chain_id = self.runtime.chain_id().to_string();
 
-                                self.runtime
-                                    .prepare_message(game2048::Message::TriggerUpdate {
+                                self.send_message(
+                                    leaderboard_chain_id,
+                                    game2048::Message::TriggerUpdate {
                                         triggerer_chain_id: my_chain_id,
                                         tournament_id: tournament_id.clone(),
                                         timestamp: current_time,
-                                    })
-                                    .send_to(leaderboard_chain_id);
+                                    },
+                                );
 
                                 // Update last trigger sent and reset operation counter
                                 self.state.last_trigger_sent.set(current_time);
@@ -163,15 +173,28 @@ impl Contract for Game2048Contract {
         // This replaces the event-based trigger logic to ensure latest state is used
         // 🧪 TEST: Skip if auto-triggers disabled
         if !disable_auto_triggers {
-            self.check_and_send_trigger_if_needed_in_block_production().await;
+            self.check_and_send_trigger_if_needed_in_block_production()
+                .await;
         }
 
-        OperationDispatcher::dispatch(self, operation).await;
+        ContractHelpers::record_operation(self, std::mem::size_of_val(&operation));
+        // Commit the bookkeeping above, so a failed operation only discards its own changes
+        self.state.save().await.expect("Failed to save state");
+        let response = match OperationDispatcher::dispatch(self, operation).await {
+            Ok(response) => response,
+            // ❌ A failed operation keeps none of its changes and answers with its `ErrorResponse` JSON
+            Err(error) => {
+                self.state.rollback();
+                serde_json::to_string(&error.to_response()).unwrap()
+            }
+        };
         ContractHelpers::update_balance(self);
+        response
     }
     async fn execute_message(&mut self, message: Self::Message) {
         use crate::contract_domain::MessageDispatcher;
 
+        ContractHelpers::record_message_received(self, std::mem::size_of_val(&message));
         MessageDispatcher::dispatch(self, message).await;
         ContractHelpers::update_balance(self);
     }
@@ -199,7 +222,14 @@ impl Game2048Contract {
     // (Direct message preparation & sending)
     // ========================================
 
+    /// Send a message, counting it in the chain's resource usage
+    fn send_message(&mut self, destination: ChainId, message: Message) {
+        ContractHelpers::record_message_sent(self);
+        self.runtime.prepare_message(message).send_to(destination);
+    }
+
     fn register_player(&mut self, chain_id: ChainId, player: &str, password_hash: &str) {
+        ContractHelpers::record_message_sent(self);
         self.runtime
             .prepare_message(Message::RegisterPlayer {
                 username: player.to_string(),
@@ -209,6 +239,7 @@ impl Game2048Contract {
             .send_to(chain_id);
     }
 
+    #[allow(clippy::too_many_arguments)]
     async fn upsert_leaderboard(
         &mut self,
         chain_id: ChainId,
@@ -219,8 +250,9 @@ impl Game2048Contract {
         end_time: u64,
         send_to: Option<ChainId>,
     ) {
-        self.runtime
-            .prepare_message(Message::CreateLeaderboard {
+        self.send_message(
+            send_to.unwrap_or(chain_id),
+            Message::CreateLeaderboard {
                 leaderboard_id: chain_id.to_string(),
                 name: name.to_string(),
                 description: Some(description.to_string()),
@@ -231,8 +263,8 @@ impl Game2048Contract {
                 shard_ids: vec![], // Default empty, filled by operations handler
                 base_triggerer_count: 5, // Default value
                 total_shard_count: 1, // Default value
-            })
-            .send_to(send_to.unwrap_or(chain_id));
+            },
+        );
     }
 
     fn transfer(&mut self, destination: ChainId, amount: Amount) {
@@ -438,7 +470,7 @@ impl Game2048Contract {
             }
             value => value,
         };
-        
+
         let last_update_time = *self.state.triggerer_list_timestamp.get();
         let last_trigger_sent = *self.state.last_trigger_sent.get();
         let total_players = *self.state.total_registered_players.get();
@@ -448,12 +480,12 @@ impl Game2048Contract {
         let time_since_last_trigger = current_time.saturating_sub(last_trigger_sent);
 
         // Mathematical tier calculation with overflow protection (tiers 1-5 only)
+        // Minimal tier when threshold is 0 (prevents infinite triggering) or when
+        // time_since_update is 0 (no need for escalated triggering)
         let tier = if threshold > 0 && time_since_update > 0 {
             std::cmp::min(5, (time_since_update / threshold) + 1)
-        } else if threshold == 0 {
-            1 // Minimal tier to prevent infinite triggering
         } else {
-            1 // No need for escalated triggering if time_since_update is 0
+            1
         };
 
         // Calculate how many players should be actively triggering
@@ -510,8 +542,7 @@ impl Game2048Contract {
                 .for_each_index_value_while(|key, value| {
                     tournament_id = key;
                     // Parse tournament JSON to get leaderboard chain
-                    if let Ok(tournament) =
-                        serde_json::from_str::<game2048::TournamentInfo>(&value)
+                    if let Ok(tournament) = serde_json::from_str::<game2048::TournamentInfo>(&value)
                     {
                         leaderboard_chain_id_str = tournament.tournament_id;
                     }
@@ -524,13 +555,14 @@ impl Game2048Contract {
             if !tournament_id.is_empty() && !leaderboard_chain_id_str.is_empty() {
                 if let Ok(leaderboard_chain_id) = ChainId::from_str(&leaderboard_chain_id_str) {
                     // Send trigger message to leaderboard
-                    self.runtime
-                        .prepare_message(game2048::Message::TriggerUpdate {
+                    self.send_message(
+                        leaderboard_chain_id,
+                        game2048::Message::TriggerUpdate {
                             triggerer_chain_id: my_chain_id,
                             tournament_id: tournament_id.clone(),
                             timestamp: current_time,
-                        })
-                        .send_to(leaderboard_chain_id);
+                        },
+                    );
 
                     // Update last trigger sent time
                     self.state.last_trigger_sent.set(current_time);
@@ -2,7 +2,16 @@
 //!
 //! Common utility functions and patterns for the Game2048 contract.
 
-use linera_sdk::linera_base_types::{Account, AccountOwner, Amount, ChainId};
+use std::str::FromStr;
+
+use linera_sdk::{
+    abis::fungible::{self, FungibleOperation, FungibleTokenAbi},
+    linera_base_types::{Account, AccountOwner, Amount, ApplicationId, ChainId},
+};
+
+/// 📊 Board creation is throttled once a chain sends this many messages within one window
+const THROTTLE_MESSAGE_LIMIT: u64 = 500;
+const THROTTLE_WINDOW_MICROS: u64 = 60_000_000; // 1 minute
 
 /// Contract utility functions
 pub struct ContractHelpers;
@@ -22,6 +31,90 @@ impl ContractHelpers {
             == contract.runtime.application_creator_chain_id().to_string()
     }
 
+    /// 🔁 Next nonce for a SubmitScore sent from this chain
+    pub fn next_score_nonce(contract: &mut crate::Game2048Contract) -> u64 {
+        let nonce = *contract.state.score_message_nonce.get() + 1;
+        contract.state.score_message_nonce.set(nonce);
+        nonce
+    }
+
+    /// 📊 Count a processed operation and its estimated storage footprint
+    pub fn record_operation(contract: &mut crate::Game2048Contract, bytes: usize) {
+        let mut usage = contract.state.resource_usage.get().clone();
+        usage.total_operations_processed += 1;
+        usage.total_storage_bytes_written += bytes as u64;
+        contract.state.resource_usage.set(usage);
+    }
+
+    /// 📊 Count a received message and its estimated storage footprint
+    pub fn record_message_received(contract: &mut crate::Game2048Contract, bytes: usize) {
+        let mut usage = contract.state.resource_usage.get().clone();
+        usage.total_messages_received += 1;
+        usage.total_storage_bytes_written += bytes as u64;
+        contract.state.resource_usage.set(usage);
+    }
+
+    /// 📊 Count a sent message, in total and in the current throttle window
+    pub fn record_message_sent(contract: &mut crate::Game2048Contract) {
+        let current_time = contract.runtime.system_time().micros();
+        let mut usage = contract.state.resource_usage.get().clone();
+        usage.total_messages_sent += 1;
+        if current_time >= usage.window_start + THROTTLE_WINDOW_MICROS {
+            usage.window_start = current_time;
+            usage.window_messages_sent = 0;
+        }
+        usage.window_messages_sent += 1;
+        contract.state.resource_usage.set(usage);
+    }
+
+    /// 📊 Too many messages sent in the current window: new boards are throttled
+    pub fn is_throttled(contract: &mut crate::Game2048Contract) -> bool {
+        let current_time = contract.runtime.system_time().micros();
+        let usage = contract.state.resource_usage.get();
+        current_time < usage.window_start + THROTTLE_WINDOW_MICROS
+            && usage.window_messages_sent >= THROTTLE_MESSAGE_LIMIT
+    }
+
+    /// 🏅 Move `amount` of a prize token from `source` to `destination`.
+    /// `NATIVE_TOKEN_ID` uses the chain balance; anything else is a fungible token application ID.
+    pub fn transfer_token(
+        contract: &mut crate::Game2048Contract,
+        application_id: &str,
+        source: AccountOwner,
+        destination: Account,
+        amount: Amount,
+    ) {
+        if application_id == game2048::NATIVE_TOKEN_ID {
+            contract.runtime.transfer(source, destination, amount);
+            return;
+        }
+        let token = ApplicationId::from_str(application_id)
+            .unwrap_or_else(|_| panic!("Invalid token application ID: {}", application_id))
+            .with_abi::<FungibleTokenAbi>();
+        let operation = FungibleOperation::Transfer {
+            owner: source,
+            amount,
+            target_account: fungible::Account {
+                chain_id: destination.chain_id,
+                owner: destination.owner,
+            },
+        };
+        contract.runtime.call_application(true, token, &operation);
+    }
+
+    /// 🏅 Owner holding a prize token on a leaderboard chain: the chain for the native token,
+    /// this application for fungible tokens (so it can pay them out later)
+    pub fn prize_pool_owner(
+        contract: &mut crate::Game2048Contract,
+        application_id: &str,
+    ) -> AccountOwner {
+        if application_id == game2048::NATIVE_TOKEN_ID {
+            AccountOwner::CHAIN
+        } else {
+            AccountOwner::from(contract.runtime.application_id())
+        }
+    }
+
     /// Send a transfer to another chain
     pub fn transfer(contract: &mut crate::Game2048Contract, destination: ChainId, amount: Amount) {
         let account = Account {
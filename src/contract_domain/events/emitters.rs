@@ -1,9 +1,11 @@
 //! Event Emitters
 //!
 //! Utilities for creating and emitting events to streams.
-//! 
-//! 🚀 MESSAGE-BASED ARCHITECTURE: Score-related events are deprecated.
-//! Only ActiveTournaments event is still used for tournament discovery.
+//!
+//! 🚀 MESSAGE-BASED ARCHITECTURE: Scores reach leaderboards through SubmitScore messages;
+//! PlayerScoreUpdate only reports a board's state after each move batch to clients.
+//! ActiveTournaments is used for tournament discovery, StreamUpdate for live stream relays,
+//! BonusWindowActive for bonus window notices, FriendScoreEvent for followers' friend lists.
 
 use game2048::GameEvent;
 
@@ -28,37 +30,345 @@ impl EventEmitter {
         contract.runtime.emit(stream_name, &event);
     }
 
-    // ═══════════════════════════════════════════════════════════════
-    // DEPRECATED EMITTERS (kept for reference, may be removed later)
-    // ═══════════════════════════════════════════════════════════════
+    /// Emit live stream update for an off-chain relay endpoint
+    pub async fn emit_stream_update(
+        contract: &mut crate::Game2048Contract,
+        leaderboard_id: String,
+        endpoint_url: String,
+        top_10: Vec<game2048::LeaderboardEntry>,
+        timestamp: u64,
+    ) {
+        let event = GameEvent::StreamUpdate {
+            leaderboard_id,
+            endpoint_url,
+            top_10,
+            timestamp,
+        };
 
-    /// DEPRECATED: Use Message::SubmitScore instead
-    #[allow(dead_code)]
+        use linera_sdk::linera_base_types::StreamName;
+        let stream_name = StreamName::from("stream_feed".to_string());
+        contract.runtime.emit(stream_name, &event);
+    }
+
+    /// Emit notice that a classic game was converted to a speed game
+    pub async fn emit_variant_converted(
+        contract: &mut crate::Game2048Contract,
+        board_id: String,
+        player: String,
+        new_variant: game2048::GameVariant,
+        deadline: u64,
+    ) {
+        let event = GameEvent::VariantConverted {
+            board_id,
+            player,
+            new_variant,
+            deadline,
+        };
+
+        use linera_sdk::linera_base_types::StreamName;
+        let stream_name = StreamName::from("variant_converted".to_string());
+        contract.runtime.emit(stream_name, &event);
+    }
+
+    /// Emit a suspicious 4-tile spawn ratio for an ended game
+    pub async fn emit_abnormal_spawn_ratio(
+        contract: &mut crate::Game2048Contract,
+        board_id: String,
+        actual_ratio: f64,
+        sample_size: u32,
+    ) {
+        let event = GameEvent::AbnormalSpawnRatio {
+            board_id,
+            expected_ratio: game2048::FOUR_TILE_PROBABILITY,
+            actual_ratio,
+            sample_size,
+        };
+
+        use linera_sdk::linera_base_types::StreamName;
+        let stream_name = StreamName::from("abnormal_spawn_ratio".to_string());
+        contract.runtime.emit(stream_name, &event);
+    }
+
+    /// Emit notice that a game ended by reaching the tournament's score cap
+    pub async fn emit_score_cap_reached(
+        contract: &mut crate::Game2048Contract,
+        board_id: String,
+        player: String,
+        leaderboard_id: String,
+        score: u64,
+    ) {
+        let event = GameEvent::ScoreCapReached {
+            board_id,
+            player,
+            leaderboard_id,
+            score,
+            message: "Score cap reached! You finished the tournament successfully.".to_string(),
+        };
+
+        use linera_sdk::linera_base_types::StreamName;
+        let stream_name = StreamName::from("score_cap_reached".to_string());
+        contract.runtime.emit(stream_name, &event);
+    }
+
+    /// Emit notice that the contract ended a game early
+    pub async fn emit_game_ended(
+        contract: &mut crate::Game2048Contract,
+        board_id: String,
+        player: String,
+        leaderboard_id: String,
+        score: u64,
+        reason: game2048::GameEndReason,
+    ) {
+        let event = GameEvent::GameEnded {
+            board_id,
+            player,
+            leaderboard_id,
+            score,
+            reason,
+        };
+
+        use linera_sdk::linera_base_types::StreamName;
+        let stream_name = StreamName::from("game_ended".to_string());
+        contract.runtime.emit(stream_name, &event);
+    }
+
+    /// Emit a countdown to the tournament end (on the active tournaments channel)
+    pub async fn emit_tournament_countdown(
+        contract: &mut crate::Game2048Contract,
+        leaderboard_id: String,
+        seconds_remaining: u64,
+        current_leader: Option<String>,
+    ) {
+        let event = GameEvent::TournamentCountdown {
+            leaderboard_id,
+            seconds_remaining,
+            current_leader,
+        };
+
+        use linera_sdk::linera_base_types::StreamName;
+        let stream_name = StreamName::from("active_tournaments".to_string());
+        contract.runtime.emit(stream_name, &event);
+    }
+
+    /// Emit that players advanced from a qualifier to this tournament
+    pub async fn emit_players_qualified(
+        contract: &mut crate::Game2048Contract,
+        count: u32,
+        from_tournament: String,
+        to_tournament: String,
+    ) {
+        let event = GameEvent::PlayersQualified {
+            count,
+            from_tournament,
+            to_tournament,
+        };
+
+        use linera_sdk::linera_base_types::StreamName;
+        let stream_name = StreamName::from("players_qualified".to_string());
+        contract.runtime.emit(stream_name, &event);
+    }
+
+    /// Emit that a tournament's warmup window has opened
+    pub async fn emit_warmup_started(
+        contract: &mut crate::Game2048Contract,
+        leaderboard_id: String,
+        starts_at: u64,
+    ) {
+        let event = GameEvent::WarmupStarted {
+            leaderboard_id,
+            starts_at,
+        };
+
+        use linera_sdk::linera_base_types::StreamName;
+        let stream_name = StreamName::from("warmup_started".to_string());
+        contract.runtime.emit(stream_name, &event);
+    }
+
+    /// Emit that a tournament with a warmup window has started counting scores
+    pub async fn emit_tournament_started(
+        contract: &mut crate::Game2048Contract,
+        leaderboard_id: String,
+    ) {
+        let event = GameEvent::TournamentStarted { leaderboard_id };
+
+        use linera_sdk::linera_base_types::StreamName;
+        let stream_name = StreamName::from("tournament_started".to_string());
+        contract.runtime.emit(stream_name, &event);
+    }
+
+    /// Emit a completed randomness beacon round
+    pub async fn emit_beacon_updated(
+        contract: &mut crate::Game2048Contract,
+        beacon: game2048::RandomnessBeacon,
+    ) {
+        let event = GameEvent::BeaconUpdated { beacon };
+
+        use linera_sdk::linera_base_types::StreamName;
+        let stream_name = StreamName::from("randomness_beacon".to_string());
+        contract.runtime.emit(stream_name, &event);
+    }
+
+    /// Emit how many stale claimed chains went back to the chain pool
+    pub async fn emit_chains_recovered(
+        contract: &mut crate::Game2048Contract,
+        count: u32,
+        total_available: u32,
+    ) {
+        let event = GameEvent::ChainsRecovered {
+            count,
+            total_available,
+        };
+
+        use linera_sdk::linera_base_types::StreamName;
+        let stream_name = StreamName::from("chains_recovered".to_string());
+        contract.runtime.emit(stream_name, &event);
+    }
+
+    /// Emit notice that off-chain history was imported for a tournament
+    pub async fn emit_historical_data_imported(
+        contract: &mut crate::Game2048Contract,
+        record_count: u32,
+        leaderboard_id: String,
+    ) {
+        let event = GameEvent::HistoricalDataImported {
+            record_count,
+            leaderboard_id,
+        };
+
+        use linera_sdk::linera_base_types::StreamName;
+        let stream_name = StreamName::from("historical_import".to_string());
+        contract.runtime.emit(stream_name, &event);
+    }
+
+    /// Emit bonus window notice while a window is active
+    pub async fn emit_bonus_window_active(
+        contract: &mut crate::Game2048Contract,
+        leaderboard_id: String,
+        window_name: String,
+        expires_in_seconds: u64,
+    ) {
+        let event = GameEvent::BonusWindowActive {
+            leaderboard_id,
+            window_name,
+            expires_in_seconds,
+        };
+
+        use linera_sdk::linera_base_types::StreamName;
+        let stream_name = StreamName::from("bonus_window".to_string());
+        contract.runtime.emit(stream_name, &event);
+    }
+
+    /// 👫 Emit a player's new best score, only when some friend chain follows this chain
+    pub async fn emit_friend_score_update(
+        contract: &mut crate::Game2048Contract,
+        player: String,
+        leaderboard_id: String,
+        best_score: u64,
+        timestamp: u64,
+    ) {
+        if contract.state.friend_subscribers.count().await.unwrap() == 0 {
+            return;
+        }
+        let event = GameEvent::FriendScoreEvent {
+            player,
+            leaderboard_id,
+            best_score,
+            timestamp,
+        };
+
+        use linera_sdk::linera_base_types::StreamName;
+        let stream_name = StreamName::from("friend_updates".to_string());
+        contract.runtime.emit(stream_name, &event);
+    }
+
+    /// Emit that the host paused or resumed scoring on this leaderboard
+    pub async fn emit_leaderboard_paused(
+        contract: &mut crate::Game2048Contract,
+        leaderboard_id: String,
+        paused: bool,
+        reason: String,
+        timestamp: u64,
+    ) {
+        let event = if paused {
+            GameEvent::LeaderboardPaused {
+                leaderboard_id,
+                reason,
+                timestamp,
+            }
+        } else {
+            GameEvent::LeaderboardResumed {
+                leaderboard_id,
+                reason,
+                timestamp,
+            }
+        };
+
+        use linera_sdk::linera_base_types::StreamName;
+        let stream_name = StreamName::from("leaderboard_update".to_string());
+        contract.runtime.emit(stream_name, &event);
+    }
+
+    /// 🌟 Emit a platform highlight for spectators and bots (main chain)
+    pub async fn emit_platform_highlight(
+        contract: &mut crate::Game2048Contract,
+        highlight: game2048::PlatformHighlight,
+    ) {
+        let event = GameEvent::PlatformHighlight {
+            player: highlight.player,
+            score: highlight.score,
+            highest_tile: highlight.highest_tile,
+            leaderboard_id: highlight.leaderboard_id,
+            rank: highlight.rank,
+        };
+
+        use linera_sdk::linera_base_types::StreamName;
+        let stream_name = StreamName::from("platform_events".to_string());
+        contract.runtime.emit(stream_name, &event);
+    }
+
+    /// 🧹 Emit the number of players a shard purged
+    pub async fn emit_purge_completed(
+        contract: &mut crate::Game2048Contract,
+        leaderboard_id: String,
+        purged_count: u32,
+        timestamp: u64,
+    ) {
+        let event = GameEvent::PurgeCompleted {
+            leaderboard_id,
+            purged_count,
+            timestamp,
+        };
+
+        use linera_sdk::linera_base_types::StreamName;
+        let stream_name = StreamName::from("admin_events".to_string());
+        contract.runtime.emit(stream_name, &event);
+    }
+
+    /// Emit a board's state after a move batch (player chain, read by client UIs).
+    /// Leaderboards still receive scores through Message::SubmitScore.
     pub async fn emit_player_score_update(
-        _contract: &mut crate::Game2048Contract,
-        _player: String,
-        _board_id: String,
-        _score: u64,
-        _chain_id: String,
-        _timestamp: u64,
-        _game_status: game2048::GameStatus,
-        _highest_tile: u64,
-        _moves_count: u32,
-        _leaderboard_id: String,
-        _current_leaderboard_best: u64,
-        _boards_in_tournament: u32,
+        contract: &mut crate::Game2048Contract,
+        event: GameEvent,
     ) {
-        // No-op: Use Message::SubmitScore instead
+        use linera_sdk::linera_base_types::StreamName;
+        let stream_name = StreamName::from("player_score_update".to_string());
+        contract.runtime.emit(stream_name, &event);
     }
 
+    // ═══════════════════════════════════════════════════════════════
+    // DEPRECATED EMITTERS (kept for reference, may be removed later)
+    // ═══════════════════════════════════════════════════════════════
+
     /// DEPRECATED: No longer using shard aggregation
     #[allow(dead_code)]
+    #[allow(clippy::too_many_arguments)]
     pub async fn emit_shard_score_update(
         _contract: &mut crate::Game2048Contract,
         _shard_chain_id: String,
         _player_scores: std::collections::HashMap<String, game2048::PlayerScoreSummary>,
         _player_activity_scores: std::collections::HashMap<String, u32>,
         _player_board_counts: std::collections::HashMap<String, u32>,
+        _player_efficiency_scores: std::collections::HashMap<String, u64>,
         _aggregation_timestamp: u64,
         _total_players: u32,
         _leaderboard_id: String,
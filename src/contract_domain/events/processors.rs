@@ -1,7 +1,7 @@
 //! Stream Processors
 //!
 //! Logic for processing incoming stream updates and events.
-//! 
+//!
 //! 🚀 MESSAGE-BASED ARCHITECTURE: Score updates now use SubmitScore message.
 //! Only ActiveTournaments events are processed for tournament discovery.
 
@@ -13,10 +13,9 @@ pub struct StreamProcessor;
 
 impl StreamProcessor {
     /// Process all stream updates for the contract
-    /// 
-    /// 🚀 MESSAGE-BASED: Only processes active_tournaments events.
-    /// Score updates (player_score_update, shard_score_update, leaderboard_update)
-    /// are no longer used - replaced by direct SubmitScore messages.
+    ///
+    /// 🚀 MESSAGE-BASED: Scores arrive as SubmitScore messages. Shards only read move timing
+    /// from player_score_update; shard_score_update and leaderboard_update are no longer used.
     pub async fn process_updates(
         contract: &mut crate::Game2048Contract,
         updates: Vec<StreamUpdate>,
@@ -38,8 +37,20 @@ impl StreamProcessor {
                     "active_tournaments" => {
                         Self::process_active_tournaments(contract, update, event_index).await;
                     }
-                    // All score-related events are deprecated (use SubmitScore message instead)
-                    "player_score_update" | "shard_score_update" | "leaderboard_update" => {
+                    // 🎲 Beacon mixed into new board seeds
+                    "randomness_beacon" => {
+                        Self::process_randomness_beacon(contract, update, event_index);
+                    }
+                    // 👫 Best scores of followed friends
+                    "friend_updates" => {
+                        Self::process_friend_update(contract, update, event_index).await;
+                    }
+                    // ⏲️ Shards keep their players' move timing from score updates
+                    "player_score_update" => {
+                        Self::process_player_score_update(contract, update, event_index).await;
+                    }
+                    // Other score-related events are deprecated (use SubmitScore message instead)
+                    "shard_score_update" | "leaderboard_update" => {
                         // DEPRECATED: No-op for backward compatibility
                     }
                     _ => {}
@@ -67,6 +78,104 @@ impl StreamProcessor {
         }
     }
 
+    /// Cache the main chain's latest randomness beacon
+    fn process_randomness_beacon(
+        contract: &mut crate::Game2048Contract,
+        update: &StreamUpdate,
+        event_index: u32,
+    ) {
+        if let Some(game2048::GameEvent::BeaconUpdated { beacon }) =
+            EventReader::read_randomness_beacon_event_from_chain(
+                contract,
+                update.chain_id,
+                event_index,
+            )
+        {
+            if beacon.beacon_round > contract.state.randomness_beacon.get().beacon_round {
+                contract.state.randomness_beacon.set(beacon);
+            }
+        }
+    }
+
+    /// ⏲️ Record a player's average move time on a shard, if the event came from the
+    /// chain the shard knows for that player
+    async fn process_player_score_update(
+        contract: &mut crate::Game2048Contract,
+        update: &StreamUpdate,
+        event_index: u32,
+    ) {
+        let Some(game2048::GameEvent::PlayerScoreUpdate {
+            player,
+            avg_move_time_micros,
+            ..
+        }) =
+            EventReader::read_player_score_event_from_chain(contract, update.chain_id, event_index)
+        else {
+            return;
+        };
+        if contract
+            .state
+            .shards
+            .try_load_entry("")
+            .await
+            .unwrap()
+            .is_none()
+        {
+            return;
+        }
+        let shard = contract.state.shards.load_entry_mut("").await.unwrap();
+        let player_chain_id = shard.player_chain_ids.get(&player).await.unwrap();
+        if player_chain_id != Some(update.chain_id.to_string()) {
+            return;
+        }
+        shard
+            .avg_move_times
+            .insert(&player, avg_move_time_micros)
+            .unwrap();
+    }
+
+    /// 👫 Record a friend's best score, if the event came from that friend's chain
+    async fn process_friend_update(
+        contract: &mut crate::Game2048Contract,
+        update: &StreamUpdate,
+        event_index: u32,
+    ) {
+        let Some(game2048::GameEvent::FriendScoreEvent {
+            player,
+            leaderboard_id,
+            best_score,
+            ..
+        }) = EventReader::read_friend_updates_event_from_chain(
+            contract,
+            update.chain_id,
+            event_index,
+        )
+        else {
+            return;
+        };
+        let friend_chain_id = contract.state.friend_chain_ids.get(&player).await.unwrap();
+        if friend_chain_id != Some(update.chain_id.to_string()) {
+            return;
+        }
+
+        let mut scores = contract
+            .state
+            .friend_scores
+            .get(&player)
+            .await
+            .unwrap()
+            .unwrap_or_default();
+        match scores.iter_mut().find(|(id, _)| *id == leaderboard_id) {
+            Some((_, score)) => *score = (*score).max(best_score),
+            None => scores.push((leaderboard_id, best_score)),
+        }
+        contract
+            .state
+            .friend_scores
+            .insert(&player, scores)
+            .unwrap();
+    }
+
     /// Update local tournament cache with latest data from main chain
     async fn update_local_tournament_cache(
         contract: &mut crate::Game2048Contract,
@@ -92,6 +201,14 @@ impl StreamProcessor {
         // Add all tournaments to cache as JSON
         for tournament in tournaments {
             let tournament_id = tournament.tournament_id.clone();
+            // Remember end times even after the tournament leaves the registry (streaks)
+            if let Some(end_time) = tournament.end_time {
+                contract
+                    .state
+                    .tournament_end_times
+                    .insert(&tournament_id, end_time)
+                    .unwrap();
+            }
             if let Ok(tournament_json) = serde_json::to_string(&tournament) {
                 contract
                     .state
@@ -60,6 +60,40 @@ impl EventReader {
         .ok()
     }
 
+    /// 👫 Read a friend's best score event from their chain
+    pub fn read_friend_updates_event_from_chain(
+        contract: &mut crate::Game2048Contract,
+        chain_id: ChainId,
+        event_index: u32,
+    ) -> Option<GameEvent> {
+        use linera_sdk::linera_base_types::StreamName;
+        let stream_name = StreamName::from("friend_updates".to_string());
+
+        std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            contract
+                .runtime
+                .read_event(chain_id, stream_name, event_index)
+        }))
+        .ok()
+    }
+
+    /// Read randomness beacon event from chain
+    pub fn read_randomness_beacon_event_from_chain(
+        contract: &mut crate::Game2048Contract,
+        chain_id: ChainId,
+        event_index: u32,
+    ) -> Option<GameEvent> {
+        use linera_sdk::linera_base_types::StreamName;
+        let stream_name = StreamName::from("randomness_beacon".to_string());
+
+        std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            contract
+                .runtime
+                .read_event(chain_id, stream_name, event_index)
+        }))
+        .ok()
+    }
+
     /// Read leaderboard update event from chain
     pub fn read_leaderboard_update_event_from_chain(
         contract: &mut crate::Game2048Contract,
@@ -2,7 +2,7 @@
 //!
 //! Utilities for managing stream subscriptions to remote chains.
 
-use linera_sdk::linera_base_types::ChainId;
+use linera_sdk::linera_base_types::{ApplicationId, ChainId, StreamName};
 
 /// Subscription management utilities
 pub struct SubscriptionManager;
@@ -13,18 +13,7 @@ impl SubscriptionManager {
         contract: &mut crate::Game2048Contract,
         chain_id: ChainId,
     ) {
-        use linera_sdk::linera_base_types::{ApplicationId, StreamName};
-        let stream_name = StreamName::from("player_score_update".to_string());
-        let application_id = ApplicationId::new(
-            contract
-                .runtime
-                .application_id()
-                .application_description_hash,
-        );
-
-        contract
-            .runtime
-            .subscribe_to_events(chain_id, application_id, stream_name);
+        Self::subscribe(contract, "player_score_update", chain_id);
     }
 
     /// Subscribes to shard score events from another chain
@@ -32,18 +21,7 @@ impl SubscriptionManager {
         contract: &mut crate::Game2048Contract,
         chain_id: ChainId,
     ) {
-        use linera_sdk::linera_base_types::{ApplicationId, StreamName};
-        let stream_name = StreamName::from("shard_score_update".to_string());
-        let application_id = ApplicationId::new(
-            contract
-                .runtime
-                .application_id()
-                .application_description_hash,
-        );
-
-        contract
-            .runtime
-            .subscribe_to_events(chain_id, application_id, stream_name);
+        Self::subscribe(contract, "shard_score_update", chain_id);
     }
 
     /// Subscribe to leaderboard update events from another chain
@@ -51,17 +29,130 @@ impl SubscriptionManager {
         contract: &mut crate::Game2048Contract,
         chain_id: ChainId,
     ) {
-        use linera_sdk::linera_base_types::{ApplicationId, StreamName};
-        let stream_name = StreamName::from("leaderboard_update".to_string());
-        let application_id = ApplicationId::new(
+        Self::subscribe(contract, "leaderboard_update", chain_id);
+    }
+
+    /// 👫 Subscribe to a friend's best score events
+    pub fn subscribe_to_friend_updates(contract: &mut crate::Game2048Contract, chain_id: ChainId) {
+        Self::subscribe(contract, "friend_updates", chain_id);
+    }
+
+    /// 👫 Stop receiving a former friend's best score events
+    pub fn unsubscribe_from_friend_updates(
+        contract: &mut crate::Game2048Contract,
+        chain_id: ChainId,
+    ) {
+        Self::unsubscribe(contract, "friend_updates", chain_id);
+    }
+
+    /// Subscribes to a channel (event stream) of this application on another chain
+    pub fn subscribe(contract: &mut crate::Game2048Contract, channel: &str, chain_id: ChainId) {
+        let application_id = Self::application_id(contract);
+        contract.runtime.subscribe_to_events(
+            chain_id,
+            application_id,
+            StreamName::from(channel.to_string()),
+        );
+        contract
+            .state
+            .subscriptions
+            .insert(&(channel.to_string(), chain_id))
+            .unwrap();
+    }
+
+    /// 🔕 Stops receiving a channel's events from another chain
+    pub fn unsubscribe(contract: &mut crate::Game2048Contract, channel: &str, chain_id: ChainId) {
+        let application_id = Self::application_id(contract);
+        contract.runtime.unsubscribe_from_events(
+            chain_id,
+            application_id,
+            StreamName::from(channel.to_string()),
+        );
+        contract
+            .state
+            .subscriptions
+            .remove(&(channel.to_string(), chain_id))
+            .unwrap();
+    }
+
+    /// 🔕 Stops receiving every channel subscribed to on `chain_id`
+    pub async fn unsubscribe_all(contract: &mut crate::Game2048Contract, chain_id: ChainId) {
+        let subscriptions = contract.state.subscriptions.indices().await.unwrap();
+        for (channel, _) in Self::matching(&subscriptions, None, Some(chain_id)) {
+            Self::unsubscribe(contract, &channel, chain_id);
+        }
+    }
+
+    /// 🔕 Stops receiving a channel's events from every chain it was subscribed to on
+    pub async fn unsubscribe_from_channel(contract: &mut crate::Game2048Contract, channel: &str) {
+        let subscriptions = contract.state.subscriptions.indices().await.unwrap();
+        for (_, chain_id) in Self::matching(&subscriptions, Some(channel), None) {
+            Self::unsubscribe(contract, channel, chain_id);
+        }
+    }
+
+    /// Tracked subscriptions on `channel` and/or `chain_id` (None = any)
+    fn matching(
+        subscriptions: &[(String, ChainId)],
+        channel: Option<&str>,
+        chain_id: Option<ChainId>,
+    ) -> Vec<(String, ChainId)> {
+        subscriptions
+            .iter()
+            .filter(|(subscribed_channel, subscribed_chain_id)| {
+                channel.is_none_or(|channel| channel == subscribed_channel)
+                    && chain_id.is_none_or(|chain_id| chain_id == *subscribed_chain_id)
+            })
+            .cloned()
+            .collect()
+    }
+
+    fn application_id(contract: &mut crate::Game2048Contract) -> ApplicationId {
+        ApplicationId::new(
             contract
                 .runtime
                 .application_id()
                 .application_description_hash,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SubscriptionManager;
+    use linera_sdk::linera_base_types::ChainId;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_unsubscribe_matches_channel_and_chain() {
+        let chain_a =
+            ChainId::from_str("e476187f6ddfeb9d588c7b45d3df334d5501d6499b3f9ad5595cae86cce16a65")
+                .unwrap();
+        let chain_b =
+            ChainId::from_str("256e1dbc00482ddd619c293cc0df94d366afe7980022bb22d99e33036fd465dd")
+                .unwrap();
+        let subscriptions = vec![
+            ("friend_updates".to_string(), chain_a),
+            ("friend_updates".to_string(), chain_b),
+            ("leaderboard_update".to_string(), chain_a),
+        ];
+
+        let by_channel =
+            SubscriptionManager::matching(&subscriptions, Some("friend_updates"), None);
+        assert_eq!(by_channel, subscriptions[..2]);
+
+        let by_chain = SubscriptionManager::matching(&subscriptions, None, Some(chain_a));
+        assert_eq!(
+            by_chain,
+            vec![subscriptions[0].clone(), subscriptions[2].clone()]
         );
 
-        contract
-            .runtime
-            .subscribe_to_events(chain_id, application_id, stream_name);
+        // An unsubscribed channel no longer matches, so its events are not read again
+        let remaining: Vec<_> = subscriptions
+            .iter()
+            .filter(|subscription| !by_channel.contains(subscription))
+            .cloned()
+            .collect();
+        assert!(SubscriptionManager::matching(&remaining, Some("friend_updates"), None).is_empty());
     }
 }
@@ -1,4 +1,4 @@
-use game2048::{Direction, Game};
+use game2048::{ComboMultiplier, Direction, Game, Moves, SpawnDistribution};
 
 pub struct GameMoveProcessor;
 
@@ -9,11 +9,15 @@ pub struct ProcessedMove {
     pub score_after: u64,
     // 🎵 Rhythm mode: which beat this move was on (0 = miss/off-beat, >0 = on-beat)
     pub beat_number: u32,
+    pub merged_tiles: Vec<u64>, // 🃏 Tiles produced by merges in this move
 }
 
 impl GameMoveProcessor {
     /// Process a batch of moves, skipping any that were already processed (duplicate detection).
-    /// 
+    ///
+    /// The whole batch is played on a copy of the board: nothing is written until the caller
+    /// applies a `Success`, so a move failing validation leaves the board as it was.
+    ///
     /// # Arguments
     /// * `board_id` - The board identifier
     /// * `player` - The player making the moves
@@ -23,24 +27,38 @@ impl GameMoveProcessor {
     /// * `last_processed_timestamp` - Last timestamp that was successfully processed (for duplicate detection)
     /// * `start_time` - Tournament start time in microseconds (None = unlimited)
     /// * `end_time` - Tournament end time in microseconds (None = unlimited)
+    /// * `bonus_multiplier` - ⏰ Multiplier of the bonus window active at block time (1.0 = none);
+    ///   merge points of the batch earn `bonus_points`
+    /// * `spawns` - 🎲 The board's tile spawn probabilities
+    /// * `combo_multiplier_enabled` - 💥 Moves with 3+ merges earn a combo bonus in `bonus_points`
+    /// * `current_combo` - 💥 Consecutive moves with merges before this batch
+    #[allow(clippy::too_many_arguments)]
     pub fn process_moves(
         board_id: &str,
         player: &str,
-        moves: &[(Direction, u64, u32)],  // 🎵 Added beat_number
+        moves: &[(Direction, u64, u32)], // 🎵 Added beat_number
         initial_board: u64,
         last_processed_timestamp: u64, // 🔒 NEW: For duplicate detection
         start_time: Option<u64>,
         end_time: Option<u64>,
+        bonus_multiplier: f64,
+        spawns: &SpawnDistribution,
+        combo_multiplier_enabled: bool,
+        mut current_combo: u8,
     ) -> GameMoveResult {
         let initial_highest_tile = Game::highest_tile(initial_board);
         let mut current_board = initial_board;
         let mut any_change = false;
         let mut latest_timestamp = last_processed_timestamp; // 🔒 FIX: Start from last processed
         let mut is_ended = false;
+        let mut is_deadlocked = false; // 🏁 No direction changes the board any more
         let mut move_history: Vec<ProcessedMove> = Vec::new();
         let mut skipped_duplicate_count = 0; // 🔒 NEW: Track skipped duplicates
+        let mut bonus_points = 0u64; // ⏰ Extra points from bonus windows
+        let mut null_moves = 0u32; // 🛡️ Moves that did not change the board (anti-cheat)
+        let mut last_combo_bonus = 0u64; // 💥 Combo bonus of the latest move
 
-        for (direction, timestamp, beat_number) in moves.iter() {
+        for (index, (direction, timestamp, beat_number)) in moves.iter().enumerate() {
             if is_ended {
                 break;
             }
@@ -51,9 +69,10 @@ impl GameMoveProcessor {
             // 🔒 VALIDATION: Reject moves before tournament start
             if let Some(start_time_value) = start_time {
                 if timestamp_micros < start_time_value {
-                    return GameMoveResult::Error(
-                        "Move timestamp is before tournament start time".to_string(),
-                    );
+                    return GameMoveResult::InvalidMoveSequence {
+                        at_index: index,
+                        reason: "Move timestamp is before tournament start time".to_string(),
+                    };
                 }
             }
 
@@ -78,17 +97,38 @@ impl GameMoveProcessor {
                 board_id: board_id.to_string(),
                 username: player.to_string(),
                 timestamp: *timestamp,
+                spawns: spawns.clone(),
             };
 
             let new_board = game.execute(*direction);
+            let merged_tiles = Game::merged_tiles(current_board, *direction);
 
             if current_board == new_board {
+                null_moves += 1;
                 continue;
             }
 
             any_change = true;
+            let (_, merge_points) = Moves::score_move(current_board, *direction);
             current_board = new_board;
-            let current_score = Game::score(current_board);
+
+            // ⏰ BONUS WINDOW: Multiply merge points earned inside an active window
+            if bonus_multiplier > 1.0 {
+                bonus_points += (merge_points as f64 * (bonus_multiplier - 1.0)).round() as u64;
+            }
+
+            // 💥 COMBO: Several merges in one move multiply the merged value
+            current_combo = if merged_tiles.is_empty() {
+                0
+            } else {
+                current_combo.saturating_add(1)
+            };
+            last_combo_bonus = if combo_multiplier_enabled {
+                ComboMultiplier::bonus(merged_tiles.iter().sum(), merged_tiles.len())
+            } else {
+                0
+            };
+            bonus_points += last_combo_bonus;
 
             // Store this move in history
             // 🎵 beat_number from input: 0 = miss/off-beat, >0 = on-beat
@@ -96,11 +136,14 @@ impl GameMoveProcessor {
                 direction: *direction,
                 timestamp: *timestamp,
                 board_after: current_board,
-                score_after: current_score,
+                score_after: Game::score(current_board),
                 beat_number: *beat_number,
+                merged_tiles,
             });
 
-            is_ended = Game::is_ended(current_board);
+            // 🏁 Game over: a 32768 tile, or no direction changes the board any more
+            is_deadlocked = !Moves::has_any_valid_move(current_board);
+            is_ended = Game::highest_tile(current_board) >= 32768 || is_deadlocked;
 
             if is_ended {
                 break;
@@ -127,8 +170,13 @@ impl GameMoveProcessor {
             final_highest_tile,
             initial_highest_tile,
             is_ended,
+            is_deadlocked,
             latest_timestamp,
             move_history,
+            bonus_points,
+            null_moves,
+            current_combo,
+            last_combo_bonus,
         }
     }
 }
@@ -141,13 +189,133 @@ pub enum GameMoveResult {
         final_highest_tile: u64,
         initial_highest_tile: u64, // Kept for potential future use
         is_ended: bool,
+        is_deadlocked: bool, // 🏁 The final board has no valid move
         latest_timestamp: u64,
         move_history: Vec<ProcessedMove>,
+        bonus_points: u64, // ⏰ Extra points from bonus windows and combos (not in final_score)
+        null_moves: u32,   // 🛡️ Moves that did not change the board
+        current_combo: u8, // 💥 Consecutive moves with merges
+        last_combo_bonus: u64, // 💥 Combo bonus of the latest move
     },
     /// 🔒 NEW: All moves in the batch were duplicates (already processed)
     /// This is NOT an error - it means a retry succeeded but had no new moves
     NoNewMoves {
         skipped_count: usize,
     },
+    /// 🔒 The move at `at_index` is invalid; none of the batch is applied
+    InvalidMoveSequence {
+        at_index: usize,
+        reason: String,
+    },
     Error(String),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use game2048::BonusWindow;
+
+    fn process(moves: &[(Direction, u64, u32)], start_time: Option<u64>) -> GameMoveResult {
+        GameMoveProcessor::process_moves(
+            "board",
+            "player",
+            moves,
+            0x0000_0000_0000_0011,
+            0,
+            start_time,
+            None,
+            1.0,
+            &SpawnDistribution::default(),
+            false,
+            0,
+        )
+    }
+
+    #[test]
+    fn test_invalid_move_rejects_whole_batch() {
+        let moves = [
+            (Direction::Left, 5_000, 0),
+            (Direction::Up, 6_000, 0),
+            (Direction::Right, 1_000, 0),
+        ];
+        // The third move predates the start: its index is reported and no board is returned
+        match process(&moves, Some(2_000_000)) {
+            GameMoveResult::InvalidMoveSequence { at_index, .. } => assert_eq!(at_index, 2),
+            _ => panic!("expected an invalid move sequence"),
+        }
+        assert!(matches!(
+            process(&moves[..1], Some(2_000_000)),
+            GameMoveResult::Success { .. }
+        ));
+    }
+
+    #[test]
+    fn test_bonus_window_multiplies_merge_points_only() {
+        let window = BonusWindow {
+            start: 1_000,
+            end: 2_000,
+            multiplier: 2.0,
+            window_name: "double".to_string(),
+        };
+        // The window is looked up at block time, whatever the moves' client timestamps say
+        let multiplier = BonusWindow::multiplier_at(std::slice::from_ref(&window), 1_500);
+        // Two 2s merge into a 4: 4 merge points, doubled, whatever tile spawns
+        for timestamp in 1..20 {
+            let result = GameMoveProcessor::process_moves(
+                "board",
+                "player",
+                &[(Direction::Left, timestamp, 0)],
+                0x0000_0000_0000_0011,
+                0,
+                None,
+                None,
+                multiplier,
+                &SpawnDistribution::default(),
+                false,
+                0,
+            );
+            match result {
+                GameMoveResult::Success { bonus_points, .. } => assert_eq!(bonus_points, 4),
+                _ => panic!("expected the move to succeed"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_deadlock_is_flagged_on_the_move_that_causes_it() {
+        // Alternating 8s and 16s with one gap: filling the gap leaves no merge anywhere
+        let board = 0x3434_4343_3434_0434;
+        let direction = [Direction::Left, Direction::Right]
+            .into_iter()
+            .find(|direction| Game::slide(board, *direction) != board)
+            .unwrap();
+        match GameMoveProcessor::process_moves(
+            "board",
+            "player",
+            &[(direction, 1, 0)],
+            board,
+            0,
+            None,
+            None,
+            1.0,
+            &SpawnDistribution::default(),
+            false,
+            0,
+        ) {
+            GameMoveResult::Success {
+                final_board,
+                is_ended,
+                is_deadlocked,
+                ..
+            } => {
+                assert!(is_deadlocked && is_ended);
+                assert!(!Moves::has_any_valid_move(final_board));
+            }
+            _ => panic!("expected the move to succeed"),
+        }
+        match process(&[(Direction::Left, 1, 0)], None) {
+            GameMoveResult::Success { is_deadlocked, .. } => assert!(!is_deadlocked),
+            _ => panic!("expected the move to succeed"),
+        }
+    }
+}
@@ -0,0 +1,92 @@
+//! Beacon Messages Handler
+//!
+//! Collects contributions from registered shard chains on the main chain and publishes a new
+//! randomness beacon once enough distinct shards have contributed. Each contribution is derived
+//! from the receiving block, so the last shard of a round cannot grind the beacon value.
+
+use game2048::{
+    beacon_contribution, next_beacon_value, RandomnessBeacon, BEACON_CONTRIBUTIONS_PER_ROUND,
+};
+use linera_sdk::linera_base_types::ChainId;
+
+pub struct BeaconMessageHandler;
+
+impl BeaconMessageHandler {
+    /// 🎲 Record a contribution; each registered shard counts once per round
+    pub async fn handle_beacon_contribution(
+        contract: &mut crate::Game2048Contract,
+        shard_chain_id: ChainId,
+    ) {
+        if !contract.is_main_chain() {
+            return;
+        }
+        // The contribution must be sent by the chain it claims to come from
+        if contract.runtime.message_origin_chain_id() != Some(shard_chain_id) {
+            return;
+        }
+        if !Self::is_registered_shard(contract, &shard_chain_id.to_string()).await {
+            return;
+        }
+
+        let mut contributions = contract.state.beacon_contributions.get().clone();
+        if contributions
+            .iter()
+            .any(|(chain_id, _)| *chain_id == shard_chain_id)
+        {
+            return;
+        }
+        let received_at = contract.runtime.system_time().micros();
+        let block_height = contract.runtime.block_height().0;
+        contributions.push((
+            shard_chain_id,
+            beacon_contribution(&shard_chain_id.to_string(), received_at, block_height),
+        ));
+
+        if contributions.len() < BEACON_CONTRIBUTIONS_PER_ROUND {
+            contract.state.beacon_contributions.set(contributions);
+            return;
+        }
+
+        let previous = contract.state.randomness_beacon.get().clone();
+        let chain_hashes: Vec<[u8; 32]> = contributions.iter().map(|(_, hash)| *hash).collect();
+        let beacon = RandomnessBeacon {
+            beacon_value: next_beacon_value(previous.beacon_value, &chain_hashes),
+            beacon_round: previous.beacon_round + 1,
+            contributed_chains: contributions
+                .into_iter()
+                .map(|(chain_id, _)| chain_id)
+                .collect(),
+        };
+        contract.state.beacon_contributions.set(Vec::new());
+        contract.state.randomness_beacon.set(beacon.clone());
+
+        use crate::contract_domain::events::emitters::EventEmitter;
+        EventEmitter::emit_beacon_updated(contract, beacon).await;
+    }
+
+    /// Whether the chain is a shard of a leaderboard created on the main chain
+    async fn is_registered_shard(contract: &mut crate::Game2048Contract, chain_id: &str) -> bool {
+        for leaderboard_id in contract.state.leaderboards.indices().await.unwrap() {
+            let Some(leaderboard) = contract
+                .state
+                .leaderboards
+                .try_load_entry(&leaderboard_id)
+                .await
+                .unwrap()
+            else {
+                continue;
+            };
+            if leaderboard
+                .shard_ids
+                .elements()
+                .await
+                .unwrap()
+                .iter()
+                .any(|shard_id| shard_id == chain_id)
+            {
+                return true;
+            }
+        }
+        false
+    }
+}
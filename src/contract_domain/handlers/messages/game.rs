@@ -1,14 +1,16 @@
-use game2048::{hash_seed, Game, RegistrationCheck};
-/// Game Messages Handler
-///
-/// Handles game-related messages including board creation.
-/// 
-/// 🚀 MESSAGE-BASED ARCHITECTURE: Score updates now use SubmitScore message.
-/// No events are emitted on board creation (score=0 boards don't send messages).
+//! Game Messages Handler
+//!
+//! Handles game-related messages including board creation.
+//!
+//! 🚀 MESSAGE-BASED ARCHITECTURE: Score updates now use SubmitScore message.
+//! No events are emitted on board creation (score=0 boards don't send messages).
+
+use game2048::{hash_seed, Game, RegistrationCheck, SpawnDistribution, SpawnGenerator, TileWeight};
 
 pub struct GameMessageHandler;
 
 impl GameMessageHandler {
+    #[allow(clippy::too_many_arguments)]
     pub async fn handle_create_new_board(
         contract: &mut crate::Game2048Contract,
         seed: String,
@@ -20,6 +22,8 @@ impl GameMessageHandler {
         end_time: u64,
         // 🎵 Rhythm mode: which music track was used (-1 = no rhythm/metronome, 0+ = track index)
         rhythm_track_index: i16,
+        // 🎲 Spawn weights of the tournament (empty = classic)
+        tile_weights: Vec<TileWeight>,
     ) {
         contract
             .check_player_registered(&player, RegistrationCheck::EnsureRegistered)
@@ -40,7 +44,10 @@ impl GameMessageHandler {
         let mut board_id = hash_seed(&seed, &player, timestamp).to_string();
         board_id = format!("{}.{}", player_obj.chain_id.get(), board_id);
 
-        let new_board = Game::new(&board_id, &player, timestamp).board;
+        let Ok(spawns) = SpawnDistribution::from_weights(&tile_weights) else {
+            return;
+        };
+        let new_board = Game::with_spawns(&board_id, &player, timestamp, spawns).board;
         let game = contract
             .state
             .boards
@@ -58,6 +65,8 @@ impl GameMessageHandler {
         game.created_at.set(timestamp);
         // 🎵 Rhythm mode: store track index for replay (-1 = no rhythm/metronome)
         game.rhythm_track_index.set(rhythm_track_index);
+        game.tile_weights.set(tile_weights);
+        game.spawn_generator.set(SpawnGenerator::CURRENT);
 
         contract.state.latest_board_id.set(board_id);
 
@@ -1,11 +1,20 @@
 /// Leaderboard Messages Handler
 ///
 /// Handles leaderboard-related messages including creation and score submissions.
+use std::str::FromStr;
+
+use crate::contract_domain::ContractHelpers;
+use game2048::{
+    BracketMatchup, Matchup, PrizePoolEntry, RewardStructure, ShardAuth, TournamentStatus,
+    MAX_PLATFORM_HIGHLIGHTS,
+};
+use linera_sdk::linera_base_types::{Account, AccountOwner, Amount, ChainId};
 use linera_sdk::views::View;
 
 pub struct LeaderboardMessageHandler;
 
 impl LeaderboardMessageHandler {
+    #[allow(clippy::too_many_arguments)]
     pub async fn handle_create_leaderboard(
         contract: &mut crate::Game2048Contract,
         leaderboard_id: String,
@@ -30,8 +39,9 @@ impl LeaderboardMessageHandler {
         // 🔒 FIX: Clear old tournament data when creating/updating a tournament
         // Check if this is a NEW tournament (different leaderboard_id) or fresh chain
         let current_tournament_id = leaderboard.leaderboard_id.get();
-        let is_new_tournament = current_tournament_id.is_empty() || current_tournament_id != &leaderboard_id;
-        
+        let is_new_tournament =
+            current_tournament_id.is_empty() || current_tournament_id != &leaderboard_id;
+
         if is_new_tournament && !leaderboard_id.is_empty() {
             // Clear all old score data from previous tournament
             leaderboard.score.clear();
@@ -40,18 +50,25 @@ impl LeaderboardMessageHandler {
             leaderboard.active_boards.clear();
             leaderboard.player_activity_scores.clear();
             leaderboard.player_board_counts.clear();
+            leaderboard.predictions.clear();
+            leaderboard.prediction_winner.set(None);
             leaderboard.total_boards.set(0);
             leaderboard.total_players.set(0);
-            
+            leaderboard.games_by_hour.set(Vec::new());
+            leaderboard.games_per_day.clear();
+
             // Clear shard data too
             shard.score.clear();
             shard.board_ids.clear();
             shard.is_ended.clear();
             shard.player_chain_ids.clear();
             shard.highest_tiles.clear();
+            shard.avg_move_times.clear();
             shard.game_statuses.clear();
             shard.counter.set(0);
             shard.active_boards.clear();
+            shard.compact_scores.clear();
+            shard.bulk_sent_scores.clear();
             shard.tournament_player_board_counts.clear();
             shard.monitored_player_chains.clear();
             shard.active_players_count.set(0);
@@ -68,7 +85,9 @@ impl LeaderboardMessageHandler {
         leaderboard.host.set(host);
         leaderboard.start_time.set(start_time);
         leaderboard.end_time.set(end_time);
-        leaderboard.admin_base_triggerer_count.set(base_triggerer_count);
+        leaderboard
+            .admin_base_triggerer_count
+            .set(base_triggerer_count);
         // total_shard_count is used by shards, not leaderboard
         let _ = total_shard_count;
 
@@ -95,11 +114,12 @@ impl LeaderboardMessageHandler {
     }
 
     /// 🚀 PRIMARY: Handle direct score submission from player chain
-    /// 
+    ///
     /// This is the main handler for the message-based architecture.
     /// Player chains send SubmitScore directly to leaderboard chain.
-    /// 
+    ///
     /// 🔒 VALIDATION: Validates that tournament times in message match leaderboard times (if set)
+    #[allow(clippy::too_many_arguments)]
     pub async fn handle_submit_score(
         contract: &mut crate::Game2048Contract,
         player: String,
@@ -109,10 +129,30 @@ impl LeaderboardMessageHandler {
         highest_tile: u64,
         game_status: game2048::GameStatus,
         timestamp: u64,
-        boards_in_tournament: u32,
+        boards_per_leaderboard: std::collections::HashMap<String, u32>,
         start_time: u64,
         end_time: u64,
+        streak_bonus_applied: bool,
+        is_warmup: bool,
+        average_branching_factor: f64,
+        hints_used: u32,
+        moves_count: u32,
+        message_nonce: u64,
+        tile_milestone_hit: Option<(u32, u64)>,
+        top_boards: Vec<game2048::BoardSummary>,
     ) {
+        // 🧳 MIGRATION: Scores from a chain the player migrated away from no longer count
+        if contract
+            .state
+            .migrated_players
+            .contains_key(&player_chain_id)
+            .await
+            .unwrap()
+        {
+            return;
+        }
+
+        let current_time = contract.runtime.system_time().micros();
         let leaderboard = contract
             .state
             .leaderboards
@@ -120,11 +160,34 @@ impl LeaderboardMessageHandler {
             .await
             .unwrap();
 
+        // 🏁 The final ranking is fixed once the tournament is finalised
+        if *leaderboard.status.get() == TournamentStatus::Finalised {
+            return;
+        }
+
+        // 🔁 DEDUP: Drop retried or out-of-order score messages for the same board
+        let last_applied = leaderboard.last_applied_nonce.get(&board_id).await.unwrap();
+        if !game2048::is_fresh_nonce(last_applied, message_nonce) {
+            return;
+        }
+        if message_nonce > 0 {
+            leaderboard
+                .last_applied_nonce
+                .insert(&board_id, message_nonce)
+                .unwrap();
+        }
+        if !top_boards.is_empty() {
+            leaderboard
+                .player_top_boards
+                .insert(&player, top_boards)
+                .unwrap();
+        }
+
         // 🔒 VALIDATION: Verify tournament times match leaderboard's stored times
         // Only validate if BOTH have non-zero times (0 = unlimited)
         let lb_start_time = *leaderboard.start_time.get();
         let lb_end_time = *leaderboard.end_time.get();
-        
+
         // Validate start_time: if both are set (non-zero), they must match
         if start_time != 0 && lb_start_time != 0 && start_time != lb_start_time {
             // Silently reject - times don't match (possible tampering or stale board)
@@ -136,9 +199,100 @@ impl LeaderboardMessageHandler {
             return;
         }
 
+        // 🏁 WARMUP: Warmup games exercise the pipeline but are not ranked
+        if is_warmup {
+            let best = leaderboard
+                .warmup_scores
+                .get(&board_id)
+                .await
+                .unwrap()
+                .unwrap_or(0);
+            leaderboard
+                .warmup_scores
+                .insert(&board_id, best.max(score))
+                .unwrap();
+            leaderboard.warmup_players.insert(&player, true).unwrap();
+            return;
+        }
+
+        // 🎟️ WAITLIST: Players without a slot in a full tournament wait instead of ranking
+        if !Self::claim_slot(
+            leaderboard,
+            &mut contract.state.waitlist,
+            &player,
+            &player_chain_id,
+            timestamp,
+            current_time,
+        )
+        .await
+        {
+            return;
+        }
+
+        if average_branching_factor > 0.0 {
+            leaderboard
+                .branching_factors
+                .insert(&board_id, average_branching_factor)
+                .unwrap();
+        }
+
+        // ⚡ EFFICIENCY: Tracked apart from the best score, on whichever board did best per move
+        let efficiency = game2048::efficiency(score, moves_count);
+        if efficiency
+            > leaderboard
+                .efficiency
+                .get(&player)
+                .await
+                .unwrap()
+                .unwrap_or(0)
+        {
+            leaderboard.efficiency.insert(&player, efficiency).unwrap();
+            leaderboard
+                .best_efficiency_board_ids
+                .insert(&player, board_id.clone())
+                .unwrap();
+        }
+
+        // 🏎️ SPEED RUN: Keep the player's fastest time to the target tile
+        if let (Some(target_tile), Some((tile, time_to_target))) =
+            (leaderboard.mode.get().target_tile(), tile_milestone_hit)
+        {
+            let fastest = leaderboard.time_to_target.get(&player).await.unwrap();
+            if tile == target_tile && fastest.is_none_or(|fastest| time_to_target < fastest) {
+                leaderboard
+                    .time_to_target
+                    .insert(&player, time_to_target)
+                    .unwrap();
+                leaderboard.leaderboard_last_update.set(timestamp);
+            }
+        }
+
+        // ↪️ CONTINUATION: The player's entry moves to the new board with the carried-over score
+        if let game2048::GameStatus::Ended(game2048::GameEndReason::Continued { new_board_id }) =
+            &game_status
+        {
+            if leaderboard.board_ids.get(&player).await.unwrap().as_ref() == Some(&board_id) {
+                leaderboard.score.insert(&player, score).unwrap();
+                leaderboard
+                    .board_ids
+                    .insert(&player, new_board_id.clone())
+                    .unwrap();
+                Self::set_highest_tile(leaderboard, &player, highest_tile).await;
+                leaderboard.last_update.insert(&player, timestamp).unwrap();
+                leaderboard
+                    .streak_bonus_applied
+                    .insert(&player, streak_bonus_applied)
+                    .unwrap();
+                leaderboard.leaderboard_last_update.set(timestamp);
+            }
+            let _ = leaderboard.active_boards.remove(&board_id);
+            return;
+        }
+
         // Get current best score for this player
         let current_best = leaderboard.score.get(&player).await.unwrap().unwrap_or(0);
-        let is_new_player = current_best == 0 && leaderboard.board_ids.get(&player).await.unwrap().is_none();
+        let is_new_player =
+            current_best == 0 && leaderboard.board_ids.get(&player).await.unwrap().is_none();
 
         // Only update if better score (or equal score from ended game)
         let is_ended = matches!(game_status, game2048::GameStatus::Ended(_));
@@ -146,14 +300,49 @@ impl LeaderboardMessageHandler {
 
         if should_update {
             leaderboard.score.insert(&player, score).unwrap();
-            leaderboard.board_ids.insert(&player, board_id.clone()).unwrap();
-            leaderboard.highest_tiles.insert(&player, highest_tile).unwrap();
+            leaderboard
+                .board_ids
+                .insert(&player, board_id.clone())
+                .unwrap();
+            Self::set_highest_tile(leaderboard, &player, highest_tile).await;
             leaderboard.last_update.insert(&player, timestamp).unwrap();
-            
+            leaderboard
+                .streak_bonus_applied
+                .insert(&player, streak_bonus_applied)
+                .unwrap();
+            leaderboard.hints_used.insert(&player, hints_used).unwrap();
+            leaderboard
+                .best_moves_counts
+                .insert(&player, moves_count)
+                .unwrap();
+            if score > current_best || is_new_player {
+                leaderboard
+                    .first_achievement_time
+                    .insert(&player, timestamp)
+                    .unwrap();
+            }
+
             // Update global leaderboard timestamp for staleness check
             leaderboard.leaderboard_last_update.set(timestamp);
         }
 
+        // 🎯 SCORE CAP: Players who reached the cap first rank first
+        if matches!(
+            game_status,
+            game2048::GameStatus::Ended(game2048::GameEndReason::ScoreCapReached)
+        ) && leaderboard
+            .cap_reached_at
+            .get(&player)
+            .await
+            .unwrap()
+            .is_none()
+        {
+            leaderboard
+                .cap_reached_at
+                .insert(&player, timestamp)
+                .unwrap();
+        }
+
         // Track game ended status
         if is_ended {
             leaderboard.is_ended.insert(&player, true).unwrap();
@@ -174,13 +363,24 @@ impl LeaderboardMessageHandler {
                 .unwrap();
         }
 
+        // 🏅 Remember where to send prizes
+        leaderboard
+            .player_chain_ids
+            .insert(&player, player_chain_id.clone())
+            .unwrap();
+
         // Track new player
         if is_new_player {
             let count = *leaderboard.total_players.get();
             leaderboard.total_players.set(count + 1);
         }
 
-        // Update board count for this player (take max seen)
+        // Update board count for this player (take max seen); counts for other leaderboards
+        // are ignored
+        let boards_in_tournament = boards_per_leaderboard
+            .get(leaderboard.leaderboard_id.get())
+            .copied()
+            .unwrap_or(0);
         let current_board_count = leaderboard
             .player_board_counts
             .get(&player_chain_id)
@@ -193,6 +393,25 @@ impl LeaderboardMessageHandler {
                 .insert(&player_chain_id, boards_in_tournament)
                 .unwrap();
 
+            // 📊 ANALYTICS: Count the newly reported boards in this hour and day
+            let new_boards = boards_in_tournament - current_board_count;
+            let now_seconds = contract.runtime.system_time().micros() / 1_000_000;
+            let mut games_by_hour = leaderboard.games_by_hour.get().clone();
+            games_by_hour.resize(24, 0);
+            games_by_hour[(now_seconds / 3600 % 24) as usize] += new_boards;
+            leaderboard.games_by_hour.set(games_by_hour);
+            let day = now_seconds / 86_400;
+            let games_today = leaderboard
+                .games_per_day
+                .get(&day)
+                .await
+                .unwrap()
+                .unwrap_or(0);
+            leaderboard
+                .games_per_day
+                .insert(&day, games_today + new_boards)
+                .unwrap();
+
             // Recalculate total boards
             let mut total = 0u32;
             leaderboard
@@ -206,4 +425,1301 @@ impl LeaderboardMessageHandler {
             leaderboard.total_boards.set(total);
         }
     }
+
+    /// 🔮 SPECTATOR: Store a winner prediction (one per spectator chain)
+    ///
+    /// Late predictions (after tournament end), duplicates and predictions whose
+    /// origin chain doesn't match the claimed spectator chain are silently rejected.
+    pub async fn handle_submit_prediction(
+        contract: &mut crate::Game2048Contract,
+        spectator_chain_id: String,
+        predicted_winner: String,
+        predicted_score: u64,
+        timestamp: u64,
+    ) {
+        if let Some(origin) = contract.runtime.message_origin_chain_id() {
+            if origin.to_string() != spectator_chain_id {
+                return;
+            }
+        }
+
+        let current_time = contract.runtime.system_time().micros();
+        let leaderboard = contract
+            .state
+            .leaderboards
+            .load_entry_mut("")
+            .await
+            .unwrap();
+
+        let end_time = *leaderboard.end_time.get();
+        if end_time != 0 && current_time >= end_time {
+            return;
+        }
+
+        if leaderboard
+            .predictions
+            .contains_key(&spectator_chain_id)
+            .await
+            .unwrap()
+        {
+            return;
+        }
+
+        leaderboard
+            .predictions
+            .insert(
+                &spectator_chain_id,
+                game2048::TournamentPrediction {
+                    spectator_chain_id: spectator_chain_id.clone(),
+                    predicted_winner,
+                    predicted_score,
+                    submitted_at: timestamp,
+                },
+            )
+            .unwrap();
+    }
+
+    /// 📡 LIVE STREAM: Register (or refresh) a relay endpoint, max 3 per leaderboard
+    pub async fn handle_register_stream_endpoint(
+        contract: &mut crate::Game2048Contract,
+        endpoint_url: String,
+        auth_token_hash: String,
+        relay_owner: String,
+        timestamp: u64,
+    ) {
+        let leaderboard = contract
+            .state
+            .leaderboards
+            .load_entry_mut("")
+            .await
+            .unwrap();
+
+        let mut endpoints = leaderboard.stream_endpoints.get().clone();
+        if let Some(existing) = endpoints
+            .iter_mut()
+            .find(|endpoint| endpoint.endpoint_url == endpoint_url)
+        {
+            existing.auth_token_hash = auth_token_hash;
+            existing.relay_owner = relay_owner;
+            existing.consecutive_failures = 0;
+        } else if endpoints.len() < 3 {
+            endpoints.push(game2048::StreamEndpoint {
+                endpoint_url,
+                auth_token_hash,
+                relay_owner,
+                consecutive_failures: 0,
+                registered_at: timestamp,
+            });
+        } else {
+            // Cap reached - silently ignore
+            return;
+        }
+        leaderboard.stream_endpoints.set(endpoints);
+    }
+
+    /// ⏰ BONUS WINDOW: Store a window scheduled by the main chain (max 5 pending/active)
+    pub async fn handle_add_bonus_window(
+        contract: &mut crate::Game2048Contract,
+        window: game2048::BonusWindow,
+    ) {
+        let current_time = contract.runtime.system_time().micros();
+        let leaderboard = contract
+            .state
+            .leaderboards
+            .load_entry_mut("")
+            .await
+            .unwrap();
+
+        let mut windows = leaderboard.active_bonus_windows.get().clone();
+        windows.retain(|existing| existing.end > current_time);
+        if windows.len() >= game2048::MAX_BONUS_WINDOWS {
+            // Cap reached - silently ignore
+            return;
+        }
+        windows.push(window);
+        leaderboard.active_bonus_windows.set(windows);
+    }
+
+    /// ⚖️ HANDICAP: Store a player's score multiplier set by the main chain
+    pub async fn handle_set_player_handicap(
+        contract: &mut crate::Game2048Contract,
+        player: String,
+        multiplier: f64,
+    ) {
+        if !(game2048::MIN_HANDICAP_MULTIPLIER..=1.0).contains(&multiplier) {
+            return;
+        }
+        let leaderboard = contract
+            .state
+            .leaderboards
+            .load_entry_mut("")
+            .await
+            .unwrap();
+        if multiplier == 1.0 {
+            leaderboard.player_handicaps.remove(&player).unwrap();
+        } else {
+            leaderboard
+                .player_handicaps
+                .insert(&player, multiplier)
+                .unwrap();
+        }
+    }
+
+    /// 📦 Apply a shard's batched score changes, one entry at a time (best score per player)
+    pub async fn handle_bulk_score_update(
+        contract: &mut crate::Game2048Contract,
+        scores: Vec<game2048::ScoreUpdateEntry>,
+        shard_id: String,
+        batch_timestamp: u64,
+    ) {
+        if contract
+            .runtime
+            .message_origin_chain_id()
+            .map(|origin| origin.to_string())
+            != Some(shard_id.clone())
+        {
+            return;
+        }
+        let current_time = contract.runtime.system_time().micros();
+        let leaderboard = contract
+            .state
+            .leaderboards
+            .load_entry_mut("")
+            .await
+            .unwrap();
+
+        // 🔑 Shards with a registered key must sign the blocks that send their updates
+        if let Some(public_key) = leaderboard.shard_registry.get(&shard_id).await.unwrap() {
+            if contract.runtime.authenticated_signer() != Some(ShardAuth::owner(&public_key)) {
+                return;
+            }
+        }
+
+        let mut any_update = false;
+        for entry in scores {
+            let current_best = leaderboard.score.get(&entry.player).await.unwrap();
+            if current_best.is_none()
+                && leaderboard
+                    .board_ids
+                    .get(&entry.player)
+                    .await
+                    .unwrap()
+                    .is_none()
+            {
+                let count = *leaderboard.total_players.get();
+                leaderboard.total_players.set(count + 1);
+            }
+            leaderboard
+                .participants
+                .insert(&entry.player, current_time)
+                .unwrap();
+            let current_best = current_best.unwrap_or(0);
+            if entry.score > current_best || (entry.score == current_best && entry.is_end) {
+                leaderboard
+                    .score
+                    .insert(&entry.player, entry.score)
+                    .unwrap();
+                leaderboard
+                    .board_ids
+                    .insert(&entry.player, entry.board_id.clone())
+                    .unwrap();
+                Self::set_highest_tile(leaderboard, &entry.player, entry.highest_tile).await;
+                leaderboard
+                    .last_update
+                    .insert(&entry.player, entry.timestamp)
+                    .unwrap();
+                any_update = true;
+            }
+            if entry.is_end {
+                leaderboard.is_ended.insert(&entry.player, true).unwrap();
+            }
+        }
+
+        if any_update {
+            leaderboard.leaderboard_last_update.set(batch_timestamp);
+        }
+    }
+
+    /// 🏅 PRIZE POOL: Store the prize pool set by the main chain (fixed once payouts started)
+    pub async fn handle_configure_prize_pool(
+        contract: &mut crate::Game2048Contract,
+        entries: Vec<PrizePoolEntry>,
+    ) {
+        if !Self::is_from_main_chain(contract) || PrizePoolEntry::validate_all(&entries).is_err() {
+            return;
+        }
+        let leaderboard = contract
+            .state
+            .leaderboards
+            .load_entry_mut("")
+            .await
+            .unwrap();
+        if leaderboard.prize_pool_distributed.count().await.unwrap() > 0 {
+            return;
+        }
+        leaderboard.prize_pool_entries.set(entries);
+    }
+
+    /// 🏁 WARMUP: Store the warmup window length (used to announce warmup and start)
+    pub async fn handle_configure_warmup(
+        contract: &mut crate::Game2048Contract,
+        warmup_duration_seconds: u32,
+    ) {
+        if !Self::is_from_main_chain(contract) {
+            return;
+        }
+        contract
+            .state
+            .leaderboards
+            .load_entry_mut("")
+            .await
+            .unwrap()
+            .warmup_duration_seconds
+            .set(warmup_duration_seconds);
+    }
+
+    /// ⏳ COUNTDOWN: Store the thresholds announced before the tournament ends
+    pub async fn handle_configure_countdown(
+        contract: &mut crate::Game2048Contract,
+        thresholds: Vec<u64>,
+    ) {
+        if !Self::is_from_main_chain(contract) {
+            return;
+        }
+        contract
+            .state
+            .leaderboards
+            .load_entry_mut("")
+            .await
+            .unwrap()
+            .countdown_thresholds
+            .set(thresholds);
+    }
+
+    /// 🎟️ QUALIFIER: Store where this qualifier's top players advance
+    pub async fn handle_configure_qualifier(
+        contract: &mut crate::Game2048Contract,
+        settings: game2048::QualifierSettings,
+    ) {
+        if !Self::is_from_main_chain(contract) {
+            return;
+        }
+        contract
+            .state
+            .leaderboards
+            .load_entry_mut("")
+            .await
+            .unwrap()
+            .qualifier_settings
+            .set(Some(settings));
+    }
+
+    /// 🎟️ WAITLIST: Store how many players the tournament admits (0 = no limit)
+    pub async fn handle_configure_max_participants(
+        contract: &mut crate::Game2048Contract,
+        max_participants: u32,
+    ) {
+        if !Self::is_from_main_chain(contract) {
+            return;
+        }
+        contract
+            .state
+            .leaderboards
+            .load_entry_mut("")
+            .await
+            .unwrap()
+            .max_participants
+            .set((max_participants > 0).then_some(max_participants));
+    }
+
+    /// 🎟️ WAITLIST: Queue a player whose chain found the tournament full
+    pub async fn handle_join_waitlist(
+        contract: &mut crate::Game2048Contract,
+        player: String,
+        timestamp: u64,
+    ) {
+        let Some(origin) = contract.runtime.message_origin_chain_id() else {
+            return;
+        };
+        let leaderboard = contract
+            .state
+            .leaderboards
+            .load_entry_mut("")
+            .await
+            .unwrap();
+        if leaderboard
+            .participants
+            .contains_key(&player)
+            .await
+            .unwrap()
+            || leaderboard
+                .waitlist_admitted
+                .contains_key(&player)
+                .await
+                .unwrap()
+        {
+            return;
+        }
+        leaderboard
+            .waitlist_chain_ids
+            .insert(&player, origin.to_string())
+            .unwrap();
+        let leaderboard_id = leaderboard.leaderboard_id.get().clone();
+        Self::add_to_waitlist(
+            &mut contract.state.waitlist,
+            &leaderboard_id,
+            player,
+            timestamp,
+        )
+        .await;
+    }
+
+    /// 🎟️ WAITLIST: Hold a slot for a player who started a board, or queue them if the
+    /// tournament is full
+    pub async fn handle_register_participant(
+        contract: &mut crate::Game2048Contract,
+        player: String,
+        timestamp: u64,
+    ) {
+        let Some(origin) = contract.runtime.message_origin_chain_id() else {
+            return;
+        };
+        let current_time = contract.runtime.system_time().micros();
+        let leaderboard = contract
+            .state
+            .leaderboards
+            .load_entry_mut("")
+            .await
+            .unwrap();
+        Self::claim_slot(
+            leaderboard,
+            &mut contract.state.waitlist,
+            &player,
+            &origin.to_string(),
+            timestamp,
+            current_time,
+        )
+        .await;
+    }
+
+    /// 🎟️ WAITLIST: Give the player a slot (or refresh theirs) and return true, or queue them
+    /// and return false if the tournament is full. Admitted and pre-qualified players always
+    /// get a slot.
+    async fn claim_slot(
+        leaderboard: &mut crate::state::Leaderboard,
+        waitlist: &mut linera_sdk::views::MapView<String, Vec<(String, u64)>>,
+        player: &str,
+        player_chain_id: &str,
+        timestamp: u64,
+        current_time: u64,
+    ) -> bool {
+        if !leaderboard.participants.contains_key(player).await.unwrap() {
+            let is_admitted = leaderboard
+                .waitlist_admitted
+                .contains_key(player)
+                .await
+                .unwrap();
+            if is_admitted {
+                leaderboard.waitlist_admitted.remove(player).unwrap();
+                let _ = leaderboard.waitlist_chain_ids.remove(player);
+            } else if !leaderboard
+                .pre_qualified
+                .contains_key(player)
+                .await
+                .unwrap()
+                && Self::is_full(leaderboard).await
+            {
+                leaderboard
+                    .waitlist_chain_ids
+                    .insert(player, player_chain_id.to_string())
+                    .unwrap();
+                let leaderboard_id = leaderboard.leaderboard_id.get().clone();
+                Self::add_to_waitlist(waitlist, &leaderboard_id, player.to_string(), timestamp)
+                    .await;
+                return false;
+            }
+        }
+        leaderboard
+            .participants
+            .insert(player, current_time)
+            .unwrap();
+        true
+    }
+
+    /// 🎟️ WAITLIST: Players holding a slot plus those holding an admission
+    async fn is_full(leaderboard: &crate::state::Leaderboard) -> bool {
+        let Some(max_participants) = *leaderboard.max_participants.get() else {
+            return false;
+        };
+        let participants = leaderboard.participants.count().await.unwrap() as u32;
+        let admitted = leaderboard.waitlist_admitted.count().await.unwrap() as u32;
+        participants + admitted >= max_participants
+    }
+
+    /// 🎟️ WAITLIST: Append the player unless already waiting (kept in timestamp order)
+    async fn add_to_waitlist(
+        waitlist: &mut linera_sdk::views::MapView<String, Vec<(String, u64)>>,
+        leaderboard_id: &str,
+        player: String,
+        timestamp: u64,
+    ) {
+        let mut entries = waitlist
+            .get(leaderboard_id)
+            .await
+            .unwrap()
+            .unwrap_or_default();
+        if entries.iter().any(|(waiting, _)| *waiting == player) {
+            return;
+        }
+        let position = entries.partition_point(|(_, joined_at)| *joined_at <= timestamp);
+        entries.insert(position, (player, timestamp));
+        waitlist.insert(leaderboard_id, entries).unwrap();
+    }
+
+    /// 🎟️ QUALIFIER: Record players advancing from a qualifier (must come from the qualifier's chain)
+    pub async fn handle_add_qualified_players(
+        contract: &mut crate::Game2048Contract,
+        players: Vec<String>,
+        source_leaderboard_id: String,
+    ) {
+        let Ok(source_chain_id) = ChainId::from_str(&source_leaderboard_id) else {
+            return;
+        };
+        if contract.runtime.message_origin_chain_id() != Some(source_chain_id) {
+            return;
+        }
+
+        let leaderboard = contract
+            .state
+            .leaderboards
+            .load_entry_mut("")
+            .await
+            .unwrap();
+        for player in &players {
+            leaderboard
+                .pre_qualified
+                .insert(player, source_leaderboard_id.clone())
+                .unwrap();
+        }
+        let to_tournament = leaderboard.leaderboard_id.get().clone();
+
+        use crate::contract_domain::events::emitters::EventEmitter;
+        EventEmitter::emit_players_qualified(
+            contract,
+            players.len() as u32,
+            source_leaderboard_id,
+            to_tournament,
+        )
+        .await;
+    }
+
+    /// 🔑 Register the public key of one of this leaderboard's shards (sent by the main chain)
+    pub async fn handle_register_shard_key(
+        contract: &mut crate::Game2048Contract,
+        shard_chain_id: String,
+        public_key: [u8; 32],
+    ) {
+        if !Self::is_from_main_chain(contract) {
+            return;
+        }
+        let leaderboard = contract
+            .state
+            .leaderboards
+            .load_entry_mut("")
+            .await
+            .unwrap();
+        let shard_ids = leaderboard.shard_ids.elements().await.unwrap();
+        if !shard_ids.contains(&shard_chain_id) {
+            return;
+        }
+        leaderboard
+            .shard_registry
+            .insert(&shard_chain_id, public_key)
+            .unwrap();
+    }
+
+    /// 🏅 PRIZE POOL: Credit tokens transferred along with this message.
+    /// Funding is capped at the token's pool amount; anything beyond it (or for a token the pool
+    /// does not list) is sent back to the funder, so the funded total can always be paid out.
+    pub async fn handle_prize_pool_funded(
+        contract: &mut crate::Game2048Contract,
+        application_id: String,
+        amount: Amount,
+        funder: AccountOwner,
+    ) {
+        let leaderboard = contract
+            .state
+            .leaderboards
+            .load_entry_mut("")
+            .await
+            .unwrap();
+        let funded = leaderboard
+            .prize_pool_funded
+            .get(&application_id)
+            .await
+            .unwrap()
+            .unwrap_or(Amount::ZERO);
+        let accepted = leaderboard
+            .prize_pool_entries
+            .get()
+            .iter()
+            .find(|entry| entry.application_id == application_id)
+            .map(|entry| entry.accepted_funding(funded, amount))
+            .unwrap_or(Amount::ZERO);
+        if accepted > Amount::ZERO {
+            leaderboard
+                .prize_pool_funded
+                .insert(&application_id, funded.saturating_add(accepted))
+                .unwrap();
+        }
+
+        let surplus = amount.saturating_sub(accepted);
+        if surplus > Amount::ZERO {
+            let Some(origin_chain_id) = contract.runtime.message_origin_chain_id() else {
+                return;
+            };
+            let source = ContractHelpers::prize_pool_owner(contract, &application_id);
+            let destination = Account {
+                chain_id: origin_chain_id,
+                owner: funder,
+            };
+            ContractHelpers::transfer_token(
+                contract,
+                &application_id,
+                source,
+                destination,
+                surplus,
+            );
+        }
+    }
+
+    /// 🏅 PRIZE POOL: Pay each token's payouts to the final ranking, one transfer per token and winner.
+    /// A token is paid out once, and only when its funded amount equals the sum of its payouts.
+    /// Players without a known chain cannot receive prizes and are left out of the ranking.
+    pub async fn handle_distribute_prizes(contract: &mut crate::Game2048Contract) {
+        if !Self::is_from_main_chain(contract) {
+            return;
+        }
+        let leaderboard = contract
+            .state
+            .leaderboards
+            .load_entry_mut("")
+            .await
+            .unwrap();
+
+        let ranking: Vec<ChainId> = Self::final_ranking(leaderboard)
+            .await
+            .into_iter()
+            .filter_map(|(_, chain_id, _)| chain_id)
+            .collect();
+        if ranking.is_empty() {
+            return;
+        }
+
+        let mut transfers = Vec::new();
+        for entry in leaderboard.prize_pool_entries.get().clone() {
+            if leaderboard
+                .prize_pool_distributed
+                .contains_key(&entry.application_id)
+                .await
+                .unwrap()
+            {
+                continue;
+            }
+            let funded = leaderboard
+                .prize_pool_funded
+                .get(&entry.application_id)
+                .await
+                .unwrap()
+                .unwrap_or(Amount::ZERO);
+            if funded != entry.amount || entry.payout_total() != funded {
+                continue;
+            }
+
+            let mut distributed = Amount::ZERO;
+            for (winner, payout) in ranking.iter().zip(&entry.payouts) {
+                transfers.push((entry.application_id.clone(), *winner, *payout));
+                distributed = distributed.saturating_add(*payout);
+            }
+            leaderboard
+                .prize_pool_distributed
+                .insert(&entry.application_id, distributed)
+                .unwrap();
+        }
+
+        for (application_id, winner, amount) in transfers {
+            let source = ContractHelpers::prize_pool_owner(contract, &application_id);
+            let destination = Account {
+                chain_id: winner,
+                owner: AccountOwner::CHAIN,
+            };
+            ContractHelpers::transfer_token(contract, &application_id, source, destination, amount);
+        }
+    }
+
+    /// 🏁 Ranking of every scored player with their chain (None = unknown) and effective
+    /// (handicapped) score: best effective score first, ties go to whoever reached the score
+    /// cap (or else the score) first
+    async fn final_ranking(
+        leaderboard: &crate::state::Leaderboard,
+    ) -> Vec<(String, Option<ChainId>, u64)> {
+        let mut scores: Vec<(String, u64)> = Vec::new();
+        leaderboard
+            .score
+            .for_each_index_value(|username, score| {
+                if *score > 0 {
+                    scores.push((username, *score));
+                }
+                Ok(())
+            })
+            .await
+            .unwrap();
+        let mut ranking = Vec::with_capacity(scores.len());
+        for (username, raw_score) in scores {
+            let score =
+                game2048::handicapped_score(raw_score, leaderboard.handicap(&username).await);
+            let chain_id = leaderboard
+                .player_chain_ids
+                .get(&username)
+                .await
+                .unwrap()
+                .and_then(|chain_id| ChainId::from_str(&chain_id).ok());
+            let reached_at = match leaderboard.cap_reached_at.get(&username).await.unwrap() {
+                Some(cap_reached_at) => cap_reached_at,
+                None => leaderboard
+                    .last_update
+                    .get(&username)
+                    .await
+                    .unwrap()
+                    .unwrap_or(0),
+            };
+            ranking.push((username, chain_id, score, reached_at));
+        }
+        ranking.sort_by(|a, b| b.2.cmp(&a.2).then(a.3.cmp(&b.3)));
+        ranking
+            .into_iter()
+            .map(|(username, chain_id, score, _)| (username, chain_id, score))
+            .collect()
+    }
+
+    /// 🏁 Snapshot the final ranking, stop accepting changes and ask the main chain to pay each
+    /// rewarded rank. Players without a known chain keep their rank but receive no reward.
+    pub async fn handle_finalise_leaderboard(
+        contract: &mut crate::Game2048Contract,
+        reward_structure: Option<RewardStructure>,
+    ) {
+        if !Self::is_from_main_chain(contract) {
+            return;
+        }
+        let leaderboard = contract
+            .state
+            .leaderboards
+            .load_entry_mut("")
+            .await
+            .unwrap();
+        if *leaderboard.status.get() == TournamentStatus::Finalised {
+            return;
+        }
+
+        let ranking = Self::final_ranking(leaderboard).await;
+        let payouts = reward_structure
+            .as_ref()
+            .map(|rewards| rewards.payouts(ranking.len()))
+            .unwrap_or_default();
+        let transfers: Vec<(ChainId, Amount)> = ranking
+            .iter()
+            .zip(payouts)
+            .filter_map(|((_, chain_id, _), amount)| Some(((*chain_id)?, amount)))
+            .filter(|(_, amount)| *amount > Amount::ZERO)
+            .collect();
+        // 🔮 Settle the spectator prediction leaderboard against the winner
+        if let Some((winner, _, winner_score)) = ranking.first() {
+            let mut predictions = Vec::new();
+            leaderboard
+                .predictions
+                .for_each_index_value(|_, prediction| {
+                    predictions.push(prediction.into_owned());
+                    Ok(())
+                })
+                .await
+                .unwrap();
+            let best = game2048::TournamentPrediction::rank_all(predictions, winner, *winner_score)
+                .into_iter()
+                .next();
+            leaderboard
+                .prediction_winner
+                .set(best.map(|prediction| prediction.spectator_chain_id));
+        }
+        leaderboard.final_ranking.set(
+            ranking
+                .into_iter()
+                .map(|(username, _, score)| (username, score))
+                .collect(),
+        );
+        leaderboard.status.set(TournamentStatus::Finalised);
+        leaderboard.reward_structure.set(reward_structure);
+
+        let main_chain_id = contract.runtime.application_creator_chain_id();
+        for (chain_id, amount) in transfers {
+            contract.send_message(
+                main_chain_id,
+                game2048::Message::Transfer { chain_id, amount },
+            );
+        }
+    }
+
+    /// 🏆 Record a player's highest tile and move them to its milestone bucket in `tiles_index`
+    async fn set_highest_tile(
+        leaderboard: &mut crate::state::Leaderboard,
+        player: &str,
+        highest_tile: u64,
+    ) {
+        let previous = leaderboard.highest_tiles.get(player).await.unwrap();
+        leaderboard
+            .highest_tiles
+            .insert(player, highest_tile)
+            .unwrap();
+
+        let milestone = game2048::tile_milestone(highest_tile);
+        if let Some(previous_milestone) = previous.map(game2048::tile_milestone) {
+            if previous_milestone == milestone {
+                return;
+            }
+            let mut players = leaderboard
+                .tiles_index
+                .get(&previous_milestone)
+                .await
+                .unwrap()
+                .unwrap_or_default();
+            players.retain(|name| name != player);
+            if players.is_empty() {
+                leaderboard.tiles_index.remove(&previous_milestone).unwrap();
+            } else {
+                leaderboard
+                    .tiles_index
+                    .insert(&previous_milestone, players)
+                    .unwrap();
+            }
+        }
+        let mut players = leaderboard
+            .tiles_index
+            .get(&milestone)
+            .await
+            .unwrap()
+            .unwrap_or_default();
+        players.push(player.to_string());
+        leaderboard.tiles_index.insert(&milestone, players).unwrap();
+    }
+
+    /// 🌟 PLATFORM EVENTS: On the main chain, publish a tournament's highlight and fan it out
+    /// to subscribers; on a subscriber chain, keep it with the latest highlights
+    pub async fn handle_platform_highlight(
+        contract: &mut crate::Game2048Contract,
+        highlight: game2048::PlatformHighlight,
+    ) {
+        let Some(origin) = contract.runtime.message_origin_chain_id() else {
+            return;
+        };
+
+        if !contract.is_main_chain() {
+            if !Self::is_from_main_chain(contract) {
+                return;
+            }
+            let highlights = &mut contract.state.platform_highlights;
+            highlights.push_back(highlight);
+            while highlights.count() > MAX_PLATFORM_HIGHLIGHTS {
+                highlights.delete_front();
+            }
+            return;
+        }
+
+        // Only the tournament's own leaderboard chain can announce its highlights
+        if origin.to_string() != highlight.leaderboard_id
+            || contract
+                .state
+                .leaderboards
+                .try_load_entry(&highlight.leaderboard_id)
+                .await
+                .unwrap()
+                .is_none()
+        {
+            return;
+        }
+
+        let mut subscribers = Vec::new();
+        contract
+            .state
+            .platform_subscribers
+            .for_each_index(|chain_id| {
+                subscribers.push(chain_id);
+                Ok(())
+            })
+            .await
+            .unwrap();
+        for chain_id in subscribers {
+            contract.send_message(
+                chain_id,
+                game2048::Message::PlatformHighlight {
+                    highlight: highlight.clone(),
+                },
+            );
+        }
+
+        use crate::contract_domain::events::emitters::EventEmitter;
+        EventEmitter::emit_platform_highlight(contract, highlight).await;
+    }
+
+    /// ⏸️ PAUSE: Shards hold or replay score updates; the leaderboard chain announces the change
+    pub async fn handle_set_leaderboard_paused(
+        contract: &mut crate::Game2048Contract,
+        leaderboard_id: String,
+        paused: bool,
+        reason: String,
+        timestamp: u64,
+    ) {
+        if !Self::is_from_main_chain(contract) {
+            return;
+        }
+        crate::contract_domain::handlers::messages::ShardMessageHandler::set_paused(
+            contract,
+            &leaderboard_id,
+            paused,
+        )
+        .await;
+
+        if contract.runtime.chain_id().to_string() == leaderboard_id {
+            use crate::contract_domain::events::emitters::EventEmitter;
+            EventEmitter::emit_leaderboard_paused(
+                contract,
+                leaderboard_id,
+                paused,
+                reason,
+                timestamp,
+            )
+            .await;
+        }
+    }
+
+    /// 🧳 MIGRATION: Retire the player's old chain and point their chain id at the new one
+    /// (leaderboard and shard chains)
+    pub async fn handle_player_migration(
+        contract: &mut crate::Game2048Contract,
+        old_player: String,
+        new_chain_id: String,
+        new_player_record: game2048::PlayerMigrationRecord,
+    ) {
+        if !Self::is_from_main_chain(contract) {
+            return;
+        }
+        contract
+            .state
+            .migrated_players
+            .insert(&new_player_record.old_chain_id, new_chain_id.clone())
+            .unwrap();
+
+        if contract
+            .state
+            .leaderboards
+            .try_load_entry("")
+            .await
+            .unwrap()
+            .is_some()
+        {
+            let leaderboard = contract
+                .state
+                .leaderboards
+                .load_entry_mut("")
+                .await
+                .unwrap();
+            if leaderboard
+                .player_chain_ids
+                .contains_key(&old_player)
+                .await
+                .unwrap()
+            {
+                leaderboard
+                    .player_chain_ids
+                    .insert(&old_player, new_chain_id.clone())
+                    .unwrap();
+            }
+        }
+        if contract
+            .state
+            .shards
+            .try_load_entry("")
+            .await
+            .unwrap()
+            .is_some()
+        {
+            let shard = contract.state.shards.load_entry_mut("").await.unwrap();
+            if shard
+                .player_chain_ids
+                .contains_key(&old_player)
+                .await
+                .unwrap()
+            {
+                shard
+                    .player_chain_ids
+                    .insert(&old_player, new_chain_id)
+                    .unwrap();
+            }
+        }
+    }
+
+    /// 📅 DAILY CHALLENGE: Keep each player's best result of the day on the main chain.
+    /// The first result of a new day resets the daily leaderboard.
+    pub async fn handle_daily_challenge_score(
+        contract: &mut crate::Game2048Contract,
+        date: String,
+        record: game2048::DailyChallengeRecord,
+    ) {
+        if !contract.is_main_chain() {
+            return;
+        }
+        // Only the player's own chain reports their daily challenge
+        let Some(origin) = contract.runtime.message_origin_chain_id() else {
+            return;
+        };
+        let player_chain_id = match contract.state.players.try_load_entry(&record.player).await {
+            Ok(Some(player)) => player.chain_id.get().clone(),
+            _ => return,
+        };
+        if player_chain_id != origin.to_string() {
+            return;
+        }
+
+        let leaderboard = contract
+            .state
+            .leaderboards
+            .load_entry_mut("")
+            .await
+            .unwrap();
+        let current_date = leaderboard.daily_leaderboard_date.get().clone();
+        if date < current_date {
+            return; // Finished after the day rolled over
+        }
+        if date > current_date {
+            leaderboard.daily_leaderboard.clear();
+            leaderboard.daily_leaderboard_date.set(date);
+        }
+
+        let best = leaderboard
+            .daily_leaderboard
+            .get(&record.player)
+            .await
+            .unwrap();
+        if best.is_none_or(|best| record.score > best.score) {
+            leaderboard
+                .daily_leaderboard
+                .insert(&record.player.clone(), record)
+                .unwrap();
+        }
+    }
+
+    fn is_from_main_chain(contract: &mut crate::Game2048Contract) -> bool {
+        contract.runtime.message_origin_chain_id()
+            == Some(contract.runtime.application_creator_chain_id())
+    }
+
+    /// 👣 VISITORS: Record an interaction from the message's origin chain
+    pub async fn record_visitor(contract: &mut crate::Game2048Contract) {
+        let Some(origin) = contract.runtime.message_origin_chain_id() else {
+            return;
+        };
+        let current_time = contract.runtime.system_time().micros();
+        let day_number = current_time / 86_400_000_000;
+
+        let leaderboard = contract
+            .state
+            .leaderboards
+            .load_entry_mut("")
+            .await
+            .unwrap();
+
+        let hash = <[u64; 4]>::from(origin.0)[0];
+        let (index, rank) = game2048::HyperLogLog::position(hash);
+        let current_rank = leaderboard
+            .visitor_registers
+            .get(&index)
+            .await
+            .unwrap()
+            .unwrap_or(0);
+        if rank > current_rank {
+            leaderboard.visitor_registers.insert(&index, rank).unwrap();
+        }
+
+        leaderboard
+            .total_visits
+            .set(*leaderboard.total_visits.get() + 1);
+        let (day, count) = *leaderboard.daily_visitors.get();
+        if day == day_number {
+            leaderboard.daily_visitors.set((day, count + 1));
+        } else {
+            leaderboard.daily_visitors.set((day_number, 1));
+        }
+    }
+
+    /// 🔁 REPLAY: Enter replay mode, discarding any previous replay
+    pub async fn handle_initiate_replay(contract: &mut crate::Game2048Contract) {
+        let leaderboard = contract
+            .state
+            .leaderboards
+            .load_entry_mut("")
+            .await
+            .unwrap();
+        leaderboard.replay_mode.set(true);
+        leaderboard.replay_scores.clear();
+        leaderboard.replay_results.set(Vec::new());
+    }
+
+    /// 🔁 REPLAY: Keep the best replayed score per player (only players with an original score)
+    pub async fn handle_submit_replay_score(
+        contract: &mut crate::Game2048Contract,
+        player: String,
+        score: u64,
+    ) {
+        let leaderboard = contract
+            .state
+            .leaderboards
+            .load_entry_mut("")
+            .await
+            .unwrap();
+        if !*leaderboard.replay_mode.get() {
+            return;
+        }
+        if leaderboard.score.get(&player).await.unwrap().is_none() {
+            return;
+        }
+
+        let current = leaderboard
+            .replay_scores
+            .get(&player)
+            .await
+            .unwrap()
+            .unwrap_or(0);
+        if score > current {
+            leaderboard.replay_scores.insert(&player, score).unwrap();
+        }
+    }
+
+    /// 💀 ELIMINATION: Track the lives left on a board that lost one
+    pub async fn handle_elimination_life_lost(
+        contract: &mut crate::Game2048Contract,
+        board_id: String,
+        lives_remaining: u8,
+    ) {
+        let leaderboard = contract
+            .state
+            .leaderboards
+            .load_entry_mut("")
+            .await
+            .unwrap();
+        leaderboard
+            .elimination_lives
+            .insert(&board_id, lives_remaining)
+            .unwrap();
+    }
+
+    /// 🚩 Zero the player's score if it came from a board confirmed as cheating
+    pub async fn handle_score_invalidated(
+        contract: &mut crate::Game2048Contract,
+        player: String,
+        board_id: String,
+    ) {
+        if !Self::is_from_main_chain(contract) {
+            return;
+        }
+        let leaderboard = contract
+            .state
+            .leaderboards
+            .load_entry_mut("")
+            .await
+            .unwrap();
+        if leaderboard.board_ids.get(&player).await.unwrap() == Some(board_id) {
+            leaderboard.score.insert(&player, 0).unwrap();
+        }
+    }
+
+    /// 🥊 BRACKET: Seed the players by current score into first-round matches. Needs exactly
+    /// `bracket_size` players with a score and no bracket in progress.
+    pub async fn handle_start_bracket(
+        contract: &mut crate::Game2048Contract,
+        bracket_size: u32,
+        round_duration_seconds: u64,
+    ) {
+        if !Self::is_from_main_chain(contract)
+            || BracketMatchup::validate_size(bracket_size).is_err()
+        {
+            return;
+        }
+        let current_time = contract.runtime.system_time().micros();
+        let leaderboard = contract
+            .state
+            .leaderboards
+            .load_entry_mut("")
+            .await
+            .unwrap();
+        if *leaderboard.bracket_size.get() > 0 && leaderboard.bracket_champion.get().is_empty() {
+            return;
+        }
+
+        let mut players: Vec<(String, u64)> = Vec::new();
+        leaderboard
+            .score
+            .for_each_index_value(|username, score| {
+                players.push((username, *score));
+                Ok(())
+            })
+            .await
+            .unwrap();
+        if players.len() != bracket_size as usize {
+            return;
+        }
+        players.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+        let round_duration = round_duration_seconds * 1_000_000;
+        leaderboard.bracket_matchups.clear();
+        leaderboard.bracket_champion.set(String::new());
+        leaderboard.bracket_size.set(bracket_size);
+        leaderboard.bracket_round_duration.set(round_duration);
+        let seeds = BracketMatchup::seed_order(bracket_size);
+        for (index, pair) in seeds.chunks(2).enumerate() {
+            let player_a = players[pair[0]].0.clone();
+            let player_b = players[pair[1]].0.clone();
+            let game_id_a = leaderboard
+                .board_ids
+                .get(&player_a)
+                .await
+                .unwrap()
+                .unwrap_or_default();
+            let game_id_b = leaderboard
+                .board_ids
+                .get(&player_b)
+                .await
+                .unwrap()
+                .unwrap_or_default();
+            let matchup = BracketMatchup {
+                round: 1,
+                player_a,
+                player_b,
+                game_id_a,
+                game_id_b,
+                deadline: current_time + round_duration,
+                winner: String::new(),
+            };
+            leaderboard
+                .bracket_matchups
+                .insert(&BracketMatchup::id(1, index as u32), matchup)
+                .unwrap();
+        }
+    }
+
+    /// 🔄 ROUND ROBIN: Pair the tournament's players (best score first) for `rounds` rounds and
+    /// start the first round. Ignored while another round robin is still running.
+    pub async fn handle_start_round_robin(
+        contract: &mut crate::Game2048Contract,
+        rounds: u8,
+        round_duration_seconds: u64,
+    ) {
+        if !Self::is_from_main_chain(contract) || rounds == 0 {
+            return;
+        }
+        let current_time = contract.runtime.system_time().micros();
+        let leaderboard = contract
+            .state
+            .leaderboards
+            .load_entry_mut("")
+            .await
+            .unwrap();
+        let current_round = *leaderboard.rr_current_round.get();
+        if current_round > 0 && current_round <= *leaderboard.rr_rounds.get() {
+            return;
+        }
+
+        let mut players: Vec<(String, u64)> = Vec::new();
+        leaderboard
+            .score
+            .for_each_index_value(|username, score| {
+                players.push((username, *score));
+                Ok(())
+            })
+            .await
+            .unwrap();
+        if players.len() < 2 {
+            return;
+        }
+        players.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        let usernames: Vec<String> = players
+            .iter()
+            .map(|(username, _)| username.clone())
+            .collect();
+
+        leaderboard.rr_schedule.clear();
+        leaderboard.rr_wins.clear();
+        leaderboard.rr_round_start_scores.clear();
+        for (index, pairs) in Matchup::round_robin(&usernames, rounds)
+            .into_iter()
+            .enumerate()
+        {
+            leaderboard
+                .rr_schedule
+                .insert(&(index as u8 + 1), pairs)
+                .unwrap();
+        }
+        for (username, score) in &players {
+            leaderboard
+                .rr_round_start_scores
+                .insert(username, *score)
+                .unwrap();
+        }
+        let round_duration = round_duration_seconds * 1_000_000;
+        leaderboard.rr_rounds.set(rounds);
+        leaderboard.rr_current_round.set(1);
+        leaderboard.rr_round_duration.set(round_duration);
+        leaderboard
+            .rr_round_deadline
+            .set(current_time + round_duration);
+    }
+
+    /// 🔁 REPLAY: Compare replayed scores with the originals and leave replay mode
+    pub async fn handle_finalize_replay(contract: &mut crate::Game2048Contract) {
+        let leaderboard = contract
+            .state
+            .leaderboards
+            .load_entry_mut("")
+            .await
+            .unwrap();
+        if !*leaderboard.replay_mode.get() {
+            return;
+        }
+
+        let mut originals: Vec<(String, u64)> = Vec::new();
+        leaderboard
+            .score
+            .for_each_index_value(|username, score| {
+                originals.push((username, *score));
+                Ok(())
+            })
+            .await
+            .unwrap();
+
+        let mut results = Vec::new();
+        for (username, original_score) in originals {
+            let replay_score = leaderboard
+                .replay_scores
+                .get(&username)
+                .await
+                .unwrap()
+                .unwrap_or(0);
+            results.push(game2048::ReplayResult::compare(
+                username,
+                original_score,
+                replay_score,
+            ));
+        }
+        results.sort_by(|a, b| b.replay_score.cmp(&a.replay_score));
+
+        leaderboard.replay_results.set(results);
+        leaderboard.replay_mode.set(false);
+    }
 }
@@ -2,13 +2,19 @@
 //!
 //! Business logic handlers for messages organized by domain.
 
+pub mod beacon;
 pub mod game;
 pub mod leaderboard;
 pub mod player;
+pub mod shard;
+pub mod team;
 pub mod transfer;
 
 // Re-export handlers for easier access
+pub use beacon::BeaconMessageHandler;
 pub use game::GameMessageHandler;
 pub use leaderboard::LeaderboardMessageHandler;
 pub use player::PlayerMessageHandler;
+pub use shard::ShardMessageHandler;
+pub use team::TeamMessageHandler;
 pub use transfer::TransferMessageHandler;
@@ -2,7 +2,11 @@
 //!
 //! Handles player-related messages including registration.
 
-use game2048::RegistrationCheck;
+use std::str::FromStr;
+
+use crate::contract_domain::SubscriptionManager;
+use game2048::{Message, RegistrationCheck};
+use linera_sdk::linera_base_types::ChainId;
 
 pub struct PlayerMessageHandler;
 
@@ -26,6 +30,29 @@ impl PlayerMessageHandler {
         player.username.set(username.clone());
         player.password_hash.set(password_hash);
         player.chain_id.set(chain_id);
+
+        // ♻️ Tell the main chain this pool chain is in use
+        let main_chain_id = contract.runtime.application_creator_chain_id();
+        if contract.runtime.chain_id() != main_chain_id {
+            contract.send_message(main_chain_id, Message::ChainInitialized);
+        }
+    }
+
+    /// ♻️ Mark the sending pool chain as initialized (main chain)
+    pub async fn handle_chain_initialized(contract: &mut crate::Game2048Contract) {
+        let Some(origin) = contract.runtime.message_origin_chain_id() else {
+            return;
+        };
+        let chain_id = origin.to_string();
+        let Some(mut claim) = contract.state.claimed_chains.get(&chain_id).await.unwrap() else {
+            return;
+        };
+        claim.initialized = true;
+        contract
+            .state
+            .claimed_chains
+            .insert(&chain_id, claim)
+            .unwrap();
     }
 
     /// Handle subscription to main chain's active tournaments
@@ -33,21 +60,305 @@ impl PlayerMessageHandler {
         contract: &mut crate::Game2048Contract,
         main_chain_id: String,
     ) {
-        use linera_sdk::linera_base_types::{ApplicationId, ChainId, StreamName};
-        use std::str::FromStr;
-
         if let Ok(main_chain_id) = ChainId::from_str(&main_chain_id) {
-            let stream_name = StreamName::from("active_tournaments".to_string());
-            let application_id = ApplicationId::new(
-                contract
-                    .runtime
-                    .application_id()
-                    .application_description_hash,
-            );
+            // 🎲 The randomness beacon is published alongside the active tournaments
+            for stream in ["active_tournaments", "randomness_beacon"] {
+                SubscriptionManager::subscribe(contract, stream, main_chain_id);
+            }
+        }
+    }
+
+    /// 🎟️ QUALIFIER: Remember that this player advanced to a tournament from a qualifier
+    pub async fn handle_qualified_for_tournament(
+        contract: &mut crate::Game2048Contract,
+        leaderboard_id: String,
+    ) {
+        if contract.runtime.message_origin_chain_id().is_none() {
+            return;
+        }
+        contract
+            .state
+            .qualified_tournaments
+            .insert(&leaderboard_id, true)
+            .unwrap();
+    }
+
+    /// 🧹 PURGE: Remember that a shard removed this player from a tournament
+    pub async fn handle_player_removed(
+        contract: &mut crate::Game2048Contract,
+        leaderboard_id: String,
+        reason: String,
+    ) {
+        if contract.runtime.message_origin_chain_id().is_none() {
+            return;
+        }
+        contract
+            .state
+            .removed_from_tournaments
+            .insert(&leaderboard_id, reason)
+            .unwrap();
+    }
+
+    /// 🔑 PASSWORD: Replace this chain's copy of the player's password hash (from main chain)
+    pub async fn handle_password_changed(
+        contract: &mut crate::Game2048Contract,
+        player: String,
+        password_hash: String,
+    ) {
+        if contract.runtime.message_origin_chain_id()
+            != Some(contract.runtime.application_creator_chain_id())
+        {
+            return;
+        }
+        let is_registered = contract
+            .state
+            .players
+            .try_load_entry(&player)
+            .await
+            .unwrap()
+            .is_some();
+        if !is_registered {
+            return;
+        }
+        let player_state = contract
+            .state
+            .players
+            .load_entry_mut(&player)
+            .await
+            .unwrap();
+        player_state.password_hash.set(password_hash);
+    }
+
+    /// 🎟️ WAITLIST: A slot freed up, so the next NewBoard may join the full tournament
+    pub async fn handle_admitted_from_waitlist(
+        contract: &mut crate::Game2048Contract,
+        leaderboard_id: String,
+    ) {
+        let Ok(leaderboard_chain_id) = ChainId::from_str(&leaderboard_id) else {
+            return;
+        };
+        if contract.runtime.message_origin_chain_id() != Some(leaderboard_chain_id) {
+            return;
+        }
+        contract
+            .state
+            .waitlist_admissions
+            .insert(&leaderboard_id, true)
+            .unwrap();
+    }
+
+    /// 🥊 BRACKET: Record the latest bracket result of this chain's player
+    pub async fn handle_bracket_advancement(
+        contract: &mut crate::Game2048Contract,
+        leaderboard_id: String,
+        round: u32,
+        loser: String,
+        next_matchup_id: Option<String>,
+    ) {
+        if contract.runtime.message_origin_chain_id().is_none() {
+            return;
+        }
+        let eliminated = contract
+            .state
+            .players
+            .try_load_entry(&loser)
+            .await
+            .unwrap()
+            .is_some();
+        let status = crate::state::BracketStatus {
+            round,
+            eliminated,
+            next_matchup_id: if eliminated { None } else { next_matchup_id },
+        };
+        contract
+            .state
+            .bracket_status
+            .insert(&leaderboard_id, status)
+            .unwrap();
+    }
+
+    /// 🚩 Keep a game flagged by its player chain for admin review (main chain)
+    pub async fn handle_game_flagged(
+        contract: &mut crate::Game2048Contract,
+        board_id: String,
+        player: String,
+        leaderboard_id: String,
+        score: u64,
+        reason: String,
+    ) {
+        if !contract.is_main_chain() {
+            return;
+        }
+        // Board IDs start with the player chain ID, so a chain can only flag its own boards
+        let Some(origin) = contract.runtime.message_origin_chain_id() else {
+            return;
+        };
+        let player_chain_id = origin.to_string();
+        if !board_id.starts_with(&player_chain_id) {
+            return;
+        }
+        if contract
+            .state
+            .admin_flags
+            .contains_key(&board_id)
+            .await
+            .unwrap()
+        {
+            return;
+        }
+        let flagged_at = contract.runtime.system_time().micros();
+        contract
+            .state
+            .admin_flags
+            .insert(
+                &board_id.clone(),
+                crate::state::FlaggedGameEntry {
+                    board_id,
+                    player,
+                    player_chain_id,
+                    leaderboard_id,
+                    score,
+                    reason,
+                    flagged_at,
+                    verdict: None,
+                    reviewed_by: String::new(),
+                },
+            )
+            .unwrap();
+    }
+
+    /// 🪙 TOKEN GATE: Store a balance attestation if it comes from the tournament's attestation chain
+    pub async fn handle_balance_attestation(
+        contract: &mut crate::Game2048Contract,
+        leaderboard_id: String,
+        attestation: game2048::BalanceAttestation,
+    ) {
+        let Some(origin) = contract.runtime.message_origin_chain_id() else {
+            return;
+        };
+        let Some(requirement) = contract
+            .get_cached_tournament(&leaderboard_id)
+            .await
+            .and_then(|tournament| tournament.entry_token_requirement)
+        else {
+            return;
+        };
+        if origin != requirement.chain_id {
+            // Not the configured attestation service - ignore
+            return;
+        }
+
+        contract
+            .state
+            .balance_attestations
+            .insert(&leaderboard_id, attestation)
+            .unwrap();
+    }
+
+    /// 👫 FRIENDS: Connect the requesting chain with the friend's chain (main chain)
+    pub async fn handle_friend_request(
+        contract: &mut crate::Game2048Contract,
+        friend_username: String,
+    ) {
+        if !contract.is_main_chain() {
+            return;
+        }
+        let Some(origin) = contract.runtime.message_origin_chain_id() else {
+            return;
+        };
+        let Some(friend) = contract
+            .state
+            .players
+            .try_load_entry(&friend_username)
+            .await
+            .unwrap()
+        else {
+            // Unknown player - nothing to follow
+            return;
+        };
+        let Ok(friend_chain_id) = ChainId::from_str(friend.chain_id.get()) else {
+            return;
+        };
+        drop(friend);
+
+        contract.send_message(
+            friend_chain_id,
+            Message::FriendSubscription {
+                subscriber_chain_id: origin.to_string(),
+                subscribe: true,
+            },
+        );
+        contract.send_message(
+            origin,
+            Message::FriendChainResolved {
+                friend_username,
+                friend_chain_id: friend_chain_id.to_string(),
+            },
+        );
+    }
 
+    /// 👫 FRIENDS: Subscribe to the friend's score events now that their chain is known
+    pub async fn handle_friend_chain_resolved(
+        contract: &mut crate::Game2048Contract,
+        friend_username: String,
+        friend_chain_id: String,
+    ) {
+        if contract.runtime.message_origin_chain_id()
+            != Some(contract.runtime.application_creator_chain_id())
+        {
+            return;
+        }
+        let Ok(chain_id) = ChainId::from_str(&friend_chain_id) else {
+            return;
+        };
+        // The friend may have been removed while the lookup was in flight
+        if !contract
+            .state
+            .friends
+            .contains(&friend_username)
+            .await
+            .unwrap()
+        {
+            return;
+        }
+
+        contract
+            .state
+            .friend_chain_ids
+            .insert(&friend_username, friend_chain_id)
+            .unwrap();
+        SubscriptionManager::subscribe_to_friend_updates(contract, chain_id);
+    }
+
+    /// 👫 FRIENDS: Track the chains following this chain's player, so scores are only
+    /// published while someone listens
+    pub async fn handle_friend_subscription(
+        contract: &mut crate::Game2048Contract,
+        subscriber_chain_id: String,
+        subscribe: bool,
+    ) {
+        let Some(origin) = contract.runtime.message_origin_chain_id() else {
+            return;
+        };
+        // Followers are added through the main chain; a follower may remove itself
+        if origin != contract.runtime.application_creator_chain_id()
+            && origin.to_string() != subscriber_chain_id
+        {
+            return;
+        }
+
+        if subscribe {
+            contract
+                .state
+                .friend_subscribers
+                .insert(&subscriber_chain_id)
+                .unwrap();
+        } else {
             contract
-                .runtime
-                .subscribe_to_events(main_chain_id, application_id, stream_name);
+                .state
+                .friend_subscribers
+                .remove(&subscriber_chain_id)
+                .unwrap();
         }
     }
 }
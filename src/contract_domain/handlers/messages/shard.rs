@@ -0,0 +1,478 @@
+//! Shard Messages Handler
+//!
+//! Handles score messages sent to shard chains. Messages that fail to process are kept
+//! in the dead letter queue so admins can retry them instead of losing them.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use crate::contract_domain::SubscriptionManager;
+use crate::state::{ActiveBoardInfo, DeadLetterEntry, QueuedScoreUpdate};
+use game2048::Message;
+use linera_sdk::linera_base_types::ChainId;
+use linera_sdk::views::View;
+
+pub struct ShardMessageHandler;
+
+impl ShardMessageHandler {
+    /// Process an `UpdateScore` or `Flush` message; failures go to the dead letter queue.
+    /// ⏸️ While the leaderboard is paused, `UpdateScore` is held instead.
+    pub async fn handle_or_dead_letter(contract: &mut crate::Game2048Contract, message: Message) {
+        let origin = contract
+            .runtime
+            .message_origin_chain_id()
+            .map(|chain_id| chain_id.to_string())
+            .unwrap_or_default();
+
+        if let Message::UpdateScore {
+            player,
+            board_id,
+            score,
+            is_end,
+            timestamp,
+            message_nonce,
+        } = &message
+        {
+            if contract
+                .state
+                .shards
+                .try_load_entry("")
+                .await
+                .unwrap()
+                .is_some()
+            {
+                let shard = contract.state.shards.load_entry_mut("").await.unwrap();
+                if *shard.is_paused.get() {
+                    let leaderboard_id = shard.leaderboard_id.get().clone();
+                    let mut queue = shard
+                        .paused_score_queue
+                        .get(&leaderboard_id)
+                        .await
+                        .unwrap()
+                        .unwrap_or_default();
+                    queue.push(QueuedScoreUpdate {
+                        player: player.clone(),
+                        board_id: board_id.clone(),
+                        score: *score,
+                        is_end: *is_end,
+                        timestamp: *timestamp,
+                        message_nonce: *message_nonce,
+                        origin_chain_id: origin,
+                    });
+                    shard
+                        .paused_score_queue
+                        .insert(&leaderboard_id, queue)
+                        .unwrap();
+                    return;
+                }
+            }
+        }
+
+        Self::apply_or_dead_letter(contract, message, origin).await;
+    }
+
+    /// ⏸️ PAUSE: Hold (or stop holding) score updates. Resuming applies the held updates
+    /// in the order they arrived.
+    pub async fn set_paused(
+        contract: &mut crate::Game2048Contract,
+        leaderboard_id: &str,
+        paused: bool,
+    ) {
+        if contract
+            .state
+            .shards
+            .try_load_entry("")
+            .await
+            .unwrap()
+            .is_none()
+        {
+            return;
+        }
+        let shard = contract.state.shards.load_entry_mut("").await.unwrap();
+        shard.is_paused.set(paused);
+        if paused {
+            return;
+        }
+        let queue = shard
+            .paused_score_queue
+            .get(leaderboard_id)
+            .await
+            .unwrap()
+            .unwrap_or_default();
+        shard.paused_score_queue.remove(leaderboard_id).unwrap();
+        for update in queue {
+            let message = Message::UpdateScore {
+                player: update.player,
+                board_id: update.board_id,
+                score: update.score,
+                is_end: update.is_end,
+                timestamp: update.timestamp,
+                message_nonce: update.message_nonce,
+            };
+            Self::apply_or_dead_letter(contract, message, update.origin_chain_id).await;
+        }
+    }
+
+    async fn apply_or_dead_letter(
+        contract: &mut crate::Game2048Contract,
+        message: Message,
+        origin: String,
+    ) {
+        let Err(failure_reason) = Self::process(contract, &message).await else {
+            return;
+        };
+
+        let message_nonce = match &message {
+            Message::UpdateScore { message_nonce, .. } | Message::Flush { message_nonce, .. } => {
+                *message_nonce
+            }
+            _ => 0,
+        };
+        let key = Self::dead_letter_key(contract, &origin, message_nonce).await;
+
+        let entry = DeadLetterEntry {
+            message_bytes: linera_sdk::bcs::to_bytes(&message).unwrap(),
+            failure_reason,
+            attempts: 0,
+            first_failure_at: contract.runtime.system_time().micros(),
+            permanently_failed: false,
+        };
+        contract
+            .state
+            .dead_letter_queue
+            .insert(&key, entry)
+            .unwrap();
+    }
+
+    /// Apply a shard score message, or explain why it cannot be applied
+    pub async fn process(
+        contract: &mut crate::Game2048Contract,
+        message: &Message,
+    ) -> Result<(), String> {
+        if contract
+            .state
+            .shards
+            .try_load_entry("")
+            .await
+            .unwrap()
+            .is_none()
+        {
+            return Err("Not a shard chain".to_string());
+        }
+
+        match message {
+            Message::UpdateScore {
+                player,
+                board_id,
+                score,
+                is_end,
+                ..
+            } => Self::apply_update_score(contract, player, board_id, *score, *is_end).await,
+            Message::Flush {
+                board_ids, scores, ..
+            } => Self::apply_flush(contract, board_ids, scores).await,
+            _ => Err("Not a shard score message".to_string()),
+        }
+    }
+
+    async fn apply_update_score(
+        contract: &mut crate::Game2048Contract,
+        player: &str,
+        board_id: &str,
+        score: u64,
+        is_end: bool,
+    ) -> Result<(), String> {
+        let shard = contract.state.shards.load_entry_mut("").await.unwrap();
+
+        let active_board = shard.active_boards.get(board_id).await.unwrap();
+        let best_board_id = shard.board_ids.get(player).await.unwrap();
+        if active_board.is_none() && best_board_id.as_deref() != Some(board_id) {
+            return Err(format!("Unknown board ID {board_id}"));
+        }
+        if let Some(info) = &active_board {
+            if info.player != player {
+                return Err(format!("Board {board_id} belongs to another player"));
+            }
+        }
+
+        shard
+            .active_boards
+            .insert(
+                board_id,
+                ActiveBoardInfo {
+                    player: player.to_string(),
+                    score,
+                    is_ended: is_end,
+                },
+            )
+            .unwrap();
+
+        let best_score = shard.score.get(player).await.unwrap().unwrap_or(0);
+        if score >= best_score {
+            shard.score.insert(player, score).unwrap();
+            shard
+                .board_ids
+                .insert(player, board_id.to_string())
+                .unwrap();
+            shard.is_ended.insert(player, is_end).unwrap();
+        }
+        shard.counter.set(shard.counter.get().wrapping_add(1));
+        Ok(())
+    }
+
+    async fn apply_flush(
+        contract: &mut crate::Game2048Contract,
+        board_ids: &HashMap<String, String>,
+        scores: &HashMap<String, u64>,
+    ) -> Result<(), String> {
+        // Check every player first so a bad flush is not applied halfway
+        if let Some(player) = scores
+            .keys()
+            .find(|player| !board_ids.contains_key(*player))
+        {
+            return Err(format!("Unknown board ID for player {player}"));
+        }
+
+        let shard = contract.state.shards.load_entry_mut("").await.unwrap();
+        for (player, score) in scores {
+            let best_score = shard.score.get(player).await.unwrap().unwrap_or(0);
+            if *score >= best_score {
+                shard.score.insert(player, *score).unwrap();
+                shard
+                    .board_ids
+                    .insert(player, board_ids[player].clone())
+                    .unwrap();
+            }
+        }
+        shard.counter.set(shard.counter.get().wrapping_add(1));
+        Ok(())
+    }
+
+    /// 🧹 PURGE: Remove up to `MAX_PURGE_PER_CALL` players without a score whose chain has not
+    /// been seen since the threshold, tell each player chain, and report the count.
+    pub async fn handle_purge_inactive_players(
+        contract: &mut crate::Game2048Contract,
+        leaderboard_id: String,
+        inactive_threshold_seconds: u64,
+    ) {
+        if contract.runtime.message_origin_chain_id()
+            != Some(contract.runtime.application_creator_chain_id())
+        {
+            return;
+        }
+        if contract
+            .state
+            .shards
+            .try_load_entry("")
+            .await
+            .unwrap()
+            .is_none()
+        {
+            return;
+        }
+        let timestamp = contract.runtime.system_time().micros();
+        let cutoff = timestamp.saturating_sub(inactive_threshold_seconds.saturating_mul(1_000_000));
+        let shard = contract.state.shards.load_entry_mut("").await.unwrap();
+
+        let mut unscored = Vec::new();
+        shard
+            .score
+            .for_each_index_value(|username, score| {
+                if *score == 0 {
+                    unscored.push(username);
+                }
+                Ok(())
+            })
+            .await
+            .unwrap();
+
+        // Players never seen on this shard count as inactive since the start
+        let mut purged = Vec::new();
+        for username in unscored {
+            if purged.len() == game2048::MAX_PURGE_PER_CALL {
+                break;
+            }
+            let chain_id = shard
+                .player_chain_ids
+                .get(&username)
+                .await
+                .unwrap()
+                .unwrap_or_default();
+            let last_seen = shard
+                .player_last_seen
+                .get(&chain_id)
+                .await
+                .unwrap()
+                .unwrap_or(0);
+            if last_seen < cutoff {
+                purged.push((username, chain_id));
+            }
+        }
+
+        for (username, _) in &purged {
+            shard.score.remove(username).unwrap();
+            shard.board_ids.remove(username).unwrap();
+            shard.is_ended.remove(username).unwrap();
+            shard.player_chain_ids.remove(username).unwrap();
+            shard.highest_tiles.remove(username).unwrap();
+            shard.game_statuses.remove(username).unwrap();
+            shard.compact_scores.remove(username).unwrap();
+            shard.bulk_sent_scores.remove(username).unwrap();
+        }
+
+        for chain_id in purged
+            .iter()
+            .filter_map(|(_, chain_id)| ChainId::from_str(chain_id).ok())
+        {
+            contract.send_message(
+                chain_id,
+                Message::PlayerRemoved {
+                    leaderboard_id: leaderboard_id.clone(),
+                    reason: game2048::PURGE_REASON_INACTIVITY.to_string(),
+                },
+            );
+            // Stop reading the removed player's chain
+            SubscriptionManager::unsubscribe_all(contract, chain_id).await;
+        }
+
+        use crate::contract_domain::events::emitters::EventEmitter;
+        EventEmitter::emit_purge_completed(
+            contract,
+            leaderboard_id,
+            purged.len() as u32,
+            timestamp,
+        )
+        .await;
+    }
+
+    /// 🧳 MIGRATION: Move the player's per-chain board counts and activity to their new chain.
+    /// Active boards of the old chain can no longer report scores, so they are closed.
+    pub async fn handle_reassign_boards(
+        contract: &mut crate::Game2048Contract,
+        player: String,
+        old_chain_id: String,
+        new_chain_id: String,
+    ) {
+        if contract.runtime.message_origin_chain_id()
+            != Some(contract.runtime.application_creator_chain_id())
+        {
+            return;
+        }
+        if contract
+            .state
+            .shards
+            .try_load_entry("")
+            .await
+            .unwrap()
+            .is_none()
+        {
+            return;
+        }
+        let shard = contract.state.shards.load_entry_mut("").await.unwrap();
+
+        // Board counts are keyed "tournament_id:player_chain_id"
+        let old_suffix = format!(":{old_chain_id}");
+        let mut board_counts = Vec::new();
+        shard
+            .tournament_player_board_counts
+            .for_each_index_value(|key, count| {
+                if let Some(tournament_id) = key.strip_suffix(&old_suffix) {
+                    board_counts.push((key.clone(), tournament_id.to_string(), *count));
+                }
+                Ok(())
+            })
+            .await
+            .unwrap();
+        for (old_key, tournament_id, count) in board_counts {
+            let new_key = format!("{tournament_id}:{new_chain_id}");
+            let new_count = shard
+                .tournament_player_board_counts
+                .get(&new_key)
+                .await
+                .unwrap()
+                .unwrap_or(0);
+            shard
+                .tournament_player_board_counts
+                .remove(&old_key)
+                .unwrap();
+            shard
+                .tournament_player_board_counts
+                .insert(&new_key, new_count + count)
+                .unwrap();
+        }
+
+        if let Some(level) = shard
+            .player_activity_levels
+            .get(&old_chain_id)
+            .await
+            .unwrap()
+        {
+            shard.player_activity_levels.remove(&old_chain_id).unwrap();
+            shard
+                .player_activity_levels
+                .insert(&new_chain_id, level)
+                .unwrap();
+        }
+        if let Some(last_seen) = shard.player_last_seen.get(&old_chain_id).await.unwrap() {
+            shard.player_last_seen.remove(&old_chain_id).unwrap();
+            shard
+                .player_last_seen
+                .insert(&new_chain_id, last_seen)
+                .unwrap();
+        }
+
+        let monitored = shard.monitored_player_chains.elements().await.unwrap();
+        if monitored.contains(&old_chain_id) {
+            shard.monitored_player_chains.clear();
+            for chain_id in monitored {
+                if chain_id != old_chain_id && chain_id != new_chain_id {
+                    shard.monitored_player_chains.push_back(chain_id);
+                }
+            }
+            shard.monitored_player_chains.push_back(new_chain_id);
+        }
+
+        // Board ids start with the id of the chain the board lives on
+        let old_prefix = format!("{old_chain_id}.");
+        let mut old_boards = Vec::new();
+        shard
+            .active_boards
+            .for_each_index_value(|board_id, info| {
+                if board_id.starts_with(&old_prefix) && info.player == player && !info.is_ended {
+                    old_boards.push((board_id, info.into_owned()));
+                }
+                Ok(())
+            })
+            .await
+            .unwrap();
+        for (board_id, mut info) in old_boards {
+            info.is_ended = true;
+            shard.active_boards.insert(&board_id, info).unwrap();
+        }
+    }
+
+    /// `"{chain_id}:{message_nonce}"`; un-numbered messages use the next free local number
+    async fn dead_letter_key(
+        contract: &mut crate::Game2048Contract,
+        origin: &str,
+        message_nonce: u64,
+    ) -> String {
+        if message_nonce != 0 {
+            return format!("{origin}:{message_nonce}");
+        }
+        let mut local_nonce = contract.state.dead_letter_queue.count().await.unwrap() as u64;
+        loop {
+            local_nonce += 1;
+            let key = format!("{origin}:local-{local_nonce}");
+            if !contract
+                .state
+                .dead_letter_queue
+                .contains_key(&key)
+                .await
+                .unwrap()
+            {
+                return key;
+            }
+        }
+    }
+}
@@ -0,0 +1,110 @@
+//! Team Messages Handler
+//!
+//! Joins and moves arriving on a team game's host chain, and board syncs arriving on members' chains.
+
+use crate::contract_domain::handlers::operations::TeamOperationHandler;
+use crate::state::{TeamBoardMirror, TeamMember};
+use game2048::Direction;
+
+pub struct TeamMessageHandler;
+
+impl TeamMessageHandler {
+    /// 👥 Add the sending chain's player if the invite code is still open (host chain)
+    pub async fn handle_join_team(
+        contract: &mut crate::Game2048Contract,
+        team_board_id: String,
+        invite_code: String,
+        player: String,
+    ) {
+        let Some(origin) = contract.runtime.message_origin_chain_id() else {
+            return;
+        };
+        let Some(mut team) = contract.state.team_games.get(&team_board_id).await.unwrap() else {
+            return;
+        };
+        let is_ended = *contract
+            .state
+            .boards
+            .load_entry_mut(&team_board_id)
+            .await
+            .unwrap()
+            .is_ended
+            .get();
+        let chain_id = origin.to_string();
+        if is_ended
+            || team
+                .members
+                .iter()
+                .any(|member| member.chain_id == chain_id)
+            || !team.open_invite_codes.contains(&invite_code)
+        {
+            return;
+        }
+
+        team.open_invite_codes.retain(|code| *code != invite_code);
+        team.members.push(TeamMember { player, chain_id });
+        contract
+            .state
+            .team_games
+            .insert(&team_board_id, team)
+            .unwrap();
+        TeamOperationHandler::sync_team_board(contract, &team_board_id).await;
+    }
+
+    /// 👥 Apply a member's move and sync the board to the team (host chain)
+    pub async fn handle_team_move(
+        contract: &mut crate::Game2048Contract,
+        team_board_id: String,
+        direction: Direction,
+        player: String,
+    ) {
+        let Some(origin) = contract.runtime.message_origin_chain_id() else {
+            return;
+        };
+        let Some(team) = contract.state.team_games.get(&team_board_id).await.unwrap() else {
+            return;
+        };
+        let chain_id = origin.to_string();
+        if !team
+            .members
+            .iter()
+            .any(|member| member.chain_id == chain_id && member.player == player)
+        {
+            return;
+        }
+
+        TeamOperationHandler::apply_team_move(contract, &team_board_id, direction).await;
+        TeamOperationHandler::sync_team_board(contract, &team_board_id).await;
+    }
+
+    /// 👥 Store the latest board of a team game hosted elsewhere (member chain)
+    pub async fn handle_team_board_sync(
+        contract: &mut crate::Game2048Contract,
+        team_board_id: String,
+        tiles: u64,
+        score: u64,
+        is_ended: bool,
+        score_share: u64,
+    ) {
+        let Some(origin) = contract.runtime.message_origin_chain_id() else {
+            return;
+        };
+        if TeamOperationHandler::host_chain_id(&team_board_id) != Some(origin) {
+            return;
+        }
+        contract
+            .state
+            .team_memberships
+            .insert(
+                &team_board_id,
+                TeamBoardMirror {
+                    host_chain_id: origin.to_string(),
+                    tiles,
+                    score,
+                    is_ended,
+                    score_share,
+                },
+            )
+            .unwrap();
+    }
+}
@@ -0,0 +1,186 @@
+//! Admin Operations Handler
+//!
+//! Handles M-of-N multi-signature proposals for critical admin actions.
+
+use crate::contract_domain::handlers::operations::LeaderboardOperationHandler;
+use crate::state::{AdminProposal, MultiSigConfig};
+use game2048::{CriticalAdminAction, RegistrationCheck};
+
+pub struct AdminOperationHandler;
+
+impl AdminOperationHandler {
+    /// Configure signers and threshold (super-admin only, main chain)
+    pub async fn handle_configure_multisig(
+        contract: &mut crate::Game2048Contract,
+        required_signatures: u8,
+        admin_list: Vec<String>,
+        player: String,
+        password_hash: String,
+    ) {
+        contract
+            .validate_player_password(&player, &password_hash)
+            .await;
+
+        if player != "lpaydat" {
+            panic!("Only lpaydat can configure multi-sig");
+        }
+        if !contract.is_main_chain() {
+            panic!("Only main chain can configure multi-sig");
+        }
+        if required_signatures == 0 || required_signatures as usize > admin_list.len() {
+            panic!("Required signatures must be between 1 and the number of admins");
+        }
+
+        contract.state.multisig_config.set(MultiSigConfig {
+            required_signatures,
+            admin_list,
+        });
+    }
+
+    /// Critical actions only go through proposals once multi-sig is configured
+    pub fn reject_if_multisig_configured(contract: &crate::Game2048Contract, action: &str) {
+        if contract.state.multisig_config.get().required_signatures > 0 {
+            panic!("Multi-sig is configured: propose {} instead", action);
+        }
+    }
+
+    /// Create a pending proposal; the proposer's signature counts as the first approval
+    pub async fn handle_propose_admin_action(
+        contract: &mut crate::Game2048Contract,
+        proposer: String,
+        password_hash: String,
+        action: CriticalAdminAction,
+        proposal_id: String,
+    ) {
+        let config = Self::authorize_signer(contract, &proposer, &password_hash).await;
+        if proposal_id.trim().is_empty() {
+            panic!("Proposal ID cannot be empty");
+        }
+
+        let current_time = contract.runtime.system_time().micros();
+        if let Some(existing) = contract
+            .state
+            .pending_proposals
+            .get(&proposal_id)
+            .await
+            .unwrap()
+        {
+            if !existing.is_expired(current_time) {
+                panic!("Proposal ID already in use");
+            }
+        }
+
+        let proposal = AdminProposal {
+            proposer: proposer.clone(),
+            action,
+            approvals: vec![proposer],
+            created_at: current_time,
+        };
+        Self::store_or_execute(contract, proposal_id, proposal, &config).await;
+    }
+
+    /// Sign a pending proposal; executes once enough signatures are collected
+    pub async fn handle_approve_admin_action(
+        contract: &mut crate::Game2048Contract,
+        approver: String,
+        password_hash: String,
+        proposal_id: String,
+    ) {
+        let config = Self::authorize_signer(contract, &approver, &password_hash).await;
+
+        let Some(mut proposal) = contract
+            .state
+            .pending_proposals
+            .get(&proposal_id)
+            .await
+            .unwrap()
+        else {
+            panic!("Proposal not found");
+        };
+
+        let current_time = contract.runtime.system_time().micros();
+        if proposal.is_expired(current_time) {
+            panic!("Proposal has expired");
+        }
+        if proposal.approvals.contains(&approver) {
+            panic!("Admin has already approved this proposal");
+        }
+
+        proposal.approvals.push(approver);
+        Self::store_or_execute(contract, proposal_id, proposal, &config).await;
+    }
+
+    /// Validate password, main chain and membership in the admin list
+    async fn authorize_signer(
+        contract: &mut crate::Game2048Contract,
+        admin: &str,
+        password_hash: &str,
+    ) -> MultiSigConfig {
+        contract
+            .validate_player_password(admin, password_hash)
+            .await;
+
+        if !contract.is_main_chain() {
+            panic!("Only main chain can handle admin proposals");
+        }
+
+        let config = contract.state.multisig_config.get().clone();
+        if config.required_signatures == 0 {
+            panic!("Multi-sig is not configured");
+        }
+        if !config.admin_list.iter().any(|listed| listed == admin) {
+            panic!("Unauthorized: Not a multi-sig admin");
+        }
+        config
+    }
+
+    async fn store_or_execute(
+        contract: &mut crate::Game2048Contract,
+        proposal_id: String,
+        proposal: AdminProposal,
+        config: &MultiSigConfig,
+    ) {
+        // Only count signers still on the admin list
+        let signatures = proposal
+            .approvals
+            .iter()
+            .filter(|approver| config.admin_list.contains(approver))
+            .count();
+
+        if signatures < config.required_signatures as usize {
+            contract
+                .state
+                .pending_proposals
+                .insert(&proposal_id, proposal)
+                .unwrap();
+            return;
+        }
+
+        contract
+            .state
+            .pending_proposals
+            .remove(&proposal_id)
+            .unwrap();
+        Self::execute(contract, proposal.action).await;
+    }
+
+    async fn execute(contract: &mut crate::Game2048Contract, action: CriticalAdminAction) {
+        match action {
+            CriticalAdminAction::ToggleAdmin(username) => {
+                contract
+                    .check_player_registered(&username, RegistrationCheck::EnsureRegistered)
+                    .await;
+                let player = contract
+                    .state
+                    .players
+                    .load_entry_mut(&username)
+                    .await
+                    .unwrap();
+                player.is_mod.set(!*player.is_mod.get());
+            }
+            CriticalAdminAction::DeleteLeaderboard(leaderboard_id) => {
+                LeaderboardOperationHandler::delete_leaderboard(contract, &leaderboard_id).await;
+            }
+        }
+    }
+}
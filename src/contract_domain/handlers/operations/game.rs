@@ -3,12 +3,31 @@
 //! Handles game-related operations including moves and board creation.
 
 use crate::contract_domain::game_logic::{GameMoveProcessor, GameMoveResult};
-use game2048::{hash_seed, Direction, Game, GameEndReason, GameStatus};
+use crate::contract_domain::handlers::operations::{
+    LeaderboardOperationHandler, SystemOperationHandler, TeamOperationHandler,
+};
+use crate::state::{GameSnapshot, UndoSnapshot};
+use game2048::{
+    chain_id_hash, countdown_threshold_crossed, daily_challenge_board_id, daily_challenge_seed,
+    hash_seed, proof_of_work_bits, rank_by_highest_tile, spawn_state, AntiCheat, BoardMoveEntry,
+    BoardSize, BoardStatistics, BoardSummary, BonusWindow, DailyChallengeRecord, Direction, Game,
+    GameEndReason, GameError, GameMetadataEntry, GameStatus, GameVariant, LeaderboardEntry,
+    MoveError, MoveHint, MoveLog, MoveSample, Moves, PlatformHighlight, SizedBoard,
+    SpawnDistribution, SpawnGenerator, StreakBonusTier, BATCH_MOVES_MULTIPLIER,
+    DAILY_CHALLENGE_LEADERBOARD_ID, DEFAULT_COUNTDOWN_THRESHOLDS, DEFAULT_MAX_MOVES_PER_CALL,
+    DEFAULT_MIN_MOVE_INTERVAL_MICROS, DEFAULT_NEW_BOARD_COOLDOWN_SECONDS, DEFAULT_TOP_BOARDS,
+    DEFAULT_UNDO_HISTORY_SIZE, GAME_CONTINUATION_WINDOW_SECONDS, MAX_HINTS_PER_BOARD,
+    PLATFORM_MILESTONE_TILES, SPAWN_RATIO_MAX_DEVIATIONS, UNLIMITED_UNDOS,
+};
 use linera_sdk::linera_base_types::ChainId;
 use std::str::FromStr;
 
 pub struct GameOperationHandler;
 
+// 🎵 Move format: (Direction, timestamp_string, beat_number)
+// beat_number: 0 = miss/off-beat, >0 = on-beat (which beat number)
+type MoveInput = (Direction, String, u32);
+
 impl GameOperationHandler {
     pub async fn handle_make_moves(
         contract: &mut crate::Game2048Contract,
@@ -16,11 +35,90 @@ impl GameOperationHandler {
         moves: String,
         player: String,
         password_hash: String,
-    ) {
+    ) -> Result<(), GameError> {
         // Validate password
         contract
             .validate_player_password(&player, &password_hash)
             .await;
+
+        let moves: Vec<MoveInput> =
+            serde_json::from_str(&moves).map_err(|_| MoveError::InvalidFormat)?;
+        Self::apply_moves(contract, board_id, moves, player).await?;
+        Ok(())
+    }
+
+    /// 📦 BATCH: Apply moves to several boards; a rejected board is skipped and the
+    /// others still apply. Returns the JSON `Vec<Result<(), MoveError>>`, one per entry.
+    pub async fn handle_batch_make_moves(
+        contract: &mut crate::Game2048Contract,
+        entries: Vec<BoardMoveEntry>,
+        player: String,
+        password_hash: String,
+    ) -> String {
+        contract
+            .validate_player_password(&player, &password_hash)
+            .await;
+
+        let parsed: Vec<(String, Result<Vec<MoveInput>, MoveError>)> = entries
+            .into_iter()
+            .map(|entry| {
+                let moves =
+                    serde_json::from_str(&entry.moves).map_err(|_| MoveError::InvalidFormat);
+                (entry.board_id, moves)
+            })
+            .collect();
+
+        // 🚦 MOVE RATE LIMIT: The whole batch shares a multiple of the default per-call
+        // limit; each board still gets its tournament's own limit
+        let total_moves: usize = parsed
+            .iter()
+            .filter_map(|(_, moves)| moves.as_ref().ok())
+            .map(Vec::len)
+            .sum();
+        let max_total_moves = DEFAULT_MAX_MOVES_PER_CALL as usize * BATCH_MOVES_MULTIPLIER as usize;
+        if total_moves > max_total_moves {
+            panic!(
+                "Too many moves in one batch: {} (max {})",
+                total_moves, max_total_moves
+            );
+        }
+
+        let mut results: Vec<Result<(), MoveError>> = Vec::with_capacity(parsed.len());
+        for (board_id, moves) in parsed {
+            let result = match moves {
+                Ok(moves) => Self::apply_moves(contract, board_id, moves, player.clone()).await,
+                Err(error) => Err(error),
+            };
+            results.push(result);
+        }
+        serde_json::to_string(&results).unwrap()
+    }
+
+    /// Apply one board's moves. Rejections return before the board is changed, so a
+    /// batch can skip the board and carry on.
+    async fn apply_moves(
+        contract: &mut crate::Game2048Contract,
+        board_id: String,
+        moves: Vec<MoveInput>,
+        player: String,
+    ) -> Result<(), MoveError> {
+        // 👥 TEAMS: Team boards are moved on their host chain
+        let directions = moves.iter().map(|(direction, _, _)| *direction).collect();
+        if TeamOperationHandler::route_team_moves(contract, &board_id, &player, directions).await {
+            return Ok(());
+        }
+
+        // Loading a missing board for writing would create it
+        if contract
+            .state
+            .boards
+            .try_load_entry(&board_id)
+            .await
+            .unwrap()
+            .is_none()
+        {
+            return Err(MoveError::BoardNotFound);
+        }
         let board = contract
             .state
             .boards
@@ -29,18 +127,84 @@ impl GameOperationHandler {
             .unwrap();
 
         if player != *board.player.get() {
-            panic!("You can only make move on your own board");
+            return Err(MoveError::NotBoardOwner);
+        }
+        // 🔲 SIZED BOARDS: 3x3, 5x5 and 6x6 boards are played with `SizedBoard`
+        if *board.board_size.get() != BoardSize::FourByFour {
+            return Self::apply_sized_moves(contract, board_id, moves, player).await;
         }
-
-        // 🎵 Move format: (Direction, timestamp_string, beat_number)
-        // beat_number: 0 = miss/off-beat, >0 = on-beat (which beat number)
-        type MoveInput = (Direction, String, u32);
-        let moves: Vec<MoveInput> =
-            serde_json::from_str(&moves).unwrap_or_else(|_| panic!("Invalid moves format"));
 
         let is_ended = *board.is_ended.get();
+        // ⏱️ SPEED GAME: Past the deadline (block time), the game ends without applying moves
+        let moves = if GameVariant::is_past_deadline(
+            *board.deadline.get(),
+            contract.runtime.system_time().micros(),
+        ) {
+            Vec::new()
+        } else {
+            moves
+        };
         let start_time_raw = *board.start_time.get();
-        let end_time_raw = *board.end_time.get();
+        // 🔥 HOT RELOAD: Time caps follow the tournament's current settings; the board
+        // keeps the values from its creation for scoring and submissions
+        let leaderboard_id = board.leaderboard_id.get().clone();
+        let (
+            end_time_raw,
+            bonus_windows,
+            max_score_per_player,
+            combo_multiplier_enabled,
+            max_moves_per_call,
+            min_move_interval_micros,
+            anti_cheat,
+        ) = match contract.get_cached_tournament(&leaderboard_id).await {
+            Some(tournament) if tournament.replay_mode => return Err(MoveError::ReplayMode),
+            Some(tournament) => (
+                tournament.end_time.unwrap_or(0),
+                tournament.bonus_windows,
+                tournament.max_score_per_player,
+                tournament.combo_multiplier_enabled,
+                tournament.max_moves_per_call,
+                tournament.min_move_interval_micros,
+                tournament.anti_cheat,
+            ),
+            None => (
+                *contract
+                    .state
+                    .boards
+                    .load_entry_mut(&board_id)
+                    .await
+                    .unwrap()
+                    .end_time
+                    .get(),
+                Vec::new(),
+                0,
+                false,
+                0,
+                0,
+                None,
+            ),
+        };
+        // 🚦 MOVE RATE LIMIT: 0 = default limits
+        let max_moves_per_call = match max_moves_per_call {
+            0 => DEFAULT_MAX_MOVES_PER_CALL,
+            max_moves => max_moves,
+        };
+        let min_move_interval_micros = match min_move_interval_micros {
+            0 => DEFAULT_MIN_MOVE_INTERVAL_MICROS,
+            interval => interval,
+        };
+        if moves.len() > max_moves_per_call as usize {
+            return Err(MoveError::TooManyMoves {
+                count: moves.len(),
+                max: max_moves_per_call as u32,
+            });
+        }
+        let board = contract
+            .state
+            .boards
+            .load_entry_mut(&board_id)
+            .await
+            .unwrap();
         // Convert 0 or u64::MAX to None (unlimited), otherwise Some(value)
         let start_time = if start_time_raw == 0 || start_time_raw == u64::MAX {
             None
@@ -52,9 +216,15 @@ impl GameOperationHandler {
         } else {
             Some(end_time_raw)
         };
+        // ⏱️ SPEED GAME: The board's deadline caps the tournament end time
+        let end_time = match *board.deadline.get() {
+            0 => end_time,
+            deadline => Some(end_time.map_or(deadline, |end| end.min(deadline))),
+        };
 
         if !is_ended && !moves.is_empty() {
             let initial_board = *board.board.get();
+            let spawns = board.spawn_distribution();
             // 🔒 DUPLICATE DETECTION: Get last processed timestamp
             let last_processed_timestamp = *board.last_processed_timestamp.get();
 
@@ -82,6 +252,21 @@ impl GameOperationHandler {
                 }
             }
 
+            // 🚦 MOVE RATE LIMIT: Timestamps must increase by at least the minimum interval,
+            // otherwise the game ends without applying the batch or submitting a score
+            let timestamps: Vec<u64> = moves_u64
+                .iter()
+                .map(|(_, timestamp, _)| *timestamp)
+                .collect();
+            if !AntiCheat::move_intervals_valid(&timestamps, min_move_interval_micros) {
+                let reason = format!(
+                    "Move timestamps not increasing by at least {} micros",
+                    min_move_interval_micros
+                );
+                Self::end_suspected_cheat(contract, board_id, player, leaderboard_id, reason).await;
+                return Ok(());
+            }
+
             match GameMoveProcessor::process_moves(
                 &board_id,
                 &player,
@@ -90,6 +275,11 @@ impl GameOperationHandler {
                 last_processed_timestamp, // 🔒 NEW: Pass for duplicate detection
                 start_time,
                 end_time,
+                // ⏰ Bonus windows follow block time, not the client's move timestamps
+                BonusWindow::multiplier_at(&bonus_windows, contract.runtime.system_time().micros()),
+                &spawns,
+                combo_multiplier_enabled,
+                *board.current_combo.get(),
             ) {
                 GameMoveResult::Success {
                     final_board,
@@ -97,21 +287,115 @@ impl GameOperationHandler {
                     final_highest_tile,
                     initial_highest_tile: _, // Not needed with simplified score submission
                     is_ended,
+                    is_deadlocked,
                     latest_timestamp,
                     move_history,
+                    bonus_points,
+                    null_moves,
+                    current_combo,
+                    last_combo_bonus,
                 } => {
+                    // 🛡️ ANTI-CHEAT: A tournament's checks run on every batch; a failed check
+                    // ends the game without applying the batch
+                    if let Some(config) = &anti_cheat {
+                        let samples: Vec<MoveSample> = move_history
+                            .iter()
+                            .map(|processed| MoveSample {
+                                direction: processed.direction,
+                                timestamp: processed.timestamp,
+                                board_after: processed.board_after,
+                                score_after: processed.score_after,
+                            })
+                            .collect();
+                        if let Some(check) =
+                            AntiCheat::failed_batch_check(config, &samples, null_moves)
+                        {
+                            let reason = format!(
+                                "Failed {} check ({:.2}, threshold {:.2})",
+                                check.check, check.value, check.threshold
+                            );
+                            Self::end_suspected_cheat(
+                                contract,
+                                board_id,
+                                player,
+                                leaderboard_id,
+                                reason,
+                            )
+                            .await;
+                            return Ok(());
+                        }
+                    }
+
+                    // ⏰ BONUS WINDOW: Board score = tile score + accumulated bonus
+                    let bonus_score = *board.bonus_score.get() + bonus_points;
+                    // ↪️ Continued games keep the score carried over from the previous board
+                    let final_score = final_score + bonus_score + *board.continuation_score.get();
+
+                    // 🎯 SCORE CAP: Reaching the cap ends the game at the capped score
+                    let score_cap_reached =
+                        max_score_per_player > 0 && final_score >= max_score_per_player;
+                    let (final_score, mut is_ended) = if score_cap_reached {
+                        (max_score_per_player, true)
+                    } else {
+                        (final_score, is_ended)
+                    };
+
+                    // 💀 ELIMINATION: A deadlock with lives left restarts the board, keeping the score
+                    let mut final_board = final_board;
+                    let continuation_score = *board.continuation_score.get();
+                    let mut life_lost = false;
+                    let mut is_deadlocked = is_deadlocked;
+                    if is_ended && !score_cap_reached && *board.lives.get() > 0 && is_deadlocked {
+                        let lives = *board.lives.get() - 1;
+                        board.lives.set(lives);
+                        life_lost = true;
+                        if lives > 0 {
+                            final_board = Game::with_spawns(
+                                &board_id,
+                                &player,
+                                latest_timestamp,
+                                spawns.clone(),
+                            )
+                            .board;
+                            board.continuation_score.set(
+                                final_score.saturating_sub(bonus_score + Game::score(final_board)),
+                            );
+                            is_ended = false;
+                            is_deadlocked = false;
+                        }
+                    }
+
                     // Update board state
                     board.board.set(final_board);
+                    board.bonus_score.set(bonus_score);
+                    board.current_combo.set(current_combo);
+                    board.last_combo_bonus.set(last_combo_bonus);
+                    board
+                        .null_move_count
+                        .set(*board.null_move_count.get() + null_moves);
                     board.score.set(final_score);
+                    // 🏁 Cached as soon as the board is stuck, whether or not that ends the game
+                    board.is_deadlocked.set(is_deadlocked);
                     if is_ended {
                         board.is_ended.set(true);
                     }
-                    
+
                     // 🔒 DUPLICATE PREVENTION: Update last processed timestamp
                     board.last_processed_timestamp.set(latest_timestamp);
 
                     // 🎮 NEW: Store move history for replay feature
                     let current_move_count = *board.move_count.get();
+                    let mut spawn_2_count = *board.spawn_2_count.get();
+                    let mut spawn_4_count = *board.spawn_4_count.get();
+                    let mut previous_board = initial_board;
+                    let mut total_valid_moves_available = *board.total_valid_moves_available.get();
+                    let mut moves_with_only_one_option = *board.moves_with_only_one_option.get();
+                    let mut move_log = board.move_log.get().clone();
+                    // ↩️ UNDO: Keep the state before each move while the board has undos left
+                    let previous_bonus_score = bonus_score - bonus_points;
+                    let keep_undo_history = *board.undo_budget.get() > 0;
+                    let undo_history_size = *board.undo_history_size.get() as usize;
+                    let mut undo_history = board.undo_history.get().clone();
                     for (idx, processed_move) in move_history.iter().enumerate() {
                         let move_index = current_move_count + idx as u32;
                         let move_record = board
@@ -134,14 +418,93 @@ impl GameOperationHandler {
                         move_record.score_after.set(processed_move.score_after);
                         // 🎵 Rhythm mode: store beat number for replay
                         move_record.beat_number.set(processed_move.beat_number);
+                        MoveLog::append(&mut move_log, processed_move.direction);
+                        if keep_undo_history {
+                            undo_history.push_back(UndoSnapshot {
+                                board: previous_board,
+                                score: Game::score(previous_board)
+                                    + previous_bonus_score
+                                    + continuation_score,
+                                bonus_score: previous_bonus_score,
+                                move_count: move_index,
+                            });
+                            while undo_history.len() > undo_history_size {
+                                undo_history.pop_front();
+                            }
+                        }
+
+                        // 🛡️ Spawn ratio: count which tile the move spawned
+                        match Game::spawned_tile(
+                            previous_board,
+                            processed_move.direction,
+                            processed_move.board_after,
+                        ) {
+                            2 => spawn_2_count += 1,
+                            4 => spawn_4_count += 1,
+                            _ => {}
+                        }
+                        // 🌿 Branching factor: how many directions the player could choose from
+                        let options = Moves::count_available_moves(previous_board);
+                        total_valid_moves_available += options as u64;
+                        if options == 1 {
+                            moves_with_only_one_option += 1;
+                        }
+                        previous_board = processed_move.board_after;
+
+                        // 🃏 Combo card: count merges and remember when each tile was first made
+                        for tile in &processed_move.merged_tiles {
+                            let tile = *tile as u32;
+                            let count = board
+                                .merge_combinations
+                                .get(&tile)
+                                .await
+                                .unwrap()
+                                .unwrap_or(0);
+                            board.merge_combinations.insert(&tile, count + 1).unwrap();
+                            if count == 0 {
+                                board
+                                    .first_time_tiles
+                                    .insert(&tile, processed_move.timestamp)
+                                    .unwrap();
+                            }
+                        }
                     }
                     board
                         .move_count
                         .set(current_move_count + move_history.len() as u32);
+                    board.spawn_2_count.set(spawn_2_count);
+                    board.spawn_4_count.set(spawn_4_count);
+                    board
+                        .total_valid_moves_available
+                        .set(total_valid_moves_available);
+                    board
+                        .moves_with_only_one_option
+                        .set(moves_with_only_one_option);
+                    board.move_log.set(move_log);
+                    // ⏲️ Move timing follows block time, not the client's move timestamps
+                    board.record_move_batch(
+                        contract.runtime.system_time().micros(),
+                        move_history.len() as u32,
+                    );
+                    // 💀 ELIMINATION: A lost life cannot be undone
+                    if life_lost {
+                        undo_history.clear();
+                    }
+                    board.undo_history.set(undo_history);
+                    if is_ended {
+                        Self::seal_replay(board);
+                    }
+                    let lives = *board.lives.get();
 
                     // 🔒 FIX: Get tournament ID from the BOARD, not from local leaderboard
                     // The board knows which tournament it belongs to
                     let leaderboard_id = board.leaderboard_id.get().clone();
+                    // 🏁 WARMUP: Warmup games keep their own best score and are ignored by the leaderboard
+                    let is_warmup = *board.is_warmup.get();
+                    let average_branching_factor = board.average_branching_factor();
+                    let hints_used = *board.hints_used.get();
+                    let moves_count = *board.move_count.get();
+                    let best_score_key = Self::best_score_key(&leaderboard_id, is_warmup);
 
                     // 🔒 FIX: Get current best score for THIS TOURNAMENT from player_records
                     // This ensures we track per-tournament best scores, not all-time best
@@ -153,7 +516,7 @@ impl GameOperationHandler {
                         .unwrap();
                     let current_best = player_record
                         .best_score
-                        .get(&leaderboard_id)
+                        .get(&best_score_key)
                         .await
                         .unwrap()
                         .unwrap_or(0);
@@ -167,17 +530,97 @@ impl GameOperationHandler {
 
                     let current_time = contract.runtime.system_time().micros();
                     let end_time_val = *board.end_time.get();
-                    let tournament_just_ended = end_time_val > 0 && current_time >= end_time_val * 1000; // end_time is in millis, current_time in micros
+                    let tournament_just_ended =
+                        end_time_val > 0 && current_time >= end_time_val * 1000; // end_time is in millis, current_time in micros
                     let board_ended = is_ended;
-                    
+
+                    // 🛡️ Spawn ratio: flag ended games whose 4-tile share is statistically off
+                    if is_ended
+                        && spawns.is_classic()
+                        && AntiCheat::spawn_ratio_deviations(spawn_2_count, spawn_4_count)
+                            .is_some_and(|deviations| deviations > SPAWN_RATIO_MAX_DEVIATIONS)
+                    {
+                        use crate::contract_domain::events::emitters::EventEmitter;
+                        let sample_size = spawn_2_count + spawn_4_count;
+                        EventEmitter::emit_abnormal_spawn_ratio(
+                            contract,
+                            board_id.clone(),
+                            spawn_4_count as f64 / sample_size as f64,
+                            sample_size,
+                        )
+                        .await;
+                    }
+
+                    if score_cap_reached {
+                        use crate::contract_domain::events::emitters::EventEmitter;
+                        EventEmitter::emit_score_cap_reached(
+                            contract,
+                            board_id.clone(),
+                            player.clone(),
+                            leaderboard_id.clone(),
+                            final_score,
+                        )
+                        .await;
+                    }
+
+                    // 💀 ELIMINATION: Report the lost life to the leaderboard chain
+                    if life_lost {
+                        use linera_sdk::linera_base_types::ChainId;
+                        use std::str::FromStr;
+                        if let Ok(leaderboard_chain_id) = ChainId::from_str(&leaderboard_id) {
+                            contract.send_message(
+                                leaderboard_chain_id,
+                                game2048::Message::EliminationLifeLost {
+                                    board_id: board_id.clone(),
+                                    lives_remaining: lives,
+                                    score: final_score,
+                                },
+                            );
+                        }
+                    }
+
+                    if is_ended && leaderboard_id == DAILY_CHALLENGE_LEADERBOARD_ID {
+                        let completed_at = contract.runtime.system_time().micros();
+                        Self::submit_daily_challenge(
+                            contract,
+                            &board_id,
+                            DailyChallengeRecord {
+                                player: player.clone(),
+                                score: final_score,
+                                moves_count,
+                                completed_at,
+                            },
+                        );
+                    }
+
+                    // 🏅 HISTORY: Keep the player's best ended games
+                    if is_ended && !is_warmup {
+                        let ended_at = contract.runtime.system_time().micros();
+                        Self::record_top_board(
+                            contract,
+                            &player,
+                            BoardSummary {
+                                board_id: board_id.clone(),
+                                score: final_score,
+                                highest_tile: Game::highest_tile(final_board),
+                                moves_count,
+                                leaderboard_id: leaderboard_id.clone(),
+                                ended_at,
+                            },
+                        )
+                        .await;
+                    }
+
                     // Only send on game end or tournament end
-                    let should_send = final_score > 0 
-                        && final_score > current_best 
+                    let should_send = final_score > 0
+                        && final_score > current_best
                         && (board_ended || tournament_just_ended);
 
                     if should_send {
                         // Determine game status
-                        let game_status = if board_ended {
+                        let game_status = if score_cap_reached {
+                            GameStatus::Ended(GameEndReason::ScoreCapReached)
+                        } else if board_ended {
                             GameStatus::Ended(GameEndReason::NoMoves)
                         } else {
                             GameStatus::Ended(GameEndReason::TournamentEnded)
@@ -199,26 +642,51 @@ impl GameOperationHandler {
 
                         use linera_sdk::linera_base_types::ChainId;
                         use std::str::FromStr;
-                        
+
                         // Extract values before borrowing runtime
                         let player_chain_id = contract.runtime.chain_id().to_string();
-                        
+
+                        let (submitted_score, streak_bonus_applied) = Self::apply_streak_bonus(
+                            contract,
+                            &player,
+                            &leaderboard_id,
+                            final_score,
+                        )
+                        .await;
+
                         if let Ok(leaderboard_chain_id) = ChainId::from_str(&leaderboard_id) {
-                            contract
-                                .runtime
-                                .prepare_message(game2048::Message::SubmitScore {
+                            let top_boards = Self::top_boards(contract, &player).await;
+                            let tile_milestone_hit =
+                                Self::tile_milestone_hit(contract, &board_id).await;
+                            let message_nonce =
+                                crate::contract_domain::ContractHelpers::next_score_nonce(contract);
+                            contract.send_message(
+                                leaderboard_chain_id,
+                                game2048::Message::SubmitScore {
                                     player: player.clone(),
                                     player_chain_id,
                                     board_id: board_id.clone(),
-                                    score: final_score,
+                                    score: submitted_score,
                                     highest_tile: final_highest_tile,
                                     game_status,
                                     timestamp: latest_timestamp,
-                                    boards_in_tournament: current_board_count,
+                                    boards_per_leaderboard: [(
+                                        leaderboard_id.clone(),
+                                        current_board_count,
+                                    )]
+                                    .into(),
                                     start_time: start_time_raw,
                                     end_time: end_time_raw,
-                                })
-                                .send_to(leaderboard_chain_id);
+                                    streak_bonus_applied,
+                                    is_warmup,
+                                    average_branching_factor,
+                                    hints_used,
+                                    moves_count,
+                                    message_nonce,
+                                    tile_milestone_hit,
+                                    top_boards,
+                                },
+                            );
                         }
 
                         // Update tracking state
@@ -240,9 +708,26 @@ impl GameOperationHandler {
                             .unwrap();
                         player_record
                             .best_score
-                            .insert(&leaderboard_id, final_score)
+                            .insert(&best_score_key, final_score)
                             .unwrap();
+                        Self::notify_friends(
+                            contract,
+                            &player,
+                            &leaderboard_id,
+                            is_warmup,
+                            final_score,
+                        )
+                        .await;
                     }
+
+                    let game_status = if score_cap_reached {
+                        GameStatus::Ended(GameEndReason::ScoreCapReached)
+                    } else if is_ended {
+                        GameStatus::Ended(GameEndReason::NoMoves)
+                    } else {
+                        GameStatus::Active
+                    };
+                    Self::emit_player_score_update(contract, &board_id, game_status).await;
                 }
                 // 🔒 DUPLICATE DETECTION: All moves were already processed (retry scenario)
                 // This is NOT an error - silently succeed since moves were already applied
@@ -255,25 +740,35 @@ impl GameOperationHandler {
                     );
                     // No state changes needed - moves were already processed
                 }
+                // 🔒 ATOMIC BATCH: Nothing was written for this batch
+                GameMoveResult::InvalidMoveSequence { at_index, reason } => {
+                    return Err(MoveError::InvalidMoveSequence { at_index, reason });
+                }
                 GameMoveResult::Error(msg) => {
-                    panic!("{}", msg);
+                    return Err(MoveError::Rejected(msg));
                 }
             }
         } else if moves.is_empty() {
             // 🚀 FORCED GAME END (tournament time expired or explicit end)
             // This always sends final score if it beats tournament best
-            let score = Game::score(*board.board.get());
+            let score = Game::score(*board.board.get()) + *board.continuation_score.get();
             let highest_tile = Game::highest_tile(*board.board.get());
-            
+
             // Get tournament times for SubmitScore message
             let board_start_time = *board.start_time.get();
             let board_end_time = *board.end_time.get();
 
             // 🚀 MARK GAME AS ENDED
             board.is_ended.set(true);
+            Self::seal_replay(board);
 
             // Get tournament ID from the board
             let leaderboard_id = board.leaderboard_id.get().clone();
+            let is_warmup = *board.is_warmup.get();
+            let average_branching_factor = board.average_branching_factor();
+            let hints_used = *board.hints_used.get();
+            let moves_count = *board.move_count.get();
+            let best_score_key = Self::best_score_key(&leaderboard_id, is_warmup);
 
             // Get current best score for this player from player_records
             let player_record = contract
@@ -284,7 +779,7 @@ impl GameOperationHandler {
                 .unwrap();
             let current_best = player_record
                 .best_score
-                .get(&leaderboard_id)
+                .get(&best_score_key)
                 .await
                 .unwrap()
                 .unwrap_or(0);
@@ -307,26 +802,42 @@ impl GameOperationHandler {
 
                 use linera_sdk::linera_base_types::ChainId;
                 use std::str::FromStr;
-                
+
                 let player_chain_id = contract.runtime.chain_id().to_string();
                 let timestamp = contract.runtime.system_time().micros();
-                
+
+                let (submitted_score, streak_bonus_applied) =
+                    Self::apply_streak_bonus(contract, &player, &leaderboard_id, score).await;
+
                 if let Ok(leaderboard_chain_id) = ChainId::from_str(&leaderboard_id) {
-                    contract
-                        .runtime
-                        .prepare_message(game2048::Message::SubmitScore {
+                    let top_boards = Self::top_boards(contract, &player).await;
+                    let tile_milestone_hit = Self::tile_milestone_hit(contract, &board_id).await;
+                    let message_nonce =
+                        crate::contract_domain::ContractHelpers::next_score_nonce(contract);
+                    contract.send_message(
+                        leaderboard_chain_id,
+                        game2048::Message::SubmitScore {
                             player: player.clone(),
                             player_chain_id,
                             board_id: board_id.clone(),
-                            score,
+                            score: submitted_score,
                             highest_tile,
                             game_status: GameStatus::Ended(GameEndReason::TournamentEnded),
                             timestamp,
-                            boards_in_tournament: current_board_count,
+                            boards_per_leaderboard: [(leaderboard_id.clone(), current_board_count)]
+                                .into(),
                             start_time: board_start_time,
                             end_time: board_end_time,
-                        })
-                        .send_to(leaderboard_chain_id);
+                            streak_bonus_applied,
+                            is_warmup,
+                            average_branching_factor,
+                            hints_used,
+                            moves_count,
+                            message_nonce,
+                            tile_milestone_hit,
+                            top_boards,
+                        },
+                    );
                 }
 
                 // Update player's best score for THIS TOURNAMENT
@@ -338,20 +849,355 @@ impl GameOperationHandler {
                     .unwrap();
                 player_record
                     .best_score
-                    .insert(&leaderboard_id, score)
+                    .insert(&best_score_key, score)
                     .unwrap();
+                Self::notify_friends(contract, &player, &leaderboard_id, is_warmup, score).await;
+            }
+        } else {
+            return Err(MoveError::GameEnded);
+        }
+        Ok(())
+    }
+
+    /// 🔲 SIZED BOARDS: Apply moves to a 3x3, 5x5 or 6x6 board. Each move that changes the
+    /// board spawns a tile and scores its merges; an empty batch, the tournament end or the
+    /// speed deadline ends the game. Undo, hints, combos and elimination lives are 4x4 only.
+    async fn apply_sized_moves(
+        contract: &mut crate::Game2048Contract,
+        board_id: String,
+        moves: Vec<MoveInput>,
+        player: String,
+    ) -> Result<(), MoveError> {
+        let leaderboard_id = contract
+            .state
+            .boards
+            .load_entry_mut(&board_id)
+            .await
+            .unwrap()
+            .leaderboard_id
+            .get()
+            .clone();
+        let max_moves_per_call = match contract.get_cached_tournament(&leaderboard_id).await {
+            Some(tournament) if tournament.replay_mode => return Err(MoveError::ReplayMode),
+            Some(tournament) if tournament.max_moves_per_call > 0 => tournament.max_moves_per_call,
+            _ => DEFAULT_MAX_MOVES_PER_CALL,
+        };
+        if moves.len() > max_moves_per_call as usize {
+            return Err(MoveError::TooManyMoves {
+                count: moves.len(),
+                max: max_moves_per_call as u32,
+            });
+        }
+
+        let current_time = contract.runtime.system_time().micros();
+        let board = contract
+            .state
+            .boards
+            .load_entry_mut(&board_id)
+            .await
+            .unwrap();
+        if *board.is_ended.get() {
+            return Err(MoveError::GameEnded);
+        }
+        let end_time = *board.end_time.get();
+        let time_is_up = (end_time > 0 && current_time >= end_time)
+            || GameVariant::is_past_deadline(*board.deadline.get(), current_time);
+        let end_requested = moves.is_empty();
+
+        let mut sized = board.sized_board().expect("Board is not a sized board");
+        let mut score = *board.score.get();
+        let moves_before = *board.move_count.get();
+        let mut move_count = moves_before;
+        let mut null_moves = 0;
+        let mut move_log = board.move_log.get().clone();
+        let mut last_processed_timestamp = *board.last_processed_timestamp.get();
+        if !time_is_up {
+            for (direction, timestamp, _) in moves {
+                let timestamp = timestamp.parse::<u64>().unwrap_or(current_time);
+                // 🔒 DUPLICATE DETECTION: Moves up to the last processed timestamp were applied
+                if timestamp <= last_processed_timestamp {
+                    continue;
+                }
+                if sized.is_ended() {
+                    break;
+                }
+                let before = sized.clone();
+                let gained = sized.slide(direction);
+                last_processed_timestamp = timestamp;
+                if sized == before {
+                    null_moves += 1;
+                    continue;
+                }
+                score += gained;
+                let mut state = spawn_state(&board_id, &player, timestamp, move_count.into());
+                sized.spawn_tile(&mut state);
+                MoveLog::append(&mut move_log, direction);
+                move_count += 1;
             }
+        }
+        let is_deadlocked = sized.is_ended();
+        let is_ended = end_requested || time_is_up || is_deadlocked;
+        let highest_tile = sized.highest_tile();
+
+        board.sized_tiles.set(sized.tiles);
+        board.score.set(score);
+        board.move_count.set(move_count);
+        board
+            .null_move_count
+            .set(*board.null_move_count.get() + null_moves);
+        board.move_log.set(move_log);
+        board.last_processed_timestamp.set(last_processed_timestamp);
+        board.record_move_batch(current_time, move_count - moves_before);
+        board.is_deadlocked.set(is_deadlocked);
+        if !is_ended {
+            Self::emit_player_score_update(contract, &board_id, GameStatus::Active).await;
+            return Ok(());
+        }
+        board.is_ended.set(true);
+        Self::seal_replay(board);
+
+        let game_status = if is_deadlocked && !end_requested && !time_is_up {
+            GameStatus::Ended(GameEndReason::NoMoves)
         } else {
-            panic!("Game is ended");
+            GameStatus::Ended(GameEndReason::TournamentEnded)
+        };
+        Self::submit_sized_board_score(
+            contract,
+            &board_id,
+            &player,
+            score,
+            highest_tile,
+            game_status.clone(),
+        )
+        .await;
+        Self::emit_player_score_update(contract, &board_id, game_status).await;
+        Ok(())
+    }
+
+    /// 📡 Report the board's state after a move batch on the "player_score_update" stream
+    async fn emit_player_score_update(
+        contract: &mut crate::Game2048Contract,
+        board_id: &str,
+        game_status: GameStatus,
+    ) {
+        let board = contract
+            .state
+            .boards
+            .load_entry_mut(board_id)
+            .await
+            .unwrap();
+        let player = board.player.get().clone();
+        let leaderboard_id = board.leaderboard_id.get().clone();
+        let best_score_key = Self::best_score_key(&leaderboard_id, *board.is_warmup.get());
+        let highest_tile = match board.sized_board() {
+            Some(sized) => sized.highest_tile(),
+            None => Game::highest_tile(*board.board.get()),
+        };
+        let score = *board.score.get();
+        let timestamp = *board.last_processed_timestamp.get();
+        let moves_count = *board.move_count.get();
+        let hints_used = *board.hints_used.get();
+        let bonus_points_this_move = *board.last_combo_bonus.get();
+        let total_think_time_micros = *board.total_think_time_micros.get();
+        let min_move_time_micros = *board.min_move_time_micros.get();
+        let max_move_time_micros = *board.max_move_time_micros.get();
+        let avg_move_time_micros = board.avg_move_time_micros();
+
+        let current_leaderboard_best = contract
+            .state
+            .player_records
+            .load_entry_mut(&player)
+            .await
+            .unwrap()
+            .best_score
+            .get(&best_score_key)
+            .await
+            .unwrap()
+            .unwrap_or(0);
+        let board_count = contract
+            .state
+            .players
+            .load_entry_mut(&player)
+            .await
+            .unwrap()
+            .boards_per_tournament
+            .get(&leaderboard_id)
+            .await
+            .unwrap()
+            .unwrap_or(0);
+        let chain_id = contract.runtime.chain_id().to_string();
+
+        use crate::contract_domain::events::emitters::EventEmitter;
+        EventEmitter::emit_player_score_update(
+            contract,
+            game2048::GameEvent::PlayerScoreUpdate {
+                player,
+                board_id: board_id.to_string(),
+                score,
+                chain_id,
+                timestamp,
+                game_status,
+                highest_tile,
+                moves_count,
+                boards_per_leaderboard: [(leaderboard_id.clone(), board_count)].into(),
+                leaderboard_id,
+                current_leaderboard_best,
+                hints_used,
+                bonus_points_this_move,
+                total_think_time_micros,
+                min_move_time_micros,
+                max_move_time_micros,
+                avg_move_time_micros,
+            },
+        )
+        .await;
+    }
+
+    /// 🔲 SIZED BOARDS: Record an ended sized board and send its score to the leaderboard
+    /// when it beats the player's tournament best
+    async fn submit_sized_board_score(
+        contract: &mut crate::Game2048Contract,
+        board_id: &str,
+        player: &str,
+        score: u64,
+        highest_tile: u64,
+        game_status: GameStatus,
+    ) {
+        let board = contract
+            .state
+            .boards
+            .load_entry_mut(board_id)
+            .await
+            .unwrap();
+        let leaderboard_id = board.leaderboard_id.get().clone();
+        let is_warmup = *board.is_warmup.get();
+        let average_branching_factor = board.average_branching_factor();
+        let hints_used = *board.hints_used.get();
+        let moves_count = *board.move_count.get();
+        let start_time = *board.start_time.get();
+        let end_time = *board.end_time.get();
+        let best_score_key = Self::best_score_key(&leaderboard_id, is_warmup);
+        let timestamp = contract.runtime.system_time().micros();
+
+        if !is_warmup {
+            Self::record_top_board(
+                contract,
+                player,
+                BoardSummary {
+                    board_id: board_id.to_string(),
+                    score,
+                    highest_tile,
+                    moves_count,
+                    leaderboard_id: leaderboard_id.clone(),
+                    ended_at: timestamp,
+                },
+            )
+            .await;
+        }
+
+        let current_best = contract
+            .state
+            .player_records
+            .load_entry_mut(player)
+            .await
+            .unwrap()
+            .best_score
+            .get(&best_score_key)
+            .await
+            .unwrap()
+            .unwrap_or(0);
+        if score == 0 || score <= current_best {
+            return;
+        }
+        let current_board_count = contract
+            .state
+            .players
+            .load_entry_mut(player)
+            .await
+            .unwrap()
+            .boards_per_tournament
+            .get(&leaderboard_id)
+            .await
+            .unwrap()
+            .unwrap_or(0);
+        let player_chain_id = contract.runtime.chain_id().to_string();
+        let (submitted_score, streak_bonus_applied) =
+            Self::apply_streak_bonus(contract, player, &leaderboard_id, score).await;
+
+        if let Ok(leaderboard_chain_id) = ChainId::from_str(&leaderboard_id) {
+            let top_boards = Self::top_boards(contract, player).await;
+            let tile_milestone_hit = Self::tile_milestone_hit(contract, board_id).await;
+            let message_nonce = crate::contract_domain::ContractHelpers::next_score_nonce(contract);
+            contract.send_message(
+                leaderboard_chain_id,
+                game2048::Message::SubmitScore {
+                    player: player.to_string(),
+                    player_chain_id,
+                    board_id: board_id.to_string(),
+                    score: submitted_score,
+                    highest_tile,
+                    game_status,
+                    timestamp,
+                    boards_per_leaderboard: [(leaderboard_id.clone(), current_board_count)].into(),
+                    start_time,
+                    end_time,
+                    streak_bonus_applied,
+                    is_warmup,
+                    average_branching_factor,
+                    hints_used,
+                    moves_count,
+                    message_nonce,
+                    tile_milestone_hit,
+                    top_boards,
+                },
+            );
+        }
+
+        contract
+            .state
+            .player_records
+            .load_entry_mut(player)
+            .await
+            .unwrap()
+            .best_score
+            .insert(&best_score_key, score)
+            .unwrap();
+        Self::notify_friends(contract, player, &leaderboard_id, is_warmup, score).await;
+    }
+
+    /// 🔲 SIZED BOARDS: Switch a freshly created board to `board_size` with two starting tiles
+    async fn start_sized_board(
+        contract: &mut crate::Game2048Contract,
+        board_id: &str,
+        board_size: BoardSize,
+        player: &str,
+        timestamp: u64,
+    ) {
+        if board_size == BoardSize::FourByFour {
+            return;
         }
+        let mut sized = SizedBoard::empty(board_size.dimension());
+        let mut state = spawn_state(board_id, player, timestamp, 0);
+        sized.spawn_tile(&mut state);
+        sized.spawn_tile(&mut state);
+        let board = contract
+            .state
+            .boards
+            .load_entry_mut(board_id)
+            .await
+            .unwrap();
+        board.board_size.set(board_size);
+        board.board.set(0);
+        board.sized_tiles.set(sized.tiles);
     }
 
     /// 🚀 MESSAGE-BASED: Create a new board for the player
-    /// 
+    ///
     /// In the message-based architecture:
     /// - No shards needed
     /// - Board is created locally on player chain
     /// - Scores are sent directly to leaderboard via SubmitScore message when player makes moves
+    #[allow(clippy::too_many_arguments)]
     pub async fn handle_new_board(
         contract: &mut crate::Game2048Contract,
         player: String,
@@ -360,28 +1206,84 @@ impl GameOperationHandler {
         leaderboard_id: String,
         // 🎵 Rhythm mode: which music track was used (-1 = no rhythm/metronome, 0+ = track index)
         rhythm_track_index: i16,
+        pow_nonce: Option<u64>,
+        board_size: BoardSize,
     ) {
         // Validate password
         contract
             .validate_player_password(&player, &password_hash)
             .await;
 
-        // Get tournament times from cache (if available)
-        let (tournament_start_time, tournament_end_time) =
-            if let Some(tournament) = contract.get_cached_tournament(&leaderboard_id).await {
-                (
-                    tournament.start_time.unwrap_or(0),
-                    tournament.end_time.unwrap_or(0),
-                )
-            } else {
-                (0, 0) // Default to unlimited if tournament not in cache
-            };
+        // 📊 Auto rate-limit: this chain sent too many messages in the current window
+        if crate::contract_domain::ContractHelpers::is_throttled(contract) {
+            panic!("Board creation is temporarily throttled, try again shortly");
+        }
+
+        // Get tournament times, token gate and proof-of-work requirements from cache (if available)
+        let (
+            tournament_start_time,
+            tournament_end_time,
+            entry_token_requirement,
+            pow_difficulty,
+            warmup_duration_seconds,
+            new_board_cooldown_seconds,
+            max_participants,
+            total_players,
+        ) = if let Some(tournament) = contract.get_cached_tournament(&leaderboard_id).await {
+            (
+                tournament.start_time.unwrap_or(0),
+                tournament.end_time.unwrap_or(0),
+                tournament.entry_token_requirement,
+                tournament.pow_difficulty,
+                tournament.warmup_duration_seconds,
+                Some(match tournament.new_board_cooldown_seconds {
+                    0 => DEFAULT_NEW_BOARD_COOLDOWN_SECONDS,
+                    cooldown => cooldown,
+                }),
+                tournament.max_participants,
+                tournament.total_players,
+            )
+        } else {
+            (0, 0, None, 0, 0, None, None, 0) // Default to unlimited if tournament not in cache
+        };
+
+        // 🕒 COOLDOWN: One new board per cooldown window in a tournament, so starting tiles
+        // cannot be farmed by spamming NewBoard
+        let current_time = contract.runtime.system_time().micros();
+        if let Some(cooldown_seconds) = new_board_cooldown_seconds {
+            let last_creation = contract
+                .state
+                .players
+                .load_entry_mut(&player)
+                .await
+                .unwrap()
+                .last_board_creation
+                .get(&leaderboard_id)
+                .await
+                .unwrap();
+            if let Some(last_creation) = last_creation {
+                let retry_after = last_creation.saturating_add(cooldown_seconds * 1_000_000);
+                if current_time < retry_after {
+                    panic!(
+                        "New board cooldown: retry in {} seconds",
+                        (retry_after - current_time).div_ceil(1_000_000)
+                    );
+                }
+            }
+        }
 
         // 🔒 VALIDATION: Reject board creation if tournament hasn't started yet
+        // 🏁 WARMUP: ...unless it is in its warmup window; those games do not count
+        let mut is_warmup = false;
         if tournament_start_time > 0 {
             let current_time = contract.runtime.system_time().micros();
             if current_time < tournament_start_time {
-                panic!("Tournament has not started yet");
+                let warmup_start = tournament_start_time
+                    .saturating_sub(warmup_duration_seconds as u64 * 1_000_000);
+                if warmup_duration_seconds == 0 || current_time < warmup_start {
+                    panic!("Tournament has not started yet");
+                }
+                is_warmup = true;
             }
         }
 
@@ -393,127 +1295,1566 @@ impl GameOperationHandler {
             }
         }
 
-        // Create board locally
-        let nonce = contract.state.nonce.get();
-        let board_id = format!(
-            "{}.{}",
-            contract.runtime.chain_id(),
-            hash_seed(&nonce.to_string(), &player, timestamp)
-        );
-
-        let new_board = Game::new(&board_id, &player, timestamp).board;
+        // 🎟️ QUALIFIER: Players who advanced from a qualifier have guaranteed entry
+        let is_qualified = contract
+            .state
+            .qualified_tournaments
+            .get(&leaderboard_id)
+            .await
+            .unwrap()
+            .unwrap_or(false);
+
+        // 🪙 TOKEN GATE: Player chain balance (or attestation) must meet the minimum
+        if let Some(requirement) = entry_token_requirement.filter(|_| !is_qualified) {
+            let result = if requirement.pre_authorize {
+                let current_time = contract.runtime.system_time().micros();
+                let attestation = contract
+                    .state
+                    .balance_attestations
+                    .get(&leaderboard_id)
+                    .await
+                    .unwrap();
+                requirement.check_attestation(attestation.as_ref(), current_time)
+            } else {
+                requirement.check_balance(contract.runtime.chain_balance())
+            };
+            if let Err(error) = result {
+                panic!("{}", error);
+            }
+        }
+
+        // ⛏️ PROOF OF WORK: One hash to verify, ~2^difficulty hashes to find
+        if pow_difficulty > 0 && !is_qualified {
+            let Some(pow_nonce) = pow_nonce else {
+                panic!("This tournament requires a proof of work");
+            };
+            let player_chain_id = contract.runtime.chain_id().to_string();
+            if proof_of_work_bits(&player_chain_id, &leaderboard_id, pow_nonce)
+                < pow_difficulty as u32
+            {
+                panic!("Insufficient proof of work");
+            }
+        }
+
+        // 🎟️ WAITLIST: A full tournament only takes players who already joined it; newcomers
+        // are queued on the leaderboard chain instead of getting a board
+        let has_joined = max_participants.is_none()
+            || contract
+                .state
+                .players
+                .load_entry_mut(&player)
+                .await
+                .unwrap()
+                .last_board_creation
+                .contains_key(&leaderboard_id)
+                .await
+                .unwrap();
+        if max_participants.is_some_and(|max_participants| total_players >= max_participants)
+            && !is_qualified
+        {
+            let is_admitted = contract
+                .state
+                .waitlist_admissions
+                .contains_key(&leaderboard_id)
+                .await
+                .unwrap();
+            if !has_joined && !is_admitted {
+                if let Ok(leaderboard_chain_id) = ChainId::from_str(&leaderboard_id) {
+                    contract.send_message(
+                        leaderboard_chain_id,
+                        game2048::Message::JoinWaitlist {
+                            player,
+                            timestamp: current_time,
+                        },
+                    );
+                }
+                return;
+            }
+        }
+
+        let board_id = Self::create_board(
+            contract,
+            &player,
+            timestamp,
+            &leaderboard_id,
+            tournament_start_time,
+            tournament_end_time,
+            rhythm_track_index,
+        )
+        .await;
+        Self::start_sized_board(contract, &board_id, board_size, &player, timestamp).await;
+        if new_board_cooldown_seconds.is_some() {
+            contract
+                .state
+                .players
+                .load_entry_mut(&player)
+                .await
+                .unwrap()
+                .last_board_creation
+                .insert(&leaderboard_id, current_time)
+                .unwrap();
+        }
+        if is_warmup {
+            contract
+                .state
+                .boards
+                .load_entry_mut(&board_id)
+                .await
+                .unwrap()
+                .is_warmup
+                .set(true);
+        }
+
+        // 🎟️ WAITLIST: The first board takes a slot on the leaderboard chain (or queues the player)
+        if !has_joined {
+            if let Ok(leaderboard_chain_id) = ChainId::from_str(&leaderboard_id) {
+                contract.send_message(
+                    leaderboard_chain_id,
+                    game2048::Message::RegisterParticipant {
+                        player,
+                        timestamp: current_time,
+                    },
+                );
+            }
+        }
+
+        // 🚀 MESSAGE-BASED: No registration with shard needed
+        // No event emission needed
+        // First SubmitScore is sent when player makes moves and score > 0
+    }
+
+    /// 🏅 Add an ended game to the player's best games, evicting the lowest over capacity
+    async fn record_top_board(
+        contract: &mut crate::Game2048Contract,
+        player: &str,
+        summary: BoardSummary,
+    ) {
+        let capacity = contract
+            .state
+            .player_preferences
+            .get(player)
+            .await
+            .unwrap()
+            .and_then(|preferences| preferences.top_boards_capacity)
+            .unwrap_or(DEFAULT_TOP_BOARDS);
+        let player_state = contract.state.players.load_entry_mut(player).await.unwrap();
+        let mut top_boards = player_state.top_boards.get().clone();
+        BoardSummary::insert_ranked(&mut top_boards, summary, capacity as usize);
+        player_state.top_boards.set(top_boards);
+    }
+
+    /// 🏅 The player's best ended games, sent along with each score
+    async fn top_boards(contract: &mut crate::Game2048Contract, player: &str) -> Vec<BoardSummary> {
+        contract
+            .state
+            .players
+            .load_entry_mut(player)
+            .await
+            .unwrap()
+            .top_boards
+            .get()
+            .clone()
+    }
+
+    /// 🏎️ Speed run target tile of the board's tournament and the micros from the board's
+    /// creation to its first merge into that tile (None = not a speed run or not reached)
+    async fn tile_milestone_hit(
+        contract: &mut crate::Game2048Contract,
+        board_id: &str,
+    ) -> Option<(u32, u64)> {
+        let leaderboard_id = contract
+            .state
+            .boards
+            .load_entry_mut(board_id)
+            .await
+            .unwrap()
+            .leaderboard_id
+            .get()
+            .clone();
+        let target_tile = contract
+            .get_cached_tournament(&leaderboard_id)
+            .await?
+            .mode
+            .target_tile()?;
+        let board = contract
+            .state
+            .boards
+            .load_entry_mut(board_id)
+            .await
+            .unwrap();
+        let reached_at = board.first_time_tiles.get(&target_tile).await.unwrap()?;
+        Some((
+            target_tile,
+            reached_at.saturating_sub(*board.created_at.get()),
+        ))
+    }
+
+    /// 👫 FRIENDS: Share a new tournament best with friend chains (warmup scores are unranked)
+    async fn notify_friends(
+        contract: &mut crate::Game2048Contract,
+        player: &str,
+        leaderboard_id: &str,
+        is_warmup: bool,
+        best_score: u64,
+    ) {
+        if is_warmup {
+            return;
+        }
+        let timestamp = contract.runtime.system_time().micros();
+        use crate::contract_domain::events::emitters::EventEmitter;
+        EventEmitter::emit_friend_score_update(
+            contract,
+            player.to_string(),
+            leaderboard_id.to_string(),
+            best_score,
+            timestamp,
+        )
+        .await;
+    }
+
+    /// 🌟 PLATFORM EVENTS: Send the main chain players new to the top 10 since the last
+    /// update, and players who reached a higher milestone tile
+    async fn announce_platform_highlights(
+        contract: &mut crate::Game2048Contract,
+        leaderboard_id: &str,
+    ) {
+        let leaderboard = contract
+            .state
+            .leaderboards
+            .load_entry_mut("")
+            .await
+            .unwrap();
+        let mut top_10 = leaderboard.ranked_scores().await;
+        top_10.truncate(10);
+
+        let previous_top_10 = leaderboard.platform_top_10.get().clone();
+        let mut highlights = Vec::new();
+        for (index, entry) in top_10.iter().enumerate() {
+            if previous_top_10.contains(&entry.player) {
+                continue;
+            }
+            highlights.push(PlatformHighlight {
+                player: entry.player.clone(),
+                score: entry.score,
+                highest_tile: leaderboard
+                    .highest_tiles
+                    .get(&entry.player)
+                    .await
+                    .unwrap()
+                    .unwrap_or(0),
+                leaderboard_id: leaderboard_id.to_string(),
+                rank: index as u32 + 1,
+            });
+        }
+        leaderboard
+            .platform_top_10
+            .set(top_10.iter().map(|entry| entry.player.clone()).collect());
+
+        let mut milestones = Vec::new();
+        leaderboard
+            .highest_tiles
+            .for_each_index_value(|username, highest_tile| {
+                if let Some(milestone) = PLATFORM_MILESTONE_TILES
+                    .iter()
+                    .rev()
+                    .find(|tile| *highest_tile >= **tile)
+                {
+                    milestones.push((username, *highest_tile, *milestone));
+                }
+                Ok(())
+            })
+            .await
+            .unwrap();
+        for (username, highest_tile, milestone) in milestones {
+            let announced = leaderboard
+                .platform_milestones
+                .get(&username)
+                .await
+                .unwrap()
+                .unwrap_or(0);
+            if milestone <= announced {
+                continue;
+            }
+            leaderboard
+                .platform_milestones
+                .insert(&username, milestone)
+                .unwrap();
+            // New top-10 entries already carry their highest tile
+            if highlights
+                .iter()
+                .any(|highlight| highlight.player == username)
+            {
+                continue;
+            }
+            let rank = top_10
+                .iter()
+                .position(|entry| entry.player == username)
+                .map_or(0, |index| index as u32 + 1);
+            highlights.push(PlatformHighlight {
+                score: leaderboard.score.get(&username).await.unwrap().unwrap_or(0),
+                player: username,
+                highest_tile,
+                leaderboard_id: leaderboard_id.to_string(),
+                rank,
+            });
+        }
+
+        let main_chain_id = contract.runtime.application_creator_chain_id();
+        for highlight in highlights {
+            contract.send_message(
+                main_chain_id,
+                game2048::Message::PlatformHighlight { highlight },
+            );
+        }
+    }
+
+    /// Player records key for a board's best score (warmup games are tracked apart)
+    fn best_score_key(leaderboard_id: &str, is_warmup: bool) -> String {
+        if is_warmup {
+            format!("{}:warmup", leaderboard_id)
+        } else {
+            leaderboard_id.to_string()
+        }
+    }
+
+    /// Create a board on this chain and count it towards the player's boards in the tournament
+    pub(crate) async fn create_board(
+        contract: &mut crate::Game2048Contract,
+        player: &str,
+        timestamp: u64,
+        leaderboard_id: &str,
+        tournament_start_time: u64,
+        tournament_end_time: u64,
+        rhythm_track_index: i16,
+    ) -> String {
+        let nonce = *contract.state.nonce.get();
+        let chain_id = contract.runtime.chain_id().to_string();
+        // 🎲 Mix in the randomness beacon once one has been published
+        let beacon = contract.state.randomness_beacon.get();
+        let seed = if beacon.beacon_round > 0 {
+            format!(
+                "{}.{}",
+                beacon.beacon_value ^ chain_id_hash(&chain_id),
+                nonce
+            )
+        } else {
+            nonce.to_string()
+        };
+        let board_id = format!("{}.{}", chain_id, hash_seed(&seed, player, timestamp));
+        let tournament = contract.get_cached_tournament(leaderboard_id).await;
+        // ↩️ UNDO: Tournaments set the budget; boards outside a known tournament have no limit
+        let (undo_budget, undo_history_size) = match &tournament {
+            Some(tournament) => (tournament.undo_budget, tournament.undo_history_size),
+            None => (UNLIMITED_UNDOS, 0),
+        };
+        // ⏱️ SPEED: The time limit runs from creation, by block time
+        let variant = tournament
+            .as_ref()
+            .map(|tournament| tournament.variant)
+            .unwrap_or_default();
+        let tile_weights = tournament
+            .map(|tournament| tournament.tile_weights)
+            .unwrap_or_default();
+        let deadline = variant.deadline_from(contract.runtime.system_time().micros());
+        if let GameVariant::Elimination { lives_remaining: 0 } = variant {
+            panic!("Elimination games need at least one life");
+        }
+
+        let spawns = SpawnDistribution::from_weights(&tile_weights)
+            .unwrap_or_else(|error| panic!("{}", error));
+        let new_board = Game::with_spawns(&board_id, player, timestamp, spawns).board;
         let game = contract
             .state
             .boards
             .load_entry_mut(&board_id)
             .await
             .unwrap();
-        game.board_id.set(board_id.clone());
-        game.board.set(new_board);
-        game.player.set(player.clone());
-        game.leaderboard_id.set(leaderboard_id.clone());
-        game.shard_id.set(String::new()); // No shard in message-based architecture
-        game.chain_id.set(contract.runtime.chain_id().to_string());
-        game.created_at.set(timestamp);
-        game.start_time.set(tournament_start_time);
-        game.end_time.set(tournament_end_time);
-        // 🎵 Rhythm mode: store track index for replay (-1 = no rhythm/metronome)
-        game.rhythm_track_index.set(rhythm_track_index);
+        game.board_id.set(board_id.clone());
+        game.board.set(new_board);
+        game.player.set(player.to_string());
+        game.leaderboard_id.set(leaderboard_id.to_string());
+        game.shard_id.set(String::new()); // No shard in message-based architecture
+        game.chain_id.set(contract.runtime.chain_id().to_string());
+        game.created_at.set(timestamp);
+        game.start_time.set(tournament_start_time);
+        game.end_time.set(tournament_end_time);
+        // 🎵 Rhythm mode: store track index for replay (-1 = no rhythm/metronome)
+        game.rhythm_track_index.set(rhythm_track_index);
+        game.undo_budget.set(undo_budget);
+        game.variant.set(variant);
+        game.deadline.set(deadline);
+        game.lives.set(variant.starting_lives());
+        game.tile_weights.set(tile_weights);
+        game.spawn_generator.set(SpawnGenerator::CURRENT);
+        // ⏲️ Move timing: the first batch is timed from the board's creation
+        game.last_move_batch_time
+            .set(contract.runtime.system_time().micros());
+        game.undo_history_size.set(match undo_history_size {
+            0 => DEFAULT_UNDO_HISTORY_SIZE,
+            size => size,
+        });
+
+        contract.state.nonce.set(nonce + 1);
+        contract.state.latest_board_id.set(board_id.clone());
+
+        // Increment player's board count for this tournament
+        let player_state = contract.state.players.load_entry_mut(player).await.unwrap();
+        let current_board_count = player_state
+            .boards_per_tournament
+            .get(leaderboard_id)
+            .await
+            .unwrap()
+            .unwrap_or(0);
+        player_state
+            .boards_per_tournament
+            .insert(leaderboard_id, current_board_count + 1)
+            .unwrap();
+
+        board_id
+    }
+
+    /// 🚀 IMPROVED: Handle score aggregation using monitored player chains from shard state
+    pub async fn handle_aggregate_scores(contract: &mut crate::Game2048Contract) {
+        // Get monitored player chains from shard state
+        let shard = contract.state.shards.load_entry_mut("").await.unwrap();
+        let mut player_chain_ids = Vec::new();
+
+        // Collect all monitored player chain IDs from the queue
+        match shard.monitored_player_chains.read_front(100).await {
+            Ok(chain_id_strings) => {
+                for chain_id_str in chain_id_strings {
+                    if let Ok(chain_id) = ChainId::from_str(&chain_id_str) {
+                        player_chain_ids.push(chain_id);
+                    }
+                }
+            }
+            Err(_) => {
+                // No entries or error - proceed with empty list
+            }
+        }
+
+        // Aggregate scores from monitored player chains
+        contract
+            .aggregate_scores_from_player_chains(player_chain_ids)
+            .await;
+
+        // 🚀 CHAIN POOL: Top up the pool if it fell below its minimum
+        SystemOperationHandler::auto_refill_chain_pool(contract).await;
+
+        // 💾 SNAPSHOTS: Drop snapshots of tournaments that are over
+        Self::purge_expired_snapshots(contract).await;
+    }
+
+    /// 🚀 MESSAGE-BASED: Handle leaderboard update (manual refresh)
+    ///
+    /// With message-based architecture, this operation just needs to:
+    /// 1. Check cooldown (10s spam protection)
+    /// 2. Trigger block production (which processes all pending SubmitScore messages)
+    ///
+    /// The leaderboard chain should run with --listener-skip-process-inbox so
+    /// SubmitScore messages queue up and are processed when this operation is called.
+    pub async fn handle_update_leaderboard(contract: &mut crate::Game2048Contract) {
+        let current_time = contract.runtime.system_time().micros();
+
+        let leaderboard = contract
+            .state
+            .leaderboards
+            .load_entry_mut("")
+            .await
+            .unwrap();
+
+        // 🚀 COOLDOWN CHECK - 15s spam protection
+        let cooldown_until = *leaderboard.trigger_cooldown_until.get();
+        if current_time < cooldown_until {
+            // Still in cooldown - silently ignore
+            return;
+        }
+
+        // Set global cooldown (15 seconds)
+        let cooldown_duration = 15_000_000; // 15 seconds in microseconds
+        leaderboard
+            .trigger_cooldown_until
+            .set(current_time + cooldown_duration);
+
+        // Update trigger tracking
+        leaderboard.last_trigger_time.set(current_time);
+        leaderboard
+            .last_trigger_by
+            .set("manual_refresh".to_string());
+
+        // That's it! The act of calling this operation triggers block production,
+        // which processes all pending SubmitScore messages in the inbox.
+        // No need to send messages to shards anymore.
+
+        use crate::contract_domain::events::emitters::EventEmitter;
+        let leaderboard_id = leaderboard.leaderboard_id.get().clone();
+
+        // 🏁 WARMUP: Announce the warmup window, then the real start, once each
+        let start_time = *leaderboard.start_time.get();
+        let warmup_duration = *leaderboard.warmup_duration_seconds.get() as u64 * 1_000_000;
+        if start_time > 0 && warmup_duration > 0 {
+            let phase = if current_time >= start_time {
+                2
+            } else if current_time >= start_time.saturating_sub(warmup_duration) {
+                1
+            } else {
+                0
+            };
+            let announced = *leaderboard.warmup_phase_announced.get();
+            if phase > announced {
+                leaderboard.warmup_phase_announced.set(phase);
+                if phase == 1 {
+                    EventEmitter::emit_warmup_started(contract, leaderboard_id.clone(), start_time)
+                        .await;
+                } else {
+                    EventEmitter::emit_tournament_started(contract, leaderboard_id.clone()).await;
+                }
+            }
+        }
+
+        let leaderboard = contract
+            .state
+            .leaderboards
+            .load_entry_mut("")
+            .await
+            .unwrap();
+
+        // ⏳ COUNTDOWN: Announce the latest threshold crossed since the last update
+        let end_time = *leaderboard.end_time.get();
+        if end_time > 0 && current_time < end_time {
+            let thresholds = match leaderboard.countdown_thresholds.get() {
+                thresholds if thresholds.is_empty() => DEFAULT_COUNTDOWN_THRESHOLDS.to_vec(),
+                thresholds => thresholds.clone(),
+            };
+            let seconds_remaining = (end_time - current_time) / 1_000_000;
+            if let Some(threshold) = countdown_threshold_crossed(
+                &thresholds,
+                seconds_remaining,
+                *leaderboard.last_threshold_fired.get(),
+            ) {
+                leaderboard.last_threshold_fired.set(threshold);
+                let mut current_leader: Option<(String, u64)> = None;
+                leaderboard
+                    .score
+                    .for_each_index_value(|username, score| {
+                        if current_leader
+                            .as_ref()
+                            .is_none_or(|(_, best)| *score > *best)
+                        {
+                            current_leader = Some((username, *score));
+                        }
+                        Ok(())
+                    })
+                    .await
+                    .unwrap();
+                EventEmitter::emit_tournament_countdown(
+                    contract,
+                    leaderboard_id.clone(),
+                    seconds_remaining,
+                    current_leader.map(|(username, _)| username),
+                )
+                .await;
+            }
+        }
+
+        // 🎟️ QUALIFIER: Once the qualifier ends, its top players advance to the main tournament
+        let leaderboard = contract
+            .state
+            .leaderboards
+            .load_entry_mut("")
+            .await
+            .unwrap();
+        if let Some(qualifier) = leaderboard.qualifier_settings.get().clone() {
+            if end_time > 0 && current_time >= end_time && !*leaderboard.qualifier_advanced.get() {
+                leaderboard.qualifier_advanced.set(true);
+                let mut ranked: Vec<(String, u64)> = leaderboard
+                    .ranked_scores()
+                    .await
+                    .into_iter()
+                    .map(|entry| (entry.player, entry.score))
+                    .collect();
+                ranked.truncate(qualifier.advance_count as usize);
+
+                let mut player_chains = Vec::new();
+                for (username, _) in &ranked {
+                    if let Some(player_chain_id) =
+                        leaderboard.player_chain_ids.get(username).await.unwrap()
+                    {
+                        player_chains.push(player_chain_id);
+                    }
+                }
+                let players: Vec<String> =
+                    ranked.into_iter().map(|(username, _)| username).collect();
+
+                if let Ok(main_chain_id) = ChainId::from_str(&qualifier.main_leaderboard_id) {
+                    contract.send_message(
+                        main_chain_id,
+                        game2048::Message::AddQualifiedPlayers {
+                            players,
+                            source_leaderboard_id: leaderboard_id.clone(),
+                        },
+                    );
+                }
+                // Qualified players skip the main tournament's entry requirements
+                for player_chain_id in player_chains {
+                    if let Ok(player_chain_id) = ChainId::from_str(&player_chain_id) {
+                        contract.send_message(
+                            player_chain_id,
+                            game2048::Message::QualifiedForTournament {
+                                leaderboard_id: qualifier.main_leaderboard_id.clone(),
+                            },
+                        );
+                    }
+                }
+            }
+        }
+
+        let leaderboard = contract
+            .state
+            .leaderboards
+            .load_entry_mut("")
+            .await
+            .unwrap();
+
+        // ⏰ BONUS WINDOW: Announce windows that are currently active
+        let active_windows: Vec<_> = leaderboard
+            .active_bonus_windows
+            .get()
+            .iter()
+            .filter(|window| window.is_active(current_time))
+            .cloned()
+            .collect();
+        for window in active_windows {
+            EventEmitter::emit_bonus_window_active(
+                contract,
+                leaderboard_id.clone(),
+                window.window_name,
+                (window.end - current_time) / 1_000_000,
+            )
+            .await;
+        }
+
+        let leaderboard = contract
+            .state
+            .leaderboards
+            .load_entry_mut("")
+            .await
+            .unwrap();
+
+        // 🏆 TILE LEADERBOARD: Cache the highest-tile ranking separately from scores
+        let mut tiles: Vec<(String, u64)> = Vec::new();
+        leaderboard
+            .highest_tiles
+            .for_each_index_value(|username, highest_tile| {
+                tiles.push((username, *highest_tile));
+                Ok(())
+            })
+            .await
+            .unwrap();
+        let mut players = Vec::with_capacity(tiles.len());
+        for (username, highest_tile) in tiles {
+            let last_update = leaderboard
+                .last_update
+                .get(&username)
+                .await
+                .unwrap()
+                .unwrap_or(0);
+            players.push((username, highest_tile, last_update));
+        }
+        leaderboard
+            .tile_leaderboard_snapshot
+            .set(rank_by_highest_tile(players));
+
+        // 📸 SNAPSHOT: Keep the score ranking at the first update of each interval
+        let snapshot_interval = *leaderboard.snapshot_interval_seconds.get();
+        if snapshot_interval > 0 {
+            let epoch = current_time / 1_000_000 / snapshot_interval;
+            if !leaderboard
+                .score_snapshots
+                .contains_key(&epoch)
+                .await
+                .unwrap()
+            {
+                let scores: Vec<(String, u64)> = leaderboard
+                    .ranked_scores()
+                    .await
+                    .into_iter()
+                    .map(|entry| (entry.player, entry.score))
+                    .collect();
+                leaderboard.score_snapshots.insert(&epoch, scores).unwrap();
+            }
+        }
+
+        // 🗓️ SEASONS: Archive the ended season and carry part of each score into the next
+        if let Some(season) = *leaderboard.season_config.get() {
+            let current_season = *leaderboard.current_season.get();
+            let season_started_at = match *leaderboard.season_started_at.get() {
+                0 => match *leaderboard.start_time.get() {
+                    0 => current_time,
+                    start_time => start_time,
+                },
+                started_at => started_at,
+            };
+            leaderboard.season_started_at.set(season_started_at);
+            let season_end = season_started_at + season.season_duration_seconds * 1_000_000;
+            if current_time >= season_end && season.has_next_season(current_season) {
+                let scores: Vec<(String, u64)> = leaderboard
+                    .ranked_scores()
+                    .await
+                    .into_iter()
+                    .map(|entry| (entry.player, entry.score))
+                    .collect();
+                for (username, score) in &scores {
+                    leaderboard
+                        .score
+                        .insert(username, season.carry_over(*score))
+                        .unwrap();
+                }
+                leaderboard
+                    .season_archive
+                    .insert(&current_season, scores)
+                    .unwrap();
+                leaderboard.current_season.set(current_season + 1);
+                leaderboard.season_started_at.set(season_end);
+                leaderboard.leaderboard_last_update.set(current_time);
+            }
+        }
+
+        // 🌟 PLATFORM EVENTS: Report new top-10 players and milestone tiles to the main chain
+        Self::announce_platform_highlights(contract, &leaderboard_id).await;
+
+        // 🥊 BRACKET: Decide matches whose deadline has passed
+        LeaderboardOperationHandler::advance_bracket(contract, current_time).await;
+        // 🔄 ROUND ROBIN: Move to the next round once this one's deadline has passed
+        LeaderboardOperationHandler::advance_round_robin(contract, current_time).await;
+        let leaderboard = contract
+            .state
+            .leaderboards
+            .load_entry_mut("")
+            .await
+            .unwrap();
+
+        // 📡 LIVE STREAM: Publish top 10 for each registered relay endpoint
+        let endpoints = leaderboard.stream_endpoints.get().clone();
+        if endpoints.is_empty() {
+            return;
+        }
+
+        let mut top_10: Vec<LeaderboardEntry> = Vec::new();
+        leaderboard
+            .score
+            .for_each_index_value(|username, score| {
+                top_10.push(LeaderboardEntry {
+                    username,
+                    raw_score: *score,
+                    effective_score: *score,
+                });
+                Ok(())
+            })
+            .await
+            .unwrap();
+        for entry in top_10.iter_mut() {
+            let handicap = leaderboard.handicap(&entry.username).await;
+            entry.effective_score = game2048::handicapped_score(entry.raw_score, handicap);
+        }
+        top_10.sort_by(|a, b| b.effective_score.cmp(&a.effective_score));
+        top_10.truncate(10);
+
+        for endpoint in endpoints {
+            EventEmitter::emit_stream_update(
+                contract,
+                leaderboard_id.clone(),
+                endpoint.endpoint_url,
+                top_10.clone(),
+                current_time,
+            )
+            .await;
+        }
+    }
+
+    /// 🔁 REPLAY: Re-run the board's recorded moves from its initial state and submit the
+    /// replayed score to the leaderboard chain. Bonus-window points are not replayed.
+    pub async fn handle_replay_moves(
+        contract: &mut crate::Game2048Contract,
+        board_id: String,
+        player: String,
+        password_hash: String,
+    ) {
+        contract
+            .validate_player_password(&player, &password_hash)
+            .await;
+
+        let board = contract
+            .state
+            .boards
+            .load_entry_mut(&board_id)
+            .await
+            .unwrap();
+        if player != *board.player.get() {
+            panic!("You can only replay your own board");
+        }
+
+        let spawns = board.spawn_distribution();
+        let mut replay_board =
+            Game::with_spawns(&board_id, &player, *board.created_at.get(), spawns.clone()).board;
+        for index in 0..*board.move_count.get() {
+            let record = board.move_history.load_entry_mut(&index).await.unwrap();
+            let direction = match *record.direction.get() {
+                0 => Direction::Up,
+                1 => Direction::Down,
+                2 => Direction::Left,
+                _ => Direction::Right,
+            };
+            let mut game = Game {
+                board: replay_board,
+                board_id: board_id.clone(),
+                username: player.clone(),
+                timestamp: *record.timestamp.get(),
+                spawns: spawns.clone(),
+            };
+            replay_board = game.execute(direction);
+            if replay_board != *record.board_after.get() {
+                panic!("Replay diverged from recorded history at move {}", index);
+            }
+        }
+
+        let leaderboard_chain_id = ChainId::from_str(board.leaderboard_id.get())
+            .unwrap_or_else(|_| panic!("Board is not part of a tournament"));
+        contract.send_message(
+            leaderboard_chain_id,
+            game2048::Message::SubmitReplayScore {
+                player,
+                board_id,
+                score: Game::score(replay_board),
+            },
+        );
+    }
+
+    /// 🔁 Seal the board's move log with its final score
+    /// 🚩 End a game as a suspected cheat: the current batch is not applied, no score is
+    /// submitted, and the main chain keeps the game for admin review
+    async fn end_suspected_cheat(
+        contract: &mut crate::Game2048Contract,
+        board_id: String,
+        player: String,
+        leaderboard_id: String,
+        reason: String,
+    ) {
+        let board = contract
+            .state
+            .boards
+            .load_entry_mut(&board_id)
+            .await
+            .unwrap();
+        board.is_ended.set(true);
+        Self::seal_replay(board);
+        let score = *board.score.get();
+        let main_chain_id = contract.runtime.application_creator_chain_id();
+        contract.send_message(
+            main_chain_id,
+            game2048::Message::GameFlagged {
+                board_id: board_id.clone(),
+                player: player.clone(),
+                leaderboard_id: leaderboard_id.clone(),
+                score,
+                reason: reason.clone(),
+            },
+        );
+        use crate::contract_domain::events::emitters::EventEmitter;
+        EventEmitter::emit_game_ended(
+            contract,
+            board_id,
+            player,
+            leaderboard_id,
+            score,
+            GameEndReason::SuspectedCheat { reason },
+        )
+        .await;
+    }
+
+    fn seal_replay(board: &mut crate::state::BoardState) {
+        let hash = MoveLog::replay_hash(
+            board.board_id.get(),
+            board.move_log.get(),
+            *board.score.get(),
+        );
+        board.replay_hash.set(hash);
+    }
+
+    /// 🔁 REPLAY: Re-execute a shared move log from the board's initial state, using the
+    /// recorded move timestamps for tile spawns, and check it reproduces the recorded score
+    pub async fn handle_replay_game(
+        contract: &mut crate::Game2048Contract,
+        board_id: String,
+        move_log: Vec<u8>,
+    ) {
+        let board = contract
+            .state
+            .boards
+            .try_load_entry(&board_id)
+            .await
+            .unwrap()
+            .unwrap_or_else(|| panic!("Board not found"));
+        if *board.board_size.get() != BoardSize::FourByFour {
+            panic!("Only 4x4 games can be replayed");
+        }
+
+        let directions = MoveLog::decode(&move_log);
+        let move_count = *board.move_count.get();
+        if directions.len() != move_count as usize {
+            panic!(
+                "Tampered move log: {} moves, board recorded {}",
+                directions.len(),
+                move_count
+            );
+        }
+        let recorded_score = *board.score.get();
+        let replay_hash = *board.replay_hash.get();
+        if replay_hash != [0; 32]
+            && MoveLog::replay_hash(&board_id, &move_log, recorded_score) != replay_hash
+        {
+            panic!("Tampered move log: replay hash mismatch");
+        }
+
+        let player = board.player.get().clone();
+        let spawns = board.spawn_distribution();
+        let mut replay_board =
+            Game::with_spawns(&board_id, &player, *board.created_at.get(), spawns.clone()).board;
+        for (index, direction) in directions.into_iter().enumerate() {
+            let record = board
+                .move_history
+                .try_load_entry(&(index as u32))
+                .await
+                .unwrap()
+                .unwrap_or_else(|| panic!("Move {} is missing from the board history", index));
+            let mut game = Game {
+                board: replay_board,
+                board_id: board_id.clone(),
+                username: player.clone(),
+                timestamp: *record.timestamp.get(),
+                spawns: spawns.clone(),
+            };
+            replay_board = game.execute(direction);
+        }
+
+        let replayed_score =
+            Game::score(replay_board) + *board.bonus_score.get() + *board.continuation_score.get();
+        if replayed_score != recorded_score {
+            panic!(
+                "Tampered move log: replayed score {} does not match recorded score {}",
+                replayed_score, recorded_score
+            );
+        }
+    }
+
+    /// 🏷️ Replace client-defined metadata on an active game
+    pub async fn handle_set_game_metadata(
+        contract: &mut crate::Game2048Contract,
+        board_id: String,
+        player: String,
+        password_hash: String,
+        metadata: Vec<GameMetadataEntry>,
+    ) {
+        // Validate password
+        contract
+            .validate_player_password(&player, &password_hash)
+            .await;
+
+        let board = contract
+            .state
+            .boards
+            .load_entry_mut(&board_id)
+            .await
+            .unwrap();
+
+        if player != *board.player.get() {
+            panic!("You can only set metadata on your own board");
+        }
+        if *board.is_ended.get() {
+            panic!("Cannot set metadata after the game has ended");
+        }
+        if let Err(error) = GameMetadataEntry::validate_all(&metadata) {
+            panic!("{}", error);
+        }
+
+        board.metadata.set(metadata);
+    }
+
+    /// ⏱️ Convert a classic game into a speed game before its first move.
+    /// Requires the tournament to allow conversion; cannot be undone.
+    pub async fn handle_convert_to_timed_game(
+        contract: &mut crate::Game2048Contract,
+        board_id: String,
+        player: String,
+        password_hash: String,
+        time_limit_seconds: u32,
+    ) {
+        // Validate password
+        contract
+            .validate_player_password(&player, &password_hash)
+            .await;
+
+        if time_limit_seconds == 0 {
+            panic!("Time limit must be greater than zero");
+        }
+
+        let board = contract
+            .state
+            .boards
+            .load_entry_mut(&board_id)
+            .await
+            .unwrap();
+        if player != *board.player.get() {
+            panic!("You can only convert your own board");
+        }
+        if *board.variant.get() != GameVariant::Classic2048 {
+            panic!("Only classic games can be converted");
+        }
+        if *board.is_ended.get() || *board.move_count.get() > 0 || *board.score.get() > 0 {
+            panic!("Games can only be converted before the first move");
+        }
+        let leaderboard_id = board.leaderboard_id.get().clone();
+
+        let allowed = contract
+            .get_cached_tournament(&leaderboard_id)
+            .await
+            .is_some_and(|tournament| tournament.allow_variant_conversion);
+        if !allowed {
+            panic!("This tournament does not allow game variant conversion");
+        }
+
+        let new_variant = GameVariant::Speed2048 { time_limit_seconds };
+        let deadline = new_variant.deadline_from(contract.runtime.system_time().micros());
+        let board = contract
+            .state
+            .boards
+            .load_entry_mut(&board_id)
+            .await
+            .unwrap();
+        board.variant.set(new_variant);
+        board.deadline.set(deadline);
+
+        use crate::contract_domain::events::emitters::EventEmitter;
+        EventEmitter::emit_variant_converted(contract, board_id, player, new_variant, deadline)
+            .await;
+    }
+
+    /// ↩️ UNDO: Restore the board, score and move count from before the last move.
+    /// Ended games become active again; the leaderboard is told the rolled-back score.
+    pub async fn handle_undo_move(
+        contract: &mut crate::Game2048Contract,
+        board_id: String,
+        player: String,
+        password_hash: String,
+    ) {
+        contract
+            .validate_player_password(&player, &password_hash)
+            .await;
+
+        let current_time = contract.runtime.system_time().micros();
+        let board = contract
+            .state
+            .boards
+            .load_entry_mut(&board_id)
+            .await
+            .unwrap();
+        if player != *board.player.get() {
+            panic!("You can only undo moves on your own board");
+        }
+        let undo_budget = *board.undo_budget.get();
+        if undo_budget == 0 {
+            panic!("No undos left for this game");
+        }
+        if !board.continued_to.get().is_empty() {
+            panic!("Continued games cannot be undone");
+        }
+        if *board.board_size.get() != BoardSize::FourByFour {
+            panic!("Only 4x4 moves can be undone");
+        }
+        let end_time = *board.end_time.get();
+        if end_time > 0 && current_time >= end_time {
+            panic!("Tournament has already ended");
+        }
+        if GameVariant::is_past_deadline(*board.deadline.get(), current_time) {
+            panic!("Speed game time limit has passed");
+        }
+        let mut undo_history = board.undo_history.get().clone();
+        let snapshot = undo_history
+            .pop_back()
+            .unwrap_or_else(|| panic!("No moves to undo"));
+
+        let was_ended = *board.is_ended.get();
+        board.board.set(snapshot.board);
+        board.score.set(snapshot.score);
+        board.bonus_score.set(snapshot.bonus_score);
+        board.move_count.set(snapshot.move_count);
+        board.undo_history.set(undo_history);
+        if undo_budget != UNLIMITED_UNDOS {
+            board.undo_budget.set(undo_budget - 1);
+        }
+        let mut move_log = board.move_log.get().clone();
+        MoveLog::pop(&mut move_log);
+        board.move_log.set(move_log);
+        if was_ended {
+            board.is_ended.set(false);
+            board.is_deadlocked.set(false);
+            board.replay_hash.set([0; 32]);
+        }
+
+        let leaderboard_id = board.leaderboard_id.get().clone();
+        let start_time = *board.start_time.get();
+        let is_warmup = *board.is_warmup.get();
+        let average_branching_factor = board.average_branching_factor();
+        let hints_used = *board.hints_used.get();
+        let moves_count = *board.move_count.get();
+        let Ok(leaderboard_chain_id) = ChainId::from_str(&leaderboard_id) else {
+            return;
+        };
+
+        let player_state = contract
+            .state
+            .players
+            .load_entry_mut(&player)
+            .await
+            .unwrap();
+        let boards_in_tournament = player_state
+            .boards_per_tournament
+            .get(&leaderboard_id)
+            .await
+            .unwrap()
+            .unwrap_or(0);
+        let player_chain_id = contract.runtime.chain_id().to_string();
+        let top_boards = Self::top_boards(contract, &player).await;
+        let tile_milestone_hit = Self::tile_milestone_hit(contract, &board_id).await;
+        let message_nonce = crate::contract_domain::ContractHelpers::next_score_nonce(contract);
+        contract.send_message(
+            leaderboard_chain_id,
+            game2048::Message::SubmitScore {
+                player,
+                player_chain_id,
+                board_id,
+                score: snapshot.score,
+                highest_tile: Game::highest_tile(snapshot.board),
+                game_status: GameStatus::Active,
+                timestamp: current_time,
+                boards_per_leaderboard: [(leaderboard_id.clone(), boards_in_tournament)].into(),
+                start_time,
+                end_time,
+                streak_bonus_applied: false,
+                is_warmup,
+                average_branching_factor,
+                hints_used,
+                moves_count,
+                message_nonce,
+                tile_milestone_hit,
+                top_boards,
+            },
+        );
+    }
+
+    /// 💾 SNAPSHOT: Save the board's progress, replacing any earlier snapshot of it
+    pub async fn handle_save_snapshot(
+        contract: &mut crate::Game2048Contract,
+        board_id: String,
+        player: String,
+        password_hash: String,
+    ) {
+        contract
+            .validate_player_password(&player, &password_hash)
+            .await;
+
+        let current_time = contract.runtime.system_time().micros();
+        let board = contract
+            .state
+            .boards
+            .try_load_entry(&board_id)
+            .await
+            .unwrap()
+            .unwrap_or_else(|| panic!("Board not found"));
+        if player != *board.player.get() {
+            panic!("You can only save snapshots of your own board");
+        }
+        if *board.board_size.get() != BoardSize::FourByFour {
+            panic!("Only 4x4 boards can be saved as snapshots");
+        }
+        let end_time = *board.end_time.get();
+        if end_time > 0 && current_time >= end_time {
+            panic!("Tournament has already ended");
+        }
+
+        let snapshot = GameSnapshot {
+            leaderboard_id: board.leaderboard_id.get().clone(),
+            board: *board.board.get(),
+            score: *board.score.get(),
+            bonus_score: *board.bonus_score.get(),
+            move_count: *board.move_count.get(),
+            last_processed_timestamp: *board.last_processed_timestamp.get(),
+            is_ended: *board.is_ended.get(),
+            move_log: board.move_log.get().clone(),
+            end_time,
+            saved_at: current_time,
+        };
+        contract
+            .state
+            .snapshots
+            .insert(&board_id, snapshot)
+            .unwrap();
+    }
+
+    /// 💾 SNAPSHOT: Restore the board from its snapshot. The snapshot is used up so it
+    /// cannot act as an unlimited undo.
+    pub async fn handle_load_snapshot(
+        contract: &mut crate::Game2048Contract,
+        board_id: String,
+        player: String,
+        password_hash: String,
+    ) {
+        contract
+            .validate_player_password(&player, &password_hash)
+            .await;
+
+        let current_time = contract.runtime.system_time().micros();
+        let snapshot = contract
+            .state
+            .snapshots
+            .get(&board_id)
+            .await
+            .unwrap()
+            .unwrap_or_else(|| panic!("No snapshot saved for this board"));
+
+        // The tournament must still be running, as far as this chain knows
+        let is_active = match contract
+            .get_cached_tournament(&snapshot.leaderboard_id)
+            .await
+        {
+            Some(tournament) => tournament.is_active(current_time),
+            None => snapshot.end_time == 0 || current_time < snapshot.end_time,
+        };
+        if !is_active {
+            panic!("Tournament is no longer active");
+        }
+
+        let board = contract
+            .state
+            .boards
+            .load_entry_mut(&board_id)
+            .await
+            .unwrap();
+        if player != *board.player.get() {
+            panic!("You can only load snapshots of your own board");
+        }
+        if *board.leaderboard_id.get() != snapshot.leaderboard_id {
+            panic!("Snapshot belongs to another tournament");
+        }
+        if !board.continued_to.get().is_empty() {
+            panic!("Continued games cannot be restored");
+        }
 
-        contract.state.nonce.set(nonce + 1);
-        contract.state.latest_board_id.set(board_id);
+        board.board.set(snapshot.board);
+        board.score.set(snapshot.score);
+        board.bonus_score.set(snapshot.bonus_score);
+        board.move_count.set(snapshot.move_count);
+        board
+            .last_processed_timestamp
+            .set(snapshot.last_processed_timestamp);
+        board.move_log.set(snapshot.move_log);
+        board.undo_history.set(Default::default());
+        board.is_ended.set(snapshot.is_ended);
+        if !snapshot.is_ended {
+            board.is_deadlocked.set(false);
+            board.replay_hash.set([0; 32]);
+        }
+        contract.state.snapshots.remove(&board_id).unwrap();
+    }
 
-        // Increment player's board count for this tournament
-        let player_state = contract
+    /// 💾 SNAPSHOT: Drop snapshots whose tournament has ended
+    async fn purge_expired_snapshots(contract: &mut crate::Game2048Contract) {
+        let current_time = contract.runtime.system_time().micros();
+        let mut expired = Vec::new();
+        contract
             .state
-            .players
-            .load_entry_mut(&player)
+            .snapshots
+            .for_each_index_value(|board_id, snapshot| {
+                if snapshot.end_time > 0 && current_time >= snapshot.end_time {
+                    expired.push(board_id);
+                }
+                Ok(())
+            })
             .await
             .unwrap();
-        let current_board_count = player_state
-            .boards_per_tournament
-            .get(&leaderboard_id)
+        for board_id in expired {
+            contract.state.snapshots.remove(&board_id).unwrap();
+        }
+    }
+
+    /// 📅 DAILY CHALLENGE: Create today's board. The starting tiles come from the day and
+    /// the platform (main chain id) alone, so every player starts from the same board.
+    pub async fn handle_get_daily_challenge(
+        contract: &mut crate::Game2048Contract,
+        player: String,
+        password_hash: String,
+    ) {
+        contract
+            .validate_player_password(&player, &password_hash)
+            .await;
+
+        let current_time = contract.runtime.system_time().micros();
+        let day_number = current_time / 86_400_000_000;
+        let board_id = daily_challenge_board_id(day_number);
+        if contract
+            .state
+            .boards
+            .try_load_entry(&board_id)
             .await
             .unwrap()
-            .unwrap_or(0);
-        player_state
-            .boards_per_tournament
-            .insert(&leaderboard_id, current_board_count + 1)
+            .is_some()
+        {
+            panic!("Today's daily challenge has already been started");
+        }
+
+        let platform_salt = contract.runtime.application_creator_chain_id().to_string();
+        let seed = daily_challenge_seed(day_number, &platform_salt);
+        let new_board = Game::new(&seed, "", day_number).board;
+        let chain_id = contract.runtime.chain_id().to_string();
+        let game = contract
+            .state
+            .boards
+            .load_entry_mut(&board_id)
+            .await
             .unwrap();
+        game.board_id.set(board_id.clone());
+        game.board.set(new_board);
+        game.player.set(player);
+        game.leaderboard_id
+            .set(DAILY_CHALLENGE_LEADERBOARD_ID.to_string());
+        game.chain_id.set(chain_id);
+        game.created_at.set(current_time);
+        game.rhythm_track_index.set(-1);
+        game.spawn_generator.set(SpawnGenerator::CURRENT);
+        game.last_move_batch_time.set(current_time);
+        // ↩️ UNDO: Everyone plays the same board, so no undos
+        game.undo_budget.set(0);
+        game.undo_history_size.set(DEFAULT_UNDO_HISTORY_SIZE);
+        contract.state.latest_board_id.set(board_id);
+    }
 
-        // 🚀 MESSAGE-BASED: No registration with shard needed
-        // No event emission needed
-        // First SubmitScore is sent when player makes moves and score > 0
+    /// 📅 DAILY CHALLENGE: Send a finished daily challenge to the main chain's daily leaderboard
+    fn submit_daily_challenge(
+        contract: &mut crate::Game2048Contract,
+        board_id: &str,
+        record: DailyChallengeRecord,
+    ) {
+        let Some(date) = board_id.strip_prefix("daily_") else {
+            return;
+        };
+        let main_chain_id = contract.runtime.application_creator_chain_id();
+        contract.send_message(
+            main_chain_id,
+            game2048::Message::DailyChallengeScore {
+                date: date.to_string(),
+                record,
+            },
+        );
     }
 
-    /// 🚀 IMPROVED: Handle score aggregation using monitored player chains from shard state
-    pub async fn handle_aggregate_scores(contract: &mut crate::Game2048Contract) {
-        // Get monitored player chains from shard state
-        let shard = contract.state.shards.load_entry_mut("").await.unwrap();
-        let mut player_chain_ids = Vec::new();
+    /// 💡 HINT: Store the best move from a 2-ply search; the count is reported with the score
+    pub async fn handle_request_hint(
+        contract: &mut crate::Game2048Contract,
+        board_id: String,
+        player: String,
+        password_hash: String,
+    ) {
+        contract
+            .validate_player_password(&player, &password_hash)
+            .await;
 
-        // Collect all monitored player chain IDs from the queue
-        match shard.monitored_player_chains.read_front(100).await {
-            Ok(chain_id_strings) => {
-                for chain_id_str in chain_id_strings {
-                    if let Ok(chain_id) = ChainId::from_str(&chain_id_str) {
-                        player_chain_ids.push(chain_id);
-                    }
-                }
-            }
-            Err(_) => {
-                // No entries or error - proceed with empty list
-            }
+        let board = contract
+            .state
+            .boards
+            .load_entry_mut(&board_id)
+            .await
+            .unwrap();
+        if player != *board.player.get() {
+            panic!("You can only request hints for your own board");
+        }
+        if *board.board_size.get() != BoardSize::FourByFour {
+            panic!("Hints are only available on 4x4 boards");
         }
+        if *board.is_ended.get() {
+            panic!("Game has already ended");
+        }
+        let hints_used = *board.hints_used.get();
+        if hints_used >= MAX_HINTS_PER_BOARD {
+            panic!("Hint limit of {} per game reached", MAX_HINTS_PER_BOARD);
+        }
+        let (direction, hint_score) = BoardStatistics::best_move(*board.board.get())
+            .unwrap_or_else(|| panic!("No moves available"));
 
-        // Aggregate scores from monitored player chains
+        board.hints_used.set(hints_used + 1);
+        board.last_hint.set(Some(MoveHint {
+            direction,
+            hint_score,
+            move_count: *board.move_count.get(),
+        }));
+    }
+
+    /// ↪️ Continue a game on a new board with half of its score (once, within 5 minutes of creation).
+    /// The old board ends and the leaderboard entry moves to the new board.
+    pub async fn handle_continue_game(
+        contract: &mut crate::Game2048Contract,
+        old_board_id: String,
+        player: String,
+        password_hash: String,
+        new_board_size: BoardSize,
+    ) {
+        // Validate password
         contract
-            .aggregate_scores_from_player_chains(player_chain_ids)
+            .validate_player_password(&player, &password_hash)
             .await;
-    }
 
-    /// 🚀 MESSAGE-BASED: Handle leaderboard update (manual refresh)
-    /// 
-    /// With message-based architecture, this operation just needs to:
-    /// 1. Check cooldown (10s spam protection)
-    /// 2. Trigger block production (which processes all pending SubmitScore messages)
-    /// 
-    /// The leaderboard chain should run with --listener-skip-process-inbox so
-    /// SubmitScore messages queue up and are processed when this operation is called.
-    pub async fn handle_update_leaderboard(contract: &mut crate::Game2048Contract) {
+        let board = contract
+            .state
+            .boards
+            .load_entry_mut(&old_board_id)
+            .await
+            .unwrap();
+        if player != *board.player.get() {
+            panic!("You can only continue your own board");
+        }
+        if !board.continued_to.get().is_empty() {
+            panic!("Game has already been continued");
+        }
+        if *board.is_ended.get() {
+            panic!("Ended games cannot be continued");
+        }
         let current_time = contract.runtime.system_time().micros();
+        if current_time > *board.created_at.get() + GAME_CONTINUATION_WINDOW_SECONDS * 1_000_000 {
+            panic!(
+                "Games can only be continued within {} seconds of creation",
+                GAME_CONTINUATION_WINDOW_SECONDS
+            );
+        }
+        let old_score = *board.score.get();
+        let continuation_score = old_score / 2;
+        let leaderboard_id = board.leaderboard_id.get().clone();
+        let is_warmup = *board.is_warmup.get();
+        let average_branching_factor = board.average_branching_factor();
+        let hints_used = *board.hints_used.get();
+        let moves_count = *board.move_count.get();
+        let best_score_key = Self::best_score_key(&leaderboard_id, is_warmup);
+        let start_time = *board.start_time.get();
+        let end_time = *board.end_time.get();
+        let rhythm_track_index = *board.rhythm_track_index.get();
+        let variant = *board.variant.get();
+        let deadline = *board.deadline.get();
+        if end_time > 0 && current_time >= end_time {
+            panic!("Tournament has already ended");
+        }
 
-        let leaderboard = contract
+        let new_board_id = Self::create_board(
+            contract,
+            &player,
+            current_time,
+            &leaderboard_id,
+            start_time,
+            end_time,
+            rhythm_track_index,
+        )
+        .await;
+        Self::start_sized_board(
+            contract,
+            &new_board_id,
+            new_board_size,
+            &player,
+            current_time,
+        )
+        .await;
+        let new_board = contract
             .state
-            .leaderboards
-            .load_entry_mut("")
+            .boards
+            .load_entry_mut(&new_board_id)
             .await
             .unwrap();
+        new_board.continuation_score.set(continuation_score);
+        new_board.score.set(continuation_score);
+        new_board.continued_from.set(old_board_id.clone());
+        new_board.is_warmup.set(is_warmup);
+        // ⏱️ A continued speed game keeps its original deadline
+        new_board.variant.set(variant);
+        new_board.deadline.set(deadline);
+        let highest_tile = Game::highest_tile(*new_board.board.get());
 
-        // 🚀 COOLDOWN CHECK - 15s spam protection
-        let cooldown_until = *leaderboard.trigger_cooldown_until.get();
-        if current_time < cooldown_until {
-            // Still in cooldown - silently ignore
+        let board = contract
+            .state
+            .boards
+            .load_entry_mut(&old_board_id)
+            .await
+            .unwrap();
+        board.is_ended.set(true);
+        Self::seal_replay(board);
+        board.continued_to.set(new_board_id.clone());
+
+        if old_score == 0 {
             return;
         }
 
-        // Set global cooldown (15 seconds)
-        let cooldown_duration = 15_000_000; // 15 seconds in microseconds
-        leaderboard
-            .trigger_cooldown_until
-            .set(current_time + cooldown_duration);
-
-        // Update trigger tracking
-        leaderboard.last_trigger_time.set(current_time);
-        leaderboard
-            .last_trigger_by
-            .set("manual_refresh".to_string());
+        // The tournament best now follows the new board
+        let player_record = contract
+            .state
+            .player_records
+            .load_entry_mut(&player)
+            .await
+            .unwrap();
+        if player_record.best_score.get(&best_score_key).await.unwrap() == Some(old_score) {
+            player_record
+                .best_score
+                .insert(&best_score_key, continuation_score)
+                .unwrap();
+        }
 
-        // That's it! The act of calling this operation triggers block production,
-        // which processes all pending SubmitScore messages in the inbox.
-        // No need to send messages to shards anymore.
+        let Ok(leaderboard_chain_id) = ChainId::from_str(&leaderboard_id) else {
+            return;
+        };
+        let boards_in_tournament = contract
+            .state
+            .players
+            .load_entry_mut(&player)
+            .await
+            .unwrap()
+            .boards_per_tournament
+            .get(&leaderboard_id)
+            .await
+            .unwrap()
+            .unwrap_or(0);
+        let player_chain_id = contract.runtime.chain_id().to_string();
+        let top_boards = Self::top_boards(contract, &player).await;
+        let tile_milestone_hit = Self::tile_milestone_hit(contract, &old_board_id).await;
+        let message_nonce = crate::contract_domain::ContractHelpers::next_score_nonce(contract);
+        contract.send_message(
+            leaderboard_chain_id,
+            game2048::Message::SubmitScore {
+                player,
+                player_chain_id,
+                board_id: old_board_id,
+                score: continuation_score,
+                highest_tile,
+                game_status: GameStatus::Ended(GameEndReason::Continued { new_board_id }),
+                timestamp: current_time,
+                boards_per_leaderboard: [(leaderboard_id.clone(), boards_in_tournament)].into(),
+                start_time,
+                end_time,
+                streak_bonus_applied: false,
+                is_warmup,
+                average_branching_factor,
+                hints_used,
+                moves_count,
+                message_nonce,
+                tile_milestone_hit,
+                top_boards,
+            },
+        );
     }
-    
+
     /// 🚀 MANUAL SCORE SUBMISSION: Submit current board score to leaderboard
     /// Called when user clicks "refresh leaderboard" button
     /// Only sends if: score > 0 AND score > player's tournament best
@@ -527,7 +2868,7 @@ impl GameOperationHandler {
         contract
             .validate_player_password(&player, &password_hash)
             .await;
-            
+
         let board = contract
             .state
             .boards
@@ -542,10 +2883,23 @@ impl GameOperationHandler {
 
         // Get current board state
         let current_board = *board.board.get();
-        let score = Game::score(current_board);
-        let highest_tile = Game::highest_tile(current_board);
+        let (score, highest_tile) = match board.sized_board() {
+            // 🔲 Sized boards keep their running score
+            Some(sized) => (*board.score.get(), sized.highest_tile()),
+            None => (
+                Game::score(current_board)
+                    + *board.bonus_score.get()
+                    + *board.continuation_score.get(),
+                Game::highest_tile(current_board),
+            ),
+        };
         let is_ended = *board.is_ended.get();
         let leaderboard_id = board.leaderboard_id.get().clone();
+        let is_warmup = *board.is_warmup.get();
+        let average_branching_factor = board.average_branching_factor();
+        let hints_used = *board.hints_used.get();
+        let moves_count = *board.move_count.get();
+        let best_score_key = Self::best_score_key(&leaderboard_id, is_warmup);
         let board_start_time = *board.start_time.get();
         let board_end_time = *board.end_time.get();
 
@@ -558,7 +2912,7 @@ impl GameOperationHandler {
             .unwrap();
         let current_best = player_record
             .best_score
-            .get(&leaderboard_id)
+            .get(&best_score_key)
             .await
             .unwrap()
             .unwrap_or(0);
@@ -593,22 +2947,36 @@ impl GameOperationHandler {
         let player_chain_id = contract.runtime.chain_id().to_string();
         let timestamp = contract.runtime.system_time().micros();
 
+        let (submitted_score, streak_bonus_applied) =
+            Self::apply_streak_bonus(contract, &player, &leaderboard_id, score).await;
+
         if let Ok(leaderboard_chain_id) = ChainId::from_str(&leaderboard_id) {
-            contract
-                .runtime
-                .prepare_message(game2048::Message::SubmitScore {
+            let top_boards = Self::top_boards(contract, &player).await;
+            let tile_milestone_hit = Self::tile_milestone_hit(contract, &board_id).await;
+            let message_nonce = crate::contract_domain::ContractHelpers::next_score_nonce(contract);
+            contract.send_message(
+                leaderboard_chain_id,
+                game2048::Message::SubmitScore {
                     player: player.clone(),
                     player_chain_id,
                     board_id: board_id.clone(),
-                    score,
+                    score: submitted_score,
                     highest_tile,
                     game_status,
                     timestamp,
-                    boards_in_tournament: current_board_count,
+                    boards_per_leaderboard: [(leaderboard_id.clone(), current_board_count)].into(),
                     start_time: board_start_time,
                     end_time: board_end_time,
-                })
-                .send_to(leaderboard_chain_id);
+                    streak_bonus_applied,
+                    is_warmup,
+                    average_branching_factor,
+                    hints_used,
+                    moves_count,
+                    message_nonce,
+                    tile_milestone_hit,
+                    top_boards,
+                },
+            );
         }
 
         // Update player's best score for this tournament
@@ -620,9 +2988,10 @@ impl GameOperationHandler {
             .unwrap();
         player_record
             .best_score
-            .insert(&leaderboard_id, score)
+            .insert(&best_score_key, score)
             .unwrap();
-            
+        Self::notify_friends(contract, &player, &leaderboard_id, is_warmup, score).await;
+
         // Update board tracking state
         let board = contract
             .state
@@ -633,4 +3002,67 @@ impl GameOperationHandler {
         board.highest_tile_sent.set(highest_tile);
         board.last_score_sent_time.set(timestamp);
     }
+
+    /// 🔥 STREAK BONUS: Apply the tournament's streak multiplier to `score`.
+    /// The streak is recomputed from every ended tournament this chain has seen.
+    async fn apply_streak_bonus(
+        contract: &mut crate::Game2048Contract,
+        player: &str,
+        leaderboard_id: &str,
+        score: u64,
+    ) -> (u64, bool) {
+        let tiers = contract
+            .get_cached_tournament(leaderboard_id)
+            .await
+            .map(|tournament| tournament.streak_bonus_tiers)
+            .unwrap_or_default();
+
+        let current_time = contract.runtime.system_time().micros();
+        let mut end_times = Vec::new();
+        contract
+            .state
+            .tournament_end_times
+            .for_each_index_value(|tournament_id, end_time| {
+                if tournament_id != leaderboard_id && *end_time <= current_time {
+                    end_times.push((tournament_id, *end_time));
+                }
+                Ok(())
+            })
+            .await
+            .unwrap();
+
+        let player_record = contract
+            .state
+            .player_records
+            .load_entry_mut(player)
+            .await
+            .unwrap();
+        let mut ended = Vec::new();
+        for (tournament_id, end_time) in end_times {
+            let best_score = player_record
+                .best_score
+                .get(&tournament_id)
+                .await
+                .unwrap()
+                .unwrap_or(0);
+            ended.push((end_time, best_score > 0));
+        }
+
+        let streak = StreakBonusTier::streak(&ended);
+        contract
+            .state
+            .players
+            .load_entry_mut(player)
+            .await
+            .unwrap()
+            .tournament_streak
+            .set(streak);
+
+        let multiplier = StreakBonusTier::multiplier_for(&tiers, streak);
+        if multiplier > 1.0 {
+            ((score as f64 * multiplier).round() as u64, true)
+        } else {
+            (score, false)
+        }
+    }
 }
@@ -2,10 +2,21 @@
 //!
 //! Handles leaderboard-related operations including creation, updates, management, score aggregation, and triggerer coordination.
 
+use crate::contract_domain::handlers::operations::{
+    AdminOperationHandler, ModerationOperationHandler,
+};
+use crate::contract_domain::ContractHelpers;
 use game2048::{
-    LeaderboardAction, LeaderboardSettings, Message, RegistrationCheck, TournamentInfo,
+    check_profanity, validate_custom_banned_words, BonusWindow, BracketMatchup, FinaliseError,
+    GameError, GameVariant, HistoricalRecord, LeaderboardAction, LeaderboardSettings, Message,
+    PrizePoolEntry, QualifierSettings, RegistrationCheck, SettingsVersion, TileWeight,
+    TournamentInfo, TournamentStatus, MAX_BONUS_WINDOWS, MAX_POW_DIFFICULTY, MAX_UNDO_HISTORY_SIZE,
+    MIN_HANDICAP_MULTIPLIER, NATIVE_TOKEN_ID, WAITLIST_ADMISSION_TIMEOUT_SECONDS,
+    WAITLIST_INACTIVE_TIMEOUT_SECONDS,
+};
+use linera_sdk::linera_base_types::{
+    Account, AccountOwner, Amount, ApplicationPermissions, ChainId,
 };
-use linera_sdk::linera_base_types::{Amount, ApplicationPermissions, ChainId};
 use std::str::FromStr;
 
 pub struct LeaderboardOperationHandler;
@@ -18,7 +29,7 @@ impl LeaderboardOperationHandler {
         settings: LeaderboardSettings,
         player: String,
         password_hash: String,
-    ) {
+    ) -> Result<ChainId, GameError> {
         // Validate password
         contract
             .validate_player_password(&player, &password_hash)
@@ -32,7 +43,7 @@ impl LeaderboardOperationHandler {
             .check_player_registered(&player, RegistrationCheck::EnsureRegistered)
             .await;
 
-        let is_mod = contract
+        let is_mod = *contract
             .state
             .players
             .load_entry_or_insert(&player)
@@ -83,11 +94,15 @@ impl LeaderboardOperationHandler {
             }
         }
 
+        if action == LeaderboardAction::Delete {
+            AdminOperationHandler::reject_if_multisig_configured(contract, "DeleteLeaderboard");
+        }
+
         let chain_id = if action == LeaderboardAction::Create {
             let chain_ownership = contract.runtime.chain_ownership();
             let app_id = contract.runtime.application_id().forget_abi();
             let application_permissions = ApplicationPermissions::new_single(app_id);
-            let amount = Amount::from_tokens(if *is_mod { 17 } else { 1 });
+            let amount = Amount::from_tokens(if is_mod { 17 } else { 1 });
             contract
                 .runtime
                 .open_chain(chain_ownership, application_permissions, amount)
@@ -97,202 +112,1459 @@ impl LeaderboardOperationHandler {
             panic!("Leaderboard ID is required");
         };
 
+        // 🛡️ MODERATION: Check name and description before they are stored
+        if matches!(
+            action,
+            LeaderboardAction::Create | LeaderboardAction::Update
+        ) {
+            ModerationOperationHandler::enforce_leaderboard_content(
+                contract,
+                &chain_id.to_string(),
+                &settings,
+                &player,
+                is_mod,
+            )
+            .await;
+        }
+
+        let leaderboard = contract
+            .state
+            .leaderboards
+            .load_entry_mut(&chain_id.to_string())
+            .await
+            .unwrap();
+
+        let host = leaderboard.host.get().clone();
+        if !host.is_empty() && host != player && !is_mod {
+            panic!("Unauthorized: Only the host or moderator can perform this action on the leaderboard");
+        }
+
+        match action {
+            LeaderboardAction::Create | LeaderboardAction::Update => {
+                // 🤬 PROFANITY: Name and description must pass the filter and this tournament's words
+                validate_custom_banned_words(&settings.custom_banned_words)?;
+                let custom_banned_words = if settings.custom_banned_words.is_empty() {
+                    leaderboard
+                        .settings_history
+                        .back()
+                        .await
+                        .unwrap()
+                        .map(|version| version.settings.custom_banned_words)
+                        .unwrap_or_default()
+                } else {
+                    settings.custom_banned_words.clone()
+                };
+                check_profanity("name", &settings.name, &custom_banned_words)?;
+                if let Some(description) = &settings.description {
+                    check_profanity("description", description, &custom_banned_words)?;
+                }
+
+                // Parse optional start/end times: "0" or empty = None (unlimited)
+                let start_time = if settings.start_time.is_empty() || settings.start_time == "0" {
+                    None
+                } else {
+                    // FIXED: Handle parsing errors gracefully
+                    match settings.start_time.parse::<u64>() {
+                        Ok(time) => Some(time),
+                        Err(_) => {
+                            // Use current time if parsing fails
+                            Some(contract.runtime.system_time().micros())
+                        }
+                    }
+                };
+
+                let end_time = if settings.end_time.is_empty() || settings.end_time == "0" {
+                    None
+                } else {
+                    // FIXED: Handle parsing errors gracefully
+                    match settings.end_time.parse::<u64>() {
+                        Ok(time) => Some(time),
+                        Err(_) => {
+                            // Use current time + 1 year if parsing fails
+                            Some(contract.runtime.system_time().micros() + 31_536_000_000_000)
+                            // +1 year
+                        }
+                    }
+                };
+
+                // Only validate times if both are set
+                if let (Some(start), Some(end)) = (start_time, end_time) {
+                    // Temporarily skip current time validation to avoid system_time() WASM panic
+                    // let current_time = contract.runtime.system_time().micros();
+                    if start >= end {
+                        panic!("Start time cannot be after end time");
+                    }
+                    // Commented out to avoid system_time() issues:
+                    // else if current_time >= end {
+                    //     panic!("Current time cannot be after planned end time");
+                    // }
+                }
+
+                if !settings.name.is_empty() {
+                    leaderboard.name.set(settings.name.clone());
+                }
+
+                if let Some(desc) = settings.description.clone() {
+                    leaderboard.description.set(desc);
+                }
+
+                if settings.clear_entry_token_requirement == Some(true) {
+                    if settings.entry_token_requirement.is_some() {
+                        panic!("Cannot set and clear the entry token requirement at once");
+                    }
+                    leaderboard.entry_token_requirement.set(None);
+                } else if let Some(requirement) = settings.entry_token_requirement.clone() {
+                    leaderboard.entry_token_requirement.set(Some(requirement));
+                }
+
+                if let Some(anti_cheat) = settings.anti_cheat.clone() {
+                    leaderboard.anti_cheat_config.set(Some(anti_cheat));
+                }
+
+                if let Some(tiers) = settings.streak_bonus_tiers.clone() {
+                    leaderboard.streak_bonus_tiers.set(tiers);
+                }
+
+                if let Some(allow_variant_conversion) = settings.allow_variant_conversion {
+                    leaderboard
+                        .allow_variant_conversion
+                        .set(allow_variant_conversion);
+                }
+
+                if let Some(pow_difficulty) = settings.pow_difficulty {
+                    if pow_difficulty > MAX_POW_DIFFICULTY {
+                        panic!(
+                            "Proof-of-work difficulty cannot exceed {}",
+                            MAX_POW_DIFFICULTY
+                        );
+                    }
+                    leaderboard.pow_difficulty.set(pow_difficulty);
+                }
+
+                if let Some(max_score_per_player) = settings.max_score_per_player {
+                    leaderboard.max_score_per_player.set(max_score_per_player);
+                }
+
+                if let Some(warmup_duration_seconds) = settings.warmup_duration_seconds {
+                    leaderboard
+                        .warmup_duration_seconds
+                        .set(warmup_duration_seconds);
+                }
+
+                if let Some(undo_budget) = settings.undo_budget {
+                    leaderboard.undo_budget.set(undo_budget);
+                }
+
+                if let Some(undo_history_size) = settings.undo_history_size {
+                    if undo_history_size == 0 || undo_history_size > MAX_UNDO_HISTORY_SIZE {
+                        panic!(
+                            "Undo history size must be between 1 and {}",
+                            MAX_UNDO_HISTORY_SIZE
+                        );
+                    }
+                    leaderboard.undo_history_size.set(undo_history_size);
+                }
+
+                if let Some(time_limit_seconds) = settings.speed_time_limit_seconds {
+                    leaderboard.variant.set(match time_limit_seconds {
+                        0 => GameVariant::Classic2048,
+                        time_limit_seconds => GameVariant::Speed2048 { time_limit_seconds },
+                    });
+                }
+
+                if let Some(lives_remaining) = settings.elimination_lives {
+                    if lives_remaining > 0
+                        && settings
+                            .speed_time_limit_seconds
+                            .is_some_and(|limit| limit > 0)
+                    {
+                        panic!("A tournament cannot be both a speed and an elimination tournament");
+                    }
+                    leaderboard.variant.set(match lives_remaining {
+                        0 => GameVariant::Classic2048,
+                        lives_remaining => GameVariant::Elimination { lives_remaining },
+                    });
+                }
+
+                if let Some(tile_weights) = settings.tile_weights.clone() {
+                    TileWeight::validate_all(&tile_weights)
+                        .unwrap_or_else(|error| panic!("{}", error));
+                    leaderboard.tile_weights.set(tile_weights);
+                }
+
+                if let Some(enabled) = settings.combo_multiplier_enabled {
+                    leaderboard.combo_multiplier_enabled.set(enabled);
+                }
+
+                if let Some(interval) = settings.snapshot_interval_seconds {
+                    leaderboard.snapshot_interval_seconds.set(interval);
+                }
+
+                if let Some(max_moves) = settings.max_moves_per_call {
+                    leaderboard.max_moves_per_call.set(max_moves);
+                }
+
+                if let Some(interval) = settings.min_move_interval_micros {
+                    leaderboard.min_move_interval_micros.set(interval);
+                }
+
+                if let Some(cooldown) = settings.new_board_cooldown_seconds {
+                    leaderboard.new_board_cooldown_seconds.set(cooldown);
+                }
+
+                if let Some(power_ups) = settings.power_ups {
+                    power_ups
+                        .validate()
+                        .unwrap_or_else(|error| panic!("{}", error));
+                    leaderboard.power_ups.set(Some(power_ups));
+                }
+
+                if let Some(mode) = settings.mode {
+                    leaderboard.mode.set(mode);
+                }
+
+                if let Some(max_participants) = settings.max_participants {
+                    leaderboard
+                        .max_participants
+                        .set((max_participants > 0).then_some(max_participants));
+                }
+
+                if let Some(tie_break) = settings.tie_break {
+                    leaderboard.tie_break.set(Some(tie_break));
+                }
+
+                if let Some(season) = settings.season {
+                    season
+                        .validate()
+                        .unwrap_or_else(|error| panic!("{}", error));
+                    leaderboard.season_config.set(Some(season));
+                }
+
+                if let Some(thresholds) = settings.countdown_notification_thresholds.clone() {
+                    leaderboard.countdown_thresholds.set(thresholds);
+                }
+
+                if let Some(qualifier) = settings.qualifier_settings.clone() {
+                    if qualifier.advance_count == 0 {
+                        panic!("Qualifier must advance at least one player");
+                    }
+                    if ChainId::from_str(&qualifier.main_leaderboard_id).is_err() {
+                        panic!("Invalid main tournament ID");
+                    }
+                    if qualifier.main_leaderboard_id == chain_id.to_string() {
+                        panic!("A qualifier cannot advance players to itself");
+                    }
+                    // The qualifier's ID is its chain ID, which is not known before creation
+                    leaderboard.qualifier_settings.set(Some(QualifierSettings {
+                        qualifier_leaderboard_id: chain_id.to_string(),
+                        ..qualifier
+                    }));
+                }
+
+                if let Some(entries) = settings.prize_pool_entries.clone() {
+                    PrizePoolEntry::validate_all(&entries)
+                        .unwrap_or_else(|error| panic!("{}", error));
+                    leaderboard.prize_pool_entries.set(entries);
+                }
+
+                if let Some(reward_structure) = settings.reward_structure.clone() {
+                    reward_structure
+                        .validate()
+                        .unwrap_or_else(|error| panic!("{}", error));
+                    leaderboard.reward_structure.set(Some(reward_structure));
+                }
+
+                // Store times: None -> 0 (unlimited), Some(value) -> value
+                leaderboard.start_time.set(start_time.unwrap_or(0));
+                leaderboard.end_time.set(end_time.unwrap_or(0));
+
+                // 🔥 HOT RELOAD: Record the new settings version and when each changed key took effect
+                let effective_at = contract.runtime.system_time().micros();
+                let update = LeaderboardSettings {
+                    start_time: start_time.unwrap_or(0).to_string(),
+                    end_time: end_time.unwrap_or(0).to_string(),
+                    ..settings.clone()
+                };
+                let previous = leaderboard
+                    .settings_history
+                    .back()
+                    .await
+                    .unwrap()
+                    .map(|version| version.settings);
+                let version = match &previous {
+                    Some(previous) => previous.apply_update(&update),
+                    None => update,
+                };
+                for key in version.changed_keys(previous.as_ref()) {
+                    leaderboard
+                        .settings_effective_at
+                        .insert(key, effective_at)
+                        .unwrap();
+                }
+                leaderboard.settings_history.push_back(SettingsVersion {
+                    effective_at,
+                    settings: version,
+                });
+
+                if action == LeaderboardAction::Create {
+                    let chain_id_str = chain_id.to_string();
+                    leaderboard.leaderboard_id.set(chain_id_str.clone());
+                    leaderboard.chain_id.set(chain_id_str);
+                    leaderboard.host.set(player.clone());
+
+                    // Set base triggerer count from settings (default: 5)
+                    let base_triggerer_count = settings.base_triggerer_count.unwrap_or(5);
+                    leaderboard
+                        .admin_base_triggerer_count
+                        .set(base_triggerer_count);
+
+                    // Create shard chains from main chain (ONLY on creation)
+                    let shard_number = settings.shard_number.unwrap_or(1);
+                    let mut created_shard_ids = Vec::new();
+
+                    for _ in 0..shard_number {
+                        let shard_chain_ownership = contract.runtime.chain_ownership();
+                        let shard_app_id = contract.runtime.application_id().forget_abi();
+                        let shard_application_permissions =
+                            ApplicationPermissions::new_single(shard_app_id);
+                        let shard_amount = Amount::from_tokens(1);
+                        let shard_id = contract.runtime.open_chain(
+                            shard_chain_ownership,
+                            shard_application_permissions,
+                            shard_amount,
+                        );
+
+                        created_shard_ids.push(shard_id.to_string());
+
+                        // Send CreateLeaderboard message to each shard
+                        contract.send_message(
+                            shard_id,
+                            Message::CreateLeaderboard {
+                                leaderboard_id: chain_id.to_string(),
+                                name: settings.name.clone(),
+                                description: settings.description.clone(),
+                                chain_id: chain_id.to_string(),
+                                host: player.clone(),
+                                start_time: start_time.unwrap_or(0),
+                                end_time: end_time.unwrap_or(0),
+                                shard_ids: vec![], // Shards don't need shard IDs
+                                base_triggerer_count,
+                                total_shard_count: shard_number,
+                            },
+                        );
+                    }
+
+                    // Update main chain leaderboard list with shard info
+                    let main_leaderboard = contract
+                        .state
+                        .leaderboards
+                        .load_entry_mut(&chain_id.to_string())
+                        .await
+                        .unwrap();
+
+                    for shard_id in &created_shard_ids {
+                        main_leaderboard.shard_ids.push_back(shard_id.clone());
+                    }
+                    main_leaderboard
+                        .current_shard_id
+                        .set(created_shard_ids.first().cloned().unwrap_or_default());
+
+                    // Send CreateLeaderboard message to new leaderboard chain with shard IDs
+                    contract.send_message(
+                        chain_id,
+                        Message::CreateLeaderboard {
+                            leaderboard_id: chain_id.to_string(),
+                            name: settings.name.clone(),
+                            description: settings.description.clone(),
+                            chain_id: chain_id.to_string(),
+                            host: player.clone(),
+                            start_time: start_time.unwrap_or(0),
+                            end_time: end_time.unwrap_or(0),
+                            shard_ids: created_shard_ids.clone(),
+                            base_triggerer_count,
+                            total_shard_count: shard_number,
+                        },
+                    );
+
+                    // Main chain: emit updated active tournaments registry
+                    if is_main_chain {
+                        contract.emit_active_tournaments().await;
+                    }
+                } else if action == LeaderboardAction::Update {
+                    // For updates, just send message to existing leaderboard chain (no shard creation)
+                    let base_triggerer_count = settings.base_triggerer_count.unwrap_or(5);
+                    let shard_number = settings.shard_number.unwrap_or(1);
+                    contract.send_message(
+                        chain_id,
+                        Message::CreateLeaderboard {
+                            leaderboard_id: chain_id.to_string(),
+                            name: settings.name.clone(),
+                            description: settings.description.clone(),
+                            chain_id: chain_id.to_string(),
+                            host: player.clone(),
+                            start_time: start_time.unwrap_or(0),
+                            end_time: end_time.unwrap_or(0),
+                            shard_ids: vec![], // No shard changes on update
+                            base_triggerer_count,
+                            total_shard_count: shard_number,
+                        },
+                    );
+
+                    // Main chain: emit updated active tournaments registry
+                    if is_main_chain {
+                        contract.emit_active_tournaments().await;
+                    }
+                }
+
+                // 🏅 PRIZE POOL: The leaderboard chain tracks funding and pays out
+                if let Some(entries) = settings.prize_pool_entries.clone() {
+                    contract.send_message(chain_id, Message::ConfigurePrizePool { entries });
+                }
+
+                // 🏁 WARMUP: The leaderboard chain announces the warmup and the start
+                if let Some(warmup_duration_seconds) = settings.warmup_duration_seconds {
+                    contract.send_message(
+                        chain_id,
+                        Message::ConfigureWarmup {
+                            warmup_duration_seconds,
+                        },
+                    );
+                }
+
+                // ⏳ COUNTDOWN: The leaderboard chain announces the approaching end
+                if let Some(thresholds) = settings.countdown_notification_thresholds.clone() {
+                    contract.send_message(chain_id, Message::ConfigureCountdown { thresholds });
+                }
+
+                // 🎟️ WAITLIST: The leaderboard chain waitlists new players past the limit
+                if let Some(max_participants) = settings.max_participants {
+                    contract.send_message(
+                        chain_id,
+                        Message::ConfigureMaxParticipants { max_participants },
+                    );
+                }
+
+                // 🎟️ QUALIFIER: The leaderboard chain advances its top players when it ends
+                if let Some(qualifier) = settings.qualifier_settings.clone() {
+                    contract.send_message(
+                        chain_id,
+                        Message::ConfigureQualifier {
+                            settings: QualifierSettings {
+                                qualifier_leaderboard_id: chain_id.to_string(),
+                                ..qualifier
+                            },
+                        },
+                    );
+                }
+            }
+            LeaderboardAction::Delete => {
+                Self::delete_leaderboard(contract, &leaderboard_id).await;
+            }
+            LeaderboardAction::TogglePin => {
+                if !is_mod {
+                    panic!("Only admin can pin event");
+                }
+
+                leaderboard.is_pinned.set(!*leaderboard.is_pinned.get());
+            }
+        }
+        Ok(chain_id)
+    }
+
+    /// 📋 CLONE: Create a leaderboard with the latest settings of `source_leaderboard_id`,
+    /// a new name and new times. The new leaderboard records where it was cloned from.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn handle_clone_leaderboard(
+        contract: &mut crate::Game2048Contract,
+        source_leaderboard_id: String,
+        new_name: String,
+        new_start_time: u64,
+        new_end_time: u64,
+        player: String,
+        password_hash: String,
+    ) -> Result<(), GameError> {
+        if source_leaderboard_id.is_empty()
+            || contract
+                .state
+                .leaderboards
+                .try_load_entry(&source_leaderboard_id)
+                .await
+                .unwrap()
+                .is_none()
+        {
+            return Err(GameError::TournamentNotFound);
+        }
+        let source = contract
+            .state
+            .leaderboards
+            .load_entry_mut(&source_leaderboard_id)
+            .await
+            .unwrap();
+        let Some(source_settings) = source
+            .settings_history
+            .back()
+            .await
+            .unwrap()
+            .map(|version| version.settings)
+        else {
+            return Err(GameError::TournamentNotFound);
+        };
+        let variant = *source.variant.get();
+
+        let settings = LeaderboardSettings {
+            name: new_name,
+            start_time: new_start_time.to_string(),
+            end_time: new_end_time.to_string(),
+            ..source_settings
+        };
+        let chain_id = Self::handle_leaderboard_action(
+            contract,
+            String::new(),
+            LeaderboardAction::Create,
+            settings,
+            player,
+            password_hash,
+        )
+        .await?;
+
+        let leaderboard = contract
+            .state
+            .leaderboards
+            .load_entry_mut(&chain_id.to_string())
+            .await
+            .unwrap();
+        leaderboard.variant.set(variant);
+        leaderboard.cloned_from.set(Some(source_leaderboard_id));
+        contract.emit_active_tournaments().await;
+        Ok(())
+    }
+
+    /// Validate that `player` can administer `leaderboard_id` (host or moderator, main chain only)
+    /// Returns the leaderboard chain ID
+    pub async fn authorize_leaderboard_admin(
+        contract: &mut crate::Game2048Contract,
+        leaderboard_id: &str,
+        player: &str,
+        password_hash: &str,
+    ) -> ChainId {
+        contract
+            .validate_player_password(player, password_hash)
+            .await;
+        if !contract.is_main_chain() {
+            panic!("Only main chain can perform leaderboard admin actions");
+        }
+
+        let leaderboard_chain_id = ChainId::from_str(leaderboard_id)
+            .unwrap_or_else(|_| panic!("Invalid leaderboard ID format"));
+
+        let is_mod = *contract
+            .state
+            .players
+            .load_entry_or_insert(player)
+            .await
+            .unwrap()
+            .is_mod
+            .get();
+
+        let Some(leaderboard) = contract
+            .state
+            .leaderboards
+            .try_load_entry(leaderboard_id)
+            .await
+            .unwrap()
+        else {
+            panic!("Leaderboard not found");
+        };
+
+        if leaderboard.host.get() != player && !is_mod {
+            panic!("Unauthorized: Only the host or moderator can perform this action on the leaderboard");
+        }
+
+        leaderboard_chain_id
+    }
+
+    /// 📡 LIVE STREAM: Register a relay endpoint on the leaderboard chain (host/admin only)
+    pub async fn handle_register_stream_endpoint(
+        contract: &mut crate::Game2048Contract,
+        admin: String,
+        password_hash: String,
+        leaderboard_id: String,
+        endpoint_url: String,
+        auth_token_hash: String,
+        relay_owner: String,
+    ) {
+        let leaderboard_chain_id =
+            Self::authorize_leaderboard_admin(contract, &leaderboard_id, &admin, &password_hash)
+                .await;
+
+        if !endpoint_url.starts_with("ws://") && !endpoint_url.starts_with("wss://") {
+            panic!("Endpoint URL must be a WebSocket URL (ws:// or wss://)");
+        }
+        if auth_token_hash.len() != 64 || !auth_token_hash.chars().all(|c| c.is_ascii_hexdigit()) {
+            panic!("Auth token hash must be a hex sha256 digest, not the token");
+        }
+        if AccountOwner::from_str(&relay_owner).is_err() {
+            panic!("Invalid relay owner: {}", relay_owner);
+        }
+
+        let timestamp = contract.runtime.system_time().micros();
+        contract.send_message(
+            leaderboard_chain_id,
+            Message::RegisterStreamEndpoint {
+                endpoint_url,
+                auth_token_hash,
+                relay_owner,
+                timestamp,
+            },
+        );
+    }
+
+    /// ⏰ BONUS WINDOW: Schedule a score multiplier window (host/admin only)
+    /// Stored on the main chain registry (reaches player chains via active tournaments)
+    /// and forwarded to the leaderboard chain
+    pub async fn handle_add_bonus_window(
+        contract: &mut crate::Game2048Contract,
+        leaderboard_id: String,
+        admin: String,
+        password_hash: String,
+        window: BonusWindow,
+    ) {
+        let leaderboard_chain_id =
+            Self::authorize_leaderboard_admin(contract, &leaderboard_id, &admin, &password_hash)
+                .await;
+
+        if window.start >= window.end {
+            panic!("Bonus window start must be before its end");
+        }
+        if !window.multiplier.is_finite() || window.multiplier <= 1.0 {
+            panic!("Bonus window multiplier must be greater than 1.0");
+        }
+        if window.window_name.trim().is_empty() {
+            panic!("Bonus window name cannot be empty");
+        }
+
+        let current_time = contract.runtime.system_time().micros();
+        if window.end <= current_time {
+            panic!("Bonus window has already ended");
+        }
+
+        let leaderboard = contract
+            .state
+            .leaderboards
+            .load_entry_mut(&leaderboard_id)
+            .await
+            .unwrap();
+        let mut windows = leaderboard.active_bonus_windows.get().clone();
+        windows.retain(|existing| existing.end > current_time);
+        if windows.len() >= MAX_BONUS_WINDOWS {
+            panic!("Maximum of {} bonus windows reached", MAX_BONUS_WINDOWS);
+        }
+        windows.push(window.clone());
+        leaderboard.active_bonus_windows.set(windows);
+
+        contract.send_message(leaderboard_chain_id, Message::AddBonusWindow { window });
+
+        // Publish the new window to player chains
+        contract.emit_active_tournaments().await;
+    }
+
+    /// ⚖️ HANDICAP: Set a player's score multiplier on the leaderboard chain.
+    /// Only allowed before the tournament starts, so results cannot be changed retroactively.
+    pub async fn handle_set_player_handicap(
+        contract: &mut crate::Game2048Contract,
+        leaderboard_id: String,
+        admin: String,
+        password_hash: String,
+        player: String,
+        multiplier: f64,
+    ) {
+        let leaderboard_chain_id =
+            Self::authorize_leaderboard_admin(contract, &leaderboard_id, &admin, &password_hash)
+                .await;
+
+        if !(MIN_HANDICAP_MULTIPLIER..=1.0).contains(&multiplier) {
+            panic!(
+                "Handicap multiplier must be between {} and 1.0",
+                MIN_HANDICAP_MULTIPLIER
+            );
+        }
+        contract
+            .check_player_registered(&player, RegistrationCheck::EnsureRegistered)
+            .await;
+
+        let start_time = *contract
+            .state
+            .leaderboards
+            .load_entry_mut(&leaderboard_id)
+            .await
+            .unwrap()
+            .start_time
+            .get();
+        let current_time = contract.runtime.system_time().micros();
+        // 0 = unlimited start time (already open)
+        if start_time == 0 || current_time >= start_time {
+            panic!("Handicaps can only be set before the tournament starts");
+        }
+
+        contract.send_message(
+            leaderboard_chain_id,
+            Message::SetPlayerHandicap { player, multiplier },
+        );
+    }
+
+    /// 🏅 PRIZE POOL: Send tokens from this chain to the leaderboard chain's prize pool.
+    /// Native tokens come from the chain balance, fungible tokens from the signer's account.
+    pub async fn handle_fund_prize_pool(
+        contract: &mut crate::Game2048Contract,
+        leaderboard_id: String,
+        application_id: String,
+        amount: Amount,
+    ) {
+        if amount == Amount::ZERO {
+            panic!("Prize pool funding must be greater than zero");
+        }
+        let leaderboard_chain_id = ChainId::from_str(&leaderboard_id)
+            .unwrap_or_else(|_| panic!("Invalid leaderboard ID format"));
+
+        // Main chain reads its own registry, other chains the cached tournament list
+        let (entries, end_time) = if contract.is_main_chain() {
+            let Some(leaderboard) = contract
+                .state
+                .leaderboards
+                .try_load_entry(&leaderboard_id)
+                .await
+                .unwrap()
+            else {
+                panic!("Leaderboard not found");
+            };
+            (
+                leaderboard.prize_pool_entries.get().clone(),
+                *leaderboard.end_time.get(),
+            )
+        } else {
+            let Some(tournament) = contract.get_cached_tournament(&leaderboard_id).await else {
+                panic!("Tournament not found");
+            };
+            (
+                tournament.prize_pool_entries,
+                tournament.end_time.unwrap_or(0),
+            )
+        };
+
+        if !entries
+            .iter()
+            .any(|entry| entry.application_id == application_id)
+        {
+            panic!("Token {} is not part of this prize pool", application_id);
+        }
+        let current_time = contract.runtime.system_time().micros();
+        if end_time != 0 && current_time >= end_time {
+            panic!("Cannot fund the prize pool of an ended tournament");
+        }
+
+        let source = if application_id == NATIVE_TOKEN_ID {
+            AccountOwner::CHAIN
+        } else {
+            contract
+                .runtime
+                .authenticated_signer()
+                .unwrap_or_else(|| panic!("Funding with a token requires a signed operation"))
+        };
+        let destination = Account {
+            chain_id: leaderboard_chain_id,
+            owner: ContractHelpers::prize_pool_owner(contract, &application_id),
+        };
+        ContractHelpers::transfer_token(contract, &application_id, source, destination, amount);
+
+        contract.send_message(
+            leaderboard_chain_id,
+            Message::PrizePoolFunded {
+                application_id,
+                amount,
+                funder: source,
+            },
+        );
+    }
+
+    /// 🏅 PRIZE POOL: Ask the leaderboard chain to pay out the final ranking (after the tournament ends)
+    pub async fn handle_distribute_prizes(
+        contract: &mut crate::Game2048Contract,
+        leaderboard_id: String,
+        admin: String,
+        password_hash: String,
+    ) {
+        let leaderboard_chain_id =
+            Self::authorize_leaderboard_admin(contract, &leaderboard_id, &admin, &password_hash)
+                .await;
+
+        let leaderboard = contract
+            .state
+            .leaderboards
+            .load_entry_mut(&leaderboard_id)
+            .await
+            .unwrap();
+        if leaderboard.prize_pool_entries.get().is_empty() {
+            panic!("Tournament has no prize pool");
+        }
+        let end_time = *leaderboard.end_time.get();
+        let current_time = contract.runtime.system_time().micros();
+        // 0 = unlimited end time (never ends)
+        if end_time == 0 || current_time < end_time {
+            panic!("Prizes can only be distributed after the tournament ends");
+        }
+
+        contract.send_message(leaderboard_chain_id, Message::DistributePrizes);
+    }
+
+    /// 🏁 Close an ended tournament: mark it finalised and have the leaderboard chain snapshot its
+    /// ranking and request the rewards. A tournament is finalised once.
+    pub async fn handle_finalise_leaderboard(
+        contract: &mut crate::Game2048Contract,
+        leaderboard_id: String,
+        player: String,
+        password_hash: String,
+    ) -> Result<(), FinaliseError> {
+        let leaderboard_chain_id =
+            Self::authorize_leaderboard_admin(contract, &leaderboard_id, &player, &password_hash)
+                .await;
+
+        let current_time = contract.runtime.system_time().micros();
+        let leaderboard = contract
+            .state
+            .leaderboards
+            .load_entry_mut(&leaderboard_id)
+            .await
+            .unwrap();
+        if *leaderboard.status.get() == TournamentStatus::Finalised {
+            return Err(FinaliseError::AlreadyFinalised);
+        }
+        let end_time = *leaderboard.end_time.get();
+        // 0 = unlimited end time (never ends)
+        if end_time == 0 || current_time < end_time {
+            return Err(FinaliseError::NotEnded);
+        }
+        leaderboard.status.set(TournamentStatus::Finalised);
+        let reward_structure = leaderboard.reward_structure.get().clone();
+
+        contract.send_message(
+            leaderboard_chain_id,
+            Message::FinaliseLeaderboard { reward_structure },
+        );
+        Ok(())
+    }
+
+    /// 🥊 BRACKET: Ask the leaderboard chain to seed its players into a single-elimination bracket
+    pub async fn handle_bracket_tournament(
+        contract: &mut crate::Game2048Contract,
+        leaderboard_id: String,
+        admin: String,
+        password_hash: String,
+        bracket_size: u32,
+        round_duration_seconds: u64,
+    ) {
+        BracketMatchup::validate_size(bracket_size).unwrap_or_else(|error| panic!("{}", error));
+        if round_duration_seconds == 0 {
+            panic!("Bracket rounds need a duration");
+        }
+        let leaderboard_chain_id =
+            Self::authorize_leaderboard_admin(contract, &leaderboard_id, &admin, &password_hash)
+                .await;
+
+        contract.send_message(
+            leaderboard_chain_id,
+            Message::StartBracket {
+                bracket_size,
+                round_duration_seconds,
+            },
+        );
+    }
+
+    /// 🔄 ROUND ROBIN: Ask the leaderboard chain to pair its players for `rounds` rounds
+    pub async fn handle_schedule_round_robin(
+        contract: &mut crate::Game2048Contract,
+        leaderboard_id: String,
+        rounds: u8,
+        round_duration_seconds: u64,
+        player: String,
+        password_hash: String,
+    ) {
+        if rounds == 0 {
+            panic!("Round robin needs at least one round");
+        }
+        if round_duration_seconds == 0 {
+            panic!("Round robin rounds need a duration");
+        }
+        let leaderboard_chain_id =
+            Self::authorize_leaderboard_admin(contract, &leaderboard_id, &player, &password_hash)
+                .await;
+
+        contract.send_message(
+            leaderboard_chain_id,
+            Message::StartRoundRobin {
+                rounds,
+                round_duration_seconds,
+            },
+        );
+    }
+
+    /// 🔄 ROUND ROBIN: Once the round's deadline passes, credit each match to the player who
+    /// gained more score during the round, then start the next round from the current scores
+    /// (runs on the leaderboard chain)
+    pub async fn advance_round_robin(contract: &mut crate::Game2048Contract, current_time: u64) {
+        let leaderboard = contract
+            .state
+            .leaderboards
+            .load_entry_mut("")
+            .await
+            .unwrap();
+        let round = *leaderboard.rr_current_round.get();
+        if round == 0
+            || round > *leaderboard.rr_rounds.get()
+            || current_time < *leaderboard.rr_round_deadline.get()
+        {
+            return;
+        }
+
+        let pairs = leaderboard
+            .rr_schedule
+            .get(&round)
+            .await
+            .unwrap()
+            .unwrap_or_default();
+        for (player_a, player_b) in pairs {
+            let mut gains = [0u64; 2];
+            for (gain, player) in gains.iter_mut().zip([&player_a, &player_b]) {
+                let score = leaderboard.score.get(player).await.unwrap().unwrap_or(0);
+                let start_score = leaderboard
+                    .rr_round_start_scores
+                    .get(player)
+                    .await
+                    .unwrap()
+                    .unwrap_or(0);
+                *gain = score.saturating_sub(start_score);
+            }
+            // Equal gains are a draw
+            let winner = match gains[0].cmp(&gains[1]) {
+                std::cmp::Ordering::Greater => player_a,
+                std::cmp::Ordering::Less => player_b,
+                std::cmp::Ordering::Equal => continue,
+            };
+            let wins = leaderboard.rr_wins.get(&winner).await.unwrap().unwrap_or(0);
+            leaderboard.rr_wins.insert(&winner, wins + 1).unwrap();
+        }
+
+        // Reset per-round scores: the next round counts from here
+        let mut scores = Vec::new();
+        leaderboard
+            .score
+            .for_each_index_value(|username, score| {
+                scores.push((username, *score));
+                Ok(())
+            })
+            .await
+            .unwrap();
+        for (username, score) in scores {
+            leaderboard
+                .rr_round_start_scores
+                .insert(&username, score)
+                .unwrap();
+        }
+        leaderboard.rr_current_round.set(round + 1);
+        leaderboard
+            .rr_round_deadline
+            .set(current_time + *leaderboard.rr_round_duration.get());
+    }
+
+    /// 🥊 BRACKET: Decide matches past their deadline by current score (runs on the leaderboard
+    /// chain). Winners fill their next match, which starts once both of its players are known.
+    pub async fn advance_bracket(contract: &mut crate::Game2048Contract, current_time: u64) {
+        let leaderboard = contract
+            .state
+            .leaderboards
+            .load_entry_mut("")
+            .await
+            .unwrap();
+        let bracket_size = *leaderboard.bracket_size.get();
+        if bracket_size == 0 || !leaderboard.bracket_champion.get().is_empty() {
+            return;
+        }
+
+        let mut due: Vec<(String, BracketMatchup)> = Vec::new();
+        leaderboard
+            .bracket_matchups
+            .for_each_index_value(|id, matchup| {
+                if matchup.winner.is_empty()
+                    && matchup.deadline > 0
+                    && current_time >= matchup.deadline
+                {
+                    due.push((id, matchup.into_owned()));
+                }
+                Ok(())
+            })
+            .await
+            .unwrap();
+        // Earlier rounds first, so a decided final is always the last advancement
+        due.sort_by_key(|(_, matchup)| matchup.round);
+
+        let leaderboard_id = leaderboard.leaderboard_id.get().clone();
+        let round_duration = *leaderboard.bracket_round_duration.get();
+        let mut advancements = Vec::new();
+        for (id, mut matchup) in due {
+            let score_a = leaderboard
+                .score
+                .get(&matchup.player_a)
+                .await
+                .unwrap()
+                .unwrap_or(0);
+            let score_b = leaderboard
+                .score
+                .get(&matchup.player_b)
+                .await
+                .unwrap()
+                .unwrap_or(0);
+            let (winner, loser) = matchup.decide(score_a, score_b);
+            matchup.winner = winner.clone();
+            leaderboard
+                .bracket_matchups
+                .insert(&id, matchup.clone())
+                .unwrap();
+
+            let (_, index) = BracketMatchup::parse_id(&id).unwrap_or_default();
+            let next_matchup_id = BracketMatchup::next_id(matchup.round, index, bracket_size);
+            match &next_matchup_id {
+                Some(next_id) => {
+                    let game_id = if winner == matchup.player_a {
+                        matchup.game_id_a.clone()
+                    } else {
+                        matchup.game_id_b.clone()
+                    };
+                    let mut next = leaderboard
+                        .bracket_matchups
+                        .get(next_id)
+                        .await
+                        .unwrap()
+                        .unwrap_or(BracketMatchup {
+                            round: matchup.round + 1,
+                            ..Default::default()
+                        });
+                    if index % 2 == 0 {
+                        next.player_a = winner.clone();
+                        next.game_id_a = game_id;
+                    } else {
+                        next.player_b = winner.clone();
+                        next.game_id_b = game_id;
+                    }
+                    if !next.player_a.is_empty() && !next.player_b.is_empty() {
+                        next.deadline = current_time + round_duration;
+                    }
+                    leaderboard.bracket_matchups.insert(next_id, next).unwrap();
+                }
+                None => leaderboard.bracket_champion.set(winner.clone()),
+            }
+
+            for player in [&winner, &loser] {
+                if let Some(chain_id) = leaderboard.player_chain_ids.get(player).await.unwrap() {
+                    advancements.push((
+                        chain_id,
+                        matchup.round,
+                        winner.clone(),
+                        loser.clone(),
+                        next_matchup_id.clone(),
+                    ));
+                }
+            }
+        }
+
+        for (chain_id, round, winner, loser, next_matchup_id) in advancements {
+            if let Ok(chain_id) = ChainId::from_str(&chain_id) {
+                contract.send_message(
+                    chain_id,
+                    Message::BracketAdvancement {
+                        leaderboard_id: leaderboard_id.clone(),
+                        round,
+                        winner,
+                        loser,
+                        next_matchup_id,
+                    },
+                );
+            }
+        }
+    }
+
+    /// 📥 Import off-chain history into the tournament's archive.
+    /// Records are appended and must continue in chronological order; every player must be registered.
+    pub async fn handle_import_historical_data(
+        contract: &mut crate::Game2048Contract,
+        leaderboard_id: String,
+        admin: String,
+        password_hash: String,
+        data_json: String,
+    ) {
+        Self::authorize_leaderboard_admin(contract, &leaderboard_id, &admin, &password_hash).await;
+
+        let records =
+            HistoricalRecord::parse_batch(&data_json).unwrap_or_else(|error| panic!("{}", error));
+        for record in &records {
+            contract
+                .check_player_registered(&record.player, RegistrationCheck::EnsureRegistered)
+                .await;
+        }
+
+        let mut archive = contract
+            .state
+            .archived_tournaments
+            .get(&leaderboard_id)
+            .await
+            .unwrap()
+            .unwrap_or_default();
+        if let (Some(last), Some(first)) = (archive.records.last(), records.first()) {
+            if first.timestamp < last.timestamp {
+                panic!("Imported records must continue after the archived history");
+            }
+        }
+
+        let record_count = records.len() as u32;
+        archive.leaderboard_id = leaderboard_id.clone();
+        archive.records.extend(records);
+        archive.imported_by = admin;
+        archive.imported_at = contract.runtime.system_time().micros();
+        contract
+            .state
+            .archived_tournaments
+            .insert(&leaderboard_id, archive)
+            .unwrap();
+
+        use crate::contract_domain::events::emitters::EventEmitter;
+        EventEmitter::emit_historical_data_imported(contract, record_count, leaderboard_id).await;
+    }
+
+    /// 🔁 REPLAY: Enter (or leave) replay mode on the main chain registry and the leaderboard chain
+    pub async fn handle_set_replay_mode(
+        contract: &mut crate::Game2048Contract,
+        leaderboard_id: String,
+        admin: String,
+        password_hash: String,
+        replay_mode: bool,
+    ) {
+        let leaderboard_chain_id =
+            Self::authorize_leaderboard_admin(contract, &leaderboard_id, &admin, &password_hash)
+                .await;
+
+        let leaderboard = contract
+            .state
+            .leaderboards
+            .load_entry_mut(&leaderboard_id)
+            .await
+            .unwrap();
+        if *leaderboard.replay_mode.get() == replay_mode {
+            panic!(
+                "Tournament is {} in replay mode",
+                if replay_mode { "already" } else { "not" }
+            );
+        }
+        leaderboard.replay_mode.set(replay_mode);
+
+        let message = if replay_mode {
+            Message::InitiateReplay
+        } else {
+            Message::FinalizeReplay
+        };
+        contract.send_message(leaderboard_chain_id, message);
+
+        contract.emit_active_tournaments().await;
+    }
+
+    /// ⏸️ PAUSE: Pause (or resume) scoring on the leaderboard chain and its shards
+    pub async fn handle_set_leaderboard_paused(
+        contract: &mut crate::Game2048Contract,
+        leaderboard_id: String,
+        reason: String,
+        player: String,
+        password_hash: String,
+        paused: bool,
+    ) {
+        let leaderboard_chain_id =
+            Self::authorize_leaderboard_admin(contract, &leaderboard_id, &player, &password_hash)
+                .await;
+
         let leaderboard = contract
             .state
             .leaderboards
-            .load_entry_mut(&chain_id.to_string())
+            .load_entry_mut(&leaderboard_id)
             .await
             .unwrap();
-
-        let host = leaderboard.host.get().clone();
-        if !host.is_empty() && host != player && !is_mod {
-            panic!("Unauthorized: Only the host or moderator can perform this action on the leaderboard");
+        if *leaderboard.is_paused.get() == paused {
+            panic!(
+                "Leaderboard is {} paused",
+                if paused { "already" } else { "not" }
+            );
+        }
+        leaderboard.is_paused.set(paused);
+        let shard_ids = leaderboard.shard_ids.elements().await.unwrap();
+
+        let timestamp = contract.runtime.system_time().micros();
+        let shard_chains = shard_ids
+            .iter()
+            .filter_map(|shard_id| ChainId::from_str(shard_id).ok());
+        for chain_id in std::iter::once(leaderboard_chain_id).chain(shard_chains) {
+            contract.send_message(
+                chain_id,
+                Message::SetLeaderboardPaused {
+                    leaderboard_id: leaderboard_id.clone(),
+                    paused,
+                    reason: reason.clone(),
+                    timestamp,
+                },
+            );
         }
+    }
 
-        match action {
-            LeaderboardAction::Create | LeaderboardAction::Update => {
-                // Parse optional start/end times: "0" or empty = None (unlimited)
-                let start_time = if settings.start_time.is_empty() || settings.start_time == "0" {
-                    None
-                } else {
-                    // FIXED: Handle parsing errors gracefully
-                    match settings.start_time.parse::<u64>() {
-                        Ok(time) => Some(time),
-                        Err(_) => {
-                            // Use current time if parsing fails
-                            Some(contract.runtime.system_time().micros())
-                        }
-                    }
-                };
+    /// 🧹 PURGE: Ask every shard of the tournament to remove its inactive players without a score
+    pub async fn handle_purge_inactive_players(
+        contract: &mut crate::Game2048Contract,
+        leaderboard_id: String,
+        inactive_threshold_seconds: u64,
+        admin: String,
+        password_hash: String,
+    ) {
+        Self::authorize_leaderboard_admin(contract, &leaderboard_id, &admin, &password_hash).await;
+        if inactive_threshold_seconds == 0 {
+            panic!("Inactivity threshold must be positive");
+        }
 
-                let end_time = if settings.end_time.is_empty() || settings.end_time == "0" {
-                    None
-                } else {
-                    // FIXED: Handle parsing errors gracefully
-                    match settings.end_time.parse::<u64>() {
-                        Ok(time) => Some(time),
-                        Err(_) => {
-                            // Use current time + 1 year if parsing fails
-                            Some(contract.runtime.system_time().micros() + 31_536_000_000_000)
-                            // +1 year
-                        }
-                    }
-                };
+        let shard_ids = contract
+            .state
+            .leaderboards
+            .load_entry_mut(&leaderboard_id)
+            .await
+            .unwrap()
+            .shard_ids
+            .elements()
+            .await
+            .unwrap();
+        for chain_id in shard_ids
+            .iter()
+            .filter_map(|shard_id| ChainId::from_str(shard_id).ok())
+        {
+            contract.send_message(
+                chain_id,
+                Message::PurgeInactivePlayers {
+                    leaderboard_id: leaderboard_id.clone(),
+                    inactive_threshold_seconds,
+                },
+            );
+        }
+    }
 
-                // Only validate times if both are set
-                if let (Some(start), Some(end)) = (start_time, end_time) {
-                    // Temporarily skip current time validation to avoid system_time() WASM panic
-                    // let current_time = contract.runtime.system_time().micros();
-                    if start >= end {
-                        panic!("Start time cannot be after end time");
-                    }
-                    // Commented out to avoid system_time() issues:
-                    // else if current_time >= end {
-                    //     panic!("Current time cannot be after planned end time");
-                    // }
-                }
+    /// 🎟️ WAITLIST: Admit the earliest waitlisted players into free slots (runs on the leaderboard
+    /// chain). Unused admissions and idle participants free their slots first.
+    pub async fn handle_process_waitlist(
+        contract: &mut crate::Game2048Contract,
+        leaderboard_id: String,
+    ) {
+        let current_time = contract.runtime.system_time().micros();
+        let leaderboard = contract
+            .state
+            .leaderboards
+            .load_entry_mut("")
+            .await
+            .unwrap();
+        if *leaderboard.leaderboard_id.get() != leaderboard_id {
+            panic!("Waitlist is processed on the tournament's leaderboard chain");
+        }
 
-                if !settings.name.is_empty() {
-                    leaderboard.name.set(settings.name.clone());
+        let mut expired = Vec::new();
+        leaderboard
+            .waitlist_admitted
+            .for_each_index_value(|player, admitted_at| {
+                if current_time.saturating_sub(*admitted_at)
+                    >= WAITLIST_ADMISSION_TIMEOUT_SECONDS * 1_000_000
+                {
+                    expired.push(player);
                 }
+                Ok(())
+            })
+            .await
+            .unwrap();
+        for player in expired {
+            leaderboard.waitlist_admitted.remove(&player).unwrap();
+            let _ = leaderboard.waitlist_chain_ids.remove(&player);
+        }
 
-                if let Some(desc) = settings.description.clone() {
-                    leaderboard.description.set(desc);
+        // Participants who never scored free their slot after the admission timeout, those
+        // who stopped scoring after `WAITLIST_INACTIVE_TIMEOUT_SECONDS`
+        let mut idle = Vec::new();
+        leaderboard
+            .participants
+            .for_each_index_value(|player, last_active| {
+                let idle_micros = current_time.saturating_sub(*last_active);
+                if idle_micros >= WAITLIST_ADMISSION_TIMEOUT_SECONDS * 1_000_000 {
+                    idle.push((player, idle_micros));
                 }
+                Ok(())
+            })
+            .await
+            .unwrap();
+        for (player, idle_micros) in idle {
+            let has_scored = leaderboard.score.contains_key(&player).await.unwrap();
+            if !has_scored || idle_micros >= WAITLIST_INACTIVE_TIMEOUT_SECONDS * 1_000_000 {
+                leaderboard.participants.remove(&player).unwrap();
+            }
+        }
 
-                // Store times: None -> 0 (unlimited), Some(value) -> value
-                leaderboard.start_time.set(start_time.unwrap_or(0));
-                leaderboard.end_time.set(end_time.unwrap_or(0));
-
-                if action == LeaderboardAction::Create {
-                    let chain_id_str = chain_id.to_string();
-                    leaderboard.leaderboard_id.set(chain_id_str.clone());
-                    leaderboard.chain_id.set(chain_id_str);
-                    leaderboard.host.set(player.clone());
-
-                    // Set base triggerer count from settings (default: 5)
-                    let base_triggerer_count = settings.base_triggerer_count.unwrap_or(5);
-                    leaderboard
-                        .admin_base_triggerer_count
-                        .set(base_triggerer_count);
-
-                    // Create shard chains from main chain (ONLY on creation)
-                    let shard_number = settings.shard_number.unwrap_or(1);
-                    let mut created_shard_ids = Vec::new();
+        let mut waitlist = contract
+            .state
+            .waitlist
+            .get(&leaderboard_id)
+            .await
+            .unwrap()
+            .unwrap_or_default();
+        let free_slots = match *leaderboard.max_participants.get() {
+            None => waitlist.len(),
+            Some(max_participants) => {
+                let participants = leaderboard.participants.count().await.unwrap() as u32;
+                let admitted = leaderboard.waitlist_admitted.count().await.unwrap() as u32;
+                max_participants.saturating_sub(participants + admitted) as usize
+            }
+        };
 
-                    for _ in 0..shard_number {
-                        let shard_chain_ownership = contract.runtime.chain_ownership();
-                        let shard_app_id = contract.runtime.application_id().forget_abi();
-                        let shard_application_permissions =
-                            ApplicationPermissions::new_single(shard_app_id);
-                        let shard_amount = Amount::from_tokens(1);
-                        let shard_id = contract.runtime.open_chain(
-                            shard_chain_ownership,
-                            shard_application_permissions,
-                            shard_amount,
-                        );
+        let mut admitted_chains = Vec::new();
+        for (player, _) in waitlist.drain(..free_slots.min(waitlist.len())) {
+            leaderboard
+                .waitlist_admitted
+                .insert(&player, current_time)
+                .unwrap();
+            if let Some(chain_id) = leaderboard.waitlist_chain_ids.get(&player).await.unwrap() {
+                admitted_chains.push(chain_id);
+            }
+        }
+        if waitlist.is_empty() {
+            contract.state.waitlist.remove(&leaderboard_id).unwrap();
+        } else {
+            contract
+                .state
+                .waitlist
+                .insert(&leaderboard_id, waitlist)
+                .unwrap();
+        }
 
-                        created_shard_ids.push(shard_id.to_string());
+        for chain_id in admitted_chains {
+            if let Ok(chain_id) = ChainId::from_str(&chain_id) {
+                contract.send_message(
+                    chain_id,
+                    Message::AdmittedFromWaitlist {
+                        leaderboard_id: leaderboard_id.clone(),
+                    },
+                );
+            }
+        }
+    }
 
-                        // Send CreateLeaderboard message to each shard
-                        contract
-                            .runtime
-                            .prepare_message(Message::CreateLeaderboard {
-                                leaderboard_id: chain_id.to_string(),
-                                name: settings.name.clone(),
-                                description: settings.description.clone(),
-                                chain_id: chain_id.to_string(),
-                                host: player.clone(),
-                                start_time: start_time.unwrap_or(0),
-                                end_time: end_time.unwrap_or(0),
-                                shard_ids: vec![], // Shards don't need shard IDs
-                                base_triggerer_count,
-                                total_shard_count: shard_number,
-                            })
-                            .send_to(shard_id);
-                    }
+    /// 📡 LIVE STREAM: Record relay delivery result (runs on the leaderboard chain)
+    /// Only the endpoint's relay owner can report. Endpoints with 3+ consecutive failures are
+    /// removed
+    pub async fn handle_report_stream_delivery(
+        contract: &mut crate::Game2048Contract,
+        endpoint_url: String,
+        success: bool,
+    ) {
+        let signer = contract
+            .runtime
+            .authenticated_signer()
+            .map(|owner| owner.to_string());
+        let leaderboard = contract
+            .state
+            .leaderboards
+            .load_entry_mut("")
+            .await
+            .unwrap();
 
-                    // Update main chain leaderboard list with shard info
-                    let main_leaderboard = contract
-                        .state
-                        .leaderboards
-                        .load_entry_mut(&chain_id.to_string())
-                        .await
-                        .unwrap();
+        let mut endpoints = leaderboard.stream_endpoints.get().clone();
+        let Some(endpoint) = endpoints
+            .iter_mut()
+            .find(|endpoint| endpoint.endpoint_url == endpoint_url)
+        else {
+            panic!("Stream endpoint not registered");
+        };
+        if signer.as_ref() != Some(&endpoint.relay_owner) {
+            panic!("Only the endpoint's relay can report its deliveries");
+        }
 
-                    for shard_id in &created_shard_ids {
-                        main_leaderboard.shard_ids.push_back(shard_id.clone());
-                    }
-                    main_leaderboard
-                        .current_shard_id
-                        .set(created_shard_ids.first().cloned().unwrap_or_default());
+        if success {
+            endpoint.consecutive_failures = 0;
+        } else {
+            endpoint.consecutive_failures += 1;
+        }
+        endpoints.retain(|endpoint| endpoint.consecutive_failures < 3);
+        leaderboard.stream_endpoints.set(endpoints);
+    }
 
-                    // Send CreateLeaderboard message to new leaderboard chain with shard IDs
-                    contract
-                        .runtime
-                        .prepare_message(Message::CreateLeaderboard {
-                            leaderboard_id: chain_id.to_string(),
-                            name: settings.name.clone(),
-                            description: settings.description.clone(),
-                            chain_id: chain_id.to_string(),
-                            host: player.clone(),
-                            start_time: start_time.unwrap_or(0),
-                            end_time: end_time.unwrap_or(0),
-                            shard_ids: created_shard_ids.clone(),
-                            base_triggerer_count,
-                            total_shard_count: shard_number,
-                        })
-                        .send_to(chain_id);
+    /// 🔮 SPECTATOR: Forward a winner prediction to the leaderboard chain
+    /// Predictions are only accepted before the tournament ends
+    pub async fn handle_submit_prediction(
+        contract: &mut crate::Game2048Contract,
+        spectator_chain_id: String,
+        leaderboard_id: String,
+        predicted_winner: String,
+        predicted_score: u64,
+    ) {
+        if spectator_chain_id != contract.runtime.chain_id().to_string() {
+            panic!("Predictions must be submitted from the spectator's own chain");
+        }
+        if predicted_winner.trim().is_empty() {
+            panic!("Predicted winner cannot be empty");
+        }
 
-                    // Main chain: emit updated active tournaments registry
-                    if is_main_chain {
-                        contract.emit_active_tournaments().await;
-                    }
-                } else if action == LeaderboardAction::Update {
-                    // For updates, just send message to existing leaderboard chain (no shard creation)
-                    let base_triggerer_count = settings.base_triggerer_count.unwrap_or(5);
-                    let shard_number = settings.shard_number.unwrap_or(1);
-                    contract
-                        .runtime
-                        .prepare_message(Message::CreateLeaderboard {
-                            leaderboard_id: chain_id.to_string(),
-                            name: settings.name.clone(),
-                            description: settings.description.clone(),
-                            chain_id: chain_id.to_string(),
-                            host: player.clone(),
-                            start_time: start_time.unwrap_or(0),
-                            end_time: end_time.unwrap_or(0),
-                            shard_ids: vec![], // No shard changes on update
-                            base_triggerer_count,
-                            total_shard_count: shard_number,
-                        })
-                        .send_to(chain_id);
+        let current_time = contract.runtime.system_time().micros();
 
-                    // Main chain: emit updated active tournaments registry
-                    if is_main_chain {
-                        contract.emit_active_tournaments().await;
-                    }
-                }
+        // Reject early if the cached tournament has already ended
+        if let Some(tournament) = contract.get_cached_tournament(&leaderboard_id).await {
+            if tournament.is_ended(current_time) {
+                panic!("Tournament has already ended");
             }
-            LeaderboardAction::Delete => {
-                if leaderboard.leaderboard_id.get().is_empty() {
-                    panic!("Cannot delete the main leaderboard");
-                }
+        }
 
-                contract
-                    .state
-                    .leaderboards
-                    .remove_entry(&leaderboard_id)
-                    .unwrap();
-            }
-            LeaderboardAction::TogglePin => {
-                if !is_mod {
-                    panic!("Only admin can pin event");
-                }
+        let leaderboard_chain_id = ChainId::from_str(&leaderboard_id)
+            .unwrap_or_else(|_| panic!("Invalid leaderboard ID format"));
+
+        contract.send_message(
+            leaderboard_chain_id,
+            Message::SubmitPrediction {
+                spectator_chain_id,
+                predicted_winner,
+                predicted_score,
+                timestamp: current_time,
+            },
+        );
+    }
 
-                leaderboard.is_pinned.set(!*leaderboard.is_pinned.get());
-            }
+    /// 🗑️ Remove a leaderboard and its cached tournament info, then republish active tournaments
+    pub async fn delete_leaderboard(contract: &mut crate::Game2048Contract, leaderboard_id: &str) {
+        if leaderboard_id.is_empty() {
+            panic!("Cannot delete the main leaderboard");
+        }
+        let exists = contract
+            .state
+            .leaderboards
+            .try_load_entry(leaderboard_id)
+            .await
+            .unwrap()
+            .is_some_and(|leaderboard| !leaderboard.leaderboard_id.get().is_empty());
+        if !exists {
+            panic!("Leaderboard not found");
         }
+
+        contract
+            .state
+            .leaderboards
+            .remove_entry(leaderboard_id)
+            .unwrap();
+        contract
+            .state
+            .tournaments_cache_json
+            .remove(leaderboard_id)
+            .unwrap();
+        contract.emit_active_tournaments().await;
     }
 
     /// Emit current active tournaments (for leaderboard chains)
@@ -360,6 +1632,39 @@ impl LeaderboardOperationHandler {
                                 start_time,
                                 end_time,
                                 total_players: *leaderboard.total_players.get(),
+                                entry_token_requirement: leaderboard
+                                    .entry_token_requirement
+                                    .get()
+                                    .clone(),
+                                bonus_windows: leaderboard.active_bonus_windows.get().clone(),
+                                anti_cheat: leaderboard.anti_cheat_config.get().clone(),
+                                replay_mode: *leaderboard.replay_mode.get(),
+                                streak_bonus_tiers: leaderboard.streak_bonus_tiers.get().clone(),
+                                allow_variant_conversion: *leaderboard
+                                    .allow_variant_conversion
+                                    .get(),
+                                prize_pool_entries: leaderboard.prize_pool_entries.get().clone(),
+                                pow_difficulty: *leaderboard.pow_difficulty.get(),
+                                max_score_per_player: *leaderboard.max_score_per_player.get(),
+                                warmup_duration_seconds: *leaderboard.warmup_duration_seconds.get(),
+                                undo_budget: *leaderboard.undo_budget.get(),
+                                undo_history_size: *leaderboard.undo_history_size.get(),
+                                variant: *leaderboard.variant.get(),
+                                tile_weights: leaderboard.tile_weights.get().clone(),
+                                combo_multiplier_enabled: *leaderboard
+                                    .combo_multiplier_enabled
+                                    .get(),
+                                max_moves_per_call: *leaderboard.max_moves_per_call.get(),
+                                min_move_interval_micros: *leaderboard
+                                    .min_move_interval_micros
+                                    .get(),
+                                new_board_cooldown_seconds: *leaderboard
+                                    .new_board_cooldown_seconds
+                                    .get(),
+                                power_ups: *leaderboard.power_ups.get(),
+                                mode: *leaderboard.mode.get(),
+                                max_participants: *leaderboard.max_participants.get(),
+                                cloned_from: leaderboard.cloned_from.get().clone(),
                             };
                             tournaments.push(tournament_info);
                         }
@@ -411,6 +1716,28 @@ impl LeaderboardOperationHandler {
                         start_time,
                         end_time,
                         total_players: *leaderboard.total_players.get(),
+                        entry_token_requirement: leaderboard.entry_token_requirement.get().clone(),
+                        bonus_windows: leaderboard.active_bonus_windows.get().clone(),
+                        anti_cheat: leaderboard.anti_cheat_config.get().clone(),
+                        replay_mode: *leaderboard.replay_mode.get(),
+                        streak_bonus_tiers: leaderboard.streak_bonus_tiers.get().clone(),
+                        allow_variant_conversion: *leaderboard.allow_variant_conversion.get(),
+                        prize_pool_entries: leaderboard.prize_pool_entries.get().clone(),
+                        pow_difficulty: *leaderboard.pow_difficulty.get(),
+                        max_score_per_player: *leaderboard.max_score_per_player.get(),
+                        warmup_duration_seconds: *leaderboard.warmup_duration_seconds.get(),
+                        undo_budget: *leaderboard.undo_budget.get(),
+                        undo_history_size: *leaderboard.undo_history_size.get(),
+                        variant: *leaderboard.variant.get(),
+                        tile_weights: leaderboard.tile_weights.get().clone(),
+                        combo_multiplier_enabled: *leaderboard.combo_multiplier_enabled.get(),
+                        max_moves_per_call: *leaderboard.max_moves_per_call.get(),
+                        min_move_interval_micros: *leaderboard.min_move_interval_micros.get(),
+                        new_board_cooldown_seconds: *leaderboard.new_board_cooldown_seconds.get(),
+                        power_ups: *leaderboard.power_ups.get(),
+                        mode: *leaderboard.mode.get(),
+                        max_participants: *leaderboard.max_participants.get(),
+                        cloned_from: leaderboard.cloned_from.get().clone(),
                     };
                     tournaments.push(tournament_info);
                 }
@@ -585,23 +1912,23 @@ impl LeaderboardOperationHandler {
             return Err("No shards registered for this leaderboard".to_string());
         }
 
-        for shard_id_str in shard_ids {
-            if let Ok(shard_chain_id) = ChainId::from_str(&shard_id_str) {
-                contract
-                    .runtime
-                    .prepare_message(Message::TriggerShardAggregation {
-                        timestamp: current_time, // Use current time
-                    })
-                    .send_to(shard_chain_id);
-            }
-        }
-
         // Update aggregation counter for monitoring
         let rotation_counter = *leaderboard.trigger_rotation_counter.get();
         leaderboard
             .trigger_rotation_counter
             .set(rotation_counter + 1);
 
+        for shard_id_str in shard_ids {
+            if let Ok(shard_chain_id) = ChainId::from_str(&shard_id_str) {
+                contract.send_message(
+                    shard_chain_id,
+                    Message::TriggerShardAggregation {
+                        timestamp: current_time, // Use current time
+                    },
+                );
+            }
+        }
+
         Ok(())
     }
 }
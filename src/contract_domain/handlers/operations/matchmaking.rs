@@ -0,0 +1,168 @@
+//! Matchmaking Operations Handler
+//!
+//! Main-chain pool that pairs players of similar skill rating for head-to-head games.
+
+use crate::state::MatchmakingMatch;
+use game2048::{MatchmakingEntry, DEFAULT_SKILL_RATING, MAX_DEFAULT_TIME_LIMIT};
+
+pub struct MatchmakingOperationHandler;
+
+impl MatchmakingOperationHandler {
+    /// Add the player to the pool (any earlier match is forgotten)
+    pub async fn handle_enter_matchmaking(
+        contract: &mut crate::Game2048Contract,
+        player: String,
+        password_hash: String,
+        preferred_time_limit: u32,
+    ) {
+        contract
+            .validate_player_password(&player, &password_hash)
+            .await;
+
+        if !contract.is_main_chain() {
+            panic!("Only main chain can handle matchmaking");
+        }
+        if preferred_time_limit == 0 || preferred_time_limit > MAX_DEFAULT_TIME_LIMIT {
+            panic!(
+                "Preferred time limit must be between 1 and {} seconds",
+                MAX_DEFAULT_TIME_LIMIT
+            );
+        }
+
+        let player_entry = contract
+            .state
+            .players
+            .load_entry_mut(&player)
+            .await
+            .unwrap();
+        let player_chain_id = player_entry.chain_id.get().clone();
+        let skill_rating = match *player_entry.skill_rating.get() {
+            0 => DEFAULT_SKILL_RATING,
+            rating => rating,
+        };
+
+        let current_time = contract.runtime.system_time().micros();
+        if let Some(existing) = contract
+            .state
+            .matchmaking_pool
+            .get(&player_chain_id)
+            .await
+            .unwrap()
+        {
+            if current_time < existing.expires_at() {
+                panic!("Already waiting in the matchmaking pool");
+            }
+        }
+
+        contract
+            .state
+            .matchmaking_matches
+            .remove(&player_chain_id)
+            .unwrap();
+        contract
+            .state
+            .matchmaking_pool
+            .insert(
+                &player_chain_id,
+                MatchmakingEntry {
+                    player,
+                    skill_rating,
+                    preferred_time_limit,
+                    entered_at: current_time,
+                },
+            )
+            .unwrap();
+    }
+
+    /// Drop entries that waited too long, then pair the rest by rating (moderators only)
+    pub async fn handle_process_matchmaking(
+        contract: &mut crate::Game2048Contract,
+        admin: String,
+        password_hash: String,
+    ) {
+        contract
+            .validate_player_password(&admin, &password_hash)
+            .await;
+
+        if !contract.is_main_chain() {
+            panic!("Only main chain can handle matchmaking");
+        }
+        let is_mod = *contract
+            .state
+            .players
+            .load_entry_mut(&admin)
+            .await
+            .unwrap()
+            .is_mod
+            .get();
+        if !is_mod {
+            panic!("Only admin can process matchmaking");
+        }
+
+        let current_time = contract.runtime.system_time().micros();
+        let mut waiting = Vec::new();
+        let mut expired = Vec::new();
+        contract
+            .state
+            .matchmaking_pool
+            .for_each_index_value(|player_chain_id, entry| {
+                if current_time >= entry.expires_at() {
+                    expired.push(player_chain_id);
+                } else {
+                    waiting.push((player_chain_id, entry.into_owned()));
+                }
+                Ok(())
+            })
+            .await
+            .unwrap();
+        for player_chain_id in expired {
+            contract
+                .state
+                .matchmaking_pool
+                .remove(&player_chain_id)
+                .unwrap();
+        }
+
+        for (first_key, second_key) in MatchmakingEntry::pair(&waiting) {
+            let find = |key: &str| {
+                waiting
+                    .iter()
+                    .find(|(player_chain_id, _)| player_chain_id == key)
+                    .map(|(_, entry)| entry.clone())
+                    .unwrap()
+            };
+            let (first, second) = (find(&first_key), find(&second_key));
+            let time_limit = first.preferred_time_limit.min(second.preferred_time_limit);
+
+            for (key, entry, opponent, opponent_key) in [
+                (&first_key, &first, &second, &second_key),
+                (&second_key, &second, &first, &first_key),
+            ] {
+                contract.state.matchmaking_pool.remove(key).unwrap();
+                contract
+                    .state
+                    .matchmaking_matches
+                    .insert(
+                        key,
+                        MatchmakingMatch {
+                            opponent: opponent.player.clone(),
+                            opponent_chain_id: opponent_key.clone(),
+                            time_limit,
+                            matched_at: current_time,
+                        },
+                    )
+                    .unwrap();
+                Self::record_wait(contract, current_time.saturating_sub(entry.entered_at));
+            }
+        }
+    }
+
+    /// Moving average of how long matched players waited (for wait estimates)
+    fn record_wait(contract: &mut crate::Game2048Contract, wait: u64) {
+        let average = match *contract.state.matchmaking_average_wait.get() {
+            0 => wait,
+            average => (average * 3 + wait) / 4,
+        };
+        contract.state.matchmaking_average_wait.set(average);
+    }
+}
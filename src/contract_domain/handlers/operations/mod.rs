@@ -2,17 +2,25 @@
 //!
 //! Business logic handlers for operations organized by domain.
 
+pub mod admin;
 pub mod game;
 pub mod leaderboard;
+pub mod matchmaking;
+pub mod moderation;
 pub mod player;
 pub mod shard;
 pub mod system;
+pub mod team;
 pub mod tournament;
 
 // Re-export handlers for easier access
+pub use admin::AdminOperationHandler;
 pub use game::GameOperationHandler;
 pub use leaderboard::LeaderboardOperationHandler;
+pub use matchmaking::MatchmakingOperationHandler;
+pub use moderation::ModerationOperationHandler;
 pub use player::PlayerOperationHandler;
 pub use shard::ShardOperationHandler;
 pub use system::SystemOperationHandler;
+pub use team::TeamOperationHandler;
 pub use tournament::TournamentOperationHandler;
@@ -0,0 +1,213 @@
+//! Moderation Operations Handler
+//!
+//! Content checks for tournament names and descriptions, and super-admin management of the blocked-word list.
+
+use crate::state::{ModerationAuditEntry, ModerationConfig};
+use game2048::{FlagVerdict, LeaderboardSettings, Message};
+use linera_sdk::linera_base_types::ChainId;
+use std::str::FromStr;
+
+/// Longest single word allowed in moderated text
+const MAX_WORD_LENGTH: usize = 50;
+
+pub struct ContentCheckResult {
+    pub is_clean: bool,
+    pub violations: Vec<String>,
+}
+
+pub struct ContentModerator;
+
+impl ContentModerator {
+    /// Check free text: control characters, overly long words and blocked words
+    pub fn check(text: &str, config: &ModerationConfig) -> ContentCheckResult {
+        let mut violations = Vec::new();
+
+        if text.chars().any(|c| c.is_control()) {
+            violations.push("control characters".to_string());
+        }
+
+        if text
+            .split_whitespace()
+            .any(|word| word.chars().count() > MAX_WORD_LENGTH)
+        {
+            violations.push(format!("word longer than {} characters", MAX_WORD_LENGTH));
+        }
+
+        let lowercase = text.to_lowercase();
+        let words: Vec<&str> = lowercase
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .collect();
+        for blocked in &config.blocked_words {
+            if words.contains(&blocked.to_lowercase().as_str()) {
+                violations.push(format!("blocked word: {}", blocked));
+            }
+        }
+
+        ContentCheckResult {
+            is_clean: violations.is_empty(),
+            violations,
+        }
+    }
+
+    /// Check a tournament name: same as `check`, and no URLs
+    pub fn check_name(text: &str, config: &ModerationConfig) -> ContentCheckResult {
+        let mut result = Self::check(text, config);
+
+        let lowercase = text.to_lowercase();
+        if lowercase.contains("://") || lowercase.contains("www.") {
+            result.violations.push("URL in name".to_string());
+            result.is_clean = false;
+        }
+
+        result
+    }
+}
+
+pub struct ModerationOperationHandler;
+
+impl ModerationOperationHandler {
+    /// 🛡️ Reject a tournament name/description that fails moderation.
+    /// Moderators can bypass with `moderation_override`, which is recorded in the audit log.
+    pub async fn enforce_leaderboard_content(
+        contract: &mut crate::Game2048Contract,
+        leaderboard_id: &str,
+        settings: &LeaderboardSettings,
+        player: &str,
+        is_mod: bool,
+    ) {
+        let config = contract.state.moderation_config.get().clone();
+        let name_result = ContentModerator::check_name(&settings.name, &config);
+        let description_result = settings
+            .description
+            .as_ref()
+            .map(|description| ContentModerator::check(description, &config));
+
+        if name_result.is_clean
+            && description_result
+                .as_ref()
+                .is_none_or(|result| result.is_clean)
+        {
+            return;
+        }
+
+        let mut violations = name_result.violations;
+        if let Some(result) = description_result {
+            violations.extend(result.violations);
+        }
+
+        if !is_mod || !settings.moderation_override.unwrap_or(false) {
+            panic!("Content rejected by moderation: {}", violations.join(", "));
+        }
+
+        let timestamp = contract.runtime.system_time().micros();
+        contract
+            .state
+            .moderation_audit_log
+            .push_back(ModerationAuditEntry {
+                admin: player.to_string(),
+                leaderboard_id: leaderboard_id.to_string(),
+                violations,
+                timestamp,
+            });
+    }
+
+    /// Replace the blocked-word list (super-admin only, main chain)
+    pub async fn handle_update_blocked_words(
+        contract: &mut crate::Game2048Contract,
+        blocked_words: Vec<String>,
+        player: String,
+        password_hash: String,
+    ) {
+        contract
+            .validate_player_password(&player, &password_hash)
+            .await;
+
+        if player != "lpaydat" {
+            panic!("Only lpaydat can update the blocked-word list");
+        }
+        if !contract.is_main_chain() {
+            panic!("Only main chain can update the blocked-word list");
+        }
+
+        let blocked_words = blocked_words
+            .into_iter()
+            .map(|word| word.trim().to_lowercase())
+            .filter(|word| !word.is_empty())
+            .collect();
+        contract
+            .state
+            .moderation_config
+            .set(ModerationConfig { blocked_words });
+    }
+
+    /// 🚩 Record an admin verdict on a flagged game (admin, main chain).
+    /// A confirmed cheat zeroes the player's score on the leaderboard chain if it came from that board.
+    pub async fn handle_review_flagged_game(
+        contract: &mut crate::Game2048Contract,
+        board_id: String,
+        admin: String,
+        password_hash: String,
+        verdict: FlagVerdict,
+    ) {
+        contract
+            .validate_player_password(&admin, &password_hash)
+            .await;
+        if !contract.is_main_chain() {
+            panic!("Only main chain can review flagged games");
+        }
+        let is_mod = *contract
+            .state
+            .players
+            .load_entry_or_insert(&admin)
+            .await
+            .unwrap()
+            .is_mod
+            .get();
+        if !is_mod {
+            panic!("Only admin can review flagged games");
+        }
+
+        let Some(mut entry) = contract.state.admin_flags.get(&board_id).await.unwrap() else {
+            panic!("Flagged game not found");
+        };
+        if entry.verdict.is_some() {
+            panic!("Flagged game was already reviewed");
+        }
+        entry.verdict = Some(verdict);
+        entry.reviewed_by = admin;
+
+        if verdict == FlagVerdict::Confirmed {
+            if let Ok(leaderboard_chain_id) = ChainId::from_str(&entry.leaderboard_id) {
+                contract.send_message(
+                    leaderboard_chain_id,
+                    Message::ScoreInvalidated {
+                        player: entry.player.clone(),
+                        board_id: board_id.clone(),
+                    },
+                );
+            }
+        }
+        contract.state.admin_flags.insert(&board_id, entry).unwrap();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_content_moderator_check() {
+        let config = ModerationConfig {
+            blocked_words: vec!["spam".to_string()],
+        };
+
+        assert!(ContentModerator::check_name("Weekend Cup", &config).is_clean);
+        assert!(!ContentModerator::check_name("Join www.example.com", &config).is_clean);
+        assert!(ContentModerator::check("See https://example.com", &config).is_clean);
+        assert!(!ContentModerator::check("Free SPAM here", &config).is_clean);
+        assert!(ContentModerator::check("Spammer league", &config).is_clean);
+        assert!(!ContentModerator::check("line\u{7}bell", &config).is_clean);
+        assert!(!ContentModerator::check(&"a".repeat(51), &config).is_clean);
+    }
+}
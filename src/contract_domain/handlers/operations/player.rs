@@ -2,7 +2,15 @@
 //!
 //! Handles player-related operations including registration, authentication, and admin management.
 
-use game2048::{Message, RegistrationCheck};
+use crate::contract_domain::handlers::operations::AdminOperationHandler;
+use crate::contract_domain::{ChainIdValidator, SubscriptionManager};
+use crate::state::ClaimedChain;
+use game2048::{
+    check_password_change_token, check_profanity, next_password_change_secret,
+    password_change_token, password_change_token_hash, ExportedScore, GameError, Message,
+    PlayerDataExport, PlayerPreferences, RegistrationCheck, MAX_DEFAULT_TIME_LIMIT, MAX_TOP_BOARDS,
+    PASSWORD_CHANGE_TOKEN_TTL_SECONDS,
+};
 use linera_sdk::linera_base_types::{Amount, ApplicationPermissions, ChainId};
 use std::str::FromStr;
 
@@ -13,10 +21,11 @@ impl PlayerOperationHandler {
         contract: &mut crate::Game2048Contract,
         username: String,
         password_hash: String,
-    ) {
+    ) -> Result<(), GameError> {
         if username.trim().is_empty() {
             panic!("Username cannot be empty");
         }
+        check_profanity("username", &username, &[])?;
         let is_main_chain = contract.is_main_chain();
         if !is_main_chain {
             panic!("Only main chain can register player");
@@ -28,42 +37,41 @@ impl PlayerOperationHandler {
 
         // 🚀 CHAIN POOL: Try to claim a pre-created chain from the pool first
         // This is much faster than creating a new chain on-demand
-        let chain_id = if let Some(pooled_chain_id) =
-            contract.state.unclaimed_chains.front().await.unwrap()
-        {
-            // Pop the chain from the pool
-            contract.state.unclaimed_chains.delete_front();
-            // Parse the chain ID
-            ChainId::from_str(&pooled_chain_id).expect("Invalid chain ID in pool")
-        } else {
-            // Fallback: Pool is empty - create a new chain AND refill pool with 50 more
-            let chain_ownership = contract.runtime.chain_ownership();
-            let application_permissions = ApplicationPermissions::default();
-            let amount = Amount::from_tokens(1);
-
-            // Create one chain for this registration
-            let new_chain_id = contract.runtime.open_chain(
-                chain_ownership.clone(),
-                application_permissions.clone(),
-                amount,
-            );
+        let chain_id =
+            if let Some(pooled_chain_id) = contract.state.unclaimed_chains.front().await.unwrap() {
+                // Pop the chain from the pool
+                contract.state.unclaimed_chains.delete_front();
+                // Parse the chain ID
+                ChainIdValidator::parse(&pooled_chain_id)
+            } else {
+                // Fallback: Pool is empty - create a new chain AND refill pool with 50 more
+                let chain_ownership = contract.runtime.chain_ownership();
+                let application_permissions = ApplicationPermissions::default();
+                let amount = Amount::from_tokens(1);
 
-            // 🚀 AUTO-REFILL: Create 50 more chains to refill the pool
-            // This ensures we don't hit the slow path repeatedly
-            for _ in 0..50 {
-                let pool_chain_id = contract.runtime.open_chain(
+                // Create one chain for this registration
+                let new_chain_id = contract.runtime.open_chain(
                     chain_ownership.clone(),
                     application_permissions.clone(),
                     amount,
                 );
-                contract
-                    .state
-                    .unclaimed_chains
-                    .push_back(pool_chain_id.to_string());
-            }
 
-            new_chain_id
-        };
+                // 🚀 AUTO-REFILL: Create 50 more chains to refill the pool
+                // This ensures we don't hit the slow path repeatedly
+                for _ in 0..50 {
+                    let pool_chain_id = contract.runtime.open_chain(
+                        chain_ownership.clone(),
+                        application_permissions.clone(),
+                        amount,
+                    );
+                    contract
+                        .state
+                        .unclaimed_chains
+                        .push_back(pool_chain_id.to_string());
+                }
+
+                new_chain_id
+            };
 
         let player = contract
             .state
@@ -75,11 +83,27 @@ impl PlayerOperationHandler {
         player.password_hash.set(password_hash.clone());
         player.chain_id.set(chain_id.to_string());
 
+        // ♻️ Track the claim until the player chain reports it is initialized
+        let claim_timestamp = contract.runtime.system_time().micros();
+        contract
+            .state
+            .claimed_chains
+            .insert(
+                &chain_id.to_string(),
+                ClaimedChain {
+                    username: username.clone(),
+                    claim_timestamp,
+                    initialized: false,
+                },
+            )
+            .unwrap();
+
         // 🚀 NEW: Set up cross-chain subscription for new player chain
         // Player chains should subscribe to main chain's active_tournaments stream
         let main_chain_id = contract.runtime.application_creator_chain_id();
 
         // Send message to new player chain to subscribe to main chain's tournament events
+        crate::contract_domain::ContractHelpers::record_message_sent(contract);
         contract
             .runtime
             .prepare_message(Message::SubscribeToMainChain {
@@ -89,6 +113,7 @@ impl PlayerOperationHandler {
             .send_to(chain_id);
 
         contract.register_player(chain_id, &username, &password_hash);
+        Ok(())
     }
 
     pub async fn handle_toggle_admin(
@@ -110,6 +135,7 @@ impl PlayerOperationHandler {
         if !is_main_chain {
             panic!("Only main chain can toggle admin");
         }
+        AdminOperationHandler::reject_if_multisig_configured(contract, "ToggleAdmin");
 
         contract
             .check_player_registered(&username, RegistrationCheck::EnsureRegistered)
@@ -142,10 +168,10 @@ impl PlayerOperationHandler {
 
         match check {
             RegistrationCheck::EnsureRegistered if !is_registered => {
-                panic!("Player not registered");
+                GameError::PlayerNotRegistered.raise();
             }
             RegistrationCheck::EnsureNotRegistered if is_registered => {
-                panic!("Player already registered");
+                GameError::PlayerAlreadyRegistered.raise();
             }
             _ => {}
         }
@@ -166,7 +192,7 @@ impl PlayerOperationHandler {
         )
         .await;
         if stored_password_hash != provided_password_hash {
-            panic!("Invalid password");
+            GameError::InvalidPassword.raise();
         }
     }
 
@@ -187,16 +213,432 @@ impl PlayerOperationHandler {
 
         // Parse leaderboard_id as chain ID and send TriggerUpdate message
         if let Ok(leaderboard_chain_id) = ChainId::from_str(&leaderboard_id) {
-            contract
-                .runtime
-                .prepare_message(Message::TriggerUpdate {
+            contract.send_message(
+                leaderboard_chain_id,
+                Message::TriggerUpdate {
                     triggerer_chain_id: my_chain_id,
                     tournament_id: leaderboard_id.clone(),
                     timestamp: current_time,
-                })
-                .send_to(leaderboard_chain_id);
+                },
+            );
         } else {
             panic!("Invalid leaderboard ID format");
         }
     }
+
+    /// ⚙️ Update the player's preferences on their player chain
+    pub async fn handle_update_preferences(
+        contract: &mut crate::Game2048Contract,
+        player: String,
+        password_hash: String,
+        preferences: PlayerPreferences,
+    ) {
+        contract
+            .validate_player_password(&player, &password_hash)
+            .await;
+
+        if preferences
+            .default_time_limit
+            .is_some_and(|limit| limit > MAX_DEFAULT_TIME_LIMIT)
+        {
+            panic!(
+                "Default time limit cannot exceed {} seconds",
+                MAX_DEFAULT_TIME_LIMIT
+            );
+        }
+        if preferences
+            .top_boards_capacity
+            .is_some_and(|capacity| capacity == 0 || capacity > MAX_TOP_BOARDS)
+        {
+            panic!(
+                "Best game history must keep between 1 and {} games",
+                MAX_TOP_BOARDS
+            );
+        }
+
+        contract
+            .state
+            .player_preferences
+            .insert(&player, preferences)
+            .unwrap();
+    }
+
+    /// 👫 FRIENDS: Follow a player; the main chain resolves their chain so we can subscribe
+    pub async fn handle_add_friend(
+        contract: &mut crate::Game2048Contract,
+        friend_username: String,
+        player: String,
+        password_hash: String,
+    ) {
+        contract
+            .validate_player_password(&player, &password_hash)
+            .await;
+
+        if friend_username.trim().is_empty() {
+            panic!("Friend username cannot be empty");
+        }
+        if friend_username == player {
+            panic!("You cannot add yourself as a friend");
+        }
+        if contract
+            .state
+            .friends
+            .contains(&friend_username)
+            .await
+            .unwrap()
+        {
+            panic!("{} is already a friend", friend_username);
+        }
+
+        contract.state.friends.insert(&friend_username).unwrap();
+        let main_chain_id = contract.runtime.application_creator_chain_id();
+        contract.send_message(main_chain_id, Message::FriendRequest { friend_username });
+    }
+
+    /// 👫 FRIENDS: Stop following a player and forget their scores
+    pub async fn handle_remove_friend(
+        contract: &mut crate::Game2048Contract,
+        friend_username: String,
+        player: String,
+        password_hash: String,
+    ) {
+        contract
+            .validate_player_password(&player, &password_hash)
+            .await;
+
+        if !contract
+            .state
+            .friends
+            .contains(&friend_username)
+            .await
+            .unwrap()
+        {
+            panic!("{} is not a friend", friend_username);
+        }
+        contract.state.friends.remove(&friend_username).unwrap();
+        contract
+            .state
+            .friend_scores
+            .remove(&friend_username)
+            .unwrap();
+
+        let friend_chain_id = contract
+            .state
+            .friend_chain_ids
+            .get(&friend_username)
+            .await
+            .unwrap();
+        contract
+            .state
+            .friend_chain_ids
+            .remove(&friend_username)
+            .unwrap();
+        let Some(friend_chain_id) = friend_chain_id.and_then(|id| ChainId::from_str(&id).ok())
+        else {
+            return;
+        };
+        SubscriptionManager::unsubscribe_from_friend_updates(contract, friend_chain_id);
+        let subscriber_chain_id = contract.runtime.chain_id().to_string();
+        contract.send_message(
+            friend_chain_id,
+            Message::FriendSubscription {
+                subscriber_chain_id,
+                subscribe: false,
+            },
+        );
+    }
+
+    /// 🔑 PASSWORD: Issue a single-use change token (main chain); it replaces any earlier one
+    pub async fn handle_request_password_change(
+        contract: &mut crate::Game2048Contract,
+        player: String,
+        old_password_hash: String,
+    ) -> String {
+        if !contract.is_main_chain() {
+            panic!("Only main chain can change passwords");
+        }
+        contract
+            .validate_player_password(&player, &old_password_hash)
+            .await;
+
+        let current_time = contract.runtime.system_time().micros();
+        let secret = next_password_change_secret(
+            contract.state.password_change_secret.get(),
+            contract.state.randomness_beacon.get().beacon_value,
+            &player,
+            current_time,
+        );
+        contract.state.password_change_secret.set(secret);
+        let change_token = password_change_token(&secret);
+        let expires_at = current_time + PASSWORD_CHANGE_TOKEN_TTL_SECONDS * 1_000_000;
+        contract
+            .state
+            .pending_password_changes
+            .insert(
+                &player,
+                (password_change_token_hash(&change_token), expires_at),
+            )
+            .unwrap();
+        change_token
+    }
+
+    /// 🔑 PASSWORD: Apply a new password hash if the token matches and has not expired,
+    /// then update the copy on the player's chain
+    pub async fn handle_confirm_password_change(
+        contract: &mut crate::Game2048Contract,
+        player: String,
+        change_token: String,
+        new_password_hash: String,
+    ) {
+        if !contract.is_main_chain() {
+            panic!("Only main chain can change passwords");
+        }
+        if new_password_hash.trim().is_empty() {
+            panic!("Password hash cannot be empty");
+        }
+        let pending = contract
+            .state
+            .pending_password_changes
+            .get(&player)
+            .await
+            .unwrap();
+        let current_time = contract.runtime.system_time().micros();
+        if let Err(reason) =
+            check_password_change_token(pending.as_ref(), &change_token, current_time)
+        {
+            panic!("{}", reason);
+        }
+        // Single use
+        contract
+            .state
+            .pending_password_changes
+            .remove(&player)
+            .unwrap();
+
+        let player_state = contract
+            .state
+            .players
+            .load_entry_mut(&player)
+            .await
+            .unwrap();
+        player_state.password_hash.set(new_password_hash.clone());
+        let player_chain_id = ChainId::from_str(player_state.chain_id.get()).ok();
+
+        if let Some(player_chain_id) = player_chain_id {
+            contract.send_message(
+                player_chain_id,
+                Message::PasswordChanged {
+                    player,
+                    password_hash: new_password_hash,
+                },
+            );
+        }
+    }
+
+    /// 🧳 MIGRATION: Point the player at a new chain, register them there, retire the old
+    /// chain and tell every leaderboard and shard chain
+    pub async fn handle_migrate_player(
+        contract: &mut crate::Game2048Contract,
+        old_player: String,
+        new_chain_id: String,
+        old_password_hash: String,
+        new_password_hash: String,
+    ) {
+        if !contract.is_main_chain() {
+            panic!("Only main chain can migrate players");
+        }
+        contract
+            .validate_player_password(&old_player, &old_password_hash)
+            .await;
+        if new_password_hash.trim().is_empty() {
+            panic!("Password hash cannot be empty");
+        }
+        let new_chain = ChainIdValidator::parse(&new_chain_id);
+        let new_chain_id = new_chain.to_string();
+        if contract
+            .state
+            .migrated_players
+            .contains_key(&new_chain_id)
+            .await
+            .unwrap()
+        {
+            panic!("Cannot migrate to a retired chain");
+        }
+        if let Some(claim) = contract
+            .state
+            .claimed_chains
+            .get(&new_chain_id)
+            .await
+            .unwrap()
+        {
+            if claim.username != old_player {
+                panic!("Chain belongs to another player");
+            }
+        }
+
+        let player_state = contract
+            .state
+            .players
+            .load_entry_mut(&old_player)
+            .await
+            .unwrap();
+        let old_chain_id = player_state.chain_id.get().clone();
+        if old_chain_id == new_chain_id {
+            panic!("Player already uses this chain");
+        }
+        player_state.chain_id.set(new_chain_id.clone());
+        player_state.password_hash.set(new_password_hash.clone());
+        contract
+            .state
+            .migrated_players
+            .insert(&old_chain_id, new_chain_id.clone())
+            .unwrap();
+
+        // Same setup as a new registration, on the player's own chain
+        let main_chain_id = contract.runtime.application_creator_chain_id();
+        crate::contract_domain::ContractHelpers::record_message_sent(contract);
+        contract
+            .runtime
+            .prepare_message(Message::SubscribeToMainChain {
+                main_chain_id: main_chain_id.to_string(),
+            })
+            .with_tracking() // Ensure application is deployed on target chain
+            .send_to(new_chain);
+        contract.register_player(new_chain, &old_player, &new_password_hash);
+
+        // Leaderboard chains stop taking the old chain's scores; shard chains also move
+        // their per-chain board counts
+        let mut leaderboard_chains = Vec::new();
+        let mut shard_chains = Vec::new();
+        for leaderboard_id in contract.state.leaderboards.indices().await.unwrap() {
+            let Ok(leaderboard_chain) = ChainId::from_str(&leaderboard_id) else {
+                continue;
+            };
+            leaderboard_chains.push(leaderboard_chain);
+            let shard_ids = contract
+                .state
+                .leaderboards
+                .load_entry_mut(&leaderboard_id)
+                .await
+                .unwrap()
+                .shard_ids
+                .elements()
+                .await
+                .unwrap();
+            shard_chains.extend(
+                shard_ids
+                    .iter()
+                    .filter_map(|shard_id| ChainId::from_str(shard_id).ok()),
+            );
+        }
+
+        let new_player_record = game2048::PlayerMigrationRecord {
+            username: old_player.clone(),
+            old_chain_id: old_chain_id.clone(),
+            new_chain_id: new_chain_id.clone(),
+            migrated_at: contract.runtime.system_time().micros(),
+        };
+        for chain_id in leaderboard_chains.iter().chain(&shard_chains) {
+            contract.send_message(
+                *chain_id,
+                Message::PlayerMigration {
+                    old_player: old_player.clone(),
+                    new_chain_id: new_chain_id.clone(),
+                    new_player_record: new_player_record.clone(),
+                },
+            );
+        }
+        for chain_id in shard_chains {
+            contract.send_message(
+                chain_id,
+                Message::ReassignBoards {
+                    player: old_player.clone(),
+                    old_chain_id: old_chain_id.clone(),
+                    new_chain_id: new_chain_id.clone(),
+                },
+            );
+        }
+    }
+
+    /// 📦 The player's registration, best games, scores in active tournaments and total moves
+    /// on this chain, as JSON with a SHA-256 hash of the payload
+    pub async fn handle_export_player_data(
+        contract: &mut crate::Game2048Contract,
+        player: String,
+        password_hash: String,
+    ) -> String {
+        contract
+            .validate_player_password(&player, &password_hash)
+            .await;
+        let generated_at = contract.runtime.system_time().micros();
+
+        let player_state = contract
+            .state
+            .players
+            .load_entry_mut(&player)
+            .await
+            .unwrap();
+        let chain_id = player_state.chain_id.get().clone();
+        let is_mod = *player_state.is_mod.get();
+        let top_boards = player_state.top_boards.get().clone();
+
+        let mut active_scores = Vec::new();
+        let mut tournaments = Vec::new();
+        contract
+            .state
+            .tournaments_cache_json
+            .for_each_index_value(|leaderboard_id, tournament_json| {
+                if let Ok(tournament) =
+                    serde_json::from_str::<game2048::TournamentInfo>(&tournament_json)
+                {
+                    if tournament
+                        .end_time
+                        .is_none_or(|end_time| end_time > generated_at)
+                    {
+                        tournaments.push(leaderboard_id);
+                    }
+                }
+                Ok(())
+            })
+            .await
+            .unwrap();
+        let player_record = contract
+            .state
+            .player_records
+            .load_entry_mut(&player)
+            .await
+            .unwrap();
+        for leaderboard_id in tournaments {
+            if let Some(best_score) = player_record.best_score.get(&leaderboard_id).await.unwrap() {
+                active_scores.push(ExportedScore {
+                    leaderboard_id,
+                    best_score,
+                });
+            }
+        }
+
+        let mut total_moves = 0u64;
+        for board_id in contract.state.boards.indices().await.unwrap() {
+            let board = contract
+                .state
+                .boards
+                .load_entry_mut(&board_id)
+                .await
+                .unwrap();
+            if *board.player.get() == player {
+                total_moves += *board.move_count.get() as u64;
+            }
+        }
+
+        PlayerDataExport {
+            username: player,
+            chain_id,
+            is_mod,
+            top_boards,
+            active_scores,
+            total_moves,
+            generated_at,
+        }
+        .to_json_with_hash()
+    }
 }
@@ -1,9 +1,16 @@
 //! Shard Operations Handler
 //!
-//! Handles shard-related operations including score aggregation and activity tracking.
+//! Handles shard-related operations including score aggregation, activity tracking and
+//! dead letter retries.
 
-use game2048::{ActiveBoardSummary, GameStatus, PlayerScoreSummary};
+use crate::contract_domain::handlers::messages::ShardMessageHandler;
+use crate::contract_domain::ContractHelpers;
+use game2048::{
+    ActiveBoardSummary, GameStatus, Message, PlayerScoreSummary, ScoreUpdateEntry,
+    MAX_DEAD_LETTER_RETRIES,
+};
 use linera_sdk::linera_base_types::ChainId;
+use std::str::FromStr;
 
 pub struct ShardOperationHandler;
 
@@ -97,6 +104,12 @@ impl ShardOperationHandler {
                     .unwrap_or(GameStatus::Active); // Fallback to Active if not stored
 
                 let active_boards = active_boards_by_player.remove(&player).unwrap_or_default();
+                let avg_move_time_micros = shard
+                    .avg_move_times
+                    .get(&player)
+                    .await
+                    .unwrap()
+                    .unwrap_or(0);
 
                 // Create summary from cached data
                 let summary = PlayerScoreSummary {
@@ -107,8 +120,13 @@ impl ShardOperationHandler {
                     highest_tile,
                     last_update: current_time,
                     game_status,
-                    boards_in_tournament: board_count,
+                    boards_per_leaderboard: [(leaderboard_id.clone(), board_count)].into(),
                     active_boards,
+                    avg_move_time_micros,
+                    efficiency: 0,
+                    best_efficiency_board_id: String::new(),
+                    first_achievement_time: 0,
+                    time_to_target_micros: None,
                 };
 
                 player_summaries.insert(player.clone(), summary);
@@ -124,9 +142,12 @@ impl ShardOperationHandler {
 
             // Build player board counts map for this tournament
             let mut player_board_counts = std::collections::HashMap::new();
-            for (_player, summary) in player_summaries.iter() {
+            let mut player_efficiency_scores = std::collections::HashMap::new();
+            for (player, summary) in player_summaries.iter() {
                 // Extract player chain ID and use their board count
-                player_board_counts.insert(summary.chain_id.clone(), summary.boards_in_tournament);
+                player_board_counts
+                    .insert(summary.chain_id.clone(), summary.boards_in(&leaderboard_id));
+                player_efficiency_scores.insert(player.clone(), summary.efficiency);
             }
 
             use crate::contract_domain::events::emitters::EventEmitter;
@@ -138,6 +159,7 @@ impl ShardOperationHandler {
                 player_summaries.clone(),
                 std::collections::HashMap::new(), // Empty for MVP simplicity
                 player_board_counts,              // Board counts for distributed counting
+                player_efficiency_scores,
                 timestamp,
                 player_summaries.len() as u32,
                 leaderboard_id,
@@ -145,6 +167,11 @@ impl ShardOperationHandler {
             .await;
         }
 
+        // 📦 Send players whose score changed since the last cycle, batched per message
+        if !player_summaries.is_empty() {
+            Self::send_bulk_score_updates(contract, &player_summaries).await;
+        }
+
         // Update local shard state with comprehensive tracking
         if !player_summaries.is_empty() {
             let shard = contract.state.shards.load_entry_mut("").await.unwrap();
@@ -160,6 +187,11 @@ impl ShardOperationHandler {
                         .board_ids
                         .insert(player, summary.board_id.clone())
                         .unwrap();
+                    // Summaries the compact form cannot hold losslessly keep only the full data
+                    match summary.compress() {
+                        Ok(compact) => shard.compact_scores.insert(player, compact).unwrap(),
+                        Err(_) => shard.compact_scores.remove(player).unwrap(),
+                    }
 
                     if summary.best_score > current_score {
                         shard_updated_players += 1;
@@ -179,6 +211,53 @@ impl ShardOperationHandler {
         }
     }
 
+    /// 📦 Batch pending score changes into `BulkScoreUpdate` messages to the leaderboard chain
+    async fn send_bulk_score_updates(
+        contract: &mut crate::Game2048Contract,
+        player_summaries: &std::collections::HashMap<String, PlayerScoreSummary>,
+    ) {
+        let shard = contract.state.shards.load_entry_mut("").await.unwrap();
+        let Ok(leaderboard_chain_id) = ChainId::from_str(shard.leaderboard_id.get()) else {
+            return;
+        };
+
+        let mut pending = Vec::new();
+        for (player, summary) in player_summaries {
+            let sent_score = shard.bulk_sent_scores.get(player).await.unwrap();
+            if sent_score == Some(summary.best_score) {
+                continue;
+            }
+            shard
+                .bulk_sent_scores
+                .insert(player, summary.best_score)
+                .unwrap();
+            pending.push(ScoreUpdateEntry {
+                player: player.clone(),
+                board_id: summary.board_id.clone(),
+                score: summary.best_score,
+                is_end: matches!(summary.game_status, GameStatus::Ended(_)),
+                highest_tile: summary.highest_tile,
+                timestamp: summary.last_update,
+            });
+        }
+
+        // 🔑 Authenticated: the leaderboard checks the block signer against the shard's key
+        let shard_id = contract.runtime.chain_id().to_string();
+        let batch_timestamp = contract.runtime.system_time().micros();
+        for scores in ScoreUpdateEntry::batches(pending) {
+            ContractHelpers::record_message_sent(contract);
+            contract
+                .runtime
+                .prepare_message(Message::BulkScoreUpdate {
+                    scores,
+                    shard_id: shard_id.clone(),
+                    batch_timestamp,
+                })
+                .with_authentication()
+                .send_to(leaderboard_chain_id);
+        }
+    }
+
     /// Update game count when games are created/ended
     pub async fn track_game_activity(contract: &mut crate::Game2048Contract) {
         let shard = contract.state.shards.load_entry_mut("").await.unwrap();
@@ -189,4 +268,53 @@ impl ShardOperationHandler {
             .last_activity
             .set(contract.runtime.system_time().micros());
     }
+
+    /// 📮 Re-process a dead letter (admin only, shard chain). Successful retries leave the
+    /// queue; failed ones count an attempt until `MAX_DEAD_LETTER_RETRIES` is reached.
+    pub async fn handle_retry_dead_letter(
+        contract: &mut crate::Game2048Contract,
+        key: String,
+        admin: String,
+        password_hash: String,
+    ) {
+        contract
+            .validate_player_password(&admin, &password_hash)
+            .await;
+
+        let player = contract
+            .state
+            .players
+            .load_entry_or_insert(&admin)
+            .await
+            .unwrap();
+
+        if !player.is_mod.get() {
+            panic!("Only admins can retry dead letters");
+        }
+
+        let Some(mut entry) = contract.state.dead_letter_queue.get(&key).await.unwrap() else {
+            panic!("Dead letter not found");
+        };
+        if entry.permanently_failed {
+            panic!("Dead letter has permanently failed");
+        }
+
+        let message: Message = linera_sdk::bcs::from_bytes(&entry.message_bytes)
+            .expect("Dead letter message cannot be decoded");
+        match ShardMessageHandler::process(contract, &message).await {
+            Ok(()) => {
+                contract.state.dead_letter_queue.remove(&key).unwrap();
+            }
+            Err(failure_reason) => {
+                entry.failure_reason = failure_reason;
+                entry.attempts += 1;
+                entry.permanently_failed = entry.attempts >= MAX_DEAD_LETTER_RETRIES;
+                contract
+                    .state
+                    .dead_letter_queue
+                    .insert(&key, entry)
+                    .unwrap();
+            }
+        }
+    }
 }
@@ -1,4 +1,6 @@
-use game2048::Message;
+use crate::contract_domain::ChainIdValidator;
+use crate::state::ShardKeyRotation;
+use game2048::{Message, DEFAULT_UNCLAIMED_TIMEOUT_HOURS, MAX_PLATFORM_SUBSCRIBERS};
 /// System Operations Handler
 ///
 /// Handles system-level operations including faucet, shard management, and chain operations.
@@ -20,13 +22,13 @@ impl SystemOperationHandler {
         let app_chain_id = contract.runtime.application_creator_chain_id();
         let chain_id = contract.runtime.chain_id();
 
-        contract
-            .runtime
-            .prepare_message(Message::Transfer {
+        contract.send_message(
+            app_chain_id,
+            Message::Transfer {
                 chain_id,
                 amount: Amount::from_tokens(1),
-            })
-            .send_to(app_chain_id);
+            },
+        );
     }
 
     pub async fn handle_new_shard(contract: &mut crate::Game2048Contract) {
@@ -67,7 +69,7 @@ impl SystemOperationHandler {
     }
 
     pub fn handle_close_chain(contract: &mut crate::Game2048Contract, chain_id: String) {
-        let chain_id = ChainId::from_str(&chain_id).unwrap();
+        let chain_id = ChainIdValidator::parse(&chain_id);
         let account = Account {
             chain_id,
             owner: AccountOwner::CHAIN,
@@ -129,6 +131,36 @@ impl SystemOperationHandler {
         }
     }
 
+    /// 📊 Reset resource usage counters for a new billing cycle (admin only)
+    pub async fn handle_reset_resource_usage(
+        contract: &mut crate::Game2048Contract,
+        admin: String,
+        password_hash: String,
+    ) {
+        contract
+            .validate_player_password(&admin, &password_hash)
+            .await;
+
+        let player = contract
+            .state
+            .players
+            .load_entry_or_insert(&admin)
+            .await
+            .unwrap();
+
+        if !player.is_mod.get() {
+            panic!("Only admins can reset resource usage");
+        }
+
+        contract
+            .state
+            .resource_usage
+            .set(crate::state::ResourceUsage {
+                last_reset: contract.runtime.system_time().micros(),
+                ..Default::default()
+            });
+    }
+
     // ============================================
     // CHAIN POOL OPERATIONS
     // ============================================
@@ -153,6 +185,12 @@ impl SystemOperationHandler {
             panic!("Count must be at most 500 per call");
         }
 
+        // ♻️ Reuse stale claims first when the pool is running low
+        let pool_size = contract.state.unclaimed_chains.count() as u32;
+        if pool_size < *contract.state.chain_pool_low_threshold.get() {
+            Self::recover_stale_chains(contract).await;
+        }
+
         // Create chains and add to pool
         let chain_ownership = contract.runtime.chain_ownership();
         let application_permissions = ApplicationPermissions::default();
@@ -169,5 +207,209 @@ impl SystemOperationHandler {
                 .unclaimed_chains
                 .push_back(chain_id.to_string());
         }
+
+        let total_created = *contract.state.chain_pool_total_created.get();
+        contract
+            .state
+            .chain_pool_total_created
+            .set(total_created.saturating_add(count));
+        let current_time = contract.runtime.system_time().micros();
+        contract.state.chain_pool_last_refill_at.set(current_time);
+    }
+
+    /// 🚀 Refill the pool up to `min_pool_size` when it runs below it (main chain).
+    /// Contracts cannot schedule operations, so this runs RefillChainPool in place.
+    pub async fn auto_refill_chain_pool(contract: &mut crate::Game2048Contract) {
+        if !contract.is_main_chain() {
+            return;
+        }
+        let available = contract.state.unclaimed_chains.count() as u32;
+        let min_pool_size = *contract.state.chain_pool_min_size.get();
+        if available >= min_pool_size {
+            return;
+        }
+        let count = (min_pool_size - available).min(500);
+        Self::handle_refill_chain_pool(contract, count).await;
+    }
+
+    /// 🔑 ADMIN: Record a shard's new public key and send it to the shard's leaderboard chain.
+    /// The key pair is generated off-chain; the shard chain signs its blocks with the secret key.
+    pub async fn handle_rotate_shard_key(
+        contract: &mut crate::Game2048Contract,
+        admin: String,
+        password_hash: String,
+        leaderboard_id: String,
+        shard_chain_id: String,
+        public_key: [u8; 32],
+    ) {
+        contract
+            .validate_player_password(&admin, &password_hash)
+            .await;
+
+        if !contract.is_main_chain() {
+            panic!("Only main chain can regenerate shard keys");
+        }
+        let is_mod = *contract
+            .state
+            .players
+            .load_entry_mut(&admin)
+            .await
+            .unwrap()
+            .is_mod
+            .get();
+        if !is_mod {
+            panic!("Only admin can regenerate shard keys");
+        }
+        let leaderboard_chain = ChainIdValidator::parse(&leaderboard_id);
+        let shard_ids = contract
+            .state
+            .leaderboards
+            .load_entry_mut(&leaderboard_id)
+            .await
+            .unwrap()
+            .shard_ids
+            .elements()
+            .await
+            .unwrap();
+        if !shard_ids.contains(&shard_chain_id) {
+            panic!("Shard does not belong to this leaderboard");
+        }
+
+        let timestamp = contract.runtime.system_time().micros();
+        contract
+            .state
+            .shard_key_rotations
+            .push_back(ShardKeyRotation {
+                shard_chain_id: shard_chain_id.clone(),
+                admin,
+                public_key,
+                timestamp,
+            });
+        contract.send_message(
+            leaderboard_chain,
+            Message::RegisterShardKey {
+                shard_chain_id,
+                public_key,
+            },
+        );
+    }
+
+    /// 🎲 Join the main chain's current randomness beacon round
+    pub fn handle_contribute_to_beacon(
+        contract: &mut crate::Game2048Contract,
+        shard_chain_id: ChainId,
+    ) {
+        if shard_chain_id != contract.runtime.chain_id() {
+            panic!("Beacon contributions must be sent from the contributing chain");
+        }
+
+        let main_chain_id = contract.runtime.application_creator_chain_id();
+        contract.send_message(
+            main_chain_id,
+            Message::BeaconContribution { shard_chain_id },
+        );
+    }
+
+    /// ♻️ ADMIN: Return chains that were claimed but never initialized to the pool
+    pub async fn handle_recover_stale_chains(
+        contract: &mut crate::Game2048Contract,
+        admin: String,
+        password_hash: String,
+    ) {
+        contract
+            .validate_player_password(&admin, &password_hash)
+            .await;
+
+        if !contract.is_main_chain() {
+            panic!("Only main chain can recover stale chains");
+        }
+        let is_mod = *contract
+            .state
+            .players
+            .load_entry_mut(&admin)
+            .await
+            .unwrap()
+            .is_mod
+            .get();
+        if !is_mod {
+            panic!("Only admin can recover stale chains");
+        }
+
+        Self::recover_stale_chains(contract).await;
+    }
+
+    /// Move claims older than the timeout that never initialized back to `unclaimed_chains`
+    pub async fn recover_stale_chains(contract: &mut crate::Game2048Contract) {
+        let timeout_hours = match *contract.state.unclaimed_timeout_hours.get() {
+            0 => DEFAULT_UNCLAIMED_TIMEOUT_HOURS,
+            hours => hours,
+        };
+        let cutoff = contract
+            .runtime
+            .system_time()
+            .micros()
+            .saturating_sub(timeout_hours as u64 * 3_600_000_000);
+
+        let mut stale = Vec::new();
+        contract
+            .state
+            .claimed_chains
+            .for_each_index_value(|chain_id, claim| {
+                if !claim.initialized && claim.claim_timestamp < cutoff {
+                    stale.push(chain_id);
+                }
+                Ok(())
+            })
+            .await
+            .unwrap();
+        if stale.is_empty() {
+            return;
+        }
+
+        let count = stale.len() as u32;
+        for chain_id in stale {
+            contract.state.claimed_chains.remove(&chain_id).unwrap();
+            contract.state.unclaimed_chains.push_back(chain_id);
+        }
+
+        use crate::contract_domain::events::emitters::EventEmitter;
+        let total_available = contract.state.unclaimed_chains.count() as u32;
+        EventEmitter::emit_chains_recovered(contract, count, total_available).await;
+    }
+
+    // ============================================
+    // PLATFORM EVENTS
+    // ============================================
+
+    /// 🌟 Add a chain to the platform highlight fan-out (main chain)
+    pub async fn handle_subscribe_platform_events(
+        contract: &mut crate::Game2048Contract,
+        subscriber_chain_id: String,
+    ) {
+        if !contract.is_main_chain() {
+            panic!("Only main chain accepts platform event subscriptions");
+        }
+        let chain_id = ChainIdValidator::parse(&subscriber_chain_id);
+        if contract
+            .state
+            .platform_subscribers
+            .contains(&chain_id)
+            .await
+            .unwrap()
+        {
+            return;
+        }
+        let subscriber_count = contract.state.platform_subscribers.count().await.unwrap();
+        if subscriber_count >= MAX_PLATFORM_SUBSCRIBERS {
+            panic!(
+                "Platform events already have the maximum of {} subscribers",
+                MAX_PLATFORM_SUBSCRIBERS
+            );
+        }
+        contract
+            .state
+            .platform_subscribers
+            .insert(&chain_id)
+            .unwrap();
     }
 }
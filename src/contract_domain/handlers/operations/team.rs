@@ -0,0 +1,209 @@
+//! Team Operations Handler
+//!
+//! Collaborative games: one board on the host player's chain, moved by every team member.
+//! Members forward their moves to the host chain, which syncs the board back to them.
+
+use crate::contract_domain::handlers::operations::GameOperationHandler;
+use crate::state::TeamGame;
+use game2048::{validate_invite_codes, Direction, Game, GameVariant, Message};
+use linera_sdk::linera_base_types::ChainId;
+use std::str::FromStr;
+
+pub struct TeamOperationHandler;
+
+impl TeamOperationHandler {
+    /// Create the shared board on this chain, with one invite code per invited player
+    pub async fn handle_create_team_game(
+        contract: &mut crate::Game2048Contract,
+        team_name: String,
+        player: String,
+        password_hash: String,
+        invite_codes: Vec<String>,
+    ) {
+        contract
+            .validate_player_password(&player, &password_hash)
+            .await;
+        if team_name.trim().is_empty() {
+            panic!("Team name cannot be empty");
+        }
+        validate_invite_codes(&invite_codes).unwrap_or_else(|error| panic!("{}", error));
+
+        let timestamp = contract.runtime.system_time().micros() / 1000;
+        let board_id =
+            GameOperationHandler::create_board(contract, &player, timestamp, "", 0, 0, -1).await;
+        contract
+            .state
+            .boards
+            .load_entry_mut(&board_id)
+            .await
+            .unwrap()
+            .variant
+            .set(GameVariant::Collaborative {
+                team_size: invite_codes.len() as u8 + 1,
+            });
+        contract
+            .state
+            .team_games
+            .insert(
+                &board_id,
+                TeamGame {
+                    team_name,
+                    host: player,
+                    members: Vec::new(),
+                    open_invite_codes: invite_codes,
+                    score_share: 0,
+                },
+            )
+            .unwrap();
+    }
+
+    /// Ask the host chain (the chain prefix of the board ID) to add this chain's player
+    pub async fn handle_join_team_game(
+        contract: &mut crate::Game2048Contract,
+        team_board_id: String,
+        invite_code: String,
+        player: String,
+        password_hash: String,
+    ) {
+        contract
+            .validate_player_password(&player, &password_hash)
+            .await;
+        let host_chain_id = Self::host_chain_id(&team_board_id)
+            .unwrap_or_else(|| panic!("Invalid team board ID format"));
+        if host_chain_id == contract.runtime.chain_id() {
+            panic!("Team games are joined from another player chain");
+        }
+
+        contract.send_message(
+            host_chain_id,
+            Message::JoinTeam {
+                team_board_id,
+                invite_code,
+                player,
+            },
+        );
+    }
+
+    /// 👥 MakeMoves on a team board: moves of the host are applied here, moves of members
+    /// are forwarded to the host chain. Returns false when `board_id` is not a team board.
+    pub async fn route_team_moves(
+        contract: &mut crate::Game2048Contract,
+        board_id: &str,
+        player: &str,
+        directions: Vec<Direction>,
+    ) -> bool {
+        if let Some(membership) = contract.state.team_memberships.get(board_id).await.unwrap() {
+            if membership.is_ended {
+                panic!("Game is ended");
+            }
+            let host_chain_id = ChainId::from_str(&membership.host_chain_id).unwrap();
+            for direction in directions {
+                contract.send_message(
+                    host_chain_id,
+                    Message::TeamMove {
+                        team_board_id: board_id.to_string(),
+                        direction,
+                        player: player.to_string(),
+                    },
+                );
+            }
+            return true;
+        }
+
+        let Some(team) = contract.state.team_games.get(board_id).await.unwrap() else {
+            return false;
+        };
+        if team.host != player {
+            panic!("You can only make move on your own board");
+        }
+        for direction in directions {
+            Self::apply_team_move(contract, board_id, direction).await;
+        }
+        Self::sync_team_board(contract, board_id).await;
+        true
+    }
+
+    /// Apply one move to a team board; ignored once the game has ended
+    pub async fn apply_team_move(
+        contract: &mut crate::Game2048Contract,
+        board_id: &str,
+        direction: Direction,
+    ) {
+        let current_time = contract.runtime.system_time().micros();
+        let board = contract
+            .state
+            .boards
+            .load_entry_mut(board_id)
+            .await
+            .unwrap();
+        if *board.is_ended.get() {
+            return;
+        }
+        let move_count = *board.move_count.get();
+        let mut game = Game {
+            board: *board.board.get(),
+            board_id: board_id.to_string(),
+            username: board.player.get().clone(),
+            // Several moves can share a block, so the move count keeps spawns distinct
+            timestamp: current_time + move_count as u64,
+            spawns: board.spawn_distribution(),
+        };
+        let new_board = game.execute(direction);
+        if new_board == *board.board.get() {
+            return;
+        }
+        board.board.set(new_board);
+        board.score.set(Game::score(new_board));
+        board.move_count.set(move_count + 1);
+        if Game::is_ended(new_board) {
+            board.is_ended.set(true);
+            board.is_deadlocked.set(true);
+        }
+    }
+
+    /// Send the board to every member; once it has ended, split the score equally
+    pub async fn sync_team_board(contract: &mut crate::Game2048Contract, board_id: &str) {
+        let Some(mut team) = contract.state.team_games.get(board_id).await.unwrap() else {
+            return;
+        };
+        let board = contract
+            .state
+            .boards
+            .load_entry_mut(board_id)
+            .await
+            .unwrap();
+        let tiles = *board.board.get();
+        let score = *board.score.get();
+        let is_ended = *board.is_ended.get();
+
+        if is_ended && team.score_share == 0 {
+            team.score_share = score / (team.members.len() as u64 + 1);
+            contract
+                .state
+                .team_games
+                .insert(board_id, team.clone())
+                .unwrap();
+        }
+
+        for member in &team.members {
+            if let Ok(member_chain_id) = ChainId::from_str(&member.chain_id) {
+                contract.send_message(
+                    member_chain_id,
+                    Message::TeamBoardSync {
+                        team_board_id: board_id.to_string(),
+                        tiles,
+                        score,
+                        is_ended,
+                        score_share: team.score_share,
+                    },
+                );
+            }
+        }
+    }
+
+    /// Host chain of a team board: board IDs start with the chain that created them
+    pub fn host_chain_id(team_board_id: &str) -> Option<ChainId> {
+        let (chain_id, _) = team_board_id.split_once('.')?;
+        ChainId::from_str(chain_id).ok()
+    }
+}
@@ -2,7 +2,9 @@
 //!
 //! Handles tournament-related operations including validation and shard selection.
 
-use game2048::hash_seed;
+use crate::contract_domain::ChainIdValidator;
+use game2048::{hash_seed, BalanceAttestation, Message};
+use linera_sdk::linera_base_types::Amount;
 
 pub struct TournamentOperationHandler;
 
@@ -37,6 +39,26 @@ impl TournamentOperationHandler {
         }
     }
 
+    /// 🪙 TOKEN GATE: Attestation service chain vouches for a player chain's balance
+    /// The player chain only accepts it if this chain is the tournament's attestation chain
+    pub fn handle_attest_balance(
+        contract: &mut crate::Game2048Contract,
+        player_chain_id: String,
+        leaderboard_id: String,
+        held: Amount,
+        expires_at: u64,
+    ) {
+        let player_chain_id = ChainIdValidator::parse(&player_chain_id);
+
+        contract.send_message(
+            player_chain_id,
+            Message::BalanceAttestation {
+                leaderboard_id,
+                attestation: BalanceAttestation { held, expires_at },
+            },
+        );
+    }
+
     /// Select optimal shard for a tournament using hash-based distribution
     pub async fn select_optimal_shard(
         contract: &mut crate::Game2048Contract,
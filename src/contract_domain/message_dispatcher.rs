@@ -1,13 +1,15 @@
 //! Message Dispatcher
 //!
 //! Main dispatcher for routing messages directly to handlers.
-//! 
+//!
 //! 🚀 MESSAGE-BASED ARCHITECTURE: Score updates now use SubmitScore message
-//! directly from player chains to leaderboard chain. Shard-related messages
-//! are deprecated and made no-ops for backward compatibility.
+//! directly from player chains to leaderboard chain. Most shard-related messages
+//! are deprecated and made no-ops for backward compatibility; shard score messages
+//! that fail to process are kept in the dead letter queue.
 
 use crate::contract_domain::handlers::messages::{
-    GameMessageHandler, LeaderboardMessageHandler, PlayerMessageHandler, TransferMessageHandler,
+    BeaconMessageHandler, GameMessageHandler, LeaderboardMessageHandler, PlayerMessageHandler,
+    ShardMessageHandler, TeamMessageHandler, TransferMessageHandler,
 };
 use crate::Message;
 
@@ -17,11 +19,21 @@ pub struct MessageDispatcher;
 impl MessageDispatcher {
     /// Dispatch a message directly to the appropriate handler
     pub async fn dispatch(contract: &mut crate::Game2048Contract, message: Message) {
+        // 👣 VISITORS: Count player chain interactions with this leaderboard chain
+        if matches!(
+            message,
+            Message::SubmitScore { .. }
+                | Message::SubmitPrediction { .. }
+                | Message::TriggerUpdate { .. }
+        ) {
+            LeaderboardMessageHandler::record_visitor(contract).await;
+        }
+
         match message {
             // ═══════════════════════════════════════════════════════════════
             // ACTIVE MESSAGES (Message-based architecture)
             // ═══════════════════════════════════════════════════════════════
-            
+
             // Player registration
             Message::RegisterPlayer {
                 username,
@@ -46,6 +58,7 @@ impl MessageDispatcher {
                 start_time,
                 end_time,
                 rhythm_track_index,
+                tile_weights,
             } => {
                 GameMessageHandler::handle_create_new_board(
                     contract,
@@ -57,6 +70,7 @@ impl MessageDispatcher {
                     start_time,
                     end_time,
                     rhythm_track_index,
+                    tile_weights,
                 )
                 .await;
             }
@@ -99,9 +113,17 @@ impl MessageDispatcher {
                 highest_tile,
                 game_status,
                 timestamp,
-                boards_in_tournament,
+                boards_per_leaderboard,
                 start_time,
                 end_time,
+                streak_bonus_applied,
+                is_warmup,
+                average_branching_factor,
+                hints_used,
+                moves_count,
+                message_nonce,
+                tile_milestone_hit,
+                top_boards,
             } => {
                 LeaderboardMessageHandler::handle_submit_score(
                     contract,
@@ -112,9 +134,400 @@ impl MessageDispatcher {
                     highest_tile,
                     game_status,
                     timestamp,
-                    boards_in_tournament,
+                    boards_per_leaderboard,
                     start_time,
                     end_time,
+                    streak_bonus_applied,
+                    is_warmup,
+                    average_branching_factor,
+                    hints_used,
+                    moves_count,
+                    message_nonce,
+                    tile_milestone_hit,
+                    top_boards,
+                )
+                .await;
+            }
+
+            // 🔮 Spectator prediction stored on the leaderboard chain
+            Message::SubmitPrediction {
+                spectator_chain_id,
+                predicted_winner,
+                predicted_score,
+                timestamp,
+            } => {
+                LeaderboardMessageHandler::handle_submit_prediction(
+                    contract,
+                    spectator_chain_id,
+                    predicted_winner,
+                    predicted_score,
+                    timestamp,
+                )
+                .await;
+            }
+
+            // 📡 Live stream relay endpoint registration
+            Message::RegisterStreamEndpoint {
+                endpoint_url,
+                auth_token_hash,
+                relay_owner,
+                timestamp,
+            } => {
+                LeaderboardMessageHandler::handle_register_stream_endpoint(
+                    contract,
+                    endpoint_url,
+                    auth_token_hash,
+                    relay_owner,
+                    timestamp,
+                )
+                .await;
+            }
+
+            // ⏰ Bonus window scheduled by the main chain
+            Message::AddBonusWindow { window } => {
+                LeaderboardMessageHandler::handle_add_bonus_window(contract, window).await;
+            }
+
+            // ⚖️ Player handicap (leaderboard chain)
+            Message::SetPlayerHandicap { player, multiplier } => {
+                LeaderboardMessageHandler::handle_set_player_handicap(contract, player, multiplier)
+                    .await;
+            }
+
+            // 📦 Batched shard scores (leaderboard chain)
+            Message::BulkScoreUpdate {
+                scores,
+                shard_id,
+                batch_timestamp,
+            } => {
+                LeaderboardMessageHandler::handle_bulk_score_update(
+                    contract,
+                    scores,
+                    shard_id,
+                    batch_timestamp,
+                )
+                .await;
+            }
+
+            // 🔑 Shard signing keys
+            Message::RegisterShardKey {
+                shard_chain_id,
+                public_key,
+            } => {
+                LeaderboardMessageHandler::handle_register_shard_key(
+                    contract,
+                    shard_chain_id,
+                    public_key,
+                )
+                .await;
+            }
+
+            // 🏁 Warmup window (leaderboard chain)
+            Message::ConfigureWarmup {
+                warmup_duration_seconds,
+            } => {
+                LeaderboardMessageHandler::handle_configure_warmup(
+                    contract,
+                    warmup_duration_seconds,
+                )
+                .await;
+            }
+
+            // ⏳ Countdown announcements (leaderboard chain)
+            Message::ConfigureCountdown { thresholds } => {
+                LeaderboardMessageHandler::handle_configure_countdown(contract, thresholds).await;
+            }
+
+            // 🎟️ Qualifier rounds
+            Message::ConfigureQualifier { settings } => {
+                LeaderboardMessageHandler::handle_configure_qualifier(contract, settings).await;
+            }
+            Message::AddQualifiedPlayers {
+                players,
+                source_leaderboard_id,
+            } => {
+                LeaderboardMessageHandler::handle_add_qualified_players(
+                    contract,
+                    players,
+                    source_leaderboard_id,
+                )
+                .await;
+            }
+            Message::QualifiedForTournament { leaderboard_id } => {
+                PlayerMessageHandler::handle_qualified_for_tournament(contract, leaderboard_id)
+                    .await;
+            }
+            Message::PlayerRemoved {
+                leaderboard_id,
+                reason,
+            } => {
+                PlayerMessageHandler::handle_player_removed(contract, leaderboard_id, reason).await;
+            }
+
+            // 🎟️ Waitlist for full tournaments
+            Message::ConfigureMaxParticipants { max_participants } => {
+                LeaderboardMessageHandler::handle_configure_max_participants(
+                    contract,
+                    max_participants,
+                )
+                .await;
+            }
+            Message::JoinWaitlist { player, timestamp } => {
+                LeaderboardMessageHandler::handle_join_waitlist(contract, player, timestamp).await;
+            }
+            Message::AdmittedFromWaitlist { leaderboard_id } => {
+                PlayerMessageHandler::handle_admitted_from_waitlist(contract, leaderboard_id).await;
+            }
+            Message::RegisterParticipant { player, timestamp } => {
+                LeaderboardMessageHandler::handle_register_participant(contract, player, timestamp)
+                    .await;
+            }
+
+            // 🌟 Platform highlights (main chain fans out, subscribers keep the latest)
+            Message::PlatformHighlight { highlight } => {
+                LeaderboardMessageHandler::handle_platform_highlight(contract, highlight).await;
+            }
+
+            // 🧳 Player migrated to another chain
+            Message::PlayerMigration {
+                old_player,
+                new_chain_id,
+                new_player_record,
+            } => {
+                LeaderboardMessageHandler::handle_player_migration(
+                    contract,
+                    old_player,
+                    new_chain_id,
+                    new_player_record,
+                )
+                .await;
+            }
+            Message::PurgeInactivePlayers {
+                leaderboard_id,
+                inactive_threshold_seconds,
+            } => {
+                ShardMessageHandler::handle_purge_inactive_players(
+                    contract,
+                    leaderboard_id,
+                    inactive_threshold_seconds,
+                )
+                .await;
+            }
+            Message::ReassignBoards {
+                player,
+                old_chain_id,
+                new_chain_id,
+            } => {
+                ShardMessageHandler::handle_reassign_boards(
+                    contract,
+                    player,
+                    old_chain_id,
+                    new_chain_id,
+                )
+                .await;
+            }
+
+            // 📅 Daily challenge results (main chain)
+            Message::DailyChallengeScore { date, record } => {
+                LeaderboardMessageHandler::handle_daily_challenge_score(contract, date, record)
+                    .await;
+            }
+
+            // 🔑 Password changed on the main chain
+            Message::PasswordChanged {
+                player,
+                password_hash,
+            } => {
+                PlayerMessageHandler::handle_password_changed(contract, player, password_hash)
+                    .await;
+            }
+
+            // 👫 Friends
+            Message::FriendRequest { friend_username } => {
+                PlayerMessageHandler::handle_friend_request(contract, friend_username).await;
+            }
+            Message::FriendChainResolved {
+                friend_username,
+                friend_chain_id,
+            } => {
+                PlayerMessageHandler::handle_friend_chain_resolved(
+                    contract,
+                    friend_username,
+                    friend_chain_id,
+                )
+                .await;
+            }
+            Message::FriendSubscription {
+                subscriber_chain_id,
+                subscribe,
+            } => {
+                PlayerMessageHandler::handle_friend_subscription(
+                    contract,
+                    subscriber_chain_id,
+                    subscribe,
+                )
+                .await;
+            }
+
+            // 🏅 Prize pool (leaderboard chain)
+            Message::ConfigurePrizePool { entries } => {
+                LeaderboardMessageHandler::handle_configure_prize_pool(contract, entries).await;
+            }
+            Message::PrizePoolFunded {
+                application_id,
+                amount,
+                funder,
+            } => {
+                LeaderboardMessageHandler::handle_prize_pool_funded(
+                    contract,
+                    application_id,
+                    amount,
+                    funder,
+                )
+                .await;
+            }
+            Message::DistributePrizes => {
+                LeaderboardMessageHandler::handle_distribute_prizes(contract).await;
+            }
+            Message::FinaliseLeaderboard { reward_structure } => {
+                LeaderboardMessageHandler::handle_finalise_leaderboard(contract, reward_structure)
+                    .await;
+            }
+
+            // 🔁 Tournament replay (leaderboard chain)
+            // ⏸️ Scoring paused or resumed by the host
+            Message::SetLeaderboardPaused {
+                leaderboard_id,
+                paused,
+                reason,
+                timestamp,
+            } => {
+                LeaderboardMessageHandler::handle_set_leaderboard_paused(
+                    contract,
+                    leaderboard_id,
+                    paused,
+                    reason,
+                    timestamp,
+                )
+                .await;
+            }
+            Message::InitiateReplay => {
+                LeaderboardMessageHandler::handle_initiate_replay(contract).await;
+            }
+            Message::SubmitReplayScore { player, score, .. } => {
+                LeaderboardMessageHandler::handle_submit_replay_score(contract, player, score)
+                    .await;
+            }
+            Message::FinalizeReplay => {
+                LeaderboardMessageHandler::handle_finalize_replay(contract).await;
+            }
+
+            // 🥊 Single-elimination bracket
+            Message::StartBracket {
+                bracket_size,
+                round_duration_seconds,
+            } => {
+                LeaderboardMessageHandler::handle_start_bracket(
+                    contract,
+                    bracket_size,
+                    round_duration_seconds,
+                )
+                .await;
+            }
+            Message::BracketAdvancement {
+                leaderboard_id,
+                round,
+                loser,
+                next_matchup_id,
+                ..
+            } => {
+                PlayerMessageHandler::handle_bracket_advancement(
+                    contract,
+                    leaderboard_id,
+                    round,
+                    loser,
+                    next_matchup_id,
+                )
+                .await;
+            }
+
+            // 🔄 Round robin
+            Message::StartRoundRobin {
+                rounds,
+                round_duration_seconds,
+            } => {
+                LeaderboardMessageHandler::handle_start_round_robin(
+                    contract,
+                    rounds,
+                    round_duration_seconds,
+                )
+                .await;
+            }
+
+            // 👥 Team games: joins and moves on the host chain, board syncs on member chains
+            Message::JoinTeam {
+                team_board_id,
+                invite_code,
+                player,
+            } => {
+                TeamMessageHandler::handle_join_team(contract, team_board_id, invite_code, player)
+                    .await;
+            }
+            Message::TeamMove {
+                team_board_id,
+                direction,
+                player,
+            } => {
+                TeamMessageHandler::handle_team_move(contract, team_board_id, direction, player)
+                    .await;
+            }
+            Message::TeamBoardSync {
+                team_board_id,
+                tiles,
+                score,
+                is_ended,
+                score_share,
+            } => {
+                TeamMessageHandler::handle_team_board_sync(
+                    contract,
+                    team_board_id,
+                    tiles,
+                    score,
+                    is_ended,
+                    score_share,
+                )
+                .await;
+            }
+
+            // 💀 Elimination life lost on a player chain
+            Message::EliminationLifeLost {
+                board_id,
+                lives_remaining,
+                ..
+            } => {
+                LeaderboardMessageHandler::handle_elimination_life_lost(
+                    contract,
+                    board_id,
+                    lives_remaining,
+                )
+                .await;
+            }
+
+            // 🚩 Confirmed cheat: drop the score if it came from the flagged board
+            Message::ScoreInvalidated { player, board_id } => {
+                LeaderboardMessageHandler::handle_score_invalidated(contract, player, board_id)
+                    .await;
+            }
+
+            // 🪙 Token gate attestation from the attestation service chain
+            Message::BalanceAttestation {
+                leaderboard_id,
+                attestation,
+            } => {
+                PlayerMessageHandler::handle_balance_attestation(
+                    contract,
+                    leaderboard_id,
+                    attestation,
                 )
                 .await;
             }
@@ -124,23 +537,47 @@ impl MessageDispatcher {
                 PlayerMessageHandler::handle_subscribe_to_main_chain(contract, main_chain_id).await;
             }
 
+            Message::ChainInitialized => {
+                PlayerMessageHandler::handle_chain_initialized(contract).await;
+            }
+
+            // 🚩 Game flagged as a suspected cheat (main chain)
+            Message::GameFlagged {
+                board_id,
+                player,
+                leaderboard_id,
+                score,
+                reason,
+            } => {
+                PlayerMessageHandler::handle_game_flagged(
+                    contract,
+                    board_id,
+                    player,
+                    leaderboard_id,
+                    score,
+                    reason,
+                )
+                .await;
+            }
+
+            // 🎲 Randomness beacon contribution (main chain)
+            Message::BeaconContribution { shard_chain_id } => {
+                BeaconMessageHandler::handle_beacon_contribution(contract, shard_chain_id).await;
+            }
+
+            // 📮 Shard score messages (failures go to the dead letter queue)
+            message @ (Message::UpdateScore { .. } | Message::Flush { .. }) => {
+                ShardMessageHandler::handle_or_dead_letter(contract, message).await;
+            }
+
             // ═══════════════════════════════════════════════════════════════
             // DEPRECATED MESSAGES (No-ops for backward compatibility)
             // These are kept to process any pending messages in the queue
             // but no longer do anything meaningful.
             // ═══════════════════════════════════════════════════════════════
-            
             Message::LeaderboardNewGame { .. } => {
                 // DEPRECATED: Board counting now via SubmitScore
             }
-            
-            Message::UpdateScore { .. } => {
-                // DEPRECATED: Use SubmitScore instead
-            }
-            
-            Message::Flush { .. } => {
-                // DEPRECATED: No longer using shard flush system
-            }
 
             Message::RegisterPlayerWithShard { .. } => {
                 // DEPRECATED: No longer using shard registration
@@ -4,6 +4,7 @@ pub mod game_logic;
 pub mod handlers;
 pub mod message_dispatcher;
 pub mod operation_dispatcher;
+pub mod validators;
 
 // Game logic types exported for internal use
 
@@ -11,6 +12,7 @@ pub use contract_helpers::ContractHelpers;
 pub use events::{EventReader, StreamProcessor, SubscriptionManager};
 pub use message_dispatcher::MessageDispatcher;
 pub use operation_dispatcher::OperationDispatcher;
+pub use validators::ChainIdValidator;
 
 // Re-export handlers for contract use
 // Note: GameOperationHandler and SystemOperationHandler are used by operation_dispatcher.rs
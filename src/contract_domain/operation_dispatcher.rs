@@ -3,17 +3,26 @@
 //! Main dispatcher for routing operations directly to handlers.
 
 use crate::contract_domain::handlers::operations::{
-    GameOperationHandler, LeaderboardOperationHandler, PlayerOperationHandler,
-    SystemOperationHandler,
+    AdminOperationHandler, GameOperationHandler, LeaderboardOperationHandler,
+    MatchmakingOperationHandler, ModerationOperationHandler, PlayerOperationHandler,
+    ShardOperationHandler, SystemOperationHandler, TeamOperationHandler,
+    TournamentOperationHandler,
 };
+use crate::contract_domain::SubscriptionManager;
 use crate::Operation;
+use game2048::GameError;
 
 /// Main operation dispatcher that routes operations directly to handlers
 pub struct OperationDispatcher;
 
 impl OperationDispatcher {
-    /// Dispatch an operation directly to the appropriate handler
-    pub async fn dispatch(contract: &mut crate::Game2048Contract, operation: Operation) {
+    /// Dispatch an operation directly to the appropriate handler.
+    /// Returns the operation's response (empty except for ExportPlayerData,
+    /// RequestPasswordChange and BatchMakeMoves), or why it failed.
+    pub async fn dispatch(
+        contract: &mut crate::Game2048Contract,
+        operation: Operation,
+    ) -> Result<String, GameError> {
         match operation {
             // Player operations
             Operation::RegisterPlayer {
@@ -21,7 +30,7 @@ impl OperationDispatcher {
                 password_hash,
             } => {
                 PlayerOperationHandler::handle_register_player(contract, username, password_hash)
-                    .await;
+                    .await?;
             }
             Operation::ToggleAdmin {
                 username,
@@ -36,6 +45,95 @@ impl OperationDispatcher {
                 )
                 .await;
             }
+            Operation::ExportPlayerData {
+                player,
+                password_hash,
+            } => {
+                return Ok(PlayerOperationHandler::handle_export_player_data(
+                    contract,
+                    player,
+                    password_hash,
+                )
+                .await);
+            }
+            Operation::RequestPasswordChange {
+                player,
+                old_password_hash,
+            } => {
+                return Ok(PlayerOperationHandler::handle_request_password_change(
+                    contract,
+                    player,
+                    old_password_hash,
+                )
+                .await);
+            }
+            Operation::MigratePlayer {
+                old_player,
+                new_chain_id,
+                old_password_hash,
+                new_password_hash,
+            } => {
+                PlayerOperationHandler::handle_migrate_player(
+                    contract,
+                    old_player,
+                    new_chain_id,
+                    old_password_hash,
+                    new_password_hash,
+                )
+                .await;
+            }
+            Operation::ConfirmPasswordChange {
+                player,
+                change_token,
+                new_password_hash,
+            } => {
+                PlayerOperationHandler::handle_confirm_password_change(
+                    contract,
+                    player,
+                    change_token,
+                    new_password_hash,
+                )
+                .await;
+            }
+            Operation::UpdatePreferences {
+                player,
+                password_hash,
+                preferences,
+            } => {
+                PlayerOperationHandler::handle_update_preferences(
+                    contract,
+                    player,
+                    password_hash,
+                    preferences,
+                )
+                .await;
+            }
+            Operation::AddFriend {
+                friend_username,
+                player,
+                password_hash,
+            } => {
+                PlayerOperationHandler::handle_add_friend(
+                    contract,
+                    friend_username,
+                    player,
+                    password_hash,
+                )
+                .await;
+            }
+            Operation::RemoveFriend {
+                friend_username,
+                player,
+                password_hash,
+            } => {
+                PlayerOperationHandler::handle_remove_friend(
+                    contract,
+                    friend_username,
+                    player,
+                    password_hash,
+                )
+                .await;
+            }
 
             // Game operations
             Operation::MakeMoves {
@@ -51,7 +149,20 @@ impl OperationDispatcher {
                     player,
                     password_hash,
                 )
-                .await;
+                .await?;
+            }
+            Operation::BatchMakeMoves {
+                entries,
+                player,
+                password_hash,
+            } => {
+                return Ok(GameOperationHandler::handle_batch_make_moves(
+                    contract,
+                    entries,
+                    player,
+                    password_hash,
+                )
+                .await);
             }
             Operation::NewBoard {
                 player,
@@ -59,6 +170,8 @@ impl OperationDispatcher {
                 password_hash,
                 leaderboard_id,
                 rhythm_track_index,
+                pow_nonce,
+                board_size,
             } => {
                 GameOperationHandler::handle_new_board(
                     contract,
@@ -67,6 +180,8 @@ impl OperationDispatcher {
                     password_hash,
                     leaderboard_id,
                     rhythm_track_index,
+                    pow_nonce,
+                    board_size,
                 )
                 .await;
             }
@@ -87,7 +202,26 @@ impl OperationDispatcher {
                     player,
                     password_hash,
                 )
-                .await;
+                .await?;
+            }
+            Operation::CloneLeaderboard {
+                source_leaderboard_id,
+                new_name,
+                new_start_time,
+                new_end_time,
+                player,
+                password_hash,
+            } => {
+                LeaderboardOperationHandler::handle_clone_leaderboard(
+                    contract,
+                    source_leaderboard_id,
+                    new_name,
+                    new_start_time,
+                    new_end_time,
+                    player,
+                    password_hash,
+                )
+                .await?;
             }
 
             // System operations
@@ -108,6 +242,10 @@ impl OperationDispatcher {
             Operation::UpdateLeaderboard => {
                 GameOperationHandler::handle_update_leaderboard(contract).await;
             }
+            Operation::ProcessWaitlist { leaderboard_id } => {
+                LeaderboardOperationHandler::handle_process_waitlist(contract, leaderboard_id)
+                    .await;
+            }
 
             // Tournament and workload management operations
             Operation::UpdateActiveTournaments => {
@@ -140,6 +278,29 @@ impl OperationDispatcher {
                 .await;
             }
 
+            Operation::ResetResourceUsage {
+                admin,
+                password_hash,
+            } => {
+                SystemOperationHandler::handle_reset_resource_usage(contract, admin, password_hash)
+                    .await;
+            }
+
+            // 📮 Dead letter retries (shard chain)
+            Operation::RetryDeadLetter {
+                key,
+                admin,
+                password_hash,
+            } => {
+                ShardOperationHandler::handle_retry_dead_letter(
+                    contract,
+                    key,
+                    admin,
+                    password_hash,
+                )
+                .await;
+            }
+
             // Manual leaderboard refresh
             Operation::RequestLeaderboardRefresh {
                 player,
@@ -159,13 +320,43 @@ impl OperationDispatcher {
             Operation::RefillChainPool { count } => {
                 SystemOperationHandler::handle_refill_chain_pool(contract, count).await;
             }
-            
+
             // Claim chain - just triggers block production to process inbox
             Operation::ClaimChain => {
                 // No-op operation - just triggers block production which processes inbox messages
                 // The RegisterPlayer message in inbox will be processed during this block
             }
-            
+
+            Operation::RecoverStaleChains {
+                admin,
+                password_hash,
+            } => {
+                SystemOperationHandler::handle_recover_stale_chains(contract, admin, password_hash)
+                    .await;
+            }
+
+            Operation::RotateShardKey {
+                admin,
+                password_hash,
+                leaderboard_id,
+                shard_chain_id,
+                public_key,
+            } => {
+                SystemOperationHandler::handle_rotate_shard_key(
+                    contract,
+                    admin,
+                    password_hash,
+                    leaderboard_id,
+                    shard_chain_id,
+                    public_key,
+                )
+                .await;
+            }
+
+            Operation::ContributeToBeacon { shard_chain_id } => {
+                SystemOperationHandler::handle_contribute_to_beacon(contract, shard_chain_id);
+            }
+
             // Manual score submission - user clicks "refresh leaderboard"
             Operation::SubmitCurrentScore {
                 board_id,
@@ -180,6 +371,530 @@ impl OperationDispatcher {
                 )
                 .await;
             }
+
+            Operation::RequestHint {
+                board_id,
+                player,
+                password_hash,
+            } => {
+                GameOperationHandler::handle_request_hint(
+                    contract,
+                    board_id,
+                    player,
+                    password_hash,
+                )
+                .await;
+            }
+            Operation::UndoMove {
+                board_id,
+                player,
+                password_hash,
+            } => {
+                GameOperationHandler::handle_undo_move(contract, board_id, player, password_hash)
+                    .await;
+            }
+            Operation::SaveSnapshot {
+                board_id,
+                player,
+                password_hash,
+            } => {
+                GameOperationHandler::handle_save_snapshot(
+                    contract,
+                    board_id,
+                    player,
+                    password_hash,
+                )
+                .await;
+            }
+            Operation::LoadSnapshot {
+                board_id,
+                player,
+                password_hash,
+            } => {
+                GameOperationHandler::handle_load_snapshot(
+                    contract,
+                    board_id,
+                    player,
+                    password_hash,
+                )
+                .await;
+            }
+            Operation::GetDailyChallenge {
+                player,
+                password_hash,
+            } => {
+                GameOperationHandler::handle_get_daily_challenge(contract, player, password_hash)
+                    .await;
+            }
+            Operation::ConvertToTimedGame {
+                board_id,
+                player,
+                password_hash,
+                time_limit_seconds,
+            } => {
+                GameOperationHandler::handle_convert_to_timed_game(
+                    contract,
+                    board_id,
+                    player,
+                    password_hash,
+                    time_limit_seconds,
+                )
+                .await;
+            }
+            Operation::ContinueGame {
+                old_board_id,
+                player,
+                password_hash,
+                new_board_size,
+            } => {
+                GameOperationHandler::handle_continue_game(
+                    contract,
+                    old_board_id,
+                    player,
+                    password_hash,
+                    new_board_size,
+                )
+                .await;
+            }
+            // 👥 Team games (player chains)
+            Operation::CreateTeamGame {
+                team_name,
+                player,
+                password_hash,
+                invite_codes,
+            } => {
+                TeamOperationHandler::handle_create_team_game(
+                    contract,
+                    team_name,
+                    player,
+                    password_hash,
+                    invite_codes,
+                )
+                .await;
+            }
+            Operation::JoinTeamGame {
+                team_board_id,
+                invite_code,
+                player,
+                password_hash,
+            } => {
+                TeamOperationHandler::handle_join_team_game(
+                    contract,
+                    team_board_id,
+                    invite_code,
+                    player,
+                    password_hash,
+                )
+                .await;
+            }
+            Operation::EnterMatchmaking {
+                player,
+                password_hash,
+                preferred_time_limit,
+            } => {
+                MatchmakingOperationHandler::handle_enter_matchmaking(
+                    contract,
+                    player,
+                    password_hash,
+                    preferred_time_limit,
+                )
+                .await;
+            }
+            Operation::ProcessMatchmaking {
+                admin,
+                password_hash,
+            } => {
+                MatchmakingOperationHandler::handle_process_matchmaking(
+                    contract,
+                    admin,
+                    password_hash,
+                )
+                .await;
+            }
+            Operation::SetGameMetadata {
+                board_id,
+                player,
+                password_hash,
+                metadata,
+            } => {
+                GameOperationHandler::handle_set_game_metadata(
+                    contract,
+                    board_id,
+                    player,
+                    password_hash,
+                    metadata,
+                )
+                .await;
+            }
+
+            // Spectator prediction - forwarded to the leaderboard chain
+            Operation::SubmitPrediction {
+                spectator_chain_id,
+                leaderboard_id,
+                predicted_winner,
+                predicted_score,
+            } => {
+                LeaderboardOperationHandler::handle_submit_prediction(
+                    contract,
+                    spectator_chain_id,
+                    leaderboard_id,
+                    predicted_winner,
+                    predicted_score,
+                )
+                .await;
+            }
+
+            // Live stream endpoints
+            Operation::RegisterStreamEndpoint {
+                admin,
+                password_hash,
+                leaderboard_id,
+                endpoint_url,
+                auth_token_hash,
+                relay_owner,
+            } => {
+                LeaderboardOperationHandler::handle_register_stream_endpoint(
+                    contract,
+                    admin,
+                    password_hash,
+                    leaderboard_id,
+                    endpoint_url,
+                    auth_token_hash,
+                    relay_owner,
+                )
+                .await;
+            }
+            Operation::ReportStreamDelivery {
+                endpoint_url,
+                success,
+            } => {
+                LeaderboardOperationHandler::handle_report_stream_delivery(
+                    contract,
+                    endpoint_url,
+                    success,
+                )
+                .await;
+            }
+            Operation::SubscribePlatformEvents {
+                subscriber_chain_id,
+            } => {
+                SystemOperationHandler::handle_subscribe_platform_events(
+                    contract,
+                    subscriber_chain_id,
+                )
+                .await;
+            }
+            Operation::UnsubscribeFromChannel { channel } => {
+                SubscriptionManager::unsubscribe_from_channel(contract, &channel).await;
+            }
+
+            // Multi-sig admin actions (main chain)
+            Operation::ConfigureMultiSig {
+                required_signatures,
+                admin_list,
+                player,
+                password_hash,
+            } => {
+                AdminOperationHandler::handle_configure_multisig(
+                    contract,
+                    required_signatures,
+                    admin_list,
+                    player,
+                    password_hash,
+                )
+                .await;
+            }
+            Operation::ProposeAdminAction {
+                proposer,
+                password_hash,
+                action,
+                proposal_id,
+            } => {
+                AdminOperationHandler::handle_propose_admin_action(
+                    contract,
+                    proposer,
+                    password_hash,
+                    action,
+                    proposal_id,
+                )
+                .await;
+            }
+            Operation::ApproveAdminAction {
+                approver,
+                password_hash,
+                proposal_id,
+            } => {
+                AdminOperationHandler::handle_approve_admin_action(
+                    contract,
+                    approver,
+                    password_hash,
+                    proposal_id,
+                )
+                .await;
+            }
+
+            // Moderation (main chain, super-admin)
+            Operation::UpdateBlockedWords {
+                blocked_words,
+                player,
+                password_hash,
+            } => {
+                ModerationOperationHandler::handle_update_blocked_words(
+                    contract,
+                    blocked_words,
+                    player,
+                    password_hash,
+                )
+                .await;
+            }
+            Operation::ReviewFlaggedGame {
+                board_id,
+                admin,
+                password_hash,
+                verdict,
+            } => {
+                ModerationOperationHandler::handle_review_flagged_game(
+                    contract,
+                    board_id,
+                    admin,
+                    password_hash,
+                    verdict,
+                )
+                .await;
+            }
+
+            // Bonus windows (main chain)
+            Operation::AddBonusWindow {
+                leaderboard_id,
+                admin,
+                password_hash,
+                window,
+            } => {
+                LeaderboardOperationHandler::handle_add_bonus_window(
+                    contract,
+                    leaderboard_id,
+                    admin,
+                    password_hash,
+                    window,
+                )
+                .await;
+            }
+
+            // Tournament replay
+            Operation::SetPlayerHandicap {
+                leaderboard_id,
+                admin,
+                password_hash,
+                player,
+                multiplier,
+            } => {
+                LeaderboardOperationHandler::handle_set_player_handicap(
+                    contract,
+                    leaderboard_id,
+                    admin,
+                    password_hash,
+                    player,
+                    multiplier,
+                )
+                .await;
+            }
+            Operation::FundPrizePool {
+                leaderboard_id,
+                application_id,
+                amount,
+            } => {
+                LeaderboardOperationHandler::handle_fund_prize_pool(
+                    contract,
+                    leaderboard_id,
+                    application_id,
+                    amount,
+                )
+                .await;
+            }
+            Operation::DistributePrizes {
+                leaderboard_id,
+                admin,
+                password_hash,
+            } => {
+                LeaderboardOperationHandler::handle_distribute_prizes(
+                    contract,
+                    leaderboard_id,
+                    admin,
+                    password_hash,
+                )
+                .await;
+            }
+            Operation::FinaliseLeaderboard {
+                leaderboard_id,
+                player,
+                password_hash,
+            } => {
+                LeaderboardOperationHandler::handle_finalise_leaderboard(
+                    contract,
+                    leaderboard_id,
+                    player,
+                    password_hash,
+                )
+                .await?;
+            }
+            Operation::ImportHistoricalData {
+                leaderboard_id,
+                admin,
+                password_hash,
+                data_json,
+            } => {
+                LeaderboardOperationHandler::handle_import_historical_data(
+                    contract,
+                    leaderboard_id,
+                    admin,
+                    password_hash,
+                    data_json,
+                )
+                .await;
+            }
+            Operation::InitiateTournamentReplay {
+                leaderboard_id,
+                admin,
+                password_hash,
+            } => {
+                LeaderboardOperationHandler::handle_set_replay_mode(
+                    contract,
+                    leaderboard_id,
+                    admin,
+                    password_hash,
+                    true,
+                )
+                .await;
+            }
+            Operation::ReplayMoves {
+                board_id,
+                player,
+                password_hash,
+            } => {
+                GameOperationHandler::handle_replay_moves(
+                    contract,
+                    board_id,
+                    player,
+                    password_hash,
+                )
+                .await;
+            }
+            Operation::ReplayGame { board_id, move_log } => {
+                GameOperationHandler::handle_replay_game(contract, board_id, move_log).await;
+            }
+            Operation::BracketTournament {
+                leaderboard_id,
+                admin,
+                password_hash,
+                bracket_size,
+                round_duration_seconds,
+            } => {
+                LeaderboardOperationHandler::handle_bracket_tournament(
+                    contract,
+                    leaderboard_id,
+                    admin,
+                    password_hash,
+                    bracket_size,
+                    round_duration_seconds,
+                )
+                .await;
+            }
+            Operation::ScheduleRoundRobin {
+                leaderboard_id,
+                rounds,
+                round_duration_seconds,
+                player,
+                password_hash,
+            } => {
+                LeaderboardOperationHandler::handle_schedule_round_robin(
+                    contract,
+                    leaderboard_id,
+                    rounds,
+                    round_duration_seconds,
+                    player,
+                    password_hash,
+                )
+                .await;
+            }
+            Operation::FinalizeReplay {
+                leaderboard_id,
+                admin,
+                password_hash,
+            } => {
+                LeaderboardOperationHandler::handle_set_replay_mode(
+                    contract,
+                    leaderboard_id,
+                    admin,
+                    password_hash,
+                    false,
+                )
+                .await;
+            }
+
+            Operation::PauseLeaderboard {
+                leaderboard_id,
+                reason,
+                player,
+                password_hash,
+            } => {
+                LeaderboardOperationHandler::handle_set_leaderboard_paused(
+                    contract,
+                    leaderboard_id,
+                    reason,
+                    player,
+                    password_hash,
+                    true,
+                )
+                .await;
+            }
+            Operation::ResumeLeaderboard {
+                leaderboard_id,
+                reason,
+                player,
+                password_hash,
+            } => {
+                LeaderboardOperationHandler::handle_set_leaderboard_paused(
+                    contract,
+                    leaderboard_id,
+                    reason,
+                    player,
+                    password_hash,
+                    false,
+                )
+                .await;
+            }
+            Operation::PurgeInactivePlayers {
+                leaderboard_id,
+                inactive_threshold_seconds,
+                admin,
+                password_hash,
+            } => {
+                LeaderboardOperationHandler::handle_purge_inactive_players(
+                    contract,
+                    leaderboard_id,
+                    inactive_threshold_seconds,
+                    admin,
+                    password_hash,
+                )
+                .await;
+            }
+
+            // Token gate attestation (attestation service chain)
+            Operation::AttestBalance {
+                player_chain_id,
+                leaderboard_id,
+                held,
+                expires_at,
+            } => {
+                TournamentOperationHandler::handle_attest_balance(
+                    contract,
+                    player_chain_id,
+                    leaderboard_id,
+                    held,
+                    expires_at,
+                );
+            }
         }
+        Ok(String::new())
     }
 }
@@ -0,0 +1,46 @@
+//! Input Validators
+//!
+//! Format checks for user-supplied values before they reach state or cross-chain messages.
+
+use linera_sdk::linera_base_types::ChainId;
+use std::str::FromStr;
+
+pub struct ChainIdValidator;
+
+impl ChainIdValidator {
+    /// A Linera chain ID: 32 bytes, hex encoded (64 characters)
+    pub fn is_valid_format(chain_id: &str) -> bool {
+        chain_id.len() == 64
+            && chain_id.chars().all(|c| c.is_ascii_hexdigit())
+            && ChainId::from_str(chain_id).is_ok()
+    }
+
+    /// Parse a chain ID, panicking with the offending input if it is malformed
+    pub fn parse(chain_id: &str) -> ChainId {
+        if !Self::is_valid_format(chain_id) {
+            panic!("Invalid chain ID format: '{}'", chain_id);
+        }
+        ChainId::from_str(chain_id).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chain_id_format() {
+        let valid = "e476187f6ddfeb9d588c7b45d3df334d5501d6499b3f9ad5595cae86cce16a65";
+        assert!(ChainIdValidator::is_valid_format(valid));
+        assert!(ChainIdValidator::is_valid_format(&valid.to_uppercase()));
+
+        assert!(!ChainIdValidator::is_valid_format(""));
+        assert!(!ChainIdValidator::is_valid_format("invalid"));
+        assert!(!ChainIdValidator::is_valid_format(&valid[..62]));
+        assert!(!ChainIdValidator::is_valid_format(&format!(
+            " {}",
+            &valid[1..]
+        )));
+        assert!(!ChainIdValidator::is_valid_format(&valid.replace('e', "g")));
+    }
+}
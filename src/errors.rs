@@ -0,0 +1,216 @@
+//! Operation errors with stable numeric codes.
+//!
+//! An operation that fails with a `GameError` keeps none of its changes and responds with the
+//! JSON of its `ErrorResponse`, so clients can match on `error_code` instead of parsing the
+//! message. Codes are grouped by area: 1xxx players, 2xxx boards and moves, 3xxx tournaments,
+//! 4xxx content.
+//!
+//! Only player registration, moves, leaderboard actions, cloning and finalisation return
+//! `GameError` so far. The other handlers (system, team, bracket, round-robin, waitlist and the rest)
+//! still abort with plain-string panics; moving them over is a separate follow-up.
+
+use async_graphql::SimpleObject;
+use serde::{Deserialize, Serialize};
+
+use crate::{FilterError, FinaliseError, MoveError};
+
+#[derive(Debug, thiserror::Error, Clone, PartialEq, Eq)]
+pub enum GameError {
+    #[error("Player not registered")]
+    PlayerNotRegistered,
+    #[error("Player already registered")]
+    PlayerAlreadyRegistered,
+    #[error("Invalid password")]
+    InvalidPassword,
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
+    #[error("Board not found")]
+    BoardNotFound,
+    #[error("You can only make move on your own board")]
+    NotBoardOwner,
+    #[error("Invalid moves format")]
+    InvalidMoveFormat,
+    #[error("Too many moves in one call: {count} (max {max})")]
+    TooManyMoves { count: usize, max: u32 },
+    #[error("Invalid move sequence at move {at_index}: {reason}")]
+    InvalidMove { at_index: usize, reason: String },
+    #[error("Game is ended")]
+    GameEnded,
+    #[error("Tournament is in replay mode - moves are not accepted")]
+    ReplayMode,
+    #[error("{0}")]
+    MoveRejected(String),
+    #[error("Tournament not found")]
+    TournamentNotFound,
+    #[error("Tournament has ended")]
+    TournamentExpired,
+    #[error("Tournament can only be finalised after it ends")]
+    TournamentNotEnded,
+    #[error("Tournament is already finalised")]
+    AlreadyFinalised,
+    #[error("{field} contains a banned word")]
+    ProfanityViolation { field: String },
+    #[error("{0}")]
+    InvalidCustomBannedWords(String),
+}
+
+/// What a client sees of a failed operation
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, SimpleObject)]
+pub struct ErrorResponse {
+    pub error_code: u16,
+    pub message: String,
+}
+
+impl GameError {
+    /// Stable code of this kind of failure (never reused for another variant)
+    pub fn code(&self) -> u16 {
+        match self {
+            GameError::PlayerNotRegistered => 1001,
+            GameError::PlayerAlreadyRegistered => 1002,
+            GameError::InvalidPassword => 1003,
+            GameError::Unauthorized(_) => 1004,
+            GameError::BoardNotFound => 2001,
+            GameError::NotBoardOwner => 2002,
+            GameError::InvalidMoveFormat => 2003,
+            GameError::TooManyMoves { .. } => 2004,
+            GameError::InvalidMove { .. } => 2005,
+            GameError::GameEnded => 2006,
+            GameError::ReplayMode => 2007,
+            GameError::MoveRejected(_) => 2008,
+            GameError::TournamentNotFound => 3001,
+            GameError::TournamentExpired => 3002,
+            GameError::TournamentNotEnded => 3003,
+            GameError::AlreadyFinalised => 3004,
+            GameError::ProfanityViolation { .. } => 4001,
+            GameError::InvalidCustomBannedWords(_) => 4002,
+        }
+    }
+
+    pub fn to_response(&self) -> ErrorResponse {
+        ErrorResponse {
+            error_code: self.code(),
+            message: self.to_string(),
+        }
+    }
+
+    /// Abort the operation with this error's `ErrorResponse` JSON as the panic message
+    /// (for helpers that cannot return a `Result` to the dispatcher yet)
+    pub fn raise(self) -> ! {
+        panic!("{}", serde_json::to_string(&self.to_response()).unwrap())
+    }
+}
+
+impl From<MoveError> for GameError {
+    fn from(error: MoveError) -> Self {
+        match error {
+            MoveError::InvalidFormat => GameError::InvalidMoveFormat,
+            MoveError::BoardNotFound => GameError::BoardNotFound,
+            MoveError::NotBoardOwner => GameError::NotBoardOwner,
+            MoveError::ReplayMode => GameError::ReplayMode,
+            MoveError::TooManyMoves { count, max } => GameError::TooManyMoves { count, max },
+            MoveError::InvalidMoveSequence { at_index, reason } => {
+                GameError::InvalidMove { at_index, reason }
+            }
+            MoveError::Rejected(reason) => GameError::MoveRejected(reason),
+            MoveError::GameEnded => GameError::GameEnded,
+        }
+    }
+}
+
+impl From<FinaliseError> for GameError {
+    fn from(error: FinaliseError) -> Self {
+        match error {
+            FinaliseError::AlreadyFinalised => GameError::AlreadyFinalised,
+            FinaliseError::NotEnded => GameError::TournamentNotEnded,
+        }
+    }
+}
+
+impl From<FilterError> for GameError {
+    fn from(error: FilterError) -> Self {
+        match error {
+            FilterError::ProfanityViolation { field } => GameError::ProfanityViolation { field },
+            error => GameError::InvalidCustomBannedWords(error.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_codes() {
+        let cases = [
+            (GameError::PlayerNotRegistered, 1001),
+            (GameError::PlayerAlreadyRegistered, 1002),
+            (GameError::InvalidPassword, 1003),
+            (GameError::Unauthorized("host only".to_string()), 1004),
+            (GameError::BoardNotFound, 2001),
+            (GameError::NotBoardOwner, 2002),
+            (GameError::InvalidMoveFormat, 2003),
+            (GameError::TooManyMoves { count: 9, max: 8 }, 2004),
+            (
+                GameError::InvalidMove {
+                    at_index: 3,
+                    reason: "timestamps must increase".to_string(),
+                },
+                2005,
+            ),
+            (GameError::GameEnded, 2006),
+            (GameError::ReplayMode, 2007),
+            (GameError::MoveRejected("too fast".to_string()), 2008),
+            (GameError::TournamentNotFound, 3001),
+            (GameError::TournamentExpired, 3002),
+            (GameError::TournamentNotEnded, 3003),
+            (GameError::AlreadyFinalised, 3004),
+            (
+                GameError::ProfanityViolation {
+                    field: "username".to_string(),
+                },
+                4001,
+            ),
+            (
+                GameError::InvalidCustomBannedWords("too many".to_string()),
+                4002,
+            ),
+        ];
+        for (error, code) in &cases {
+            assert_eq!(error.code(), *code, "{:?}", error);
+            assert_eq!(error.to_response().error_code, *code);
+        }
+        for (index, (error, _)) in cases.iter().enumerate() {
+            assert!(
+                cases[..index]
+                    .iter()
+                    .all(|(other, _)| other.code() != error.code()),
+                "{:?} reuses a code",
+                error
+            );
+        }
+    }
+
+    #[test]
+    fn test_error_conversions_and_response() {
+        assert_eq!(
+            GameError::from(MoveError::TooManyMoves { count: 9, max: 8 }).code(),
+            2004
+        );
+        assert_eq!(
+            GameError::from(FinaliseError::AlreadyFinalised).code(),
+            3004
+        );
+        assert_eq!(
+            GameError::from(FilterError::TooManyCustomWords { count: 51, max: 50 }),
+            GameError::InvalidCustomBannedWords(
+                "At most 50 custom banned words are allowed, got 51".to_string()
+            )
+        );
+
+        let response = GameError::InvalidPassword.to_response();
+        assert_eq!(
+            serde_json::to_string(&response).unwrap(),
+            r#"{"error_code":1003,"message":"Invalid password"}"#
+        );
+    }
+}
@@ -1,5 +1,11 @@
-use async_graphql::{scalar, InputObject};
+use async_graphql::{scalar, InputObject, SimpleObject};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use linera_sdk::linera_base_types::{Amount, ApplicationId, ChainId};
 use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::str::FromStr;
+
+use crate::{AntiCheatConfig, StreakBonusTier, TileWeight};
 
 #[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq)]
 pub enum LeaderboardAction {
@@ -11,6 +17,28 @@ pub enum LeaderboardAction {
 
 scalar!(LeaderboardAction);
 
+/// 🏎️ What a leaderboard ranks: the best score, or the fastest time to a target tile
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub enum LeaderboardMode {
+    #[default]
+    HighScore,
+    SpeedRun {
+        target_tile: u32,
+    },
+}
+
+scalar!(LeaderboardMode);
+
+impl LeaderboardMode {
+    /// Tile a speed run races to (None = high score leaderboard)
+    pub fn target_tile(&self) -> Option<u32> {
+        match self {
+            LeaderboardMode::HighScore => None,
+            LeaderboardMode::SpeedRun { target_tile } => Some(*target_tile),
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, InputObject, Clone)]
 pub struct LeaderboardSettings {
     pub name: String,
@@ -19,4 +47,1170 @@ pub struct LeaderboardSettings {
     pub end_time: String,
     pub shard_number: Option<u32>,
     pub base_triggerer_count: Option<u32>, // Number of players that can trigger updates (default: 5)
+    /// Token gate: entry requires holding at least `min_balance` (None = open entry)
+    #[serde(default)]
+    pub entry_token_requirement: Option<TokenRequirement>,
+    /// Updates only: remove the token gate (None entry_token_requirement alone keeps it)
+    #[serde(default)]
+    pub clear_entry_token_requirement: Option<bool>,
+    /// Anti-cheat thresholds, enforced on every MakeMoves batch (None = not enforced; reports use defaults)
+    #[serde(default)]
+    pub anti_cheat: Option<AntiCheatConfig>,
+    /// Moderators only: keep a name/description that fails moderation (audit logged)
+    #[serde(default)]
+    pub moderation_override: Option<bool>,
+    /// Streak multipliers by consecutive tournaments played (None = no streak bonus)
+    #[serde(default)]
+    pub streak_bonus_tiers: Option<Vec<StreakBonusTier>>,
+    /// Classic games may be converted to speed games (None = not allowed)
+    #[serde(default)]
+    pub allow_variant_conversion: Option<bool>,
+    /// Prize pool per token, paid out to the top ranks when prizes are distributed
+    #[serde(default)]
+    pub prize_pool_entries: Option<Vec<PrizePoolEntry>>,
+    /// New boards must include a proof of work with this many leading zero bits (None/0 = not required)
+    #[serde(default)]
+    pub pow_difficulty: Option<u8>,
+    /// Reaching this score ends the game; the first to reach it ranks first (None/0 = no cap)
+    #[serde(default)]
+    pub max_score_per_player: Option<u64>,
+    /// Boards can be created this long before `start_time`; their scores do not count (None = no warmup)
+    #[serde(default)]
+    pub warmup_duration_seconds: Option<u32>,
+    /// Announce a countdown when this many seconds remain (None = `DEFAULT_COUNTDOWN_THRESHOLDS`)
+    #[serde(default)]
+    pub countdown_notification_thresholds: Option<Vec<u64>>,
+    /// This tournament is a qualifier: its top players advance to another tournament when it ends
+    #[serde(default)]
+    pub qualifier_settings: Option<QualifierSettings>,
+    /// Undos per game (None/0 = no undo, the tournament stays competitive)
+    #[serde(default)]
+    pub undo_budget: Option<u32>,
+    /// Board states kept for undo (None = `DEFAULT_UNDO_HISTORY_SIZE`)
+    #[serde(default)]
+    pub undo_history_size: Option<u32>,
+    /// Boards are Speed2048 games with this time limit from creation (None = unchanged, 0 = classic)
+    #[serde(default)]
+    pub speed_time_limit_seconds: Option<u32>,
+    /// Boards are Elimination games with this many lives (None = unchanged, 0 = classic)
+    #[serde(default)]
+    pub elimination_lives: Option<u8>,
+    /// Spawn weight of each tile value, adding up to 100 (None = unchanged, empty = classic)
+    #[serde(default)]
+    pub tile_weights: Option<Vec<TileWeight>>,
+    /// Moves with 3+ merges multiply their merged value by 1 + 0.1 per merge past 2 (None = unchanged)
+    #[serde(default)]
+    pub combo_multiplier_enabled: Option<bool>,
+    /// Keep a ranked score snapshot once per interval, for history (None = unchanged, 0 = off)
+    #[serde(default)]
+    pub snapshot_interval_seconds: Option<u64>,
+    /// Most moves accepted in one MakeMoves call (None = unchanged, 0 = `DEFAULT_MAX_MOVES_PER_CALL`)
+    #[serde(default)]
+    pub max_moves_per_call: Option<u16>,
+    /// Shortest gap between consecutive moves of a call
+    /// (None = unchanged, 0 = `DEFAULT_MIN_MOVE_INTERVAL_MICROS`)
+    #[serde(default)]
+    pub min_move_interval_micros: Option<u64>,
+    /// Shortest gap between two new boards of a player
+    /// (None = unchanged, 0 = `DEFAULT_NEW_BOARD_COOLDOWN_SECONDS`)
+    #[serde(default)]
+    pub new_board_cooldown_seconds: Option<u64>,
+    /// Split the tournament into seasons that reset scores when they end (None = unchanged)
+    #[serde(default)]
+    pub season: Option<SeasonConfig>,
+    /// Chances of obstacle and bomb tiles (None = unchanged)
+    #[serde(default)]
+    pub power_ups: Option<PowerUpConfig>,
+    /// How players with the same score are ordered (None = unchanged)
+    #[serde(default)]
+    pub tie_break: Option<TieBreakPolicy>,
+    /// Rank by score or by time to a target tile (None = unchanged)
+    #[serde(default)]
+    pub mode: Option<LeaderboardMode>,
+    /// Most players admitted; later players join the waitlist (None = unchanged, 0 = no limit)
+    #[serde(default)]
+    pub max_participants: Option<u32>,
+    /// Token rewards paid by the main chain to the top ranks on finalisation (None = unchanged)
+    #[serde(default)]
+    pub reward_structure: Option<RewardStructure>,
+    /// Words this tournament adds to the profanity filter (empty = unchanged)
+    #[serde(default)]
+    #[graphql(default)]
+    pub custom_banned_words: Vec<String>,
+}
+
+/// Top `advance_count` players of the qualifier advance to `main_leaderboard_id` when it ends
+#[derive(Debug, Deserialize, Serialize, InputObject, SimpleObject, Clone, PartialEq)]
+#[graphql(input_name = "QualifierSettingsInput")]
+pub struct QualifierSettings {
+    pub qualifier_leaderboard_id: String,
+    pub main_leaderboard_id: String,
+    pub advance_count: u32,
+}
+
+/// 🗓️ Seasons of `season_duration_seconds` from the tournament start. When one ends its ranking
+/// is archived and every score drops to `carry_over_percent` of itself.
+#[derive(Debug, Deserialize, Serialize, InputObject, SimpleObject, Clone, Copy, PartialEq, Eq)]
+#[graphql(input_name = "SeasonConfigInput")]
+pub struct SeasonConfig {
+    pub season_duration_seconds: u64,
+    pub max_seasons: u8, // 0 = no limit
+    pub carry_over_percent: u8,
+}
+
+impl SeasonConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.season_duration_seconds == 0 {
+            return Err("Season duration must be positive".to_string());
+        }
+        if self.carry_over_percent > 100 {
+            return Err("Season carry-over cannot exceed 100%".to_string());
+        }
+        Ok(())
+    }
+
+    /// Whether `season` (0-based) is followed by another one
+    pub fn has_next_season(&self, season: u8) -> bool {
+        season < u8::MAX && (self.max_seasons == 0 || season + 1 < self.max_seasons)
+    }
+
+    /// Score a player starts the next season with
+    pub fn carry_over(&self, score: u64) -> u64 {
+        (score as u128 * self.carry_over_percent as u128 / 100) as u64
+    }
+}
+
+/// Highest combined chance of a power-up tile per spawn
+pub const MAX_POWER_UP_CHANCE_PERCENT: u8 = 20;
+
+/// 🧱💣 Chances of an obstacle or a bomb tile (see `SizedBoard::place_power_up`)
+#[derive(Debug, Deserialize, Serialize, InputObject, SimpleObject, Clone, Copy, PartialEq, Eq)]
+#[graphql(input_name = "PowerUpConfigInput")]
+pub struct PowerUpConfig {
+    pub obstacle_tile_chance_percent: u8,
+    pub bomb_tile_chance_percent: u8,
+}
+
+impl PowerUpConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        let total = self.obstacle_tile_chance_percent as u32 + self.bomb_tile_chance_percent as u32;
+        if total > MAX_POWER_UP_CHANCE_PERCENT as u32 {
+            return Err(format!(
+                "Power-up chances cannot exceed {}% in total",
+                MAX_POWER_UP_CHANCE_PERCENT
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// ⚖️ Order of players with the same score (after the score cap time, before the name)
+#[derive(async_graphql::Enum, Copy, Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub enum TieBreakPolicy {
+    ByHighestTile,
+    ByFewestMoves,
+    ByEarliestAchievement,
+    ByBestEfficiency,
+}
+
+impl TieBreakPolicy {
+    /// Tie-break key of a player's best game; lower keys rank first
+    pub fn key(
+        &self,
+        highest_tile: u64,
+        moves_count: u32,
+        achieved_at: u64,
+        efficiency: u64,
+    ) -> u64 {
+        match self {
+            TieBreakPolicy::ByHighestTile => u64::MAX - highest_tile,
+            TieBreakPolicy::ByFewestMoves => moves_count as u64,
+            TieBreakPolicy::ByEarliestAchievement => achieved_at,
+            TieBreakPolicy::ByBestEfficiency => u64::MAX - efficiency,
+        }
+    }
+}
+
+/// A version of a tournament's settings, in effect from `effective_at` until the next version
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct SettingsVersion {
+    pub effective_at: u64, // micros
+    pub settings: LeaderboardSettings,
+}
+
+impl LeaderboardSettings {
+    /// Apply an update on top of these settings (empty name / None fields keep the current value)
+    pub fn apply_update(&self, update: &LeaderboardSettings) -> LeaderboardSettings {
+        LeaderboardSettings {
+            name: if update.name.is_empty() {
+                self.name.clone()
+            } else {
+                update.name.clone()
+            },
+            description: update.description.clone().or(self.description.clone()),
+            start_time: update.start_time.clone(),
+            end_time: update.end_time.clone(),
+            shard_number: self.shard_number, // Shards are fixed at creation
+            base_triggerer_count: update.base_triggerer_count.or(self.base_triggerer_count),
+            entry_token_requirement: if update.clear_entry_token_requirement == Some(true) {
+                None
+            } else {
+                update
+                    .entry_token_requirement
+                    .clone()
+                    .or(self.entry_token_requirement.clone())
+            },
+            clear_entry_token_requirement: None,
+            anti_cheat: update.anti_cheat.clone().or(self.anti_cheat.clone()),
+            moderation_override: update.moderation_override,
+            streak_bonus_tiers: update
+                .streak_bonus_tiers
+                .clone()
+                .or(self.streak_bonus_tiers.clone()),
+            allow_variant_conversion: update
+                .allow_variant_conversion
+                .or(self.allow_variant_conversion),
+            prize_pool_entries: update
+                .prize_pool_entries
+                .clone()
+                .or(self.prize_pool_entries.clone()),
+            pow_difficulty: update.pow_difficulty.or(self.pow_difficulty),
+            max_score_per_player: update.max_score_per_player.or(self.max_score_per_player),
+            warmup_duration_seconds: update
+                .warmup_duration_seconds
+                .or(self.warmup_duration_seconds),
+            countdown_notification_thresholds: update
+                .countdown_notification_thresholds
+                .clone()
+                .or(self.countdown_notification_thresholds.clone()),
+            qualifier_settings: update
+                .qualifier_settings
+                .clone()
+                .or(self.qualifier_settings.clone()),
+            season: update.season.or(self.season),
+            power_ups: update.power_ups.or(self.power_ups),
+            tie_break: update.tie_break.or(self.tie_break),
+            mode: update.mode.or(self.mode),
+            max_participants: update.max_participants.or(self.max_participants),
+            reward_structure: update
+                .reward_structure
+                .clone()
+                .or(self.reward_structure.clone()),
+            custom_banned_words: if update.custom_banned_words.is_empty() {
+                self.custom_banned_words.clone()
+            } else {
+                update.custom_banned_words.clone()
+            },
+            undo_budget: update.undo_budget.or(self.undo_budget),
+            undo_history_size: update.undo_history_size.or(self.undo_history_size),
+            speed_time_limit_seconds: update
+                .speed_time_limit_seconds
+                .or(self.speed_time_limit_seconds),
+            elimination_lives: update.elimination_lives.or(self.elimination_lives),
+            tile_weights: update.tile_weights.clone().or(self.tile_weights.clone()),
+            combo_multiplier_enabled: update
+                .combo_multiplier_enabled
+                .or(self.combo_multiplier_enabled),
+            snapshot_interval_seconds: update
+                .snapshot_interval_seconds
+                .or(self.snapshot_interval_seconds),
+            max_moves_per_call: update.max_moves_per_call.or(self.max_moves_per_call),
+            min_move_interval_micros: update
+                .min_move_interval_micros
+                .or(self.min_move_interval_micros),
+            new_board_cooldown_seconds: update
+                .new_board_cooldown_seconds
+                .or(self.new_board_cooldown_seconds),
+        }
+    }
+
+    /// Keys of the settings that differ from `previous` (all keys when there is no previous version)
+    pub fn changed_keys(&self, previous: Option<&LeaderboardSettings>) -> Vec<&'static str> {
+        let Some(previous) = previous else {
+            return vec![
+                "name",
+                "description",
+                "start_time",
+                "end_time",
+                "shard_number",
+                "base_triggerer_count",
+                "entry_token_requirement",
+                "anti_cheat",
+                "streak_bonus_tiers",
+                "allow_variant_conversion",
+                "prize_pool_entries",
+                "pow_difficulty",
+                "max_score_per_player",
+                "warmup_duration_seconds",
+                "countdown_notification_thresholds",
+                "qualifier_settings",
+                "undo_budget",
+                "undo_history_size",
+                "speed_time_limit_seconds",
+                "elimination_lives",
+                "tile_weights",
+                "combo_multiplier_enabled",
+                "snapshot_interval_seconds",
+                "max_moves_per_call",
+                "min_move_interval_micros",
+                "new_board_cooldown_seconds",
+                "season",
+                "power_ups",
+                "tie_break",
+                "mode",
+                "max_participants",
+                "reward_structure",
+                "custom_banned_words",
+            ];
+        };
+        let mut keys = Vec::new();
+        if self.name != previous.name {
+            keys.push("name");
+        }
+        if self.description != previous.description {
+            keys.push("description");
+        }
+        if self.start_time != previous.start_time {
+            keys.push("start_time");
+        }
+        if self.end_time != previous.end_time {
+            keys.push("end_time");
+        }
+        if self.shard_number != previous.shard_number {
+            keys.push("shard_number");
+        }
+        if self.base_triggerer_count != previous.base_triggerer_count {
+            keys.push("base_triggerer_count");
+        }
+        if self.entry_token_requirement != previous.entry_token_requirement {
+            keys.push("entry_token_requirement");
+        }
+        if self.anti_cheat != previous.anti_cheat {
+            keys.push("anti_cheat");
+        }
+        if self.streak_bonus_tiers != previous.streak_bonus_tiers {
+            keys.push("streak_bonus_tiers");
+        }
+        if self.allow_variant_conversion != previous.allow_variant_conversion {
+            keys.push("allow_variant_conversion");
+        }
+        if self.prize_pool_entries != previous.prize_pool_entries {
+            keys.push("prize_pool_entries");
+        }
+        if self.pow_difficulty != previous.pow_difficulty {
+            keys.push("pow_difficulty");
+        }
+        if self.max_score_per_player != previous.max_score_per_player {
+            keys.push("max_score_per_player");
+        }
+        if self.warmup_duration_seconds != previous.warmup_duration_seconds {
+            keys.push("warmup_duration_seconds");
+        }
+        if self.countdown_notification_thresholds != previous.countdown_notification_thresholds {
+            keys.push("countdown_notification_thresholds");
+        }
+        if self.qualifier_settings != previous.qualifier_settings {
+            keys.push("qualifier_settings");
+        }
+        if self.undo_budget != previous.undo_budget {
+            keys.push("undo_budget");
+        }
+        if self.undo_history_size != previous.undo_history_size {
+            keys.push("undo_history_size");
+        }
+        if self.speed_time_limit_seconds != previous.speed_time_limit_seconds {
+            keys.push("speed_time_limit_seconds");
+        }
+        if self.elimination_lives != previous.elimination_lives {
+            keys.push("elimination_lives");
+        }
+        if self.tile_weights != previous.tile_weights {
+            keys.push("tile_weights");
+        }
+        if self.combo_multiplier_enabled != previous.combo_multiplier_enabled {
+            keys.push("combo_multiplier_enabled");
+        }
+        if self.snapshot_interval_seconds != previous.snapshot_interval_seconds {
+            keys.push("snapshot_interval_seconds");
+        }
+        if self.max_moves_per_call != previous.max_moves_per_call {
+            keys.push("max_moves_per_call");
+        }
+        if self.min_move_interval_micros != previous.min_move_interval_micros {
+            keys.push("min_move_interval_micros");
+        }
+        if self.new_board_cooldown_seconds != previous.new_board_cooldown_seconds {
+            keys.push("new_board_cooldown_seconds");
+        }
+        if self.season != previous.season {
+            keys.push("season");
+        }
+        if self.power_ups != previous.power_ups {
+            keys.push("power_ups");
+        }
+        if self.tie_break != previous.tie_break {
+            keys.push("tie_break");
+        }
+        if self.mode != previous.mode {
+            keys.push("mode");
+        }
+        if self.max_participants != previous.max_participants {
+            keys.push("max_participants");
+        }
+        if self.reward_structure != previous.reward_structure {
+            keys.push("reward_structure");
+        }
+        if self.custom_banned_words != previous.custom_banned_words {
+            keys.push("custom_banned_words");
+        }
+        keys
+    }
+}
+
+/// Default countdown announcements: 1 hour, 30 minutes, 10 minutes and 1 minute before the end
+pub const DEFAULT_COUNTDOWN_THRESHOLDS: [u64; 4] = [3600, 1800, 600, 60];
+
+/// Countdown threshold to announce now, if any: the smallest threshold (seconds before the end)
+/// already reached that is below `last_threshold_fired` (0 = none fired yet).
+/// Thresholds skipped between two checks are not announced late.
+pub fn countdown_threshold_crossed(
+    thresholds: &[u64],
+    seconds_remaining: u64,
+    last_threshold_fired: u64,
+) -> Option<u64> {
+    thresholds
+        .iter()
+        .copied()
+        .filter(|threshold| *threshold > 0 && seconds_remaining <= *threshold)
+        .filter(|threshold| last_threshold_fired == 0 || *threshold < last_threshold_fired)
+        .min()
+}
+
+/// Settings version in effect at `timestamp` (history ordered by `effective_at`)
+pub fn effective_settings_at(
+    history: &[SettingsVersion],
+    timestamp: u64,
+) -> Option<&SettingsVersion> {
+    history
+        .iter()
+        .rev()
+        .find(|version| version.effective_at <= timestamp)
+}
+
+/// Token balance required to create boards in a tournament
+#[derive(Debug, Deserialize, Serialize, InputObject, Clone, PartialEq)]
+#[graphql(input_name = "TokenRequirementInput")]
+pub struct TokenRequirement {
+    pub min_balance: Amount,
+    /// Attestation service chain (used when `pre_authorize` is set)
+    pub chain_id: ChainId,
+    /// true = require an attestation from `chain_id` instead of checking the player chain balance
+    pub pre_authorize: bool,
+}
+
+/// Balance attestation issued by an attestation service chain
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct BalanceAttestation {
+    pub held: Amount,
+    pub expires_at: u64, // micros
+}
+
+#[derive(Debug, thiserror::Error, PartialEq)]
+pub enum TournamentEntryError {
+    #[error("Insufficient token balance: required {required}, held {held}")]
+    InsufficientTokenBalance { required: Amount, held: Amount },
+    #[error("Missing balance attestation from the attestation service chain")]
+    MissingAttestation,
+    #[error("Balance attestation has expired")]
+    AttestationExpired,
+}
+
+impl TokenRequirement {
+    /// Direct path: check the player chain's own balance
+    pub fn check_balance(&self, held: Amount) -> Result<(), TournamentEntryError> {
+        if held < self.min_balance {
+            return Err(TournamentEntryError::InsufficientTokenBalance {
+                required: self.min_balance,
+                held,
+            });
+        }
+        Ok(())
+    }
+
+    /// Pre-authorized path: check an attestation received from the attestation chain
+    pub fn check_attestation(
+        &self,
+        attestation: Option<&BalanceAttestation>,
+        current_time: u64,
+    ) -> Result<(), TournamentEntryError> {
+        let attestation = attestation.ok_or(TournamentEntryError::MissingAttestation)?;
+        if current_time >= attestation.expires_at {
+            return Err(TournamentEntryError::AttestationExpired);
+        }
+        self.check_balance(attestation.held)
+    }
+}
+
+/// `PrizePoolEntry::application_id` of the chain's native token
+pub const NATIVE_TOKEN_ID: &str = "native";
+
+/// Prize pool for one token (`NATIVE_TOKEN_ID` or a fungible token application ID).
+/// `payouts[i]` goes to rank i + 1 and the payouts must add up to `amount`.
+#[derive(Debug, Deserialize, Serialize, InputObject, SimpleObject, Clone, PartialEq)]
+#[graphql(input_name = "PrizePoolEntryInput")]
+pub struct PrizePoolEntry {
+    pub application_id: String,
+    pub amount: Amount,
+    #[serde(default)]
+    pub payouts: Vec<Amount>,
+}
+
+#[derive(Debug, thiserror::Error, PartialEq)]
+pub enum PrizePoolError {
+    #[error("Invalid prize pool token application ID: {0}")]
+    InvalidApplicationId(String),
+    #[error("Prize pool lists token {0} more than once")]
+    DuplicateToken(String),
+    #[error("Prize pool for {0} has no payouts")]
+    NoPayouts(String),
+    #[error("Prize payouts for {application_id} add up to {total}, expected {amount}")]
+    PayoutMismatch {
+        application_id: String,
+        total: Amount,
+        amount: Amount,
+    },
+}
+
+impl PrizePoolEntry {
+    pub fn is_native(&self) -> bool {
+        self.application_id == NATIVE_TOKEN_ID
+    }
+
+    pub fn payout_total(&self) -> Amount {
+        self.payouts
+            .iter()
+            .fold(Amount::ZERO, |total, payout| total.saturating_add(*payout))
+    }
+
+    /// Part of a funding transfer the pool still needs (the rest goes back to the funder)
+    pub fn accepted_funding(&self, funded: Amount, amount: Amount) -> Amount {
+        amount.min(self.amount.saturating_sub(funded))
+    }
+
+    /// Every token listed once, and each token's payouts add up to its pool amount
+    pub fn validate_all(entries: &[PrizePoolEntry]) -> Result<(), PrizePoolError> {
+        for (index, entry) in entries.iter().enumerate() {
+            if !entry.is_native() && ApplicationId::from_str(&entry.application_id).is_err() {
+                return Err(PrizePoolError::InvalidApplicationId(
+                    entry.application_id.clone(),
+                ));
+            }
+            if entries[..index]
+                .iter()
+                .any(|other| other.application_id == entry.application_id)
+            {
+                return Err(PrizePoolError::DuplicateToken(entry.application_id.clone()));
+            }
+            if entry.payouts.is_empty() {
+                return Err(PrizePoolError::NoPayouts(entry.application_id.clone()));
+            }
+            let total = entry.payout_total();
+            if total != entry.amount {
+                return Err(PrizePoolError::PayoutMismatch {
+                    application_id: entry.application_id.clone(),
+                    total,
+                    amount: entry.amount,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 🏁 Whether a tournament still accepts scores or has been finalised
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub enum TournamentStatus {
+    #[default]
+    Active,
+    Finalised,
+}
+
+scalar!(TournamentStatus);
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum FinaliseError {
+    #[error("Tournament is already finalised")]
+    AlreadyFinalised,
+    #[error("Tournament can only be finalised after it ends")]
+    NotEnded,
+}
+
+/// Basis points of a whole reward pool
+pub const REWARD_SHARE_TOTAL_BPS: u32 = 10_000;
+
+/// Ranks `from_rank..=to_rank` (1-based) split `share_bps` of the reward pool evenly
+#[derive(Debug, Deserialize, Serialize, InputObject, SimpleObject, Clone, PartialEq)]
+#[graphql(input_name = "RewardTierInput")]
+pub struct RewardTier {
+    pub from_rank: u32,
+    pub to_rank: u32,
+    pub share_bps: u32,
+}
+
+/// 🏁 Tokens the main chain pays to the top ranks when a tournament is finalised
+#[derive(Debug, Deserialize, Serialize, InputObject, SimpleObject, Clone, PartialEq)]
+#[graphql(input_name = "RewardStructureInput")]
+pub struct RewardStructure {
+    pub prize_pool_tokens: u64,
+    pub tiers: Vec<RewardTier>,
+}
+
+#[derive(Debug, thiserror::Error, PartialEq)]
+pub enum RewardStructureError {
+    #[error("Reward tier ranks {from_rank}..={to_rank} are not a valid range")]
+    InvalidRange { from_rank: u32, to_rank: u32 },
+    #[error("Reward tiers must be in rank order without overlap (rank {0})")]
+    OverlappingTiers(u32),
+    #[error("Reward tier shares add up to {0} basis points, more than the whole pool")]
+    SharesExceedPool(u32),
+}
+
+impl RewardStructure {
+    /// Tiers in rank order, each starting after the previous one, sharing at most the whole pool
+    pub fn validate(&self) -> Result<(), RewardStructureError> {
+        let mut next_rank = 1;
+        let mut total_bps: u32 = 0;
+        for tier in &self.tiers {
+            if tier.from_rank == 0 || tier.from_rank > tier.to_rank {
+                return Err(RewardStructureError::InvalidRange {
+                    from_rank: tier.from_rank,
+                    to_rank: tier.to_rank,
+                });
+            }
+            if tier.from_rank < next_rank {
+                return Err(RewardStructureError::OverlappingTiers(tier.from_rank));
+            }
+            next_rank = tier.to_rank.saturating_add(1);
+            total_bps = total_bps.saturating_add(tier.share_bps);
+        }
+        if total_bps > REWARD_SHARE_TOTAL_BPS {
+            return Err(RewardStructureError::SharesExceedPool(total_bps));
+        }
+        Ok(())
+    }
+
+    /// Reward of each rank (index 0 = rank 1) among `ranked` players; ranks outside every tier
+    /// get `Amount::ZERO`. Tiers below the last ranked player keep their undivided share.
+    pub fn payouts(&self, ranked: usize) -> Vec<Amount> {
+        let pool = u128::from(Amount::from_tokens(u128::from(self.prize_pool_tokens)));
+        let mut payouts = Vec::new();
+        for tier in &self.tiers {
+            let from = tier.from_rank as usize;
+            if from > ranked {
+                break;
+            }
+            let players = u128::from(tier.to_rank - tier.from_rank) + 1;
+            let share = pool * u128::from(tier.share_bps) / u128::from(REWARD_SHARE_TOTAL_BPS);
+            let reward = Amount::from_attos(share / players);
+            payouts.resize(from - 1, Amount::ZERO);
+            payouts.resize((tier.to_rank as usize).min(ranked), reward);
+        }
+        payouts
+    }
+}
+
+/// Most entries in one leaderboard page
+pub const MAX_LEADERBOARD_PAGE_SIZE: u32 = 100;
+
+/// 📄 A player's standing on a leaderboard
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+pub struct RankedScore {
+    pub player: String,
+    pub score: u64,
+    pub cap_reached_at: u64, // micros, u64::MAX = cap not reached
+    #[serde(default)]
+    pub tie_break: u64, // `TieBreakPolicy::key`, 0 = no policy
+    #[serde(default)]
+    pub time_to_target: u64, // micros to the speed run target tile, 0 on high score leaderboards
+}
+
+impl RankedScore {
+    fn sort_key(&self) -> (u64, Reverse<u64>, u64, u64, &str) {
+        (
+            self.time_to_target,
+            Reverse(self.score),
+            self.cap_reached_at,
+            self.tie_break,
+            &self.player,
+        )
+    }
+
+    /// Leaderboard order: fastest speed run first, then highest score, earliest score cap first
+    /// on ties, then the tie-break policy, then by name
+    pub fn rank_all(entries: &mut [RankedScore]) {
+        entries.sort_by(|a, b| a.sort_key().cmp(&b.sort_key()));
+    }
+}
+
+/// 🏆 Tile milestone of a highest tile: the largest power of two not above it (0 for no tile)
+pub fn tile_milestone(highest_tile: u64) -> u32 {
+    match highest_tile {
+        0 => 0,
+        tile => 1 << (63 - tile.leading_zeros()).min(31),
+    }
+}
+
+/// 📄 Page cursor: the rank offset after a page and the last entry on it. The next page
+/// resumes after that entry, so players inserted between requests do not shift it.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+pub struct LeaderboardCursor {
+    pub offset: u32,
+    pub last: RankedScore,
+}
+
+impl LeaderboardCursor {
+    pub fn encode(&self) -> String {
+        URL_SAFE_NO_PAD.encode(serde_json::to_vec(self).unwrap())
+    }
+
+    pub fn decode(cursor: &str) -> Option<LeaderboardCursor> {
+        let bytes = URL_SAFE_NO_PAD.decode(cursor).ok()?;
+        serde_json::from_slice(&bytes).ok()
+    }
+
+    /// Index in `ranked` (in `RankedScore::rank_all` order) of the first entry after the cursor
+    pub fn resume_index(&self, ranked: &[RankedScore]) -> usize {
+        ranked.partition_point(|entry| entry.sort_key() <= self.last.sort_key())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_power_up_chance_limit() {
+        let config = |obstacle_tile_chance_percent, bomb_tile_chance_percent| PowerUpConfig {
+            obstacle_tile_chance_percent,
+            bomb_tile_chance_percent,
+        };
+        assert!(config(10, 10).validate().is_ok());
+        assert!(config(20, 0).validate().is_ok());
+        assert!(config(15, 6).validate().is_err());
+        assert!(config(255, 255).validate().is_err());
+    }
+
+    #[test]
+    fn test_season_rotation() {
+        let season = SeasonConfig {
+            season_duration_seconds: 3600,
+            max_seasons: 3,
+            carry_over_percent: 25,
+        };
+        assert!(season.validate().is_ok());
+        assert_eq!(season.carry_over(1000), 250);
+        assert!(season.has_next_season(1));
+        assert!(!season.has_next_season(2));
+        assert!(SeasonConfig {
+            carry_over_percent: 101,
+            ..season
+        }
+        .validate()
+        .is_err());
+    }
+
+    #[test]
+    fn test_tie_break_policies() {
+        // (player, highest tile, moves, achieved at, efficiency), all on the same score
+        let games = [
+            ("ann", 1024, 300, 50, 40),
+            ("bob", 2048, 400, 30, 30),
+            ("cat", 512, 200, 40, 60),
+            ("dan", 256, 500, 10, 20),
+        ];
+        let order = |policy: TieBreakPolicy| {
+            let mut ranked: Vec<RankedScore> = games
+                .iter()
+                .map(
+                    |(player, tile, moves, achieved_at, efficiency)| RankedScore {
+                        player: player.to_string(),
+                        score: 12_000,
+                        cap_reached_at: u64::MAX,
+                        tie_break: policy.key(*tile, *moves, *achieved_at, *efficiency),
+                        time_to_target: 0,
+                    },
+                )
+                .collect();
+            RankedScore::rank_all(&mut ranked);
+            ranked
+                .into_iter()
+                .map(|entry| entry.player)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            order(TieBreakPolicy::ByHighestTile),
+            ["bob", "ann", "cat", "dan"]
+        );
+        assert_eq!(
+            order(TieBreakPolicy::ByFewestMoves),
+            ["cat", "ann", "bob", "dan"]
+        );
+        assert_eq!(
+            order(TieBreakPolicy::ByEarliestAchievement),
+            ["dan", "bob", "cat", "ann"]
+        );
+        assert_eq!(
+            order(TieBreakPolicy::ByBestEfficiency),
+            ["cat", "ann", "bob", "dan"]
+        );
+
+        // A higher score still wins over any tie-break
+        let mut ranked = vec![
+            RankedScore {
+                player: "low".to_string(),
+                score: 100,
+                cap_reached_at: u64::MAX,
+                tie_break: 0,
+                time_to_target: 0,
+            },
+            RankedScore {
+                player: "high".to_string(),
+                score: 200,
+                cap_reached_at: u64::MAX,
+                tie_break: u64::MAX,
+                time_to_target: 0,
+            },
+        ];
+        RankedScore::rank_all(&mut ranked);
+        assert_eq!(ranked[0].player, "high");
+    }
+
+    #[test]
+    fn test_speed_run_ranks_by_time_to_target() {
+        let mode = LeaderboardMode::SpeedRun { target_tile: 2048 };
+        assert_eq!(mode.target_tile(), Some(2048));
+        assert_eq!(LeaderboardMode::default().target_tile(), None);
+
+        let entry = |player: &str, score, time_to_target| RankedScore {
+            player: player.to_string(),
+            score,
+            cap_reached_at: u64::MAX,
+            tie_break: 0,
+            time_to_target,
+        };
+        // The fastest run wins whatever the score
+        let mut ranked = vec![
+            entry("ann", 30_000, 900_000_000),
+            entry("bob", 20_000, 600_000_000),
+            entry("cat", 25_000, 600_000_000),
+        ];
+        RankedScore::rank_all(&mut ranked);
+        let order: Vec<&str> = ranked.iter().map(|entry| entry.player.as_str()).collect();
+        assert_eq!(order, vec!["cat", "bob", "ann"]);
+    }
+
+    #[test]
+    fn test_tile_milestone_filter() {
+        assert_eq!(tile_milestone(0), 0);
+        assert_eq!(tile_milestone(2048), 2048);
+        assert_eq!(tile_milestone(4095), 2048);
+        assert_eq!(tile_milestone(4096), 4096);
+
+        // Only the player in a bucket at or above the 4096 milestone is kept
+        let tiles = [("ann", 2048), ("bob", 4096), ("cat", 1024)];
+        let qualified: Vec<&str> = tiles
+            .iter()
+            .filter(|(_, tile)| tile_milestone(*tile) >= tile_milestone(4096))
+            .map(|(player, _)| *player)
+            .collect();
+        assert_eq!(qualified, vec!["bob"]);
+    }
+
+    #[test]
+    fn test_leaderboard_cursor_survives_inserts() {
+        let entry = |player: &str, score| RankedScore {
+            player: player.to_string(),
+            score,
+            cap_reached_at: u64::MAX,
+            tie_break: 0,
+            time_to_target: 0,
+        };
+        let mut ranked = vec![
+            entry("erin", 100),
+            entry("alice", 500),
+            entry("dave", 200),
+            entry("bob", 400),
+            entry("carol", 300),
+        ];
+        RankedScore::rank_all(&mut ranked);
+
+        // First page of two, then the cursor goes through its string form
+        let cursor = LeaderboardCursor {
+            offset: 2,
+            last: ranked[1].clone(),
+        };
+        let cursor = LeaderboardCursor::decode(&cursor.encode()).unwrap();
+        assert_eq!(cursor.last.player, "bob");
+        assert!(LeaderboardCursor::decode("not a cursor").is_none());
+
+        // Players join above and below the cursor before the next page is requested
+        ranked.extend([entry("zed", 900), entry("amy", 400), entry("fay", 250)]);
+        RankedScore::rank_all(&mut ranked);
+        let start = cursor.resume_index(&ranked);
+        let next_page: Vec<&str> = ranked[start..start + 3]
+            .iter()
+            .map(|entry| entry.player.as_str())
+            .collect();
+        assert_eq!(next_page, vec!["carol", "fay", "dave"]);
+    }
+    use std::str::FromStr;
+
+    fn requirement(pre_authorize: bool) -> TokenRequirement {
+        TokenRequirement {
+            min_balance: Amount::from_tokens(10),
+            chain_id: ChainId::from_str(
+                "e476187f6ddfeb9d588c7b45d3df334d5501d6499b3f9ad5595cae86cce16a65",
+            )
+            .unwrap(),
+            pre_authorize,
+        }
+    }
+
+    fn settings(name: &str, end_time: &str) -> LeaderboardSettings {
+        LeaderboardSettings {
+            name: name.to_string(),
+            description: None,
+            start_time: "0".to_string(),
+            end_time: end_time.to_string(),
+            shard_number: Some(1),
+            base_triggerer_count: None,
+            entry_token_requirement: None,
+            clear_entry_token_requirement: None,
+            anti_cheat: None,
+            moderation_override: None,
+            streak_bonus_tiers: None,
+            allow_variant_conversion: None,
+            prize_pool_entries: None,
+            pow_difficulty: None,
+            max_score_per_player: None,
+            warmup_duration_seconds: None,
+            countdown_notification_thresholds: None,
+            qualifier_settings: None,
+            undo_budget: None,
+            undo_history_size: None,
+            speed_time_limit_seconds: None,
+            elimination_lives: None,
+            tile_weights: None,
+            combo_multiplier_enabled: None,
+            snapshot_interval_seconds: None,
+            max_moves_per_call: None,
+            min_move_interval_micros: None,
+            new_board_cooldown_seconds: None,
+            season: None,
+            power_ups: None,
+            tie_break: None,
+            mode: None,
+            max_participants: None,
+            reward_structure: None,
+            custom_banned_words: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_settings_hot_reload_history() {
+        let created = settings("Cup", "5000");
+        let updated = created.apply_update(&settings("", "9000"));
+        assert_eq!(updated.name, "Cup");
+        assert_eq!(updated.changed_keys(Some(&created)), vec!["end_time"]);
+        let all_keys = created.changed_keys(None);
+        assert_eq!(all_keys.len(), 33);
+        assert!(all_keys.contains(&"reward_structure"));
+        assert!(all_keys.contains(&"custom_banned_words"));
+
+        let history = vec![
+            SettingsVersion {
+                effective_at: 100,
+                settings: created,
+            },
+            SettingsVersion {
+                effective_at: 200,
+                settings: updated,
+            },
+        ];
+        assert!(effective_settings_at(&history, 50).is_none());
+        assert_eq!(
+            effective_settings_at(&history, 150)
+                .unwrap()
+                .settings
+                .end_time,
+            "5000"
+        );
+        assert_eq!(
+            effective_settings_at(&history, 200)
+                .unwrap()
+                .settings
+                .end_time,
+            "9000"
+        );
+    }
+
+    #[test]
+    fn test_countdown_threshold_crossed() {
+        let thresholds = DEFAULT_COUNTDOWN_THRESHOLDS;
+        assert_eq!(countdown_threshold_crossed(&thresholds, 4000, 0), None);
+        assert_eq!(
+            countdown_threshold_crossed(&thresholds, 3500, 0),
+            Some(3600)
+        );
+        assert_eq!(countdown_threshold_crossed(&thresholds, 3000, 3600), None);
+        // Both 30 and 10 minutes passed since the last check: only the latest is announced
+        assert_eq!(
+            countdown_threshold_crossed(&thresholds, 500, 3600),
+            Some(600)
+        );
+        assert_eq!(countdown_threshold_crossed(&thresholds, 30, 60), None);
+    }
+
+    #[test]
+    fn test_prize_pool_validation() {
+        let entry = |application_id: &str, amount: u128, payouts: &[u128]| PrizePoolEntry {
+            application_id: application_id.to_string(),
+            amount: Amount::from_tokens(amount),
+            payouts: payouts
+                .iter()
+                .map(|payout| Amount::from_tokens(*payout))
+                .collect(),
+        };
+        assert!(PrizePoolEntry::validate_all(&[entry(NATIVE_TOKEN_ID, 10, &[6, 3, 1])]).is_ok());
+        assert_eq!(
+            PrizePoolEntry::validate_all(&[entry(NATIVE_TOKEN_ID, 10, &[6, 3])]),
+            Err(PrizePoolError::PayoutMismatch {
+                application_id: NATIVE_TOKEN_ID.to_string(),
+                total: Amount::from_tokens(9),
+                amount: Amount::from_tokens(10),
+            })
+        );
+        assert_eq!(
+            PrizePoolEntry::validate_all(&[
+                entry(NATIVE_TOKEN_ID, 5, &[5]),
+                entry(NATIVE_TOKEN_ID, 1, &[1]),
+            ]),
+            Err(PrizePoolError::DuplicateToken(NATIVE_TOKEN_ID.to_string()))
+        );
+        assert_eq!(
+            PrizePoolEntry::validate_all(&[entry("usdc", 5, &[5])]),
+            Err(PrizePoolError::InvalidApplicationId("usdc".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_prize_pool_funding_is_capped_at_pool_amount() {
+        let entry = PrizePoolEntry {
+            application_id: NATIVE_TOKEN_ID.to_string(),
+            amount: Amount::from_tokens(10),
+            payouts: vec![Amount::from_tokens(10)],
+        };
+        let accepted = |funded: u128, amount: u128| {
+            entry.accepted_funding(Amount::from_tokens(funded), Amount::from_tokens(amount))
+        };
+        assert_eq!(accepted(0, 4), Amount::from_tokens(4));
+        assert_eq!(accepted(4, 10), Amount::from_tokens(6));
+        assert_eq!(accepted(10, 1), Amount::ZERO);
+    }
+
+    #[test]
+    fn test_reward_structure_payouts() {
+        let tier = |from_rank, to_rank, share_bps| RewardTier {
+            from_rank,
+            to_rank,
+            share_bps,
+        };
+        let rewards = RewardStructure {
+            prize_pool_tokens: 100,
+            tiers: vec![tier(1, 1, 5_000), tier(2, 3, 3_000), tier(5, 5, 1_000)],
+        };
+        assert!(rewards.validate().is_ok());
+        assert_eq!(
+            rewards.payouts(10),
+            [50, 15, 15, 0, 10].map(Amount::from_tokens).to_vec()
+        );
+        assert_eq!(
+            rewards.payouts(2),
+            [50, 15].map(Amount::from_tokens).to_vec()
+        );
+        assert!(rewards.payouts(0).is_empty());
+
+        let invalid = |tiers| RewardStructure {
+            prize_pool_tokens: 100,
+            tiers,
+        };
+        assert_eq!(
+            invalid(vec![tier(2, 1, 100)]).validate(),
+            Err(RewardStructureError::InvalidRange {
+                from_rank: 2,
+                to_rank: 1
+            })
+        );
+        assert_eq!(
+            invalid(vec![tier(1, 3, 100), tier(3, 4, 100)]).validate(),
+            Err(RewardStructureError::OverlappingTiers(3))
+        );
+        assert_eq!(
+            invalid(vec![tier(1, 1, 6_000), tier(2, 2, 5_000)]).validate(),
+            Err(RewardStructureError::SharesExceedPool(11_000))
+        );
+    }
+
+    #[test]
+    fn test_token_gate_balance_path() {
+        let requirement = requirement(false);
+        assert!(requirement.check_balance(Amount::from_tokens(10)).is_ok());
+        assert_eq!(
+            requirement.check_balance(Amount::from_tokens(3)),
+            Err(TournamentEntryError::InsufficientTokenBalance {
+                required: Amount::from_tokens(10),
+                held: Amount::from_tokens(3),
+            })
+        );
+    }
+
+    #[test]
+    fn test_token_gate_attestation_path() {
+        let requirement = requirement(true);
+        let attestation = BalanceAttestation {
+            held: Amount::from_tokens(12),
+            expires_at: 1_000,
+        };
+        assert!(requirement
+            .check_attestation(Some(&attestation), 999)
+            .is_ok());
+        assert_eq!(
+            requirement.check_attestation(Some(&attestation), 1_000),
+            Err(TournamentEntryError::AttestationExpired)
+        );
+        assert_eq!(
+            requirement.check_attestation(None, 0),
+            Err(TournamentEntryError::MissingAttestation)
+        );
+    }
+
+    #[test]
+    fn test_token_gate_update_can_clear_requirement() {
+        let created = LeaderboardSettings {
+            entry_token_requirement: Some(requirement(false)),
+            ..settings("Cup", "5000")
+        };
+        let kept = created.apply_update(&settings("", "5000"));
+        assert_eq!(kept.entry_token_requirement, Some(requirement(false)));
+
+        let cleared = kept.apply_update(&LeaderboardSettings {
+            clear_entry_token_requirement: Some(true),
+            ..settings("", "5000")
+        });
+        assert_eq!(cleared.entry_token_requirement, None);
+        assert_eq!(
+            cleared.changed_keys(Some(&kept)),
+            vec!["entry_token_requirement"]
+        );
+    }
 }
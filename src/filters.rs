@@ -0,0 +1,169 @@
+//! Profanity filter for usernames and tournament names/descriptions.
+//!
+//! Words are compared whole and case-insensitively after undoing common digit and symbol
+//! substitutions ("5h1t" -> "shit"); underscores and remaining digits separate words.
+
+/// Words rejected everywhere, in normalised (lowercase, unsubstituted) form
+pub const BANNED_WORDS: &[&str] = &[
+    "arse",
+    "arsehole",
+    "ass",
+    "asshole",
+    "bastard",
+    "bitch",
+    "bollocks",
+    "bullshit",
+    "cock",
+    "cunt",
+    "dick",
+    "dickhead",
+    "douche",
+    "fag",
+    "fuck",
+    "fucker",
+    "fucking",
+    "motherfucker",
+    "nazi",
+    "piss",
+    "prick",
+    "pussy",
+    "shit",
+    "shitty",
+    "slut",
+    "twat",
+    "wanker",
+    "whore",
+];
+
+/// Most words a tournament can add to the filter
+pub const MAX_CUSTOM_BANNED_WORDS: usize = 50;
+
+/// Longest word a tournament can add to the filter
+pub const MAX_CUSTOM_BANNED_WORD_LENGTH: usize = 20;
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum FilterError {
+    #[error("{field} contains a banned word")]
+    ProfanityViolation { field: String },
+    #[error("At most {max} custom banned words are allowed, got {count}")]
+    TooManyCustomWords { count: usize, max: usize },
+    #[error("Custom banned word \"{word}\" is longer than {max} characters")]
+    CustomWordTooLong { word: String, max: usize },
+}
+
+/// Lowercase `c` with digit/symbol substitutions undone (other characters unchanged)
+fn normalise_char(c: char) -> char {
+    match c {
+        '0' => 'o',
+        '1' | '!' => 'i',
+        '3' => 'e',
+        '4' | '@' => 'a',
+        '5' | '$' => 's',
+        '7' => 't',
+        '8' => 'b',
+        c => c.to_ascii_lowercase(),
+    }
+}
+
+/// Normalised words of `s`: anything that is not a letter after substitution separates words
+fn normalised_words(s: &str) -> impl Iterator<Item = String> + '_ {
+    s.split(|c: char| !normalise_char(c).is_alphabetic())
+        .filter(|word| !word.is_empty())
+        .map(|word| word.chars().map(normalise_char).collect())
+}
+
+/// `true` unless `s` contains one of `BANNED_WORDS` as a whole word
+pub fn passes_profanity_filter(s: &str) -> bool {
+    passes_profanity_filter_with(s, &[])
+}
+
+/// `true` unless `s` contains one of `BANNED_WORDS` or `custom_banned_words` as a whole word
+pub fn passes_profanity_filter_with(s: &str, custom_banned_words: &[String]) -> bool {
+    let custom: Vec<String> = custom_banned_words
+        .iter()
+        .flat_map(|word| normalised_words(word))
+        .collect();
+    normalised_words(s)
+        .all(|word| !BANNED_WORDS.contains(&word.as_str()) && !custom.contains(&word))
+}
+
+/// Reject `value` of `field` when it fails the filter
+pub fn check_profanity(
+    field: &str,
+    value: &str,
+    custom_banned_words: &[String],
+) -> Result<(), FilterError> {
+    if passes_profanity_filter_with(value, custom_banned_words) {
+        Ok(())
+    } else {
+        Err(FilterError::ProfanityViolation {
+            field: field.to_string(),
+        })
+    }
+}
+
+/// A tournament's own banned words: at most `MAX_CUSTOM_BANNED_WORDS` words of at most
+/// `MAX_CUSTOM_BANNED_WORD_LENGTH` characters
+pub fn validate_custom_banned_words(words: &[String]) -> Result<(), FilterError> {
+    if words.len() > MAX_CUSTOM_BANNED_WORDS {
+        return Err(FilterError::TooManyCustomWords {
+            count: words.len(),
+            max: MAX_CUSTOM_BANNED_WORDS,
+        });
+    }
+    if let Some(word) = words
+        .iter()
+        .find(|word| word.chars().count() > MAX_CUSTOM_BANNED_WORD_LENGTH)
+    {
+        return Err(FilterError::CustomWordTooLong {
+            word: word.clone(),
+            max: MAX_CUSTOM_BANNED_WORD_LENGTH,
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profanity_filter_normalises_words() {
+        assert!(passes_profanity_filter("lpaydat"));
+        assert!(passes_profanity_filter("Weekend Cup 2048"));
+        // Whole words only
+        assert!(passes_profanity_filter("Scunthorpe classic"));
+        assert!(passes_profanity_filter("grasshopper"));
+
+        assert!(!passes_profanity_filter("SHIT"));
+        assert!(!passes_profanity_filter("big_5h1t"));
+        assert!(!passes_profanity_filter("b1tch99"));
+        assert!(!passes_profanity_filter("what the fuck"));
+
+        let custom = vec!["Tetris".to_string()];
+        assert!(passes_profanity_filter("T3tr1s fans"));
+        assert!(!passes_profanity_filter_with("T3tr1s fans", &custom));
+        assert_eq!(
+            check_profanity("username", "t3tris_pro", &custom),
+            Err(FilterError::ProfanityViolation {
+                field: "username".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_custom_banned_words_limits() {
+        assert!(validate_custom_banned_words(&["tetris".to_string()]).is_ok());
+        assert_eq!(
+            validate_custom_banned_words(&vec!["word".to_string(); 51]),
+            Err(FilterError::TooManyCustomWords { count: 51, max: 50 })
+        );
+        assert_eq!(
+            validate_custom_banned_words(&["a".repeat(21)]),
+            Err(FilterError::CustomWordTooLong {
+                word: "a".repeat(21),
+                max: 20
+            })
+        );
+    }
+}
@@ -1,4 +1,7 @@
-use crate::{rnd_range, Direction, ROW_MASK};
+use crate::{
+    lehmer_next, next_empty_cell, rnd_range, spawn_state, Direction, SpawnGenerator, TileWeight,
+    TileWeightsError, ROW_MASK,
+};
 use lazy_static::lazy_static;
 use std::ops::Add;
 include!("../moves_data.rs");
@@ -37,6 +40,88 @@ lazy_static! {
     };
 }
 
+/// Tile spawn probabilities of a game, built once from its tournament's tile weights,
+/// and the generator drawing from them.
+///
+/// The default (no weights) keeps the classic 90% twos and 10% fours of `Game::tile`,
+/// drawn by `SpawnGenerator::CURRENT`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpawnDistribution {
+    // (tile exponent, cumulative weight percent), in the order the weights were given
+    cumulative: Vec<(u64, u8)>,
+    generator: SpawnGenerator,
+}
+
+impl Default for SpawnDistribution {
+    fn default() -> Self {
+        SpawnDistribution {
+            cumulative: Vec::new(),
+            generator: SpawnGenerator::CURRENT,
+        }
+    }
+}
+
+impl SpawnDistribution {
+    /// Builds the distribution, rejecting weights that fail `TileWeight::validate_all`.
+    ///
+    /// ```
+    /// use game2048::{SpawnDistribution, TileWeight};
+    ///
+    /// let weights = [
+    ///     TileWeight { tile_value: 2, weight_percent: 80 },
+    ///     TileWeight { tile_value: 8, weight_percent: 20 },
+    /// ];
+    /// let spawns = SpawnDistribution::from_weights(&weights).unwrap();
+    /// let mut state = 7;
+    /// assert!([1, 3].contains(&spawns.sample(&mut state)));
+    /// ```
+    pub fn from_weights(weights: &[TileWeight]) -> Result<Self, TileWeightsError> {
+        TileWeight::validate_all(weights)?;
+        let mut total = 0;
+        let cumulative = weights
+            .iter()
+            .map(|weight| {
+                total += weight.weight_percent;
+                (weight.tile_value.trailing_zeros() as u64, total)
+            })
+            .collect();
+        Ok(SpawnDistribution {
+            cumulative,
+            ..Default::default()
+        })
+    }
+
+    /// The same probabilities drawn by `generator` (the one recorded on the board)
+    pub fn with_generator(self, generator: SpawnGenerator) -> Self {
+        SpawnDistribution { generator, ..self }
+    }
+
+    pub fn generator(&self) -> SpawnGenerator {
+        self.generator
+    }
+
+    /// True for the classic 90% twos and 10% fours
+    pub fn is_classic(&self) -> bool {
+        self.cumulative.is_empty()
+    }
+
+    /// Returns the exponent of the next spawned tile.
+    pub fn sample(&self, state: &mut u64) -> u64 {
+        if self.is_classic() {
+            return Game::tile(state);
+        }
+        self.tile_for_roll((lehmer_next(state) % 100) as u8)
+    }
+
+    /// Exponent of the tile whose weight range holds `roll` (0..100)
+    fn tile_for_roll(&self, roll: u8) -> u64 {
+        self.cumulative
+            .iter()
+            .find(|(_, cumulative)| roll < *cumulative)
+            .map_or(1, |(tile, _)| *tile)
+    }
+}
+
 /// Struct used to play a single game of 2048.
 ///
 /// `tfe::Game` uses a single `u64` as board value.
@@ -51,25 +136,44 @@ pub struct Game {
     pub board_id: String,
     pub username: String,
     pub timestamp: u64,
+    pub spawns: SpawnDistribution,
 }
 
 impl Game {
     /// Constructs a new `tfe::Game`.
     /// Accepts either a u32 or any string as seed
     pub fn new(board_id: &str, username: &str, timestamp: u64) -> Self {
+        Self::with_spawns(board_id, username, timestamp, SpawnDistribution::default())
+    }
+
+    /// Constructs a new game whose tiles spawn from `spawns`, including the two starting tiles.
+    pub fn with_spawns(
+        board_id: &str,
+        username: &str,
+        timestamp: u64,
+        spawns: SpawnDistribution,
+    ) -> Self {
         let mut game = Game {
             board: 0x0000_0000_0000_0000_u64,
             board_id: board_id.to_string(),
             username: username.to_string(),
             timestamp,
+            spawns,
         };
 
-        game.board |= Self::spawn_tile(&game.board_id, &game.username, game.timestamp, game.board);
+        game.board |= Self::spawn_tile(
+            &game.board_id,
+            &game.username,
+            game.timestamp,
+            game.board,
+            &game.spawns,
+        );
         game.board |= Self::spawn_tile(
             &game.board_id,
             &game.username,
             game.timestamp - 1,
             game.board,
+            &game.spawns,
         );
 
         game
@@ -87,27 +191,29 @@ impl Game {
     /// Simple example:
     ///
     /// ```
-    /// use tfe::{Game, Direction};
+    /// use game2048::{Game, Direction};
     ///
     /// let board = 0x0000_0000_0022_1100;
-    /// let moved = Game::execute(board, &[Direction::Left]);
+    /// let mut game = Game {
+    ///     board,
+    ///     board_id: "board".to_string(),
+    ///     username: "player".to_string(),
+    ///     timestamp: 1,
+    ///     spawns: Default::default(),
+    /// };
+    /// let moved = game.execute(Direction::Left);
     ///
     /// // | 0 | 0 | 0 | 0 |      | 0 | 0 | 0 | 0 |
     /// // | 0 | 0 | 0 | 0 |  =>  | 0 | 0 | 0 | 0 |
     /// // | 0 | 0 | 4 | 4 |      | 8 | 0 | 0 | 0 |
     /// // | 2 | 2 | 0 | 0 |      | 4 | 0 | 0 | 0 |
+    /// //                          (+ one spawned tile)
     ///
     /// assert_eq!(board, 0x0000_0000_0022_1100);
-    /// assert_eq!(moved, 0x0000_0000_3000_2000);
+    /// assert_eq!(moved & 0x0000_0000_F000_F000, 0x0000_0000_3000_2000);
     /// ```
     pub fn execute(&mut self, direction: Direction) -> u64 {
-        let mut current_board = self.board;
-        current_board = match direction {
-            Direction::Left => Self::move_left(current_board),
-            Direction::Right => Self::move_right(current_board),
-            Direction::Down => Self::move_down(current_board),
-            Direction::Up => Self::move_up(current_board),
-        };
+        let mut current_board = Self::slide(self.board, direction);
 
         if current_board != self.board {
             current_board = current_board
@@ -116,12 +222,33 @@ impl Game {
                     &self.username,
                     self.timestamp,
                     current_board,
+                    &self.spawns,
                 )
         }
 
         current_board
     }
 
+    /// Slides and merges the tiles of `board` in `direction`, without spawning a tile.
+    pub fn slide(board: u64, direction: Direction) -> u64 {
+        match direction {
+            Direction::Left => Self::move_left(board),
+            Direction::Right => Self::move_right(board),
+            Direction::Down => Self::move_down(board),
+            Direction::Up => Self::move_up(board),
+        }
+    }
+
+    /// Returns the tile (`2` or `4`) spawned by the move from `board` to `board_after`,
+    /// or `0` if the move did not spawn one.
+    pub fn spawned_tile(board: u64, direction: Direction, board_after: u64) -> u64 {
+        let spawned = Self::slide(board, direction) ^ board_after;
+        (0..16)
+            .map(|i| (spawned >> (i * 4)) & 0xF)
+            .find(|tile| *tile != 0)
+            .map_or(0, |tile| 1 << tile)
+    }
+
     /// Converts a 64-bit board representation to a 4x4 matrix of u16 values.
     ///
     /// This function takes a u64 board representation where each 4 bits represent
@@ -139,6 +266,8 @@ impl Game {
     /// # Example
     ///
     /// ```
+    /// use game2048::Game;
+    ///
     /// let board = 0x0000_0000_0022_1100; // represents a board with 1, 1, 2, 2 in the bottom two rows
     /// let matrix = Game::convert_to_matrix(board);
     /// assert_eq!(matrix, [
@@ -161,7 +290,7 @@ impl Game {
     /// Determines if the game has ended.
     ///
     /// The game is considered ended if:
-    /// 1. Any tile on the board has reached the value of 2048.
+    /// 1. Any tile on the board has reached the value of 32768.
     /// 2. No moves in any direction (left, right, up, down) result in a change in the board.
     ///
     /// # Arguments
@@ -170,18 +299,18 @@ impl Game {
     ///
     /// # Returns
     ///
-    /// * `true` if the game is ended, either by reaching 2048 or having no possible moves left.
+    /// * `true` if the game is ended, either by reaching 32768 or having no possible moves left.
     /// * `false` otherwise.
     ///
     /// # Examples
     ///
     /// ```
-    /// use tfe::Game;
+    /// use game2048::Game;
     ///
-    /// let board = 0x0000_0000_0000_0B00; // A board with a tile value of 2048
+    /// let board = 0x0000_0000_0000_0F00; // A board with a tile value of 32768
     /// assert!(Game::is_ended(board)); // Game should be ended
     ///
-    /// let board = 0x0000_0000_0000_0000; // An empty board
+    /// let board = 0x0000_0000_0000_0011; // A board with two mergeable tiles
     /// assert!(!Game::is_ended(board)); // Game should not be ended
     /// ```
     pub fn is_ended(board: u64) -> bool {
@@ -195,22 +324,13 @@ impl Game {
         }
 
         // Check if any move changes the board
-        let left = Self::move_left(board);
-        let right = Self::move_right(board);
-        let up = Self::move_up(board);
-        let down = Self::move_down(board);
-
-        if board == left && board == right && board == up && board == down {
-            return true;
-        }
-
-        false
+        !crate::Moves::has_any_valid_move(board)
     }
 
     /// Returns a transposed board where rows are transformed into columns and vice versa.
     ///
     /// ```
-    /// use tfe::Game;
+    /// use game2048::Game;
     ///
     /// // | F | E | D | C |       | F | B | 7 | 3 |
     /// // | B | A | 9 | 8 |   =>  | E | A | 6 | 2 |
@@ -239,7 +359,7 @@ impl Game {
     /// # Examples
     ///
     /// ```
-    /// use tfe::Game;
+    /// use game2048::Game;
     ///
     /// let board  = 0x0000_0000_0000_0011_u64;
     /// let result = Game::move_up(board);
@@ -269,7 +389,7 @@ impl Game {
     /// # Examples
     ///
     /// ```
-    /// use tfe::Game;
+    /// use game2048::Game;
     ///
     /// let board  = 0x0011_0000_0000_0011_u64;
     /// let result = Game::move_down(board);
@@ -299,7 +419,7 @@ impl Game {
     /// # Examples
     ///
     /// ```
-    /// use tfe::Game;
+    /// use game2048::Game;
     ///
     /// let board  = 0x0000_0000_0000_2211_u64;
     /// let result = Game::move_right(board);
@@ -328,7 +448,7 @@ impl Game {
     /// # Examples
     ///
     /// ```
-    /// use tfe::Game;
+    /// use game2048::Game;
     ///
     /// let board  = 0x0000_0000_0000_2211_u64;
     /// let result = Game::move_left(board);
@@ -351,12 +471,30 @@ impl Game {
         result
     }
 
+    /// Returns the board moved `[up, down, left, right]`.
+    /// Same as the four `move_*` calls, but transposes once and reads each row once.
+    pub fn move_all_directions(board: u64) -> [u64; 4] {
+        let transposed = Self::transpose(board);
+        let mut results = [board; 4];
+
+        for i in 0..4 {
+            let column = ((transposed >> (i * 16)) & ROW_MASK) as usize;
+            let row = ((board >> (i * 16)) & ROW_MASK) as usize;
+            results[0] ^= MOVES.up[column] << (i * 4);
+            results[1] ^= MOVES.down[column] << (i * 4);
+            results[2] ^= MOVES.left[row] << (i * 16);
+            results[3] ^= MOVES.right[row] << (i * 16);
+        }
+
+        results
+    }
+
     /// Returns the count of tiles with a value of `0`.
     ///
     /// # Examples
     ///
     /// ```
-    /// use tfe::Game;
+    /// use game2048::Game;
     ///
     /// let board  = 0x0000_0000_0000_2211_u64;
     /// let result = Game::count_empty(board);
@@ -390,19 +528,56 @@ impl Game {
     }
 
     /// Returns a `2` with 90% chance and `4` with 10% chance.
-    pub fn tile(board_id: &str, username: &str, timestamp: u64) -> u64 {
-        if rnd_range(board_id, username, timestamp, 0, 10) == 9 {
+    pub fn tile(state: &mut u64) -> u64 {
+        if lehmer_next(state) % 10 == 9 {
             2
         } else {
             1
         }
     }
 
-    /// Returns a `1` shifted to the position of any `0` bit in `board` randomly.
-    pub fn spawn_tile(board_id: &str, username: &str, timestamp: u64, board: u64) -> u64 {
+    /// Returns a new tile shifted to a random empty cell of `board`, drawn by the generator of
+    /// `spawns`. The Lehmer generator is re-seeded from the board and the move timestamp for
+    /// every spawn.
+    pub fn spawn_tile(
+        board_id: &str,
+        username: &str,
+        timestamp: u64,
+        board: u64,
+        spawns: &SpawnDistribution,
+    ) -> u64 {
+        match spawns.generator() {
+            SpawnGenerator::Hashed => {
+                Self::hashed_spawn_tile(board_id, username, timestamp, board, spawns)
+            }
+            SpawnGenerator::Lehmer => {
+                let mut state = spawn_state(board_id, username, timestamp, board);
+                let cell = next_empty_cell(&mut state, board);
+                spawns.sample(&mut state) << (cell * 4)
+            }
+        }
+    }
+
+    /// `SpawnGenerator::Hashed` spawn: the hashed seed of the move picks both the empty cell
+    /// (counted from the lowest bits) and the tile.
+    fn hashed_spawn_tile(
+        board_id: &str,
+        username: &str,
+        timestamp: u64,
+        board: u64,
+        spawns: &SpawnDistribution,
+    ) -> u64 {
         let mut tmp = board;
         let mut idx = rnd_range(board_id, username, timestamp, 0, Self::count_empty(board));
-        let mut t = Self::tile(board_id, username, timestamp);
+        let mut t = if spawns.is_classic() {
+            if rnd_range(board_id, username, timestamp, 0, 10) == 9 {
+                2
+            } else {
+                1
+            }
+        } else {
+            spawns.tile_for_roll(rnd_range(board_id, username, timestamp, 0, 100) as u8)
+        };
 
         loop {
             while (tmp & 0xF) != 0 {
@@ -423,6 +598,46 @@ impl Game {
         t
     }
 
+    /// Returns the values of the tiles produced by merges when `board` is moved in `direction`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use game2048::{Game, Direction};
+    ///
+    /// // | 0 | 0 | 0 | 0 |
+    /// // | 0 | 0 | 0 | 0 |
+    /// // | 0 | 0 | 4 | 4 |
+    /// // | 2 | 2 | 2 | 0 |
+    /// let board = 0x0000_0000_0022_1110;
+    /// assert_eq!(Game::merged_tiles(board, Direction::Left), vec![8, 4]);
+    /// assert_eq!(Game::merged_tiles(board, Direction::Up), Vec::<u64>::new());
+    /// ```
+    pub fn merged_tiles(board: u64, direction: Direction) -> Vec<u64> {
+        let matrix = Self::convert_to_matrix(board);
+        let mut merged = Vec::new();
+        for i in 0..4 {
+            // Tiles of the line in the order they slide towards the edge
+            let line: [u16; 4] = match direction {
+                Direction::Left => matrix[i],
+                Direction::Right => [matrix[i][3], matrix[i][2], matrix[i][1], matrix[i][0]],
+                Direction::Up => [matrix[0][i], matrix[1][i], matrix[2][i], matrix[3][i]],
+                Direction::Down => [matrix[3][i], matrix[2][i], matrix[1][i], matrix[0][i]],
+            };
+            let tiles: Vec<u16> = line.into_iter().filter(|tile| *tile > 0).collect();
+            let mut j = 0;
+            while j < tiles.len() {
+                if j + 1 < tiles.len() && tiles[j] == tiles[j + 1] {
+                    merged.push(1 << (tiles[j] + 1).min(0xF));
+                    j += 2;
+                } else {
+                    j += 1;
+                }
+            }
+        }
+        merged
+    }
+
     pub fn highest_tile(board: u64) -> u64 {
         let mut highest = 0;
         for i in 0..16 {
@@ -0,0 +1,225 @@
+use crate::{zobrist_hash, Direction, Game, Moves};
+
+/// Board evaluation heuristics.
+///
+/// Tiles are compared by exponent (the 4-bit value stored in the board, `2 = 1`, `4 = 2`, ...),
+/// so a 1024 next to a 2048 is as close as a 2 next to a 4.
+pub struct BoardStatistics;
+
+impl BoardStatistics {
+    /// Rows then columns of tile exponents, each read left to right / top to bottom.
+    fn lines(board: u64) -> [[u16; 4]; 8] {
+        let matrix = Game::convert_to_matrix(board);
+        let mut lines = [[0u16; 4]; 8];
+        lines[..4].copy_from_slice(&matrix);
+        for (column, line) in lines[4..].iter_mut().enumerate() {
+            *line = matrix.map(|row| row[column]);
+        }
+        lines
+    }
+
+    /// How far the rows and columns are from being monotonic.
+    ///
+    /// For each row and column, sum the exponent drops going one way and the rises going the
+    /// other way, and keep the smaller of the two. The result is minus the total over all 8
+    /// lines: `0.0` means every row and column only increases or only decreases, and more
+    /// negative values are less ordered.
+    ///
+    /// ```
+    /// use game2048::BoardStatistics;
+    ///
+    /// // | 4 | 3 | 2 | 1 |
+    /// // | 3 | 2 | 1 | 0 |  every row and column only decreases
+    /// // | 2 | 1 | 0 | 0 |
+    /// // | 1 | 0 | 0 | 0 |
+    /// assert_eq!(BoardStatistics::monotonicity(0x4321_3210_2100_1000), 0.0);
+    ///
+    /// // | 1 | 3 | 1 | 0 |  the 3 is a peak in its row: min(drops 3, rises 2) = 2
+    /// assert_eq!(BoardStatistics::monotonicity(0x1310_0000_0000_0000), -2.0);
+    /// ```
+    pub fn monotonicity(board: u64) -> f64 {
+        let penalty: u32 = Self::lines(board)
+            .iter()
+            .map(|line| {
+                let (mut increasing, mut decreasing) = (0u32, 0u32);
+                for pair in line.windows(2) {
+                    let (current, next) = (pair[0] as u32, pair[1] as u32);
+                    if current > next {
+                        increasing += current - next;
+                    } else {
+                        decreasing += next - current;
+                    }
+                }
+                increasing.min(decreasing)
+            })
+            .sum();
+        -(penalty as f64)
+    }
+
+    /// Minus the sum of exponent differences between horizontally and vertically adjacent
+    /// non-empty tiles. `0.0` means all neighbouring tiles are equal (or empty).
+    ///
+    /// ```
+    /// use game2048::BoardStatistics;
+    ///
+    /// // | 1 | 1 | 2 | 0 |  |1-1| + |1-2| = 1
+    /// assert_eq!(BoardStatistics::smoothness(0x1120_0000_0000_0000), -1.0);
+    /// ```
+    pub fn smoothness(board: u64) -> f64 {
+        let matrix = Game::convert_to_matrix(board);
+        let mut difference = 0u32;
+        for i in 0..4 {
+            for j in 0..4 {
+                let tile = matrix[i][j] as i32;
+                if tile == 0 {
+                    continue;
+                }
+                for (ni, nj) in [(i, j + 1), (i + 1, j)] {
+                    if ni < 4 && nj < 4 && matrix[ni][nj] != 0 {
+                        difference += (tile - matrix[ni][nj] as i32).unsigned_abs();
+                    }
+                }
+            }
+        }
+        -(difference as f64)
+    }
+
+    /// Number of empty cells (0 - 16).
+    ///
+    /// ```
+    /// use game2048::BoardStatistics;
+    ///
+    /// assert_eq!(BoardStatistics::empty_count(0x0000_0000_0000_2211), 12);
+    /// ```
+    pub fn empty_count(board: u64) -> u8 {
+        Game::count_empty(board) as u8
+    }
+
+    /// Value of the highest tile (0 for an empty board).
+    ///
+    /// ```
+    /// use game2048::BoardStatistics;
+    ///
+    /// assert_eq!(BoardStatistics::max_tile(0x0000_0000_0000_2B11), 2048);
+    /// ```
+    pub fn max_tile(board: u64) -> u32 {
+        Game::highest_tile(board) as u32
+    }
+
+    /// Number of horizontally or vertically adjacent pairs of equal non-empty tiles,
+    /// i.e. merges available without first sliding tiles together.
+    ///
+    /// ```
+    /// use game2048::BoardStatistics;
+    ///
+    /// // | 1 | 1 | 1 | 0 |  two pairs in the row, one in the first column
+    /// // | 1 | 0 | 0 | 0 |
+    /// assert_eq!(BoardStatistics::merge_potential(0x1110_1000_0000_0000), 3);
+    /// ```
+    pub fn merge_potential(board: u64) -> u8 {
+        Self::lines(board)
+            .iter()
+            .map(|line| {
+                line.windows(2)
+                    .filter(|pair| pair[0] != 0 && pair[0] == pair[1])
+                    .count() as u8
+            })
+            .sum()
+    }
+
+    /// 💡 Best direction by a 2-ply search, with its `hint_score`.
+    ///
+    /// Each direction scores its merge points plus the best merge points of the following move,
+    /// assuming the next 2 spawns in the empty cell that leaves the least to gain. Ties go to the
+    /// earlier of `Moves::DIRECTIONS`, so a board already reached by an earlier direction is
+    /// not evaluated again. Returns `None` when no direction changes the board.
+    ///
+    /// ```
+    /// use game2048::{BoardStatistics, Direction};
+    ///
+    /// // Bottom row | 2 | 2 | 4 | 0 |, the rest empty: left scores 8 (as counted by
+    /// // `Game::score`) and leaves a pair of 4s to merge next (16), while up scores
+    /// // nothing now and 8 on the next move
+    /// assert_eq!(
+    ///     BoardStatistics::best_move(0x0000_0000_0000_1120),
+    ///     Some((Direction::Left, 24))
+    /// );
+    /// ```
+    pub fn best_move(board: u64) -> Option<(Direction, u64)> {
+        let mut best: Option<(Direction, u64)> = None;
+        let mut visited: Vec<u64> = Vec::with_capacity(4); // Zobrist hashes of evaluated boards
+        for (direction, (after, gain)) in Moves::DIRECTIONS
+            .into_iter()
+            .zip(Moves::apply_all_directions(board))
+        {
+            if after == board {
+                continue;
+            }
+            let hash = zobrist_hash(after);
+            if visited.contains(&hash) {
+                continue;
+            }
+            visited.push(hash);
+            let reply_gain = (0..16)
+                .filter(|cell| (after >> (cell * 4)) & 0xF == 0)
+                .map(|cell| {
+                    let spawned = after | (1 << (cell * 4));
+                    Moves::apply_all_directions(spawned)
+                        .into_iter()
+                        .filter(|(reply, _)| *reply != spawned)
+                        .map(|(_, reply_gain)| reply_gain)
+                        .max()
+                        .unwrap_or(0)
+                })
+                .min()
+                .unwrap_or(0);
+            let hint_score = gain + reply_gain;
+            if best.is_none_or(|(_, best_score)| hint_score > best_score) {
+                best = Some((direction, hint_score));
+            }
+        }
+        best
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_heuristics_on_known_boards() {
+        // | 2048 | 1024 | 512 | 256 |  a "snake" board: ordered rows and columns,
+        // |    2 |    4 |   8 |  16 |  with the second row reversed
+        // |    0 |    0 |   0 |   0 |
+        // |    0 |    0 |   0 |   0 |
+        let snake = 0xBA98_1234_0000_0000;
+        // Every column only drops (e.g. 2048, 2, 0, 0)
+        assert_eq!(BoardStatistics::monotonicity(snake), 0.0);
+        // Horizontal: 1 + 1 + 1 + 1 + 1 + 1, vertical: 10 + 8 + 6 + 4
+        assert_eq!(BoardStatistics::smoothness(snake), -34.0);
+        assert_eq!(BoardStatistics::empty_count(snake), 8);
+        assert_eq!(BoardStatistics::max_tile(snake), 2048);
+        assert_eq!(BoardStatistics::merge_potential(snake), 0);
+
+        // Checkerboard: nothing is ordered and nothing merges
+        let checkerboard = 0x1212_2121_1212_2121;
+        assert_eq!(BoardStatistics::monotonicity(checkerboard), -8.0);
+        assert_eq!(BoardStatistics::smoothness(checkerboard), -24.0);
+        assert_eq!(BoardStatistics::merge_potential(checkerboard), 0);
+
+        let empty = 0;
+        assert_eq!(BoardStatistics::monotonicity(empty), 0.0);
+        assert_eq!(BoardStatistics::smoothness(empty), 0.0);
+        assert_eq!(BoardStatistics::empty_count(empty), 16);
+        assert_eq!(BoardStatistics::max_tile(empty), 0);
+        assert_eq!(BoardStatistics::merge_potential(0x1111_1111_1111_1111), 24);
+
+        assert_eq!(BoardStatistics::best_move(checkerboard), None);
+        // | 2 | 2 | 0 | 0 |  sliding down scores nothing yet but the 2s still merge next,
+        //                    tying with merging now: ties go to the earlier direction
+        assert_eq!(
+            BoardStatistics::best_move(0x1100_0000_0000_0000),
+            Some((Direction::Down, 8))
+        );
+    }
+}
@@ -1,17 +1,57 @@
+// GraphQLMutationRoot generates one mutation argument per operation field
+#![allow(clippy::too_many_arguments)]
+
+mod anti_cheat;
 mod direction;
 mod elimination_game;
+mod errors;
 mod event_leaderboard;
+mod filters;
 mod game;
+mod heuristics;
+mod hyperloglog;
+mod move_log;
 mod moves;
 mod random;
+mod shard_auth;
+mod sized_board;
 
+pub use crate::anti_cheat::{
+    AntiCheat, AntiCheatCheck, AntiCheatConfig, MoveSample, DEFAULT_MAX_MOVES_PER_CALL,
+    DEFAULT_MIN_MOVE_INTERVAL_MICROS, DEFAULT_NEW_BOARD_COOLDOWN_SECONDS, FOUR_TILE_PROBABILITY,
+    SPAWN_RATIO_MAX_DEVIATIONS,
+};
 pub use crate::direction::Direction;
-pub use crate::event_leaderboard::{LeaderboardAction, LeaderboardSettings};
-pub use crate::game::Game;
-pub use crate::moves::{Moves, COL_MASK, ROW_MASK};
-pub use crate::random::{hash_seed, rnd_range};
+pub use crate::errors::{ErrorResponse, GameError};
+pub use crate::event_leaderboard::{
+    countdown_threshold_crossed, effective_settings_at, tile_milestone, BalanceAttestation,
+    FinaliseError, LeaderboardAction, LeaderboardCursor, LeaderboardMode, LeaderboardSettings,
+    PowerUpConfig, PrizePoolEntry, PrizePoolError, QualifierSettings, RankedScore, RewardStructure,
+    RewardStructureError, RewardTier, SeasonConfig, SettingsVersion, TieBreakPolicy,
+    TokenRequirement, TournamentEntryError, TournamentStatus, DEFAULT_COUNTDOWN_THRESHOLDS,
+    MAX_LEADERBOARD_PAGE_SIZE, MAX_POWER_UP_CHANCE_PERCENT, NATIVE_TOKEN_ID,
+    REWARD_SHARE_TOTAL_BPS,
+};
+pub use crate::filters::{
+    check_profanity, passes_profanity_filter, passes_profanity_filter_with,
+    validate_custom_banned_words, FilterError, BANNED_WORDS, MAX_CUSTOM_BANNED_WORDS,
+    MAX_CUSTOM_BANNED_WORD_LENGTH,
+};
+pub use crate::game::{Game, SpawnDistribution};
+pub use crate::heuristics::BoardStatistics;
+pub use crate::hyperloglog::HyperLogLog;
+pub use crate::move_log::MoveLog;
+pub use crate::moves::{zobrist_hash, BoardOps, Moves, ParseBoardError, COL_MASK, ROW_MASK};
+pub use crate::random::{
+    beacon_contribution, chain_id_hash, hash_seed, lehmer_next, next_beacon_value, next_empty_cell,
+    proof_of_work_bits, rnd_range, spawn_state,
+};
+pub use crate::shard_auth::ShardAuth;
+pub use crate::sized_board::{
+    SizedBoard, BOMB_TILE, MAX_BOARD_DIMENSION, MIN_BOARD_DIMENSION, OBSTACLE_TILE,
+};
 
-use linera_sdk::linera_base_types::{Amount, ChainId};
+use linera_sdk::linera_base_types::{AccountOwner, Amount, ChainId};
 use linera_sdk::{
     abi::{ContractAbi, ServiceAbi},
     graphql::GraphQLMutationRoot,
@@ -22,12 +62,37 @@ pub struct Game2048Abi;
 
 impl ContractAbi for Game2048Abi {
     type Operation = Operation;
-    type Response = ();
+    type Response = String; // Mostly empty, or a failed operation's ErrorResponse JSON
 }
 
 impl ServiceAbi for Game2048Abi {
-    type Query = async_graphql::Request;
-    type QueryResponse = async_graphql::Response;
+    type Query = GraphQLQuery;
+    type QueryResponse = GraphQLQueryResponse;
+}
+
+/// Maximum queries in one batched GraphQL request
+pub const MAX_BATCH_QUERIES: usize = 10;
+
+/// 🧹 Most players one shard removes per `PurgeInactivePlayers` (keeps the block in time)
+pub const MAX_PURGE_PER_CALL: usize = 500;
+
+/// 🧹 `PlayerRemoved::reason` of players purged for inactivity
+pub const PURGE_REASON_INACTIVITY: &str = "inactivity";
+
+/// 📦 A single GraphQL request, or a batch of independent requests (JSON array)
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum GraphQLQuery {
+    Single(async_graphql::Request),
+    Batch(Vec<async_graphql::Request>),
+}
+
+/// Responses in the same shape as the `GraphQLQuery` they answer
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum GraphQLQueryResponse {
+    Single(async_graphql::Response),
+    Batch(Vec<async_graphql::Response>),
 }
 
 #[derive(async_graphql::SimpleObject, Debug, Deserialize, Serialize)]
@@ -37,6 +102,7 @@ struct MoveEntry {
     timestamp: u64,
 }
 
+#[allow(clippy::large_enum_variant)] // Operations are deserialized once per block, not stored
 #[derive(Debug, Deserialize, Serialize, GraphQLMutationRoot)]
 pub enum Operation {
     RegisterPlayer {
@@ -50,6 +116,11 @@ pub enum Operation {
         leaderboard_id: String, // Must specify leaderboard
         // 🎵 Rhythm mode: which music track was used (-1 = no rhythm/metronome, 0+ = track index)
         rhythm_track_index: i16,
+        /// ⛏️ Proof-of-work nonce, required when the tournament sets `pow_difficulty`
+        pow_nonce: Option<u64>,
+        /// 🔲 Board dimensions (4x4 unless chosen otherwise)
+        #[serde(default)]
+        board_size: BoardSize,
     },
     NewShard,
     MakeMoves {
@@ -58,6 +129,13 @@ pub enum Operation {
         player: String,
         password_hash: String,
     },
+    /// 📦 Moves for several boards in one call; the response is the JSON
+    /// `Vec<Result<(), MoveError>>` with one result per entry
+    BatchMakeMoves {
+        entries: Vec<BoardMoveEntry>,
+        player: String,
+        password_hash: String,
+    },
     LeaderboardAction {
         leaderboard_id: String,
         action: LeaderboardAction,
@@ -65,6 +143,15 @@ pub enum Operation {
         player: String,
         password_hash: String,
     },
+    /// 📋 Create a leaderboard with another's settings, a new name and new times (main chain)
+    CloneLeaderboard {
+        source_leaderboard_id: String,
+        new_name: String,
+        new_start_time: u64,
+        new_end_time: u64,
+        player: String,
+        password_hash: String,
+    },
     ToggleAdmin {
         username: String,
         player: String,
@@ -91,6 +178,11 @@ pub enum Operation {
         password_hash: String,
         base_triggerer_count: u32,
     },
+    /// 📊 Reset this chain's resource usage counters (admin only, e.g. monthly billing cycle)
+    ResetResourceUsage {
+        admin: String,
+        password_hash: String,
+    },
     /// 🚀 NEW: Manual leaderboard refresh - player can trigger update when their score is higher
     RequestLeaderboardRefresh {
         player: String,
@@ -103,12 +195,33 @@ pub enum Operation {
     RefillChainPool {
         count: u32,
     },
-    
+
     /// 🚀 MESSAGE-BASED: Claim/initialize player chain after registration
     /// This processes the inbox to receive RegisterPlayer message
     /// No authentication needed - just triggers block production
     ClaimChain,
-    
+
+    /// ♻️ CHAIN POOL: Return chains claimed but never initialized to the pool (admin, main chain)
+    RecoverStaleChains {
+        admin: String,
+        password_hash: String,
+    },
+
+    /// 🔑 ADMIN: Register a shard's new public key, generated off-chain (main chain);
+    /// the shard's leaderboard chain then only accepts its score updates signed with that key
+    RotateShardKey {
+        admin: String,
+        password_hash: String,
+        leaderboard_id: String,
+        shard_chain_id: String,
+        public_key: [u8; 32],
+    },
+
+    /// 🎲 BEACON: Contribute this shard chain to the main chain's randomness beacon round
+    ContributeToBeacon {
+        shard_chain_id: ChainId,
+    },
+
     /// 🚀 MANUAL SCORE SUBMISSION: Submit current board score to leaderboard
     /// Called when user clicks "refresh leaderboard" button
     /// Only sends if: score > 0 AND score > player's tournament best
@@ -117,6 +230,317 @@ pub enum Operation {
         player: String,
         password_hash: String,
     },
+
+    /// 🔮 SPECTATOR: Predict the tournament winner and their final score
+    /// Must be submitted from the spectator's own chain before the tournament ends
+    SubmitPrediction {
+        spectator_chain_id: String,
+        leaderboard_id: String,
+        predicted_winner: String,
+        predicted_score: u64,
+    },
+
+    /// 📡 LIVE STREAM: Register an off-chain relay endpoint for a tournament (host/admin only)
+    /// Executed on main chain, forwarded to the leaderboard chain. Only the hash of the relay's
+    /// auth token goes on chain (`stream_auth_token_hash`); `relay_owner` is the account the
+    /// relay signs its delivery reports with
+    RegisterStreamEndpoint {
+        admin: String,
+        password_hash: String,
+        leaderboard_id: String,
+        endpoint_url: String,
+        auth_token_hash: String,
+        relay_owner: String,
+    },
+    /// 📡 LIVE STREAM: Relay reports delivery result for an endpoint (on leaderboard chain)
+    /// Must be signed by the endpoint's relay owner. Endpoints are removed after 3 consecutive
+    /// failures
+    ReportStreamDelivery {
+        endpoint_url: String,
+        success: bool,
+    },
+    /// 🌟 PLATFORM EVENTS: Receive platform highlights on a chain (on main chain, max
+    /// `MAX_PLATFORM_SUBSCRIBERS` chains)
+    SubscribePlatformEvents {
+        subscriber_chain_id: String,
+    },
+    /// 🔕 Stop receiving a channel's events from every chain this chain subscribed to it on
+    UnsubscribeFromChannel {
+        channel: String,
+    },
+    /// ⚙️ Update the player's preferences (stored on the player chain)
+    UpdatePreferences {
+        player: String,
+        password_hash: String,
+        preferences: PlayerPreferences,
+    },
+    /// 👫 FRIENDS: Follow another player's best scores (stored on the player chain)
+    AddFriend {
+        friend_username: String,
+        player: String,
+        password_hash: String,
+    },
+    /// 👫 FRIENDS: Stop following a player
+    RemoveFriend {
+        friend_username: String,
+        player: String,
+        password_hash: String,
+    },
+    /// 🔑 PASSWORD: Start a password change (main chain). The operation's response is a
+    /// single-use change token, valid for `PASSWORD_CHANGE_TOKEN_TTL_SECONDS`
+    RequestPasswordChange {
+        player: String,
+        old_password_hash: String,
+    },
+    /// 🔑 PASSWORD: Set the new password hash with a token from `RequestPasswordChange`
+    ConfirmPasswordChange {
+        player: String,
+        change_token: String,
+        new_password_hash: String,
+    },
+    /// 🧳 MIGRATION: Move the player to another chain they own (main chain). Scores sent
+    /// from the old chain stop counting.
+    MigratePlayer {
+        old_player: String,
+        new_chain_id: String,
+        old_password_hash: String,
+        new_password_hash: String,
+    },
+    /// 📦 Export the player's data as JSON with a SHA-256 integrity hash (the operation's response)
+    ExportPlayerData {
+        player: String,
+        password_hash: String,
+    },
+    /// 🏷️ Replace client-defined metadata on a game (only while the game is active)
+    SetGameMetadata {
+        board_id: String,
+        player: String,
+        password_hash: String,
+        metadata: Vec<GameMetadataEntry>,
+    },
+    /// ⏱️ Turn a fresh classic game into a speed game with a deadline (one way)
+    ConvertToTimedGame {
+        board_id: String,
+        player: String,
+        password_hash: String,
+        time_limit_seconds: u32,
+    },
+    /// ↩️ UNDO: Roll back the last move, using one of the board's undos
+    UndoMove {
+        board_id: String,
+        player: String,
+        password_hash: String,
+    },
+    /// 💾 SNAPSHOT: Save the board so it can be resumed later (replaces any earlier snapshot)
+    SaveSnapshot {
+        board_id: String,
+        player: String,
+        password_hash: String,
+    },
+    /// 💾 SNAPSHOT: Restore the board from its snapshot while the tournament is still active
+    LoadSnapshot {
+        board_id: String,
+        player: String,
+        password_hash: String,
+    },
+    /// 📅 DAILY CHALLENGE: Start today's board; every player gets the same starting tiles
+    GetDailyChallenge {
+        player: String,
+        password_hash: String,
+    },
+    /// 💡 HINT: Compute the suggested next move for a board (max `MAX_HINTS_PER_BOARD`)
+    RequestHint {
+        board_id: String,
+        player: String,
+        password_hash: String,
+    },
+    /// ↪️ Move a game's progress to a new board at half score (once, shortly after creation)
+    ContinueGame {
+        old_board_id: String,
+        player: String,
+        password_hash: String,
+        new_board_size: BoardSize,
+    },
+    /// 🤝 MATCHMAKING: Wait in the main chain's pool for a similarly rated opponent
+    EnterMatchmaking {
+        player: String,
+        password_hash: String,
+        preferred_time_limit: u32, // seconds
+    },
+    /// 👥 TEAMS: Start a shared board on this (host) chain, joinable with one invite code per player
+    CreateTeamGame {
+        team_name: String,
+        player: String,
+        password_hash: String,
+        invite_codes: Vec<String>,
+    },
+    /// 👥 TEAMS: Join a team game from the player's own chain with an unused invite code
+    JoinTeamGame {
+        team_board_id: String,
+        invite_code: String,
+        player: String,
+        password_hash: String,
+    },
+    /// 🤝 MATCHMAKING: Pair waiting players and drop expired entries (admin, main chain)
+    ProcessMatchmaking {
+        admin: String,
+        password_hash: String,
+    },
+    /// 🔐 MULTI-SIG: Configure admin signers and threshold (super-admin)
+    ConfigureMultiSig {
+        required_signatures: u8,
+        admin_list: Vec<String>,
+        player: String,
+        password_hash: String,
+    },
+    /// 🔐 MULTI-SIG: Propose a critical admin action (proposer signs automatically)
+    ProposeAdminAction {
+        proposer: String,
+        password_hash: String,
+        action: CriticalAdminAction,
+        proposal_id: String,
+    },
+    /// 🔐 MULTI-SIG: Sign a pending proposal (executes at the signature threshold)
+    ApproveAdminAction {
+        approver: String,
+        password_hash: String,
+        proposal_id: String,
+    },
+    /// 🛡️ MODERATION: Replace the blocked-word list for tournament names/descriptions (super-admin)
+    UpdateBlockedWords {
+        blocked_words: Vec<String>,
+        player: String,
+        password_hash: String,
+    },
+    /// 🚩 MODERATION: Decide on a game flagged as a suspected cheat (admin, main chain)
+    ReviewFlaggedGame {
+        board_id: String,
+        admin: String,
+        password_hash: String,
+        verdict: FlagVerdict,
+    },
+    /// ⏰ BONUS WINDOW: Schedule a score multiplier window (host/admin only)
+    /// Executed on main chain, forwarded to the leaderboard chain
+    AddBonusWindow {
+        leaderboard_id: String,
+        admin: String,
+        password_hash: String,
+        window: BonusWindow,
+    },
+    /// ⚖️ HANDICAP: Scale a player's effective score (host/admin, before the tournament starts)
+    /// Executed on main chain, forwarded to the leaderboard chain
+    SetPlayerHandicap {
+        leaderboard_id: String,
+        admin: String,
+        password_hash: String,
+        player: String,
+        multiplier: f64, // 0.5 - 1.0
+    },
+    /// 📥 Import off-chain tournament history as an archive (host/admin, main chain)
+    /// `data_json`: JSON array of `{ player, score, timestamp, board_id }`, max 500 records
+    ImportHistoricalData {
+        leaderboard_id: String,
+        admin: String,
+        password_hash: String,
+        data_json: String,
+    },
+    /// 🔁 REPLAY: Put a tournament in replay mode (host/admin, main chain)
+    InitiateTournamentReplay {
+        leaderboard_id: String,
+        admin: String,
+        password_hash: String,
+    },
+    /// 🔁 REPLAY: Re-run a board's recorded moves and submit the replayed score (player chain)
+    ReplayMoves {
+        board_id: String,
+        player: String,
+        password_hash: String,
+    },
+    /// 🔁 REPLAY: Verify a shared run-length-encoded move log (see `MoveLog`) against a board
+    ReplayGame {
+        board_id: String,
+        move_log: Vec<u8>,
+    },
+    /// 🔁 REPLAY: Compare replayed scores with the originals and leave replay mode (host/admin, main chain)
+    FinalizeReplay {
+        leaderboard_id: String,
+        admin: String,
+        password_hash: String,
+    },
+    /// ⏸️ PAUSE: Hold the tournament's shard score updates until it resumes (host/admin, main chain)
+    PauseLeaderboard {
+        leaderboard_id: String,
+        reason: String,
+        player: String,
+        password_hash: String,
+    },
+    /// ⏸️ PAUSE: Apply the held score updates in order and resume scoring (host/admin, main chain)
+    ResumeLeaderboard {
+        leaderboard_id: String,
+        reason: String,
+        player: String,
+        password_hash: String,
+    },
+    /// 🧹 PURGE: Remove players who never scored and have been inactive for the threshold
+    /// (host/admin, main chain; each shard removes at most `MAX_PURGE_PER_CALL`)
+    PurgeInactivePlayers {
+        leaderboard_id: String,
+        inactive_threshold_seconds: u64,
+        admin: String,
+        password_hash: String,
+    },
+    /// 🪙 TOKEN GATE: Attestation service chain vouches for a player chain's balance
+    AttestBalance {
+        player_chain_id: String,
+        leaderboard_id: String,
+        held: Amount,
+        expires_at: u64,
+    },
+    /// 🏅 PRIZE POOL: Send tokens from this chain into a tournament's prize pool
+    FundPrizePool {
+        leaderboard_id: String,
+        application_id: String,
+        amount: Amount,
+    },
+    /// 🏅 PRIZE POOL: Pay out the prize pool to the final ranking (host/admin, main chain, after the end)
+    DistributePrizes {
+        leaderboard_id: String,
+        admin: String,
+        password_hash: String,
+    },
+    /// 🏁 Close an ended tournament and pay its reward structure to the top ranks (host/admin, main chain)
+    FinaliseLeaderboard {
+        leaderboard_id: String,
+        player: String,
+        password_hash: String,
+    },
+    /// 🥊 BRACKET: Seed the tournament's players into a single-elimination bracket (host/admin, main chain)
+    BracketTournament {
+        leaderboard_id: String,
+        admin: String,
+        password_hash: String,
+        bracket_size: u32,
+        round_duration_seconds: u64,
+    },
+    /// 🔄 ROUND ROBIN: Pair the tournament's players by the circle method, one round per period
+    /// (host/admin, main chain)
+    ScheduleRoundRobin {
+        leaderboard_id: String,
+        rounds: u8,
+        round_duration_seconds: u64,
+        player: String,
+        password_hash: String,
+    },
+    /// 🎟️ WAITLIST: Admit waitlisted players into free slots (tournament's leaderboard chain)
+    ProcessWaitlist {
+        leaderboard_id: String,
+    },
+    /// 📮 DEAD LETTER: Re-process a failed shard message (admin, shard chain)
+    RetryDeadLetter {
+        key: String,
+        admin: String,
+        password_hash: String,
+    },
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -142,6 +566,9 @@ pub enum Message {
         end_time: u64,   // Tournament end time (0 = unlimited)
         // 🎵 Rhythm mode: which music track was used (-1 = no rhythm/metronome, 0+ = track index)
         rhythm_track_index: i16,
+        // 🎲 Spawn weights of the tournament (empty = classic)
+        #[serde(default)]
+        tile_weights: Vec<TileWeight>,
     },
     CreateLeaderboard {
         leaderboard_id: String,
@@ -166,10 +593,28 @@ pub enum Message {
         score: u64,
         is_end: bool,
         timestamp: u64,
+        /// 📮 Dead letter key of the message on its origin chain (0 = not numbered)
+        #[serde(default)]
+        message_nonce: u64,
+    },
+    /// 📦 Shard sends its pending score changes in one message per `MAX_BULK_SCORE_ENTRIES` players
+    BulkScoreUpdate {
+        scores: Vec<ScoreUpdateEntry>,
+        shard_id: String,
+        batch_timestamp: u64,
+    },
+    /// 🔑 Shard's new public key (main chain -> leaderboard chain)
+    RegisterShardKey {
+        shard_chain_id: String,
+        public_key: [u8; 32],
     },
+    /// Full score data - shards otherwise sync the compact form (`CompactPlayerScore`)
     Flush {
         board_ids: std::collections::HashMap<String, String>,
         scores: std::collections::HashMap<String, u64>,
+        /// 📮 Dead letter key of the message on its origin chain (0 = not numbered)
+        #[serde(default)]
+        message_nonce: u64,
     },
     /// 🚀 NEW: Player registers with shard for tournament monitoring
     RegisterPlayerWithShard {
@@ -190,6 +635,113 @@ pub enum Message {
     SubscribeToMainChain {
         main_chain_id: String,
     },
+    /// 🏁 WARMUP: Warmup window length for this tournament (main chain -> leaderboard chain)
+    ConfigureWarmup {
+        warmup_duration_seconds: u32,
+    },
+    /// ⏳ COUNTDOWN: Seconds before the end at which to announce a countdown (main chain -> leaderboard chain)
+    ConfigureCountdown {
+        thresholds: Vec<u64>,
+    },
+    /// 🎟️ QUALIFIER: Where this qualifier's top players advance (main chain -> leaderboard chain)
+    ConfigureQualifier {
+        settings: QualifierSettings,
+    },
+    /// 🎟️ QUALIFIER: Top players of an ended qualifier (qualifier chain -> main tournament chain)
+    AddQualifiedPlayers {
+        players: Vec<String>,
+        source_leaderboard_id: String,
+    },
+    /// 🎟️ QUALIFIER: This player advanced to `leaderboard_id` (qualifier chain -> player chain)
+    QualifiedForTournament {
+        leaderboard_id: String,
+    },
+    /// 🎟️ WAITLIST: Most players the tournament admits, 0 = no limit (main chain -> leaderboard chain)
+    ConfigureMaxParticipants {
+        max_participants: u32,
+    },
+    /// 🎟️ WAITLIST: A player tried to join the full tournament (player chain -> leaderboard chain)
+    JoinWaitlist {
+        player: String,
+        timestamp: u64,
+    },
+    /// 🎟️ WAITLIST: A slot freed up for this chain's player (leaderboard chain -> player chain)
+    AdmittedFromWaitlist {
+        leaderboard_id: String,
+    },
+    /// 👫 FRIENDS: Look up a friend's chain (player chain -> main chain)
+    FriendRequest {
+        friend_username: String,
+    },
+    /// 👫 FRIENDS: The friend's chain, to subscribe to its "friend_updates" (main chain -> player chain)
+    FriendChainResolved {
+        friend_username: String,
+        friend_chain_id: String,
+    },
+    /// 👫 FRIENDS: A chain started or stopped following this chain's player
+    FriendSubscription {
+        subscriber_chain_id: String,
+        subscribe: bool,
+    },
+    /// 🌟 PLATFORM EVENTS: New top-10 entry or milestone tile
+    /// (leaderboard chain -> main chain -> platform subscribers)
+    PlatformHighlight {
+        highlight: PlatformHighlight,
+    },
+    /// 📅 DAILY CHALLENGE: A player finished the daily challenge of `date` (player chain -> main chain)
+    DailyChallengeScore {
+        date: String,
+        record: DailyChallengeRecord,
+    },
+    /// 🧳 MIGRATION: The player moved to `new_chain_id`; the old chain is retired
+    /// (main chain -> leaderboard and shard chains)
+    PlayerMigration {
+        old_player: String,
+        new_chain_id: String,
+        new_player_record: PlayerMigrationRecord,
+    },
+    /// 🧳 MIGRATION: Move the shard's per-chain board counts and activity from the old chain
+    /// to the new one (main chain -> shard chains, after `PlayerMigration`)
+    ReassignBoards {
+        player: String,
+        old_chain_id: String,
+        new_chain_id: String,
+    },
+    /// 🧹 PURGE: Main chain asks a shard to remove its inactive players without a score
+    PurgeInactivePlayers {
+        leaderboard_id: String,
+        inactive_threshold_seconds: u64,
+    },
+    /// 🧹 PURGE: The player was removed from a tournament (shard chain -> player chain)
+    PlayerRemoved {
+        leaderboard_id: String,
+        reason: String,
+    },
+    /// 🔑 PASSWORD: The player's password changed; replaces the player chain's copy of the hash
+    /// (main chain -> player chain)
+    PasswordChanged {
+        player: String,
+        password_hash: String,
+    },
+    /// ♻️ CHAIN POOL: Player chain processed its registration (sent to main chain)
+    ChainInitialized,
+    /// 🚩 Player chain ended a game as a suspected cheat (sent to main chain for review)
+    GameFlagged {
+        board_id: String,
+        player: String,
+        leaderboard_id: String,
+        score: u64,
+        reason: String,
+    },
+    /// 🚩 Admin confirmed a flagged game; the board's score no longer counts (main chain → leaderboard chain)
+    ScoreInvalidated {
+        player: String,
+        board_id: String,
+    },
+    /// 🎲 BEACON: A shard chain joins the current beacon round (sent to main chain)
+    BeaconContribution {
+        shard_chain_id: ChainId,
+    },
     /// 🚀 NEW: Shard registers first player with leaderboard for triggerer system
     RegisterFirstPlayer {
         shard_chain_id: String,
@@ -208,7 +760,7 @@ pub enum Message {
         tournament_id: String,
         timestamp: u64,
     },
-    
+
     /// 🚀 NEW (Message-based architecture): Player submits score directly to leaderboard
     /// Sent when: score > 0 AND score > current_best (new personal best)
     /// Replaces: PlayerScoreUpdate event + shard aggregation
@@ -220,64 +772,1310 @@ pub enum Message {
         highest_tile: u64,
         game_status: GameStatus,
         timestamp: u64,
-        boards_in_tournament: u32,
+        /// The player's board count in the receiving leaderboard (leaderboard_id -> count)
+        boards_per_leaderboard: std::collections::HashMap<String, u32>,
         /// Tournament start time (0 = unlimited)
         start_time: u64,
         /// Tournament end time (0 = unlimited)
         end_time: u64,
+        /// Score includes a tournament streak multiplier
+        streak_bonus_applied: bool,
+        /// 🏁 Board was created during the warmup window (not ranked)
+        is_warmup: bool,
+        /// 🌿 Average valid moves per move on the board (0.0 = no moves yet)
+        average_branching_factor: f64,
+        /// 💡 Hints requested on the board
+        hints_used: u32,
+        /// ⚡ Moves made on the board (0 = unknown)
+        moves_count: u32,
+        /// 🔁 Increases with every score message of the player chain (0 = not deduplicated)
+        message_nonce: u64,
+        /// 🏎️ Speed run target tile and micros from board creation to first merging it
+        #[serde(default)]
+        tile_milestone_hit: Option<(u32, u64)>,
+        /// 🏅 The player's best ended games, best first
+        top_boards: Vec<BoardSummary>,
     },
-}
 
-/// 🚀 ENHANCED: Four event types for four channels
-#[derive(Debug, Deserialize, Serialize, Clone)]
-pub enum GameEvent {
-    /// Channel: "player_score_update" - Emitted on every score change, game creation, game end
-    PlayerScoreUpdate {
-        player: String,
-        board_id: String,
-        score: u64,
-        chain_id: String,
+    /// 🔮 SPECTATOR: Prediction forwarded from a spectator chain to the leaderboard chain
+    SubmitPrediction {
+        spectator_chain_id: String,
+        predicted_winner: String,
+        predicted_score: u64,
         timestamp: u64,
-        game_status: GameStatus,
-        highest_tile: u64,
-        moves_count: u32,
-        leaderboard_id: String,
-        /// Current best score for this player in this leaderboard (for shard filtering)
-        current_leaderboard_best: u64,
-        /// 🚀 NEW: Player's total board count in this tournament (for distributed counting)
-        boards_in_tournament: u32,
     },
 
-    /// Channel: "shard_score_update" - Emitted by shard chains with aggregated scores
-    ShardScoreUpdate {
-        shard_chain_id: String,
-        player_scores: std::collections::HashMap<String, PlayerScoreSummary>,
-        player_activity_scores: std::collections::HashMap<String, u32>, // NEW: player -> activity_score
-        /// 🚀 NEW: Player board counts for distributed board counting (player_chain_id -> board_count)
-        player_board_counts: std::collections::HashMap<String, u32>,
-        aggregation_timestamp: u64,
-        total_players: u32,
-        leaderboard_id: String,
+    /// 📡 LIVE STREAM: Main chain registers a stream endpoint on the leaderboard chain
+    RegisterStreamEndpoint {
+        endpoint_url: String,
+        auth_token_hash: String,
+        relay_owner: String,
+        timestamp: u64,
     },
 
-    /// Channel: "active_tournaments" - Emitted by leaderboard with current active tournaments
-    ActiveTournaments {
-        tournaments: Vec<TournamentInfo>,
-        timestamp: u64,
+    /// ⏰ BONUS WINDOW: Main chain schedules a bonus window on the leaderboard chain
+    AddBonusWindow {
+        window: BonusWindow,
     },
 
-    /// Channel: "leaderboard_update" - Emitted by leaderboard with triggerer list updates
-    LeaderboardUpdate {
-        leaderboard_id: String,
-        triggerer_list: Vec<(String, u32)>, // (player_chain_id, activity_score) sorted by activity (most active first)
-        last_update_timestamp: u64,
-        threshold_config: u64, // Minimum time between triggers (microseconds)
-        total_registered_players: u32,
+    /// ⚖️ HANDICAP: Main chain sets a player's handicap on the leaderboard chain
+    SetPlayerHandicap {
+        player: String,
+        multiplier: f64,
     },
-}
 
-/// Tournament information for the registry
-#[derive(Debug, Deserialize, Serialize, Clone)]
+    /// ⏸️ PAUSE: Main chain pauses or resumes scoring (main chain -> leaderboard and shard chains)
+    SetLeaderboardPaused {
+        leaderboard_id: String,
+        paused: bool,
+        reason: String,
+        timestamp: u64,
+    },
+    /// 🔁 REPLAY: Main chain puts the leaderboard chain in replay mode
+    InitiateReplay,
+    /// 🔁 REPLAY: Player chain submits a replayed board score
+    SubmitReplayScore {
+        player: String,
+        board_id: String,
+        score: u64,
+    },
+    /// 🔁 REPLAY: Main chain asks the leaderboard chain to compare replay results
+    FinalizeReplay,
+
+    /// 💀 ELIMINATION: Player chain reports a deadlock that cost a life
+    EliminationLifeLost {
+        board_id: String,
+        lives_remaining: u8,
+        score: u64,
+    },
+
+    /// 🪙 TOKEN GATE: Balance attestation delivered to a player chain
+    BalanceAttestation {
+        leaderboard_id: String,
+        attestation: BalanceAttestation,
+    },
+
+    /// 🏅 PRIZE POOL: Main chain sets the prize pool on the leaderboard chain
+    ConfigurePrizePool {
+        entries: Vec<PrizePoolEntry>,
+    },
+    /// 🏅 PRIZE POOL: Tokens were sent to the leaderboard chain for its prize pool
+    PrizePoolFunded {
+        application_id: String,
+        amount: Amount,
+        /// Account on the sending chain that receives any surplus back
+        funder: AccountOwner,
+    },
+    /// 🏅 PRIZE POOL: Main chain asks the leaderboard chain to pay out prizes
+    DistributePrizes,
+    /// 🏁 Main chain finalises the tournament on the leaderboard chain
+    FinaliseLeaderboard {
+        reward_structure: Option<RewardStructure>,
+    },
+
+    /// 🥊 BRACKET: Main chain asks the leaderboard chain to seed its players into a bracket
+    StartBracket {
+        bracket_size: u32,
+        round_duration_seconds: u64,
+    },
+    /// 🔄 ROUND ROBIN: Main chain asks the leaderboard chain to schedule a round robin
+    StartRoundRobin {
+        rounds: u8,
+        round_duration_seconds: u64,
+    },
+    /// 🥊 BRACKET: Leaderboard chain tells both players of a decided match
+    BracketAdvancement {
+        leaderboard_id: String,
+        round: u32,
+        winner: String,
+        loser: String,
+        next_matchup_id: Option<String>, // None = the winner won the bracket
+    },
+    /// 👥 TEAMS: Member chain asks the host chain to join a team game
+    JoinTeam {
+        team_board_id: String,
+        invite_code: String,
+        player: String,
+    },
+    /// 👥 TEAMS: Member chain forwards a move to the host chain
+    TeamMove {
+        team_board_id: String,
+        direction: Direction,
+        player: String,
+    },
+    /// 👥 TEAMS: Host chain shares the board with its members after a join or move
+    TeamBoardSync {
+        team_board_id: String,
+        tiles: u64,
+        score: u64,
+        is_ended: bool,
+        score_share: u64, // Each member's equal share of the score (0 until the game ends)
+    },
+    /// 🎟️ WAITLIST: A player started their first board in a tournament with a participant
+    /// limit (player chain -> leaderboard chain)
+    RegisterParticipant {
+        player: String,
+        timestamp: u64,
+    },
+}
+
+/// 🚀 ENHANCED: Four event types for four channels
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub enum GameEvent {
+    /// Channel: "player_score_update" - Emitted by player chains after every move batch
+    PlayerScoreUpdate {
+        player: String,
+        board_id: String,
+        score: u64,
+        chain_id: String,
+        timestamp: u64,
+        game_status: GameStatus,
+        highest_tile: u64,
+        moves_count: u32,
+        leaderboard_id: String,
+        /// Current best score for this player in this leaderboard (for shard filtering)
+        current_leaderboard_best: u64,
+        /// 🚀 NEW: Player's board count per leaderboard (for distributed counting)
+        boards_per_leaderboard: std::collections::HashMap<String, u32>,
+        /// 💡 Hints requested on the board
+        #[serde(default)]
+        hints_used: u32,
+        /// 💥 Combo bonus earned by the latest move (for combo pop-ups)
+        #[serde(default)]
+        bonus_points_this_move: u64,
+        /// ⏲️ Move timing on the board, by block time between move batches
+        /// (micros, min/max 0 = none yet)
+        #[serde(default)]
+        total_think_time_micros: u64,
+        #[serde(default)]
+        min_move_time_micros: u64,
+        #[serde(default)]
+        max_move_time_micros: u64,
+        #[serde(default)]
+        avg_move_time_micros: u64,
+    },
+
+    /// Channel: "shard_score_update" - Emitted by shard chains with aggregated scores
+    ShardScoreUpdate {
+        shard_chain_id: String,
+        player_scores: std::collections::HashMap<String, PlayerScoreSummary>,
+        player_activity_scores: std::collections::HashMap<String, u32>, // NEW: player -> activity_score
+        /// 🚀 NEW: Player board counts for distributed board counting (player_chain_id -> board_count)
+        player_board_counts: std::collections::HashMap<String, u32>,
+        /// ⚡ Best score per move of each player (player -> efficiency)
+        #[serde(default)]
+        player_efficiency_scores: std::collections::HashMap<String, u64>,
+        aggregation_timestamp: u64,
+        total_players: u32,
+        leaderboard_id: String,
+    },
+
+    /// Channel: "active_tournaments" - Emitted by leaderboard with current active tournaments
+    ActiveTournaments {
+        tournaments: Vec<TournamentInfo>,
+        timestamp: u64,
+    },
+
+    /// Channel: "leaderboard_update" - Emitted by leaderboard with triggerer list updates
+    LeaderboardUpdate {
+        leaderboard_id: String,
+        triggerer_list: Vec<(String, u32)>, // (player_chain_id, activity_score) sorted by activity (most active first)
+        last_update_timestamp: u64,
+        threshold_config: u64, // Minimum time between triggers (microseconds)
+        total_registered_players: u32,
+    },
+
+    /// Channel: "leaderboard_update" - Emitted by leaderboard when the host pauses scoring
+    LeaderboardPaused {
+        leaderboard_id: String,
+        reason: String,
+        timestamp: u64,
+    },
+
+    /// Channel: "leaderboard_update" - Emitted by leaderboard when scoring resumes
+    LeaderboardResumed {
+        leaderboard_id: String,
+        reason: String,
+        timestamp: u64,
+    },
+
+    /// Channel: "admin_events" - Emitted by each shard after purging inactive players
+    PurgeCompleted {
+        leaderboard_id: String,
+        purged_count: u32,
+        timestamp: u64,
+    },
+
+    /// Channel: "stream_feed" - Emitted by leaderboard on UpdateLeaderboard, one per stream endpoint
+    StreamUpdate {
+        leaderboard_id: String,
+        endpoint_url: String, // Relay forwards this update to the endpoint's WebSocket
+        top_10: Vec<LeaderboardEntry>,
+        timestamp: u64,
+    },
+
+    /// Channel: "bonus_window" - Emitted by leaderboard on UpdateLeaderboard while a bonus window is active
+    BonusWindowActive {
+        leaderboard_id: String,
+        window_name: String,
+        expires_in_seconds: u64,
+    },
+
+    /// Channel: "variant_converted" - Emitted by player chain when a classic game becomes a speed game
+    VariantConverted {
+        board_id: String,
+        player: String,
+        new_variant: GameVariant,
+        deadline: u64, // micros
+    },
+
+    /// Channel: "abnormal_spawn_ratio" - Emitted by player chain when an ended game's 4-tile ratio is off by more than 3 standard deviations
+    AbnormalSpawnRatio {
+        board_id: String,
+        expected_ratio: f64,
+        actual_ratio: f64,
+        sample_size: u32,
+    },
+
+    /// Channel: "score_cap_reached" - Emitted by player chain when a game ends at the tournament's score cap
+    ScoreCapReached {
+        board_id: String,
+        player: String,
+        leaderboard_id: String,
+        score: u64,
+        message: String,
+    },
+
+    /// Channel: "game_ended" - Emitted by player chain when the contract ends a game early (no score is submitted)
+    GameEnded {
+        board_id: String,
+        player: String,
+        leaderboard_id: String,
+        score: u64,
+        reason: GameEndReason,
+    },
+
+    /// Channel: "active_tournaments" - Emitted by leaderboard on UpdateLeaderboard as the end approaches
+    TournamentCountdown {
+        leaderboard_id: String,
+        seconds_remaining: u64,
+        current_leader: Option<String>,
+    },
+
+    /// Channel: "players_qualified" - Emitted by the main tournament's leaderboard when qualifiers advance
+    PlayersQualified {
+        count: u32,
+        from_tournament: String,
+        to_tournament: String,
+    },
+
+    /// Channel: "warmup_started" - Emitted by leaderboard on UpdateLeaderboard once the warmup window opens
+    WarmupStarted {
+        leaderboard_id: String,
+        starts_at: u64, // micros, when scores start counting
+    },
+
+    /// Channel: "tournament_started" - Emitted by leaderboard on UpdateLeaderboard once a warmed-up tournament starts
+    TournamentStarted { leaderboard_id: String },
+
+    /// Channel: "chains_recovered" - Emitted by main chain after returning stale claimed chains to the pool
+    ChainsRecovered { count: u32, total_available: u32 },
+
+    /// Channel: "randomness_beacon" - Emitted by main chain when a new beacon round completes
+    BeaconUpdated { beacon: RandomnessBeacon },
+
+    /// Channel: "friend_updates" - Emitted by player chains with followers when a best score improves
+    FriendScoreEvent {
+        player: String,
+        leaderboard_id: String,
+        best_score: u64,
+        timestamp: u64,
+    },
+
+    /// Channel: "platform_events" - Emitted by main chain when a player enters a tournament's
+    /// top 10 or reaches a milestone tile
+    PlatformHighlight {
+        player: String,
+        score: u64,
+        highest_tile: u64,
+        leaderboard_id: String,
+        rank: u32, // Rank in the tournament (0 = outside the top 10)
+    },
+
+    /// Channel: "historical_import" - Emitted by main chain after importing off-chain history
+    HistoricalDataImported {
+        record_count: u32,
+        leaderboard_id: String,
+    },
+}
+
+/// Highest tile on the combo card: merging every tile from 4 up to it completes the card
+pub const COMBO_CARD_MAX_TILE: u32 = 2048;
+
+/// 🃏 How often a tile value was produced by a merge, and when it was first reached
+#[derive(async_graphql::SimpleObject, Debug, Clone, PartialEq)]
+pub struct TileMergeCount {
+    pub tile: u32,
+    pub count: u32,
+    pub first_achieved_at: u64, // milliseconds
+}
+
+impl TileMergeCount {
+    /// Every tile from 4 up to `max_tile` was merged at least once
+    pub fn is_combo_complete(merges: &[TileMergeCount], max_tile: u32) -> bool {
+        let mut tile = 4;
+        while tile <= max_tile {
+            if !merges
+                .iter()
+                .any(|merge| merge.tile == tile && merge.count > 0)
+            {
+                return false;
+            }
+            tile <<= 1;
+        }
+        true
+    }
+}
+
+/// 🏷️ Client-defined game metadata (e.g. control scheme, device type)
+#[derive(
+    async_graphql::SimpleObject,
+    async_graphql::InputObject,
+    Debug,
+    Deserialize,
+    Serialize,
+    Clone,
+    PartialEq,
+)]
+#[graphql(input_name = "GameMetadataEntryInput")]
+pub struct GameMetadataEntry {
+    pub key: String,
+    pub value: String,
+}
+
+pub const MAX_GAME_METADATA_ENTRIES: usize = 5;
+pub const MAX_GAME_METADATA_LENGTH: usize = 64;
+
+#[derive(Debug, thiserror::Error, PartialEq)]
+pub enum GameMetadataError {
+    #[error("Too many metadata entries (max {MAX_GAME_METADATA_ENTRIES})")]
+    TooManyEntries,
+    #[error("Duplicate metadata key: {0}")]
+    DuplicateKey(String),
+    #[error("Invalid metadata key (1-64 alphanumeric or underscore characters): {0}")]
+    InvalidKey(String),
+    #[error("Invalid metadata value for {0} (max 64 printable ASCII characters)")]
+    InvalidValue(String),
+}
+
+impl GameMetadataEntry {
+    /// Validate a full metadata set before it replaces a game's metadata
+    pub fn validate_all(entries: &[GameMetadataEntry]) -> Result<(), GameMetadataError> {
+        if entries.len() > MAX_GAME_METADATA_ENTRIES {
+            return Err(GameMetadataError::TooManyEntries);
+        }
+        for (i, entry) in entries.iter().enumerate() {
+            let key_valid = !entry.key.is_empty()
+                && entry.key.len() <= MAX_GAME_METADATA_LENGTH
+                && entry
+                    .key
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_');
+            if !key_valid {
+                return Err(GameMetadataError::InvalidKey(entry.key.clone()));
+            }
+            let value_valid = entry.value.len() <= MAX_GAME_METADATA_LENGTH
+                && entry
+                    .value
+                    .chars()
+                    .all(|c| c.is_ascii() && !c.is_ascii_control());
+            if !value_valid {
+                return Err(GameMetadataError::InvalidValue(entry.key.clone()));
+            }
+            if entries[..i].iter().any(|other| other.key == entry.key) {
+                return Err(GameMetadataError::DuplicateKey(entry.key.clone()));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 🎮 Game rules a board is played under
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GameVariant {
+    #[default]
+    Classic2048,
+    /// Moves after the board's deadline are rejected
+    Speed2048 { time_limit_seconds: u32 },
+    /// A deadlock costs a life and restarts the board, keeping the score
+    Elimination { lives_remaining: u8 },
+    /// 👥 One board on the host's chain, moved by every team member
+    Collaborative { team_size: u8 },
+}
+
+impl GameVariant {
+    /// ⏱️ Deadline (micros) of a game of this variant started at `start` (0 = none)
+    pub fn deadline_from(&self, start: u64) -> u64 {
+        match self {
+            GameVariant::Classic2048
+            | GameVariant::Elimination { .. }
+            | GameVariant::Collaborative { .. } => 0,
+            GameVariant::Speed2048 { time_limit_seconds } => {
+                start + *time_limit_seconds as u64 * 1_000_000
+            }
+        }
+    }
+
+    /// 💀 Lives a new game of this variant starts with (0 = a deadlock ends the game)
+    pub fn starting_lives(&self) -> u8 {
+        match self {
+            GameVariant::Elimination { lives_remaining } => *lives_remaining,
+            _ => 0,
+        }
+    }
+
+    /// ⏱️ A game with this `deadline` (0 = none) is over at block time `now`
+    pub fn is_past_deadline(deadline: u64, now: u64) -> bool {
+        deadline > 0 && now >= deadline
+    }
+}
+
+/// 🎲 Generator that spawns a board's tiles, recorded when the board is created so a
+/// generator change leaves the spawns (and replays) of older boards unchanged
+#[derive(
+    async_graphql::Enum, Copy, Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq,
+)]
+pub enum SpawnGenerator {
+    /// `rnd_range` over the game's sha256 seed: boards created before the generator was recorded
+    #[default]
+    Hashed,
+    /// Lehmer generator re-seeded from the board and timestamp (`spawn_state`)
+    Lehmer,
+}
+
+impl SpawnGenerator {
+    /// Generator of new boards
+    pub const CURRENT: SpawnGenerator = SpawnGenerator::Lehmer;
+}
+
+impl std::fmt::Display for GameVariant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GameVariant::Classic2048 => write!(f, "Classic2048"),
+            GameVariant::Speed2048 { time_limit_seconds } => {
+                write!(f, "Speed2048({}s)", time_limit_seconds)
+            }
+            GameVariant::Elimination { lives_remaining } => {
+                write!(f, "Elimination({} lives)", lives_remaining)
+            }
+            GameVariant::Collaborative { team_size } => {
+                write!(f, "Collaborative({} players)", team_size)
+            }
+        }
+    }
+}
+
+/// 👥 Most players on a team game, host included
+pub const MAX_TEAM_SIZE: usize = 4;
+
+/// 👥 Invite codes of a new team game: one per invited player, non-empty and distinct
+pub fn validate_invite_codes(invite_codes: &[String]) -> Result<(), String> {
+    if invite_codes.is_empty() || invite_codes.len() >= MAX_TEAM_SIZE {
+        return Err(format!(
+            "A team game needs between 1 and {} invite codes",
+            MAX_TEAM_SIZE - 1
+        ));
+    }
+    if invite_codes.iter().any(|code| code.trim().is_empty()) {
+        return Err("Invite codes cannot be empty".to_string());
+    }
+    let mut codes: Vec<&String> = invite_codes.iter().collect();
+    codes.sort();
+    codes.dedup();
+    if codes.len() != invite_codes.len() {
+        return Err("Invite codes must be distinct".to_string());
+    }
+    Ok(())
+}
+
+/// 🎲 Spawn weight of one tile value, in percent of all spawns
+#[derive(
+    async_graphql::SimpleObject,
+    async_graphql::InputObject,
+    Debug,
+    Deserialize,
+    Serialize,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+)]
+#[graphql(input_name = "TileWeightInput")]
+pub struct TileWeight {
+    pub tile_value: u32,
+    pub weight_percent: u8,
+}
+
+/// Largest tile value a tournament can spawn
+pub const MAX_SPAWN_TILE_VALUE: u32 = 16;
+
+#[derive(Debug, thiserror::Error, PartialEq)]
+pub enum TileWeightsError {
+    #[error("Spawn tile {0} is not a power of two between 2 and {MAX_SPAWN_TILE_VALUE}")]
+    InvalidTile(u32),
+    #[error("Spawn tile {0} is listed more than once")]
+    DuplicateTile(u32),
+    #[error("Tile weights add up to {0}%, expected 100%")]
+    WeightSum(u32),
+}
+
+impl TileWeight {
+    /// Tile values are powers of two from 2 to `MAX_SPAWN_TILE_VALUE`, each listed once,
+    /// and the weights add up to 100 (an empty list keeps the classic spawns)
+    pub fn validate_all(weights: &[TileWeight]) -> Result<(), TileWeightsError> {
+        if weights.is_empty() {
+            return Ok(());
+        }
+        for (index, weight) in weights.iter().enumerate() {
+            if !weight.tile_value.is_power_of_two()
+                || !(2..=MAX_SPAWN_TILE_VALUE).contains(&weight.tile_value)
+            {
+                return Err(TileWeightsError::InvalidTile(weight.tile_value));
+            }
+            if weights[..index]
+                .iter()
+                .any(|other| other.tile_value == weight.tile_value)
+            {
+                return Err(TileWeightsError::DuplicateTile(weight.tile_value));
+            }
+        }
+        let total: u32 = weights
+            .iter()
+            .map(|weight| weight.weight_percent as u32)
+            .sum();
+        if total != 100 {
+            return Err(TileWeightsError::WeightSum(total));
+        }
+        Ok(())
+    }
+}
+
+/// Board dimensions. 4x4 boards are packed into a `u64`; the other sizes keep their tiles
+/// in `BoardState::sized_tiles` and are played with `SizedBoard`.
+#[derive(
+    async_graphql::Enum, Copy, Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq,
+)]
+pub enum BoardSize {
+    // Keep FourByFour first: stored state encodes variants by index
+    #[default]
+    FourByFour,
+    ThreeByThree,
+    FiveByFive,
+    SixBySix,
+}
+
+impl BoardSize {
+    /// Number of tiles per row and column
+    pub fn dimension(self) -> usize {
+        match self {
+            BoardSize::ThreeByThree => 3,
+            BoardSize::FourByFour => 4,
+            BoardSize::FiveByFive => 5,
+            BoardSize::SixBySix => 6,
+        }
+    }
+}
+
+/// ♻️ Claimed pool chains not initialized within this many hours can be recovered (default)
+pub const DEFAULT_UNCLAIMED_TIMEOUT_HOURS: u32 = 24;
+
+/// 🎲 A beacon round completes after this many contributions from distinct chains
+pub const BEACON_CONTRIBUTIONS_PER_ROUND: usize = 3;
+
+/// 🎟️ Players admitted from a waitlist lose their slot if they do not play within this time
+pub const WAITLIST_ADMISSION_TIMEOUT_SECONDS: u64 = 3600;
+
+/// 🎟️ Participants of a full tournament who have scored free their slot after this long
+/// without a new score (unscored ones after `WAITLIST_ADMISSION_TIMEOUT_SECONDS`)
+pub const WAITLIST_INACTIVE_TIMEOUT_SECONDS: u64 = 86400;
+
+/// 📮 A dead letter is permanently failed after this many unsuccessful retries
+pub const MAX_DEAD_LETTER_RETRIES: u8 = 3;
+
+/// 🌟 At most this many chains receive platform highlights, bounding the main chain's fan-out
+pub const MAX_PLATFORM_SUBSCRIBERS: usize = 1000;
+/// 🌟 Reaching one of these tiles is announced as a platform highlight
+pub const PLATFORM_MILESTONE_TILES: [u64; 3] = [4096, 8192, 16384];
+/// 🌟 Subscriber chains keep this many of the latest highlights
+pub const MAX_PLATFORM_HIGHLIGHTS: usize = 50;
+
+/// 🌟 A new top-10 entry or milestone tile in a tournament
+#[derive(async_graphql::SimpleObject, Debug, Default, Deserialize, Serialize, Clone, PartialEq)]
+pub struct PlatformHighlight {
+    pub player: String,
+    pub score: u64,
+    pub highest_tile: u64,
+    pub leaderboard_id: String,
+    pub rank: u32, // Rank in the tournament (0 = outside the top 10)
+}
+
+/// 📅 DAILY CHALLENGE: Leaderboard id of daily challenge boards; their scores go to the
+/// main chain's daily leaderboard instead of a tournament
+pub const DAILY_CHALLENGE_LEADERBOARD_ID: &str = "daily";
+
+/// 🔎 A live board's current state, computed from its packed tiles
+#[derive(async_graphql::SimpleObject, Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct BoardStateResponse {
+    pub tiles_hex: String, // Packed u64 tiles, 16 hex digits
+    pub score: u64,
+    pub moves_count: u32,
+    pub highest_tile: u32,
+    pub available_moves: u8,
+    pub is_game_over: bool,
+    pub elapsed_seconds: u64, // Since the board was created
+}
+
+impl BoardStateResponse {
+    /// A board is over once it ended or no direction changes it
+    pub fn from_board(
+        tiles: u64,
+        score: u64,
+        moves_count: u32,
+        is_ended: bool,
+        created_at: u64,   // micros
+        current_time: u64, // micros
+    ) -> Self {
+        let available_moves = Moves::count_available_moves(tiles);
+        BoardStateResponse {
+            tiles_hex: format!("{:016x}", tiles),
+            score,
+            moves_count,
+            highest_tile: tiles.highest_tile(),
+            available_moves,
+            is_game_over: is_ended || available_moves == 0,
+            elapsed_seconds: current_time.saturating_sub(created_at) / 1_000_000,
+        }
+    }
+}
+
+/// 📅 A finished daily challenge on the main chain's daily leaderboard
+#[derive(async_graphql::SimpleObject, Debug, Default, Deserialize, Serialize, Clone, PartialEq)]
+pub struct DailyChallengeRecord {
+    pub player: String,
+    pub score: u64,
+    pub moves_count: u32,
+    pub completed_at: u64, // Block time the game ended (micros)
+}
+
+/// 📅 UTC date of `day_number` (days since the Unix epoch) as `YYYY-MM-DD`
+pub fn date_ymd(day_number: u64) -> String {
+    // Civil-from-days over 400-year eras of 146097 days, with years starting in March
+    let days = day_number + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = era * 400 + year_of_era + u64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// 📅 Shared seed of a day's challenge: hex of `sha256(day_number || platform_salt)`
+pub fn daily_challenge_seed(day_number: u64, platform_salt: &str) -> String {
+    use sha2::{Digest, Sha256};
+    let mut hasher = Sha256::new();
+    hasher.update(day_number.to_le_bytes());
+    hasher.update(platform_salt);
+    encode_hex(&hasher.finalize())
+}
+
+/// 📅 Board id of a day's challenge, the same on every player chain
+pub fn daily_challenge_board_id(day_number: u64) -> String {
+    format!("daily_{}", date_ymd(day_number))
+}
+
+/// 🎲 Randomness beacon mixed into new board seeds, so players cannot grind seeds
+/// from inputs they control alone. Round 0 means no beacon has been published yet.
+#[derive(async_graphql::SimpleObject, Debug, Default, Deserialize, Serialize, Clone, PartialEq)]
+pub struct RandomnessBeacon {
+    pub beacon_value: u64,
+    pub beacon_round: u64,
+    pub contributed_chains: Vec<ChainId>, // Chains whose hashes went into this round
+}
+
+/// 🤝 Players within this many rating points of each other can be matched
+pub const MATCHMAKING_RATING_WINDOW: u32 = 100;
+/// 🤝 Players leave the matchmaking pool after waiting this long without a match (seconds)
+pub const MATCHMAKING_TIMEOUT_SECONDS: u64 = 600;
+/// 🤝 Skill rating of players who have not been rated yet
+pub const DEFAULT_SKILL_RATING: u32 = 1200;
+
+/// 🤝 A player waiting in the matchmaking pool
+#[derive(async_graphql::SimpleObject, Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct MatchmakingEntry {
+    pub player: String,
+    pub skill_rating: u32,
+    pub preferred_time_limit: u32, // seconds
+    pub entered_at: u64,           // micros
+}
+
+impl MatchmakingEntry {
+    pub fn expires_at(&self) -> u64 {
+        self.entered_at + MATCHMAKING_TIMEOUT_SECONDS * 1_000_000
+    }
+
+    /// Pair waiting players: longest waiting first, each with the closest-rated
+    /// unpaired player within `MATCHMAKING_RATING_WINDOW`. Returns pairs of pool keys.
+    pub fn pair(entries: &[(String, MatchmakingEntry)]) -> Vec<(String, String)> {
+        let mut waiting: Vec<&(String, MatchmakingEntry)> = entries.iter().collect();
+        waiting.sort_by_key(|(key, entry)| (entry.entered_at, key.clone()));
+
+        let mut paired = vec![false; waiting.len()];
+        let mut pairs = Vec::new();
+        for i in 0..waiting.len() {
+            if paired[i] {
+                continue;
+            }
+            let rating = waiting[i].1.skill_rating;
+            let opponent = (i + 1..waiting.len())
+                .filter(|j| !paired[*j])
+                .map(|j| (j, waiting[j].1.skill_rating.abs_diff(rating)))
+                .filter(|(_, difference)| *difference <= MATCHMAKING_RATING_WINDOW)
+                .min_by_key(|(j, difference)| (*difference, *j));
+            if let Some((j, _)) = opponent {
+                paired[i] = true;
+                paired[j] = true;
+                pairs.push((waiting[i].0.clone(), waiting[j].0.clone()));
+            }
+        }
+        pairs
+    }
+}
+
+/// 🥊 Smallest and largest single-elimination brackets (players)
+pub const MIN_BRACKET_SIZE: u32 = 2;
+pub const MAX_BRACKET_SIZE: u32 = 64;
+
+#[derive(Debug, thiserror::Error, PartialEq)]
+pub enum BracketError {
+    #[error(
+        "Bracket size {0} must be a power of two between {MIN_BRACKET_SIZE} and {MAX_BRACKET_SIZE}"
+    )]
+    InvalidSize(u32),
+}
+
+/// 🥊 One match of a single-elimination bracket: the higher score at the deadline advances
+#[derive(async_graphql::SimpleObject, Debug, Default, Deserialize, Serialize, Clone, PartialEq)]
+pub struct BracketMatchup {
+    pub round: u32, // 1 = first round
+    pub player_a: String,
+    pub player_b: String, // "" until the other feeding match is decided
+    pub game_id_a: String,
+    pub game_id_b: String,
+    pub deadline: u64,  // micros, 0 until both players are known
+    pub winner: String, // "" until decided
+}
+
+impl BracketMatchup {
+    pub fn validate_size(bracket_size: u32) -> Result<(), BracketError> {
+        if !bracket_size.is_power_of_two()
+            || !(MIN_BRACKET_SIZE..=MAX_BRACKET_SIZE).contains(&bracket_size)
+        {
+            return Err(BracketError::InvalidSize(bracket_size));
+        }
+        Ok(())
+    }
+
+    /// Seeds (0 = best) in first-round order, so that consecutive pairs are the matches and
+    /// the top seeds can only meet in the last rounds (1v8, 4v5, 2v7, 3v6 for 8 players)
+    pub fn seed_order(bracket_size: u32) -> Vec<usize> {
+        let mut order = vec![0];
+        while order.len() < bracket_size as usize {
+            let last = order.len() * 2 - 1;
+            order = order.iter().flat_map(|&seed| [seed, last - seed]).collect();
+        }
+        order
+    }
+
+    pub fn id(round: u32, index: u32) -> String {
+        format!("{}.{}", round, index)
+    }
+
+    /// (round, index) of a match id made by `id`
+    pub fn parse_id(id: &str) -> Option<(u32, u32)> {
+        let (round, index) = id.split_once('.')?;
+        Some((round.parse().ok()?, index.parse().ok()?))
+    }
+
+    /// Match the winner of match `index` of `round` plays next (None after the final)
+    pub fn next_id(round: u32, index: u32, bracket_size: u32) -> Option<String> {
+        (bracket_size >> round > 1).then(|| Self::id(round + 1, index / 2))
+    }
+
+    /// (winner, loser) for these scores; player A, the better seed, wins ties
+    pub fn decide(&self, score_a: u64, score_b: u64) -> (String, String) {
+        if score_b > score_a {
+            (self.player_b.clone(), self.player_a.clone())
+        } else {
+            (self.player_a.clone(), self.player_b.clone())
+        }
+    }
+}
+
+/// 🔄 One pairing of a round-robin round: the bigger score gain during the round wins
+#[derive(async_graphql::SimpleObject, Debug, Default, Deserialize, Serialize, Clone, PartialEq)]
+pub struct Matchup {
+    pub round: u8, // 1 = first round
+    pub player_a: String,
+    pub player_b: String,
+}
+
+impl Matchup {
+    /// Pairs of the first `rounds` rounds by the circle method: the first player keeps their
+    /// seat and the others rotate one seat per round, so everyone meets everyone once every
+    /// `n - 1` rounds. With an odd number of players, the one facing the empty seat sits out.
+    pub fn round_robin(players: &[String], rounds: u8) -> Vec<Vec<(String, String)>> {
+        if players.len() < 2 {
+            return Vec::new();
+        }
+        let mut seats: Vec<Option<&String>> = players.iter().map(Some).collect();
+        if seats.len() % 2 == 1 {
+            seats.push(None);
+        }
+        let n = seats.len();
+        let mut schedule = Vec::new();
+        for _ in 0..rounds {
+            let pairs = (0..n / 2)
+                .filter_map(|i| match (seats[i], seats[n - 1 - i]) {
+                    (Some(a), Some(b)) => Some((a.clone(), b.clone())),
+                    _ => None,
+                })
+                .collect();
+            schedule.push(pairs);
+            seats[1..].rotate_right(1);
+        }
+        schedule
+    }
+}
+
+/// ↩️ Undo budget of boards outside a known tournament (no limit)
+pub const UNLIMITED_UNDOS: u32 = u32::MAX;
+
+/// ↩️ Board states kept for undo when the tournament does not set `undo_history_size`
+pub const DEFAULT_UNDO_HISTORY_SIZE: u32 = 3;
+
+/// ↩️ Most board states a tournament may keep for undo
+pub const MAX_UNDO_HISTORY_SIZE: u32 = 10;
+
+/// 💡 Hints a player can request per board
+pub const MAX_HINTS_PER_BOARD: u32 = 5;
+
+/// 💡 Suggested move, valid while the board's move count is still `move_count`
+#[derive(async_graphql::SimpleObject, Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct MoveHint {
+    pub direction: Direction,
+    pub hint_score: u64,
+    pub move_count: u32,
+}
+
+/// A game can be continued on a new board only this long after it was created (seconds)
+pub const GAME_CONTINUATION_WINDOW_SECONDS: u64 = 300;
+
+/// Longest default time limit a player can set (seconds)
+pub const MAX_DEFAULT_TIME_LIMIT: u32 = 3600;
+
+/// ⚙️ Per-player preferences stored on the player chain
+#[derive(
+    async_graphql::SimpleObject,
+    async_graphql::InputObject,
+    Debug,
+    Default,
+    Deserialize,
+    Serialize,
+    Clone,
+    PartialEq,
+)]
+#[graphql(input_name = "PlayerPreferencesInput")]
+pub struct PlayerPreferences {
+    pub auto_spectate_friends: bool,
+    pub notify_friend_milestones: bool,
+    pub preferred_board_size: BoardSize,
+    pub notify_tournament_start: bool,
+    pub default_time_limit: Option<u32>, // seconds, max 3600
+    #[serde(default)]
+    pub top_boards_capacity: Option<u8>, // best games kept, max MAX_TOP_BOARDS (None = DEFAULT_TOP_BOARDS)
+}
+
+/// 📦 A player's best score in one active tournament
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct ExportedScore {
+    pub leaderboard_id: String,
+    pub best_score: u64,
+}
+
+/// 📦 Everything a player chain stores about its player, returned by `ExportPlayerData`
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct PlayerDataExport {
+    pub username: String,
+    pub chain_id: String,
+    pub is_mod: bool,
+    pub top_boards: Vec<BoardSummary>,
+    pub active_scores: Vec<ExportedScore>,
+    pub total_moves: u64,
+    pub generated_at: u64, // micros
+}
+
+impl PlayerDataExport {
+    /// Compact `{"payload":...,"sha256":...}` JSON; the hash is over the payload's JSON
+    pub fn to_json_with_hash(&self) -> String {
+        use sha2::{Digest, Sha256};
+        let payload = serde_json::to_string(self).unwrap();
+        let sha256: String = Sha256::digest(payload.as_bytes())
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+        format!("{{\"payload\":{},\"sha256\":\"{}\"}}", payload, sha256)
+    }
+}
+
+/// 🏅 Best ended games a player chain keeps by default
+pub const DEFAULT_TOP_BOARDS: u8 = 10;
+/// Most best ended games a player can choose to keep
+pub const MAX_TOP_BOARDS: u8 = 50;
+
+/// 🏅 One of a player's best ended games
+#[derive(async_graphql::SimpleObject, Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct BoardSummary {
+    pub board_id: String,
+    pub score: u64,
+    pub highest_tile: u64,
+    pub moves_count: u32,
+    pub leaderboard_id: String,
+    pub ended_at: u64, // micros
+}
+
+impl BoardSummary {
+    /// Insert into `top_boards` (best score first, earlier game first on ties), keeping at most `capacity`
+    pub fn insert_ranked(
+        top_boards: &mut Vec<BoardSummary>,
+        summary: BoardSummary,
+        capacity: usize,
+    ) {
+        top_boards.retain(|board| board.board_id != summary.board_id);
+        let index = top_boards.partition_point(|board| {
+            (std::cmp::Reverse(board.score), board.ended_at)
+                <= (std::cmp::Reverse(summary.score), summary.ended_at)
+        });
+        top_boards.insert(index, summary);
+        top_boards.truncate(capacity);
+    }
+}
+
+/// ⛏️ Highest proof-of-work difficulty a tournament can require (leading zero bits)
+pub const MAX_POW_DIFFICULTY: u8 = 32;
+
+/// Lowest allowed handicap multiplier (1.0 = no handicap)
+pub const MIN_HANDICAP_MULTIPLIER: f64 = 0.5;
+
+/// ⚖️ Effective score of a handicapped player: the score leaderboards rank and pay by
+pub fn handicapped_score(raw_score: u64, handicap: f64) -> u64 {
+    (raw_score as f64 * handicap).round() as u64
+}
+
+/// Maximum bonus windows a tournament can have scheduled at once
+pub const MAX_BONUS_WINDOWS: usize = 5;
+
+/// ⏰ "Happy hour" window: merge points earned inside it are multiplied
+#[derive(async_graphql::InputObject, Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[graphql(input_name = "BonusWindowInput")]
+pub struct BonusWindow {
+    pub start: u64, // micros
+    pub end: u64,   // micros
+    pub multiplier: f64,
+    pub window_name: String,
+}
+
+impl BonusWindow {
+    pub fn is_active(&self, timestamp: u64) -> bool {
+        self.start <= timestamp && timestamp < self.end
+    }
+
+    /// Highest multiplier among the windows active at `timestamp` (1.0 when none)
+    pub fn multiplier_at(windows: &[BonusWindow], timestamp: u64) -> f64 {
+        windows
+            .iter()
+            .filter(|window| window.is_active(timestamp))
+            .map(|window| window.multiplier)
+            .fold(1.0, f64::max)
+    }
+}
+
+/// Fewest merges in one move that earn a combo bonus
+pub const COMBO_MIN_MERGES: usize = 3;
+
+/// 💥 Combo bonus for moves that merge several pairs at once
+pub struct ComboMultiplier;
+
+impl ComboMultiplier {
+    /// Extra points for a move merging `merge_count` pairs worth `merged_value`: the value is
+    /// multiplied by `1 + 0.1 * (merge_count - 2)`, rounded down, from `COMBO_MIN_MERGES` merges
+    ///
+    /// ```
+    /// use game2048::ComboMultiplier;
+    ///
+    /// assert_eq!(ComboMultiplier::bonus(40, 2), 0);
+    /// assert_eq!(ComboMultiplier::bonus(40, 3), 4);
+    /// assert_eq!(ComboMultiplier::bonus(28, 4), 5); // 28 * 1.2 = 33.6
+    /// ```
+    pub fn bonus(merged_value: u64, merge_count: usize) -> u64 {
+        if merge_count < COMBO_MIN_MERGES {
+            return 0;
+        }
+        merged_value * (merge_count as u64 - 2) / 10
+    }
+}
+
+/// 🔥 Score multiplier for players on a run of consecutive tournaments
+#[derive(async_graphql::InputObject, Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[graphql(input_name = "StreakBonusTierInput")]
+pub struct StreakBonusTier {
+    pub min_streak: u32,
+    pub multiplier: f64,
+}
+
+impl StreakBonusTier {
+    /// Multiplier of the highest tier reached by `streak` (1.0 when none)
+    pub fn multiplier_for(tiers: &[StreakBonusTier], streak: u32) -> f64 {
+        tiers
+            .iter()
+            .filter(|tier| streak >= tier.min_streak)
+            .max_by_key(|tier| tier.min_streak)
+            .map_or(1.0, |tier| tier.multiplier)
+    }
+
+    /// Consecutive most recent ended tournaments the player scored in.
+    /// `ended` holds (end_time, played) for each ended tournament, in any order.
+    pub fn streak(ended: &[(u64, bool)]) -> u32 {
+        let mut ended = ended.to_vec();
+        ended.sort_by(|a, b| b.0.cmp(&a.0));
+        ended.iter().take_while(|(_, played)| *played).count() as u32
+    }
+}
+
+/// 🔐 Critical admin operations that require multi-sig approval
+#[derive(async_graphql::OneofObject, Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[graphql(input_name = "CriticalAdminActionInput")]
+pub enum CriticalAdminAction {
+    /// Grant or revoke moderator status (username)
+    ToggleAdmin(String),
+    /// Delete a tournament (leaderboard_id)
+    DeleteLeaderboard(String),
+}
+
+/// Replay scores differing from the original by more than this ratio are flagged
+pub const REPLAY_DISCREPANCY_THRESHOLD: f64 = 0.01;
+
+/// 🔁 Original vs replayed best score for one player
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct ReplayResult {
+    pub username: String,
+    pub original_score: u64,
+    pub replay_score: u64,
+    pub flagged: bool, // Differs by more than 1% (e.g. timing-sensitive bonuses)
+}
+
+impl ReplayResult {
+    pub fn compare(username: String, original_score: u64, replay_score: u64) -> Self {
+        let difference = original_score.abs_diff(replay_score) as f64;
+        let flagged = if original_score == 0 {
+            replay_score > 0
+        } else {
+            difference / original_score as f64 > REPLAY_DISCREPANCY_THRESHOLD
+        };
+        Self {
+            username,
+            original_score,
+            replay_score,
+            flagged,
+        }
+    }
+}
+
+/// Maximum records in one historical data import
+pub const MAX_HISTORICAL_IMPORT_RECORDS: usize = 500;
+
+/// 📥 Score record imported from an off-chain tournament system
+#[derive(async_graphql::SimpleObject, Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct HistoricalRecord {
+    pub player: String,
+    pub score: u64,
+    pub timestamp: u64,
+    pub board_id: String,
+}
+
+#[derive(Debug, thiserror::Error, PartialEq)]
+pub enum HistoricalImportError {
+    #[error("Malformed historical data: {0}")]
+    Malformed(String),
+    #[error("Historical data must contain 1-{MAX_HISTORICAL_IMPORT_RECORDS} records")]
+    InvalidRecordCount,
+    #[error("Record {0} has a zero score")]
+    ZeroScore(usize),
+    #[error("Record {0} is not in chronological order")]
+    NotChronological(usize),
+}
+
+impl HistoricalRecord {
+    /// Parse a JSON array of records, checking size, scores and chronological order
+    pub fn parse_batch(data_json: &str) -> Result<Vec<HistoricalRecord>, HistoricalImportError> {
+        let records: Vec<HistoricalRecord> = serde_json::from_str(data_json)
+            .map_err(|error| HistoricalImportError::Malformed(error.to_string()))?;
+        if records.is_empty() || records.len() > MAX_HISTORICAL_IMPORT_RECORDS {
+            return Err(HistoricalImportError::InvalidRecordCount);
+        }
+        for (i, record) in records.iter().enumerate() {
+            if record.score == 0 {
+                return Err(HistoricalImportError::ZeroScore(i));
+            }
+            if i > 0 && record.timestamp < records[i - 1].timestamp {
+                return Err(HistoricalImportError::NotChronological(i));
+            }
+        }
+        Ok(records)
+    }
+}
+
+/// 🏆 Rank players by highest tile, independent of score.
+/// Input is (username, highest_tile, last_update); ties go to the earlier last update.
+pub fn rank_by_highest_tile(mut players: Vec<(String, u64, u64)>) -> Vec<(String, u64)> {
+    players.sort_by(|a, b| {
+        b.1.cmp(&a.1)
+            .then(a.2.cmp(&b.2))
+            .then_with(|| a.0.cmp(&b.0))
+    });
+    players
+        .into_iter()
+        .map(|(username, highest_tile, _)| (username, highest_tile))
+        .collect()
+}
+
+/// ⚔️ Largest `top_n` for a head-to-head matrix (n * (n - 1) / 2 records)
+pub const MAX_HEAD_TO_HEAD_PLAYERS: u32 = 20;
+
+/// ⚔️ Head-to-head results of two players over finished tournaments.
+/// A win is a tournament where the player finished above the other (higher best score).
+#[derive(async_graphql::SimpleObject, Debug, Default, Clone, PartialEq)]
+pub struct HeadToHeadRecord {
+    pub player_a: String,
+    pub player_b: String,
+    pub player_a_wins: u32,
+    pub player_b_wins: u32,
+    pub ties: u32,
+}
+
+impl HeadToHeadRecord {
+    /// Compare two players in every tournament both of them played
+    pub fn compute(tournaments: &[Vec<HistoricalRecord>], player_a: &str, player_b: &str) -> Self {
+        let standings: Vec<_> = tournaments
+            .iter()
+            .map(|records| Self::best_scores(records))
+            .collect();
+        Self::from_standings(&standings, player_a, player_b)
+    }
+
+    /// Records among the `top_n` players by best score, each pair once (a above b in the ranking)
+    pub fn matrix(tournaments: &[Vec<HistoricalRecord>], top_n: u32) -> Vec<Self> {
+        let standings: Vec<_> = tournaments
+            .iter()
+            .map(|records| Self::best_scores(records))
+            .collect();
+
+        let mut best: std::collections::HashMap<&str, u64> = std::collections::HashMap::new();
+        for (player, score) in standings.iter().flatten() {
+            let entry = best.entry(player).or_default();
+            *entry = (*entry).max(*score);
+        }
+        let mut top: Vec<(&str, u64)> = best.into_iter().collect();
+        top.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        top.truncate(top_n.min(MAX_HEAD_TO_HEAD_PLAYERS) as usize);
+
+        let mut records = Vec::new();
+        for (i, (player_a, _)) in top.iter().enumerate() {
+            for (player_b, _) in &top[i + 1..] {
+                records.push(Self::from_standings(&standings, player_a, player_b));
+            }
+        }
+        records
+    }
+
+    fn from_standings(
+        standings: &[std::collections::HashMap<&str, u64>],
+        player_a: &str,
+        player_b: &str,
+    ) -> Self {
+        let mut record = Self {
+            player_a: player_a.to_string(),
+            player_b: player_b.to_string(),
+            ..Default::default()
+        };
+        for scores in standings {
+            let (Some(score_a), Some(score_b)) = (scores.get(player_a), scores.get(player_b))
+            else {
+                continue;
+            };
+            match score_a.cmp(score_b) {
+                std::cmp::Ordering::Greater => record.player_a_wins += 1,
+                std::cmp::Ordering::Less => record.player_b_wins += 1,
+                std::cmp::Ordering::Equal => record.ties += 1,
+            }
+        }
+        record
+    }
+
+    /// Final standing of one tournament: each player's best score
+    fn best_scores(records: &[HistoricalRecord]) -> std::collections::HashMap<&str, u64> {
+        let mut scores = std::collections::HashMap::new();
+        for record in records {
+            let best = scores.entry(record.player.as_str()).or_default();
+            *best = record.score.max(*best);
+        }
+        scores
+    }
+}
+
+/// Leaderboard row for live stream feeds
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct LeaderboardEntry {
+    pub username: String,
+    pub raw_score: u64,
+    pub effective_score: u64, // ⚖️ Raw score * handicap, the score rows are ranked by
+}
+
+/// 📡 Off-chain relay endpoint for tournament live streams
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct StreamEndpoint {
+    pub endpoint_url: String,
+    pub auth_token_hash: String, // Chain state is public: never the token itself
+    pub relay_owner: String,     // Account allowed to report deliveries
+    pub consecutive_failures: u32,
+    pub registered_at: u64,
+}
+
+/// 📡 Hex sha256 of a stream relay's auth token, the only form of it kept on chain
+pub fn stream_auth_token_hash(auth_token: &str) -> String {
+    use sha2::{Digest, Sha256};
+    encode_hex(&Sha256::digest(auth_token.as_bytes()))
+}
+
+/// Tournament information for the registry
+#[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct TournamentInfo {
     pub tournament_id: String,
     pub name: String,
@@ -285,6 +2083,72 @@ pub struct TournamentInfo {
     pub start_time: Option<u64>, // None = unlimited start time
     pub end_time: Option<u64>,   // None = unlimited end time
     pub total_players: u32,
+    /// Token gate for board creation (None = open entry)
+    #[serde(default)]
+    pub entry_token_requirement: Option<TokenRequirement>,
+    /// Scheduled score multiplier windows
+    #[serde(default)]
+    pub bonus_windows: Vec<BonusWindow>,
+    /// Anti-cheat thresholds enforced on moves (None = not enforced; reports use defaults)
+    #[serde(default)]
+    pub anti_cheat: Option<AntiCheatConfig>,
+    /// Tournament is being replayed - regular moves are rejected
+    #[serde(default)]
+    pub replay_mode: bool,
+    /// Streak multipliers by consecutive tournaments played
+    #[serde(default)]
+    pub streak_bonus_tiers: Vec<StreakBonusTier>,
+    /// Classic games may be converted to speed games
+    #[serde(default)]
+    pub allow_variant_conversion: bool,
+    /// Prize pool per token
+    #[serde(default)]
+    pub prize_pool_entries: Vec<PrizePoolEntry>,
+    /// Leading zero bits required of a new board's proof of work (0 = none)
+    #[serde(default)]
+    pub pow_difficulty: u8,
+    /// Reaching this score ends the game (0 = no cap)
+    #[serde(default)]
+    pub max_score_per_player: u64,
+    /// Warmup boards can be created this long before the start (0 = no warmup)
+    #[serde(default)]
+    pub warmup_duration_seconds: u32,
+    /// Undos per game (0 = none, the game stays competitive)
+    #[serde(default)]
+    pub undo_budget: u32,
+    /// Board states kept for undo (0 = `DEFAULT_UNDO_HISTORY_SIZE`)
+    #[serde(default)]
+    pub undo_history_size: u32,
+    /// Variant of new boards; speed boards end their time limit after creation
+    #[serde(default)]
+    pub variant: GameVariant,
+    /// Spawn weights of new boards (empty = classic 90% twos, 10% fours)
+    #[serde(default)]
+    pub tile_weights: Vec<TileWeight>,
+    /// Moves with at least `COMBO_MIN_MERGES` merges earn a combo bonus
+    #[serde(default)]
+    pub combo_multiplier_enabled: bool,
+    /// Most moves per MakeMoves call (0 = `DEFAULT_MAX_MOVES_PER_CALL`)
+    #[serde(default)]
+    pub max_moves_per_call: u16,
+    /// Shortest gap between consecutive moves of a call (0 = `DEFAULT_MIN_MOVE_INTERVAL_MICROS`)
+    #[serde(default)]
+    pub min_move_interval_micros: u64,
+    /// Shortest gap between two new boards of a player (0 = `DEFAULT_NEW_BOARD_COOLDOWN_SECONDS`)
+    #[serde(default)]
+    pub new_board_cooldown_seconds: u64,
+    /// 🧱💣 Chances of obstacle and bomb tiles (None = no power-ups)
+    #[serde(default)]
+    pub power_ups: Option<PowerUpConfig>,
+    /// 🏎️ Rank by score or by time to a target tile
+    #[serde(default)]
+    pub mode: LeaderboardMode,
+    /// 🎟️ Most players admitted, later ones are waitlisted (None = no limit)
+    #[serde(default)]
+    pub max_participants: Option<u32>,
+    /// 📋 Leaderboard this one was cloned from (None = created from scratch)
+    #[serde(default)]
+    pub cloned_from: Option<String>,
 }
 
 impl TournamentInfo {
@@ -333,17 +2197,238 @@ pub struct PlayerScoreSummary {
     pub highest_tile: u64,
     pub last_update: u64,
     pub game_status: GameStatus,
-    /// 🚀 NEW: Player's total board count in this tournament
-    pub boards_in_tournament: u32,
+    /// Player's board count per tournament (leaderboard_id -> boards)
+    #[serde(default)]
+    pub boards_per_leaderboard: std::collections::HashMap<String, u32>,
     /// 🚀 ACTIVE BOARDS: Current active boards tracked for this player
     #[serde(default)]
     pub active_boards: Vec<ActiveBoardSummary>,
+    /// ⏲️ Average time per move in micros; lower wins ties on score (0 = unknown)
+    #[serde(default)]
+    pub avg_move_time_micros: u64,
+    /// ⚡ Best score per move over the player's boards (0 = unknown)
+    #[serde(default)]
+    pub efficiency: u64,
+    /// ⚡ Board that produced `efficiency` (may differ from `board_id`)
+    #[serde(default)]
+    pub best_efficiency_board_id: String,
+    /// ⚖️ When `best_score` was first reached, for `TieBreakPolicy::ByEarliestAchievement`
+    #[serde(default)]
+    pub first_achievement_time: u64,
+    /// 🏎️ Fastest time to the speed run target tile (None = not reached, unranked)
+    #[serde(default)]
+    pub time_to_target_micros: Option<u64>,
+}
+
+/// ⏲️ Move timing of a board in micros (min/max 0 = none yet)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MoveTiming {
+    pub total_think_time_micros: u64,
+    pub min_move_time_micros: u64,
+    pub max_move_time_micros: u64,
+}
+
+impl MoveTiming {
+    /// Add a batch of `moves` moves made `batch_time_micros` of block time after the
+    /// previous batch; each move is timed at the batch's average
+    pub fn record_batch(&mut self, batch_time_micros: u64, moves: u32) {
+        if moves == 0 {
+            return;
+        }
+        let move_time_micros = batch_time_micros / moves as u64;
+        self.total_think_time_micros += batch_time_micros;
+        if self.min_move_time_micros == 0 || move_time_micros < self.min_move_time_micros {
+            self.min_move_time_micros = move_time_micros;
+        }
+        self.max_move_time_micros = self.max_move_time_micros.max(move_time_micros);
+    }
+}
+
+/// ⚡ Score per move (0 before the first move)
+pub fn efficiency(score: u64, moves_count: u32) -> u64 {
+    match moves_count {
+        0 => 0,
+        moves_count => score / moves_count as u64,
+    }
+}
+
+/// 🔁 A score message is applied only if its nonce is newer than the board's last applied one
+/// (nonce 0 predates deduplication and is always applied)
+pub fn is_fresh_nonce(last_applied: Option<u64>, message_nonce: u64) -> bool {
+    message_nonce == 0 || last_applied.is_none_or(|last| message_nonce > last)
+}
+
+impl PlayerScoreSummary {
+    /// Player's board count in one tournament (0 when none were counted)
+    pub fn boards_in(&self, leaderboard_id: &str) -> u32 {
+        self.boards_per_leaderboard
+            .get(leaderboard_id)
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Compress into the fixed-size form used for shard-to-shard sync and quick ranking.
+    /// Drops `active_boards`, `game_status` and `boards_per_leaderboard`.
+    /// Fails unless the chain ID is 32 hex-encoded bytes and the board ID is
+    /// "<chain_id>.<numeric hash>", the only forms the compact fields can hold losslessly.
+    pub fn compress(&self) -> Result<CompactPlayerScore, CompactScoreError> {
+        let chain_id = decode_hex_32(&self.chain_id)
+            .ok_or_else(|| CompactScoreError::InvalidChainId(self.chain_id.clone()))?;
+        // Board IDs are "<chain_id>.<hash>" - only the numeric hash needs storing
+        let board_hash = self
+            .board_id
+            .rsplit_once('.')
+            .filter(|(prefix, _)| *prefix == self.chain_id)
+            .and_then(|(_, hash)| hash.parse::<u128>().ok())
+            .ok_or_else(|| CompactScoreError::InvalidBoardId(self.board_id.clone()))?;
+
+        Ok(CompactPlayerScore {
+            best_score: self.best_score,
+            board_id: board_hash.to_be_bytes(),
+            chain_id,
+            highest_tile: u32::try_from(self.highest_tile).unwrap_or(u32::MAX),
+            last_update: self.last_update,
+        })
+    }
+}
+
+#[derive(Debug, thiserror::Error, PartialEq)]
+pub enum CompactScoreError {
+    #[error("Chain ID {0} is not 32 hex-encoded bytes")]
+    InvalidChainId(String),
+    #[error("Board ID {0} is not <chain_id>.<numeric hash>")]
+    InvalidBoardId(String),
+}
+
+/// Most score entries carried by one `Message::BulkScoreUpdate`
+pub const MAX_BULK_SCORE_ENTRIES: usize = 50;
+
+/// One player's score in a shard's bulk update to the leaderboard chain
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct ScoreUpdateEntry {
+    pub player: String,
+    pub board_id: String,
+    pub score: u64,
+    pub is_end: bool,
+    pub highest_tile: u64,
+    pub timestamp: u64,
+}
+
+impl ScoreUpdateEntry {
+    /// Split pending updates into message payloads of at most `MAX_BULK_SCORE_ENTRIES`
+    pub fn batches(entries: Vec<ScoreUpdateEntry>) -> Vec<Vec<ScoreUpdateEntry>> {
+        entries
+            .chunks(MAX_BULK_SCORE_ENTRIES)
+            .map(|chunk| chunk.to_vec())
+            .collect()
+    }
+}
+
+/// 🗜️ Compact player score for shard storage (fixed 68 bytes vs ~300 for the full summary)
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq)]
+pub struct CompactPlayerScore {
+    pub best_score: u64,
+    pub board_id: [u8; 16], // Numeric board hash (big-endian), chain prefix is `chain_id`
+    pub chain_id: [u8; 32], // Raw chain ID bytes
+    pub highest_tile: u32,
+    pub last_update: u64,
+}
+
+impl CompactPlayerScore {
+    /// Expand back into a `PlayerScoreSummary`.
+    /// `active_boards` is left empty and status/board count use defaults.
+    pub fn expand_partial(&self, player: &str) -> PlayerScoreSummary {
+        let chain_id = encode_hex(&self.chain_id);
+        let board_id = format!("{}.{}", chain_id, u128::from_be_bytes(self.board_id));
+
+        PlayerScoreSummary {
+            player: player.to_string(),
+            best_score: self.best_score,
+            board_id,
+            chain_id,
+            highest_tile: self.highest_tile as u64,
+            last_update: self.last_update,
+            game_status: GameStatus::Active,
+            boards_per_leaderboard: std::collections::HashMap::new(),
+            active_boards: Vec::new(),
+            avg_move_time_micros: 0,
+            efficiency: 0,
+            best_efficiency_board_id: String::new(),
+            first_achievement_time: 0,
+            time_to_target_micros: None,
+        }
+    }
+}
+
+fn decode_hex_32(hex: &str) -> Option<[u8; 32]> {
+    if hex.len() != 64 || !hex.is_ascii() {
+        return None;
+    }
+    let mut bytes = [0u8; 32];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).ok()?;
+    }
+    Some(bytes)
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// 🔮 Spectator prediction of a tournament winner (one per spectator chain)
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct TournamentPrediction {
+    pub spectator_chain_id: String,
+    pub predicted_winner: String,
+    pub predicted_score: u64,
+    pub submitted_at: u64, // Submission timestamp (micros)
+}
+
+impl TournamentPrediction {
+    /// Relative distance to the actual winner score:
+    /// `abs(predicted_score - actual_winner_score) / actual_winner_score` (0.0 = exact)
+    pub fn proximity(&self, actual_winner_score: u64) -> f64 {
+        if actual_winner_score == 0 {
+            return if self.predicted_score == 0 {
+                0.0
+            } else {
+                f64::MAX
+            };
+        }
+        self.predicted_score.abs_diff(actual_winner_score) as f64 / actual_winner_score as f64
+    }
+
+    /// Predictions best first: correct winner, then proximity to the winning score, then the
+    /// earliest submission (then the spectator chain, for determinism)
+    pub fn rank_all(mut predictions: Vec<Self>, winner: &str, winner_score: u64) -> Vec<Self> {
+        predictions.sort_by(|a, b| {
+            (b.predicted_winner == winner)
+                .cmp(&(a.predicted_winner == winner))
+                .then(
+                    a.proximity(winner_score)
+                        .total_cmp(&b.proximity(winner_score)),
+                )
+                .then(a.submitted_at.cmp(&b.submitted_at))
+                .then_with(|| a.spectator_chain_id.cmp(&b.spectator_chain_id))
+        });
+        predictions
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub enum GameEndReason {
-    NoMoves,         // Board is full, no valid moves available
-    TournamentEnded, // Tournament/leaderboard time expired
+    NoMoves,                            // Board is full, no valid moves available
+    TournamentEnded,                    // Tournament/leaderboard time expired
+    Continued { new_board_id: String }, // Progress moved to another board
+    ScoreCapReached,                    // Score reached the tournament's max_score_per_player
+    SuspectedCheat { reason: String },  // Flagged by an anti-cheat check, pending admin review
+}
+
+/// 🚩 Admin decision on a flagged game
+#[derive(async_graphql::Enum, Copy, Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub enum FlagVerdict {
+    Confirmed, // Cheating confirmed: the board's score is invalidated
+    Cleared,   // False positive: the board's score stands
 }
 
 pub enum RegistrationCheck {
@@ -351,8 +2436,774 @@ pub enum RegistrationCheck {
     EnsureNotRegistered,
 }
 
-/// Instantiation argument for the contract
-/// Simple u32 for backward compatibility:
-/// - 0 = use default (300 chains)
-/// - N = create N chains in pool
-pub type InstantiationArgument = u32;
+/// 🔑 Password change tokens expire this long after `RequestPasswordChange` (10 minutes)
+pub const PASSWORD_CHANGE_TOKEN_TTL_SECONDS: u64 = 600;
+
+/// 🔑 Next password change secret: the main chain's previous secret re-hashed with the
+/// current beacon and the request, so it never depends on the requester's inputs alone
+pub fn next_password_change_secret(
+    secret: &[u8; 32],
+    beacon_value: u64,
+    player: &str,
+    timestamp: u64,
+) -> [u8; 32] {
+    use sha2::{Digest, Sha256};
+    let mut hasher = Sha256::new();
+    hasher.update(secret);
+    hasher.update(beacon_value.to_le_bytes());
+    hasher.update(player);
+    hasher.update(timestamp.to_le_bytes());
+    hasher.finalize().into()
+}
+
+/// 🔑 Hex-encoded 16-byte password change token issued from `secret`
+pub fn password_change_token(secret: &[u8; 32]) -> String {
+    use sha2::{Digest, Sha256};
+    let digest = Sha256::digest([secret.as_slice(), b"password-change"].concat());
+    encode_hex(&digest[..16])
+}
+
+/// 🔑 Hex sha256 of a change token; only this is kept in state, never the token itself
+pub fn password_change_token_hash(change_token: &str) -> String {
+    use sha2::{Digest, Sha256};
+    encode_hex(&Sha256::digest(change_token.as_bytes()))
+}
+
+/// 🔑 Check `change_token` against the pending `(token_hash, expires_at)` request at `now` (micros)
+pub fn check_password_change_token(
+    pending: Option<&(String, u64)>,
+    change_token: &str,
+    now: u64,
+) -> Result<(), &'static str> {
+    let Some((token_hash, expires_at)) = pending else {
+        return Err("No password change requested");
+    };
+    if now >= *expires_at {
+        return Err("Password change token has expired");
+    }
+    if password_change_token_hash(change_token) != *token_hash {
+        return Err("Invalid password change token");
+    }
+    Ok(())
+}
+
+/// 🧳 A player's move from one chain to another
+#[derive(Debug, Default, Deserialize, Serialize, Clone, PartialEq)]
+pub struct PlayerMigrationRecord {
+    pub username: String,
+    pub old_chain_id: String,
+    pub new_chain_id: String,
+    pub migrated_at: u64, // micros
+}
+
+/// 📦 One board's moves in a `BatchMakeMoves` call
+#[derive(async_graphql::InputObject, Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct BoardMoveEntry {
+    pub board_id: String,
+    pub moves: String, // JSON array of MoveEntry
+}
+
+/// A `BatchMakeMoves` call accepts up to this many times the per-call move limit
+pub const BATCH_MOVES_MULTIPLIER: u32 = 4;
+
+/// Why a board's moves were rejected
+#[derive(Debug, thiserror::Error, Deserialize, Serialize, Clone, PartialEq)]
+pub enum MoveError {
+    #[error("Invalid moves format")]
+    InvalidFormat,
+    #[error("Board not found")]
+    BoardNotFound,
+    #[error("You can only make move on your own board")]
+    NotBoardOwner,
+    #[error("Tournament is in replay mode - moves are not accepted")]
+    ReplayMode,
+    #[error("Too many moves in one call: {count} (max {max})")]
+    TooManyMoves { count: usize, max: u32 },
+    #[error("Invalid move sequence at move {at_index}: {reason}")]
+    InvalidMoveSequence { at_index: usize, reason: String },
+    #[error("{0}")]
+    Rejected(String),
+    #[error("Game is ended")]
+    GameEnded,
+}
+
+/// 🚀 CHAIN POOL: Target pool size when `InitArgs::pool_size` is 0
+pub const DEFAULT_CHAIN_POOL_SIZE: u32 = 300;
+
+/// Instantiation arguments for the contract
+#[derive(Debug, Default, Deserialize, Serialize, Clone, PartialEq)]
+pub struct InitArgs {
+    /// Target size of the chain pool (0 = `DEFAULT_CHAIN_POOL_SIZE`)
+    #[serde(default)]
+    pub pool_size: u32,
+    /// `AggregateScores` refills the pool up to this size (0 = no auto-refill)
+    #[serde(default)]
+    pub min_pool_size: u32,
+}
+
+pub type InstantiationArgument = InitArgs;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_player_export_hash_covers_payload() {
+        use sha2::{Digest, Sha256};
+        let export = PlayerDataExport {
+            username: "ann".to_string(),
+            chain_id: "chain".to_string(),
+            is_mod: false,
+            top_boards: Vec::new(),
+            active_scores: vec![ExportedScore {
+                leaderboard_id: "cup".to_string(),
+                best_score: 2048,
+            }],
+            total_moves: 120,
+            generated_at: 1_000,
+        };
+        let json: serde_json::Value = serde_json::from_str(&export.to_json_with_hash()).unwrap();
+        let payload: PlayerDataExport = serde_json::from_value(json["payload"].clone()).unwrap();
+        assert_eq!(payload, export);
+        let digest = Sha256::digest(serde_json::to_string(&payload).unwrap().as_bytes());
+        let expected: String = digest.iter().map(|byte| format!("{:02x}", byte)).collect();
+        assert_eq!(json["sha256"], expected);
+    }
+
+    #[test]
+    fn test_top_boards_evict_lowest() {
+        let summary = |board_id: &str, score, ended_at| BoardSummary {
+            board_id: board_id.to_string(),
+            score,
+            highest_tile: 0,
+            moves_count: 0,
+            leaderboard_id: String::new(),
+            ended_at,
+        };
+        let mut top_boards = Vec::new();
+        BoardSummary::insert_ranked(&mut top_boards, summary("a", 500, 1), 2);
+        BoardSummary::insert_ranked(&mut top_boards, summary("b", 900, 2), 2);
+        BoardSummary::insert_ranked(&mut top_boards, summary("c", 500, 3), 2);
+        let ids: Vec<&str> = top_boards.iter().map(|b| b.board_id.as_str()).collect();
+        assert_eq!(ids, vec!["b", "a"]);
+        BoardSummary::insert_ranked(&mut top_boards, summary("d", 100, 4), 2);
+        assert_eq!(top_boards.len(), 2);
+        assert_eq!(top_boards[1].board_id, "a");
+    }
+
+    #[test]
+    fn test_score_nonce_applied_once() {
+        // Two leaderboards receiving the same retried message each apply it once
+        for _ in 0..2 {
+            let mut last_applied = None;
+            let applied = [5, 5, 4, 6]
+                .into_iter()
+                .filter(|&nonce| {
+                    let fresh = is_fresh_nonce(last_applied, nonce);
+                    if fresh {
+                        last_applied = Some(nonce);
+                    }
+                    fresh
+                })
+                .collect::<Vec<u64>>();
+            assert_eq!(applied, vec![5, 6]);
+        }
+        assert!(is_fresh_nonce(Some(9), 0));
+    }
+
+    #[test]
+    fn test_replay_result_flags_discrepancy() {
+        assert!(!ReplayResult::compare("a".to_string(), 10_000, 10_100).flagged);
+        assert!(ReplayResult::compare("a".to_string(), 10_000, 9_800).flagged);
+        assert!(ReplayResult::compare("a".to_string(), 0, 4).flagged);
+    }
+
+    #[test]
+    fn test_speed_game_deadline() {
+        let variant = GameVariant::Speed2048 {
+            time_limit_seconds: 60,
+        };
+        let deadline = variant.deadline_from(1_000_000);
+        assert_eq!(deadline, 61_000_000);
+        assert!(!GameVariant::is_past_deadline(deadline, 60_999_999));
+        // Two blocks after the deadline: the game is over in both
+        assert!(GameVariant::is_past_deadline(deadline, 61_000_000));
+        assert!(GameVariant::is_past_deadline(deadline, 65_000_000));
+        assert_eq!(GameVariant::Classic2048.deadline_from(1_000_000), 0);
+        assert!(!GameVariant::is_past_deadline(0, u64::MAX));
+    }
+
+    #[test]
+    fn test_elimination_lives() {
+        let variant = GameVariant::Elimination { lives_remaining: 3 };
+        assert_eq!(variant.starting_lives(), 3);
+        assert_eq!(variant.deadline_from(1_000_000), 0);
+        assert_eq!(GameVariant::Classic2048.starting_lives(), 0);
+        assert_eq!(variant.to_string(), "Elimination(3 lives)");
+    }
+
+    #[test]
+    fn test_bracket_seeding() {
+        assert!(BracketMatchup::validate_size(8).is_ok());
+        assert_eq!(
+            BracketMatchup::validate_size(6),
+            Err(BracketError::InvalidSize(6))
+        );
+        assert_eq!(
+            BracketMatchup::validate_size(128),
+            Err(BracketError::InvalidSize(128))
+        );
+        assert_eq!(
+            BracketMatchup::validate_size(1),
+            Err(BracketError::InvalidSize(1))
+        );
+
+        assert_eq!(BracketMatchup::seed_order(2), vec![0, 1]);
+        assert_eq!(BracketMatchup::seed_order(8), vec![0, 7, 3, 4, 1, 6, 2, 5]);
+
+        assert_eq!(BracketMatchup::next_id(1, 3, 8), Some("2.1".to_string()));
+        assert_eq!(BracketMatchup::next_id(2, 1, 8), Some("3.0".to_string()));
+        assert_eq!(BracketMatchup::next_id(3, 0, 8), None);
+        assert_eq!(BracketMatchup::parse_id("2.1"), Some((2, 1)));
+
+        let matchup = BracketMatchup {
+            player_a: "alice".to_string(),
+            player_b: "bob".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(matchup.decide(10, 10).0, "alice");
+        assert_eq!(matchup.decide(10, 20).0, "bob");
+    }
+
+    #[test]
+    fn test_round_robin_circle_method() {
+        let players: Vec<String> = ["a", "b", "c", "d", "e"]
+            .iter()
+            .map(|name| name.to_string())
+            .collect();
+        let schedule = Matchup::round_robin(&players, 5);
+        assert_eq!(schedule.len(), 5);
+
+        let mut pairs = std::collections::HashSet::new();
+        for round in &schedule {
+            // 5 players: two matches per round, one player sits out
+            assert_eq!(round.len(), 2);
+            let mut seen = std::collections::HashSet::new();
+            for (a, b) in round {
+                assert!(seen.insert(a) && seen.insert(b), "player twice in a round");
+                pairs.insert(if a < b { (a, b) } else { (b, a) });
+            }
+        }
+        // Every player met every other player exactly once
+        assert_eq!(pairs.len(), 10);
+
+        // The cycle repeats after n - 1 rounds
+        let even = &players[..4];
+        let schedule = Matchup::round_robin(even, 4);
+        assert_eq!(
+            schedule[0],
+            vec![
+                ("a".to_string(), "d".to_string()),
+                ("b".to_string(), "c".to_string())
+            ]
+        );
+        assert_eq!(schedule[3], schedule[0]);
+        assert!(Matchup::round_robin(&players[..1], 3).is_empty());
+    }
+
+    #[test]
+    fn test_tile_weights() {
+        let weight = |tile_value, weight_percent| TileWeight {
+            tile_value,
+            weight_percent,
+        };
+        assert!(TileWeight::validate_all(&[]).is_ok());
+        assert_eq!(
+            TileWeight::validate_all(&[weight(2, 90), weight(6, 10)]),
+            Err(TileWeightsError::InvalidTile(6))
+        );
+        assert_eq!(
+            TileWeight::validate_all(&[weight(2, 90), weight(32, 10)]),
+            Err(TileWeightsError::InvalidTile(32))
+        );
+        assert_eq!(
+            TileWeight::validate_all(&[weight(2, 90), weight(4, 20)]),
+            Err(TileWeightsError::WeightSum(110))
+        );
+        assert_eq!(
+            TileWeight::validate_all(&[weight(2, 50), weight(2, 50)]),
+            Err(TileWeightsError::DuplicateTile(2))
+        );
+
+        // A 20% eights tournament spawns about one eight in five
+        let spawns = SpawnDistribution::from_weights(&[weight(2, 80), weight(8, 20)]).unwrap();
+        let mut state = 0x2048;
+        let eights = (0..10_000)
+            .filter(|_| spawns.sample(&mut state) == 3)
+            .count();
+        assert!((1_800..2_200).contains(&eights), "{} eights", eights);
+    }
+
+    #[test]
+    fn test_bonus_window_multiplier() {
+        let window = |start, end, multiplier| BonusWindow {
+            start,
+            end,
+            multiplier,
+            window_name: "happy_hour".to_string(),
+        };
+        let windows = vec![window(100, 200, 2.0), window(150, 300, 3.0)];
+        assert_eq!(BonusWindow::multiplier_at(&windows, 50), 1.0);
+        assert_eq!(BonusWindow::multiplier_at(&windows, 120), 2.0);
+        assert_eq!(BonusWindow::multiplier_at(&windows, 150), 3.0);
+        assert_eq!(BonusWindow::multiplier_at(&windows, 300), 1.0);
+    }
+
+    #[test]
+    fn test_graphql_query_single_and_batch() {
+        let single: GraphQLQuery = serde_json::from_str(r#"{"query": "{ balance }"}"#).unwrap();
+        assert!(matches!(single, GraphQLQuery::Single(request) if request.query == "{ balance }"));
+
+        let batch: GraphQLQuery =
+            serde_json::from_str(r#"[{"query": "{ balance }"}, {"query": "{ nonce }"}]"#).unwrap();
+        assert!(matches!(batch, GraphQLQuery::Batch(requests) if requests.len() == 2));
+    }
+
+    #[test]
+    fn test_historical_record_parse_batch() {
+        let records = HistoricalRecord::parse_batch(
+            r#"[{"player": "alice", "score": 2048, "timestamp": 100, "board_id": "a1"},
+                {"player": "bob", "score": 1024, "timestamp": 200, "board_id": "b1"}]"#,
+        )
+        .unwrap();
+        assert_eq!(records.len(), 2);
+
+        assert!(matches!(
+            HistoricalRecord::parse_batch("[{\"player\": \"alice\"}]"),
+            Err(HistoricalImportError::Malformed(_))
+        ));
+        assert_eq!(
+            HistoricalRecord::parse_batch("[]"),
+            Err(HistoricalImportError::InvalidRecordCount)
+        );
+        assert_eq!(
+            HistoricalRecord::parse_batch(
+                r#"[{"player": "alice", "score": 8, "timestamp": 200, "board_id": "a1"},
+                    {"player": "bob", "score": 4, "timestamp": 100, "board_id": "b1"}]"#
+            ),
+            Err(HistoricalImportError::NotChronological(1))
+        );
+    }
+
+    #[test]
+    fn test_proof_of_work() {
+        let chain_id = "e476187f6ddfeb9d588c7b45d3df334d5501d6499b3f9ad5595cae86cce16a65";
+        let nonce = (0..)
+            .find(|nonce| proof_of_work_bits(chain_id, "cup", *nonce) >= 8)
+            .unwrap();
+        assert!(proof_of_work_bits(chain_id, "cup", nonce) >= 8);
+        // The proof is bound to the chain and the tournament
+        assert!((0..nonce).all(|nonce| proof_of_work_bits(chain_id, "cup", nonce) < 8));
+        assert_ne!(
+            proof_of_work_bits(chain_id, "cup", nonce),
+            proof_of_work_bits(chain_id, "other", nonce)
+        );
+    }
+
+    #[test]
+    fn test_head_to_head_record() {
+        let record = |player: &str, score: u64| HistoricalRecord {
+            player: player.to_string(),
+            score,
+            timestamp: 0,
+            board_id: String::new(),
+        };
+        let tournaments = vec![
+            vec![record("alice", 500), record("bob", 300), record("bob", 800)],
+            vec![
+                record("alice", 900),
+                record("bob", 400),
+                record("carol", 100),
+            ],
+            vec![record("alice", 200), record("bob", 200)],
+            vec![record("carol", 5000)],
+        ];
+
+        let alice_bob = HeadToHeadRecord::compute(&tournaments, "alice", "bob");
+        assert_eq!(
+            (
+                alice_bob.player_a_wins,
+                alice_bob.player_b_wins,
+                alice_bob.ties
+            ),
+            (1, 1, 1)
+        );
+
+        // carol has the best score, so she leads the matrix: carol-alice, carol-bob, alice-bob
+        let matrix = HeadToHeadRecord::matrix(&tournaments, 3);
+        let pairs: Vec<_> = matrix
+            .iter()
+            .map(|record| (record.player_a.as_str(), record.player_b.as_str()))
+            .collect();
+        assert_eq!(
+            pairs,
+            vec![("carol", "alice"), ("carol", "bob"), ("alice", "bob")]
+        );
+        assert_eq!(matrix[0].player_b_wins, 1);
+        assert_eq!(HeadToHeadRecord::matrix(&tournaments, 1), vec![]);
+    }
+
+    #[test]
+    fn test_validate_invite_codes() {
+        let codes = |codes: &[&str]| {
+            codes
+                .iter()
+                .map(|code| code.to_string())
+                .collect::<Vec<_>>()
+        };
+        assert!(validate_invite_codes(&codes(&["a", "b", "c"])).is_ok());
+        assert!(validate_invite_codes(&[]).is_err());
+        assert!(validate_invite_codes(&codes(&["a", "b", "c", "d"])).is_err());
+        assert!(validate_invite_codes(&codes(&["a", " "])).is_err());
+        assert!(validate_invite_codes(&codes(&["a", "a"])).is_err());
+    }
+
+    #[test]
+    fn test_matchmaking_pairs_within_rating_window() {
+        let entry = |key: &str, skill_rating: u32, entered_at: u64| {
+            (
+                key.to_string(),
+                MatchmakingEntry {
+                    player: key.to_string(),
+                    skill_rating,
+                    preferred_time_limit: 300,
+                    entered_at,
+                },
+            )
+        };
+        let pool = vec![
+            entry("a", 1200, 1),
+            entry("b", 1500, 2),
+            entry("c", 1290, 3),
+            entry("d", 1250, 4),
+            entry("e", 1450, 5),
+        ];
+        // a takes the closest rating (d), b pairs with e, c is left waiting
+        assert_eq!(
+            MatchmakingEntry::pair(&pool),
+            vec![
+                ("a".to_string(), "d".to_string()),
+                ("b".to_string(), "e".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_bulk_score_batches() {
+        let entry = |i: usize| ScoreUpdateEntry {
+            player: format!("player{}", i),
+            board_id: format!("board{}", i),
+            score: i as u64,
+            is_end: false,
+            highest_tile: 2,
+            timestamp: i as u64,
+        };
+        // Messages needed vs one UpdateScore per player
+        for (players, messages) in [(100, 2), (500, 10), (1000, 20)] {
+            let batches = ScoreUpdateEntry::batches((0..players).map(entry).collect());
+            assert_eq!(batches.len(), messages);
+            assert!(batches
+                .iter()
+                .all(|batch| batch.len() <= MAX_BULK_SCORE_ENTRIES));
+            assert_eq!(batches.iter().map(Vec::len).sum::<usize>(), players);
+        }
+        assert!(ScoreUpdateEntry::batches(Vec::new()).is_empty());
+    }
+
+    #[test]
+    fn test_rank_by_highest_tile() {
+        let ranked = rank_by_highest_tile(vec![
+            ("alice".to_string(), 2048, 300),
+            ("bob".to_string(), 4096, 500),
+            ("carol".to_string(), 2048, 100),
+        ]);
+        assert_eq!(
+            ranked,
+            vec![
+                ("bob".to_string(), 4096),
+                ("carol".to_string(), 2048),
+                ("alice".to_string(), 2048),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_streak_bonus_tiers() {
+        let tiers = vec![
+            StreakBonusTier {
+                min_streak: 3,
+                multiplier: 1.1,
+            },
+            StreakBonusTier {
+                min_streak: 5,
+                multiplier: 1.25,
+            },
+        ];
+        assert_eq!(StreakBonusTier::multiplier_for(&tiers, 2), 1.0);
+        assert_eq!(StreakBonusTier::multiplier_for(&tiers, 4), 1.1);
+        assert_eq!(StreakBonusTier::multiplier_for(&tiers, 7), 1.25);
+
+        // Most recent first: played 300 and 200, skipped 100
+        assert_eq!(
+            StreakBonusTier::streak(&[(100, false), (300, true), (200, true)]),
+            2
+        );
+        assert_eq!(StreakBonusTier::streak(&[(100, true), (300, false)]), 0);
+    }
+
+    #[test]
+    fn test_game_metadata_validation() {
+        let entry = |key: &str, value: &str| GameMetadataEntry {
+            key: key.to_string(),
+            value: value.to_string(),
+        };
+        assert!(GameMetadataEntry::validate_all(&[entry("device_type", "iPhone 15 Pro")]).is_ok());
+        assert_eq!(
+            GameMetadataEntry::validate_all(&[entry("device-type", "web")]),
+            Err(GameMetadataError::InvalidKey("device-type".to_string()))
+        );
+        assert_eq!(
+            GameMetadataEntry::validate_all(&[entry("controls", "swipe\n")]),
+            Err(GameMetadataError::InvalidValue("controls".to_string()))
+        );
+        assert_eq!(
+            GameMetadataEntry::validate_all(&[entry("a", "1"), entry("a", "2")]),
+            Err(GameMetadataError::DuplicateKey("a".to_string()))
+        );
+        let too_many: Vec<_> = (0..6).map(|i| entry(&format!("k{}", i), "v")).collect();
+        assert_eq!(
+            GameMetadataEntry::validate_all(&too_many),
+            Err(GameMetadataError::TooManyEntries)
+        );
+    }
+
+    fn sample_summary(i: u32) -> PlayerScoreSummary {
+        let chain_id = format!("{:064x}", i);
+        PlayerScoreSummary {
+            player: format!("player_{}", i),
+            best_score: 10_000 + i as u64,
+            board_id: format!("{}.{}", chain_id, 3_000_000_000u32 - i),
+            chain_id,
+            highest_tile: 2048,
+            last_update: 1_700_000_000_000_000,
+            game_status: GameStatus::Active,
+            boards_per_leaderboard: [("tournament".to_string(), 3)].into(),
+            active_boards: vec![ActiveBoardSummary {
+                board_id: format!("{:064x}.{}", i, i),
+                player: format!("player_{}", i),
+                score: 500,
+                is_ended: false,
+            }],
+            avg_move_time_micros: 0,
+            efficiency: 0,
+            best_efficiency_board_id: String::new(),
+            first_achievement_time: 0,
+            time_to_target_micros: None,
+        }
+    }
+
+    #[test]
+    fn test_compact_player_score_round_trip() {
+        let summary = sample_summary(42);
+        let expanded = summary.compress().unwrap().expand_partial(&summary.player);
+
+        assert_eq!(expanded.player, summary.player);
+        assert_eq!(expanded.best_score, summary.best_score);
+        assert_eq!(expanded.board_id, summary.board_id);
+        assert_eq!(expanded.chain_id, summary.chain_id);
+        assert_eq!(expanded.highest_tile, summary.highest_tile);
+        assert!(expanded.active_boards.is_empty());
+    }
+
+    #[test]
+    fn test_compact_player_score_rejects_unencodable_ids() {
+        let mut summary = sample_summary(7);
+        summary.board_id = format!("{}.not-a-number", summary.chain_id);
+        assert_eq!(
+            summary.compress(),
+            Err(CompactScoreError::InvalidBoardId(summary.board_id.clone()))
+        );
+        summary.board_id = "daily_2026-10-15".to_string();
+        assert!(summary.compress().is_err());
+        summary.board_id = format!("{:064x}.1", 8);
+        assert!(summary.compress().is_err());
+
+        let mut summary = sample_summary(7);
+        summary.chain_id = "chain".to_string();
+        assert_eq!(
+            summary.compress(),
+            Err(CompactScoreError::InvalidChainId("chain".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_compact_storage_savings_at_1000_players() {
+        let summaries: Vec<PlayerScoreSummary> = (0..1000).map(sample_summary).collect();
+        let full_bytes: usize = summaries
+            .iter()
+            .map(|s| linera_sdk::bcs::to_bytes(s).unwrap().len())
+            .sum();
+        let compact_bytes: usize = summaries
+            .iter()
+            .map(|s| {
+                linera_sdk::bcs::to_bytes(&s.compress().unwrap())
+                    .unwrap()
+                    .len()
+            })
+            .sum();
+
+        // Compact form is fixed at 68 bytes per player
+        assert_eq!(compact_bytes, 1000 * 68);
+        assert!(compact_bytes * 3 < full_bytes);
+    }
+
+    #[test]
+    fn test_password_change_needs_the_issued_token() {
+        let secret = next_password_change_secret(&[7; 32], 42, "alice", 1_000);
+        let token = password_change_token(&secret);
+        assert_eq!(token.len(), 32);
+        let pending = (password_change_token_hash(&token), 2_000);
+
+        assert_eq!(
+            check_password_change_token(Some(&pending), &token, 1_500),
+            Ok(())
+        );
+        // No token, a guessed one, or a token for a different secret
+        assert!(check_password_change_token(Some(&pending), "", 1_500).is_err());
+        assert!(check_password_change_token(Some(&pending), &"0".repeat(32), 1_500).is_err());
+        let other =
+            password_change_token(&next_password_change_secret(&[0; 32], 42, "alice", 1_000));
+        assert!(check_password_change_token(Some(&pending), &other, 1_500).is_err());
+        // Expired, or never requested
+        assert!(check_password_change_token(Some(&pending), &token, 2_000).is_err());
+        assert!(check_password_change_token(None, &token, 1_500).is_err());
+    }
+
+    #[test]
+    fn test_board_state_response_after_moves() {
+        // | . | . | . | . |
+        // | . | . | . | . |
+        // | . | . | . | . |
+        // | 2 | 2 | 4 | 4 |
+        let mut tiles: u64 = 0x0000_0000_0000_1122;
+        let mut score = 0;
+        for direction in [Direction::Left, Direction::Right] {
+            let (new_tiles, score_delta) = Moves::score_move(tiles, direction);
+            tiles = new_tiles;
+            score += score_delta;
+        }
+        // Left: | 4 | 8 | . | . | (+4 +8), then right: | . | . | 4 | 8 |
+        let state = BoardStateResponse::from_board(tiles, score, 2, false, 1_000_000, 91_500_000);
+        assert_eq!(
+            state,
+            BoardStateResponse {
+                tiles_hex: "0000000000000023".to_string(),
+                score: 12,
+                moves_count: 2,
+                highest_tile: 8,
+                available_moves: 2, // Up and left
+                is_game_over: false,
+                elapsed_seconds: 90,
+            }
+        );
+
+        // No direction changes a checkerboard of distinct tiles
+        let stuck = BoardStateResponse::from_board(0x1234_2341_3412_4123, 60, 30, false, 0, 0);
+        assert_eq!(stuck.tiles_hex, "1234234134124123");
+        assert_eq!(stuck.highest_tile, 16);
+        assert_eq!(stuck.available_moves, 0);
+        assert!(stuck.is_game_over);
+        assert!(BoardStateResponse::from_board(tiles, 12, 2, true, 0, 0).is_game_over);
+    }
+
+    #[test]
+    fn test_daily_challenge_dates_and_seed() {
+        assert_eq!(date_ymd(0), "1970-01-01");
+        assert_eq!(date_ymd(11_016), "2000-02-29");
+        assert_eq!(date_ymd(19_782), "2024-02-29");
+        assert_eq!(date_ymd(20_818), "2026-12-31");
+        assert_eq!(daily_challenge_board_id(20_741), "daily_2026-10-15");
+
+        let seed = daily_challenge_seed(20_741, "salt");
+        assert_eq!(seed.len(), 64);
+        assert_eq!(seed, daily_challenge_seed(20_741, "salt"));
+        assert_ne!(seed, daily_challenge_seed(20_742, "salt"));
+        assert_ne!(seed, daily_challenge_seed(20_741, "other"));
+    }
+
+    #[test]
+    fn test_init_args_defaults() {
+        let args: InitArgs = serde_json::from_str(r#"{"pool_size": 100}"#).unwrap();
+        assert_eq!(
+            args,
+            InitArgs {
+                pool_size: 100,
+                min_pool_size: 0,
+            }
+        );
+        let args: InitArgs = serde_json::from_str("{}").unwrap();
+        assert_eq!(args, InitArgs::default());
+    }
+
+    #[test]
+    fn test_prediction_ranking() {
+        let prediction =
+            |spectator: &str, winner: &str, score: u64, submitted_at: u64| TournamentPrediction {
+                spectator_chain_id: spectator.to_string(),
+                predicted_winner: winner.to_string(),
+                predicted_score: score,
+                submitted_at,
+            };
+        let ranked = TournamentPrediction::rank_all(
+            vec![
+                prediction("exact_but_wrong", "bob", 1_000, 1),
+                prediction("late", "alice", 900, 3),
+                prediction("far", "alice", 500, 1),
+                prediction("early", "alice", 1_100, 2),
+            ],
+            "alice",
+            1_000,
+        );
+        let order: Vec<&str> = ranked
+            .iter()
+            .map(|prediction| prediction.spectator_chain_id.as_str())
+            .collect();
+        assert_eq!(order, ["early", "late", "far", "exact_but_wrong"]);
+    }
+
+    #[test]
+    fn test_handicapped_score() {
+        assert_eq!(handicapped_score(2_048, 1.0), 2_048);
+        assert_eq!(handicapped_score(2_048, MIN_HANDICAP_MULTIPLIER), 1_024);
+        // A handicapped leader can fall behind an unhandicapped runner-up
+        assert!(handicapped_score(3_000, 0.6) < handicapped_score(2_000, 1.0));
+        assert_eq!(handicapped_score(5, 0.75), 4);
+    }
+
+    #[test]
+    fn test_move_timing_shares_batch_time_between_moves() {
+        let mut timing = MoveTiming::default();
+        timing.record_batch(3_000_000, 3);
+        timing.record_batch(500_000, 1);
+        // A batch without applied moves leaves the statistics unchanged
+        timing.record_batch(9_000_000, 0);
+        assert_eq!(
+            timing,
+            MoveTiming {
+                total_think_time_micros: 3_500_000,
+                min_move_time_micros: 500_000,
+                max_move_time_micros: 1_000_000,
+            }
+        );
+    }
+}
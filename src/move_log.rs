@@ -0,0 +1,119 @@
+use sha2::{Digest, Sha256};
+
+use crate::Direction;
+
+/// Longest run of one direction stored in a single byte
+const MAX_RUN: u8 = 64;
+
+/// Run-length-encoded move log for sharing replays.
+///
+/// Each byte holds a direction in its top 2 bits (0 = Up, 1 = Down, 2 = Left, 3 = Right,
+/// as in `MoveRecord`) and the run length minus one in its low 6 bits.
+pub struct MoveLog;
+
+impl MoveLog {
+    pub fn direction_code(direction: Direction) -> u8 {
+        match direction {
+            Direction::Up => 0,
+            Direction::Down => 1,
+            Direction::Left => 2,
+            Direction::Right => 3,
+        }
+    }
+
+    pub fn direction_from_code(code: u8) -> Direction {
+        match code & 0b11 {
+            0 => Direction::Up,
+            1 => Direction::Down,
+            2 => Direction::Left,
+            _ => Direction::Right,
+        }
+    }
+
+    /// Appends one move, extending the last run when possible.
+    pub fn append(log: &mut Vec<u8>, direction: Direction) {
+        let code = Self::direction_code(direction);
+        if let Some(last) = log.last_mut() {
+            if *last >> 6 == code && (*last & 0x3F) + 1 < MAX_RUN {
+                *last += 1;
+                return;
+            }
+        }
+        log.push(code << 6);
+    }
+
+    /// Removes the last move, shortening its run.
+    pub fn pop(log: &mut Vec<u8>) -> Option<Direction> {
+        let last = log.last_mut()?;
+        let direction = Self::direction_from_code(*last >> 6);
+        if *last & 0x3F == 0 {
+            log.pop();
+        } else {
+            *last -= 1;
+        }
+        Some(direction)
+    }
+
+    /// ```
+    /// use game2048::{Direction, MoveLog};
+    ///
+    /// let log = MoveLog::encode(&[Direction::Left, Direction::Left, Direction::Up]);
+    /// assert_eq!(log, vec![0b1000_0001, 0b0000_0000]);
+    /// assert_eq!(MoveLog::decode(&log), vec![Direction::Left, Direction::Left, Direction::Up]);
+    /// ```
+    pub fn encode(directions: &[Direction]) -> Vec<u8> {
+        let mut log = Vec::new();
+        for &direction in directions {
+            Self::append(&mut log, direction);
+        }
+        log
+    }
+
+    pub fn decode(log: &[u8]) -> Vec<Direction> {
+        log.iter()
+            .flat_map(|&byte| {
+                std::iter::repeat_n(
+                    Self::direction_from_code(byte >> 6),
+                    (byte & 0x3F) as usize + 1,
+                )
+            })
+            .collect()
+    }
+
+    /// Hash stored on a board when its game ends, so a shared log can be checked without
+    /// replaying it. Hashes the canonical encoding, so equivalent logs hash the same.
+    pub fn replay_hash(board_id: &str, log: &[u8], score: u64) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(board_id.as_bytes());
+        hasher.update(Self::encode(&Self::decode(log)));
+        hasher.update(score.to_le_bytes());
+        hasher.finalize().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_long_runs_round_trip() {
+        let mut directions = vec![Direction::Right; 130];
+        directions.extend([Direction::Down, Direction::Up, Direction::Up]);
+        let log = MoveLog::encode(&directions);
+        assert_eq!(log, vec![0xFF, 0xFF, 0xC1, 0x40, 0x01]);
+        assert_eq!(MoveLog::decode(&log), directions);
+
+        let mut undone = log.clone();
+        assert_eq!(MoveLog::pop(&mut undone), Some(Direction::Up));
+        assert_eq!(MoveLog::pop(&mut undone), Some(Direction::Up));
+        assert_eq!(undone, vec![0xFF, 0xFF, 0xC1, 0x40]);
+
+        // A non-canonical log (split runs) replays and hashes the same
+        let split = vec![0x80, 0x80];
+        assert_eq!(MoveLog::decode(&split), vec![Direction::Left; 2]);
+        assert_eq!(
+            MoveLog::replay_hash("b", &split, 8),
+            MoveLog::replay_hash("b", &[0x81], 8)
+        );
+    }
+}
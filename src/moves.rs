@@ -1,3 +1,5 @@
+use crate::{random::lehmer_next, Direction, Game};
+
 /// A mask with a single section of 16 bits set to 0.
 /// Used to extract a "horizontal slice" out of a 64 bit integer.
 pub static ROW_MASK: u64 = 0xFFFF;
@@ -6,6 +8,40 @@ pub static ROW_MASK: u64 = 0xFFFF;
 /// Used to extract a "vertical slice" out of a 64 bit integer.
 pub static COL_MASK: u64 = 0x000F_000F_000F_000F_u64;
 
+/// Random key per (position, exponent) pair, indexed by `position * 16 + exponent`.
+/// Generated at compile time with splitmix64 from a fixed seed.
+const ZOBRIST_TABLE: [u64; 256] = zobrist_table();
+
+const fn zobrist_table() -> [u64; 256] {
+    let mut table = [0u64; 256];
+    let mut state = 0x2048_2048_2048_2048u64;
+    let mut i = 0;
+    while i < 256 {
+        state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        table[i] = z ^ (z >> 31);
+        i += 1;
+    }
+    table
+}
+
+/// Zobrist hash of a packed board: the XOR of the key of each tile's position and exponent.
+///
+/// ```
+/// use game2048::zobrist_hash;
+///
+/// assert_eq!(zobrist_hash(0x1200_0000_0000_0000), zobrist_hash(0x1200_0000_0000_0000));
+/// assert_ne!(zobrist_hash(0x1200_0000_0000_0000), zobrist_hash(0x2100_0000_0000_0000));
+/// ```
+pub fn zobrist_hash(tiles: u64) -> u64 {
+    (0..16).fold(0, |hash, position| {
+        let exponent = (tiles >> (position * 4)) & 0xF;
+        hash ^ ZOBRIST_TABLE[position * 16 + exponent as usize]
+    })
+}
+
 /// Struct that contains all available moves per row for up, down, right and left.
 /// Also stores the score for a given row.
 ///
@@ -119,8 +155,859 @@ impl Moves {
     }
 }
 
+impl Moves {
+    /// All four directions, in the order used for move enumeration.
+    pub const DIRECTIONS: [Direction; 4] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+    ];
+
+    /// Returns `board` slid in `direction` (no tile spawn).
+    pub fn apply_direction(board: u64, direction: Direction) -> u64 {
+        match direction {
+            Direction::Up => Game::move_up(board),
+            Direction::Down => Game::move_down(board),
+            Direction::Left => Game::move_left(board),
+            Direction::Right => Game::move_right(board),
+        }
+    }
+
+    /// Returns `(new_board, score_gained)` for each of `DIRECTIONS` (`[Up, Down, Left, Right]`),
+    /// sharing the transpose and row lookups between directions (no tile spawn).
+    /// `score_gained` is the change in [`Game::score`].
+    ///
+    /// ```
+    /// use game2048::{Direction, Moves};
+    ///
+    /// // | 0 | 0 | 0 | 0 |
+    /// // | 0 | 0 | 0 | 0 |
+    /// // | 0 | 0 | 0 | 0 |
+    /// // | 0 | 0 | 1 | 1 |  left merges the two 1s into a 2
+    /// let results = Moves::apply_all_directions(0x0000_0000_0000_0011);
+    /// assert_eq!(results[2], (0x0000_0000_0000_2000, 8));
+    /// assert_eq!(results[0].0, Moves::apply_direction(0x0000_0000_0000_0011, Direction::Up));
+    /// ```
+    pub fn apply_all_directions(board: u64) -> [(u64, u64); 4] {
+        let score = Game::score(board);
+        Game::move_all_directions(board)
+            .map(|new_board| (new_board, Game::score(new_board) - score))
+    }
+
+    /// Returns only the directions that change the board.
+    ///
+    /// ```
+    /// use game2048::{Direction, Moves};
+    ///
+    /// // | 0 | 0 | 0 | 0 |
+    /// // | 0 | 0 | 0 | 0 |
+    /// // | 0 | 0 | 0 | 0 |
+    /// // | 0 | 0 | 0 | 2 |
+    /// let moves = Moves::valid_moves(0x0000_0000_0000_0001);
+    /// assert_eq!(moves, vec![Direction::Up, Direction::Left]);
+    /// ```
+    pub fn valid_moves(board: u64) -> Vec<Direction> {
+        Self::DIRECTIONS
+            .into_iter()
+            .zip(Game::move_all_directions(board))
+            .filter(|(_, new_board)| *new_board != board)
+            .map(|(direction, _)| direction)
+            .collect()
+    }
+
+    /// Alias of [`Moves::valid_moves`].
+    pub fn reachable_tiles(board: u64) -> Vec<Direction> {
+        Self::valid_moves(board)
+    }
+
+    /// Returns `true` if both boards have the same Zobrist hash.
+    pub fn is_duplicate_state(prev: u64, next: u64) -> bool {
+        zobrist_hash(prev) == zobrist_hash(next)
+    }
+
+    /// Returns how many of the four directions change the board: `0` is game over, `1` is
+    /// one move away from it. Counts without building the list of directions.
+    ///
+    /// ```
+    /// use game2048::Moves;
+    ///
+    /// assert_eq!(Moves::count_available_moves(0x0000_0000_0000_0001), 2);
+    /// assert_eq!(Moves::count_available_moves(0x1234_2341_3412_4123), 0);
+    /// ```
+    pub fn count_available_moves(tiles: u64) -> u8 {
+        Game::move_all_directions(tiles)
+            .into_iter()
+            .filter(|new_tiles| *new_tiles != tiles)
+            .count() as u8
+    }
+
+    /// Returns `(new_tiles, score_delta)` for sliding `tiles` in `direction` (no tile spawn).
+    /// `score_delta` is the value of the tiles produced by merges, summed while sliding.
+    ///
+    /// ```
+    /// use game2048::{Direction, Game, Moves};
+    ///
+    /// // | 0 | 0 | 0 | 0 |
+    /// // | 0 | 0 | 0 | 0 |
+    /// // | 0 | 0 | 0 | 0 |
+    /// // | 2 | 2 | 4 | 4 |  left merges into | 4 | 8 | 0 | 0 |
+    /// let (new_tiles, score_delta) = Moves::score_move(0x0000_0000_0000_1122, Direction::Left);
+    /// assert_eq!(new_tiles, Game::slide(0x0000_0000_0000_1122, Direction::Left));
+    /// assert_eq!(score_delta, 4 + 8);
+    /// ```
+    pub fn score_move(tiles: u64, direction: Direction) -> (u64, u64) {
+        let vertical = matches!(direction, Direction::Up | Direction::Down);
+        let towards_start = matches!(direction, Direction::Up | Direction::Left);
+        // Columns become rows, each with its top tile in the highest nybble
+        let lines = if vertical {
+            Game::transpose(tiles)
+        } else {
+            tiles
+        };
+
+        let mut new_lines = 0;
+        let mut score_delta = 0;
+        for i in 0..4 {
+            let line = ((lines >> (i * 16)) & ROW_MASK) as u16;
+            let (new_line, line_delta) = if vertical {
+                Self::slide_col(line, towards_start)
+            } else {
+                Self::slide_row(line, towards_start)
+            };
+            new_lines |= (new_line as u64) << (i * 16);
+            score_delta += line_delta;
+        }
+
+        let new_tiles = if vertical {
+            Game::transpose(new_lines)
+        } else {
+            new_lines
+        };
+        (new_tiles, score_delta)
+    }
+
+    /// Slides a row packed as in [`BoardOps::row`] (left tile in the highest nybble),
+    /// returning `(new_row, score_delta)`. Two `0xF` tiles merge into one `0xF` tile.
+    ///
+    /// ```
+    /// use game2048::Moves;
+    ///
+    /// // | 2 | 2 | 4 | 0 |  =>  | 4 | 4 | 0 | 0 |
+    /// assert_eq!(Moves::slide_row(0x1120, true), (0x2200, 4));
+    /// // | 2 | 2 | 4 | 0 |  =>  | 0 | 0 | 4 | 4 |
+    /// assert_eq!(Moves::slide_row(0x1120, false), (0x0022, 4));
+    /// ```
+    pub fn slide_row(row: u16, to_left: bool) -> (u16, u64) {
+        // Nybble shift of each cell, in the order the tiles slide towards the edge
+        let shifts: [u16; 4] = if to_left {
+            [12, 8, 4, 0]
+        } else {
+            [0, 4, 8, 12]
+        };
+
+        let mut slid = [0_u16; 4];
+        let mut len = 0;
+        let mut can_merge = false; // A tile only merges once per slide
+        let mut score_delta = 0;
+        for shift in shifts {
+            let tile = (row >> shift) & 0xF;
+            if tile == 0 {
+                continue;
+            }
+            if can_merge && slid[len - 1] == tile {
+                let merged = (tile + 1).min(0xF);
+                slid[len - 1] = merged;
+                score_delta += 1_u64 << merged;
+                can_merge = false;
+            } else {
+                slid[len] = tile;
+                len += 1;
+                can_merge = true;
+            }
+        }
+
+        let new_row = shifts
+            .into_iter()
+            .zip(slid)
+            .fold(0, |new_row, (shift, tile)| new_row | (tile << shift));
+        (new_row, score_delta)
+    }
+
+    /// Slides a column packed as in [`BoardOps::col`] (top tile in the highest nybble),
+    /// returning `(new_col, score_delta)`.
+    ///
+    /// ```
+    /// use game2048::Moves;
+    ///
+    /// // Top to bottom 2, 0, 2, 8: up gives 4, 8, 0, 0
+    /// assert_eq!(Moves::slide_col(0x1013, true), (0x2300, 4));
+    /// ```
+    pub fn slide_col(col: u16, to_top: bool) -> (u16, u64) {
+        // Same packing as a row, with the top tile where the left one would be
+        Self::slide_row(col, to_top)
+    }
+
+    /// Returns `true` if at least one direction changes the board.
+    /// Stops at the first valid direction.
+    pub fn has_any_valid_move(board: u64) -> bool {
+        Self::DIRECTIONS
+            .into_iter()
+            .any(|direction| Self::apply_direction(board, direction) != board)
+    }
+
+    /// Returns `(direction, new_tiles)` for each direction that changes `tiles`, in
+    /// `DIRECTIONS` order (no tile spawn).
+    ///
+    /// ```
+    /// use game2048::{Direction, Moves};
+    ///
+    /// let successors = Moves::gen_all_successors(0x0000_0000_0000_0001);
+    /// assert_eq!(successors.len(), 2);
+    /// assert_eq!(successors[0], (Direction::Up, 0x0001_0000_0000_0000));
+    /// assert_eq!(successors[1], (Direction::Left, 0x0000_0000_0000_1000));
+    /// ```
+    pub fn gen_all_successors(tiles: u64) -> Vec<(Direction, u64)> {
+        Self::DIRECTIONS
+            .into_iter()
+            .zip(Game::move_all_directions(tiles))
+            .filter(|(_, new_tiles)| *new_tiles != tiles)
+            .collect()
+    }
+
+    /// Returns every board reachable in one turn: each successor of `tiles` with a `2` or a
+    /// `4` in each of its empty cells (`empty_cells * 2` boards per successor).
+    /// The set is shuffled with `seed` so search trees expand it in a random order.
+    pub fn gen_with_random_tile(tiles: u64, seed: &mut u64) -> Vec<u64> {
+        let mut boards = Vec::new();
+        for (_, successor) in Self::gen_all_successors(tiles) {
+            for cell in 0..16 {
+                if (successor >> (cell * 4)) & 0xF == 0 {
+                    boards.push(successor | (1 << (cell * 4)));
+                    boards.push(successor | (2 << (cell * 4)));
+                }
+            }
+        }
+        for i in (1..boards.len()).rev() {
+            let j = lehmer_next(seed) as usize % (i + 1);
+            boards.swap(i, j);
+        }
+        boards
+    }
+
+    /// Mirrors `tiles` left to right (each row reversed).
+    ///
+    /// ```
+    /// use game2048::Moves;
+    ///
+    /// assert_eq!(Moves::flip_horizontal(0x1200_0000_0000_0000), 0x0021_0000_0000_0000);
+    /// ```
+    pub fn flip_horizontal(tiles: u64) -> u64 {
+        let nibbles_swapped =
+            ((tiles & 0xF0F0_F0F0_F0F0_F0F0) >> 4) | ((tiles & 0x0F0F_0F0F_0F0F_0F0F) << 4);
+        ((nibbles_swapped & 0xFF00_FF00_FF00_FF00) >> 8)
+            | ((nibbles_swapped & 0x00FF_00FF_00FF_00FF) << 8)
+    }
+
+    /// Mirrors `tiles` top to bottom (row order reversed).
+    ///
+    /// ```
+    /// use game2048::Moves;
+    ///
+    /// assert_eq!(Moves::flip_vertical(0x1200_0000_0000_0000), 0x0000_0000_0000_1200);
+    /// ```
+    pub fn flip_vertical(tiles: u64) -> u64 {
+        let rows_swapped =
+            ((tiles & 0xFFFF_0000_FFFF_0000) >> 16) | ((tiles & 0x0000_FFFF_0000_FFFF) << 16);
+        rows_swapped.rotate_left(32)
+    }
+
+    /// Rotates `tiles` a quarter turn clockwise (the left column becomes the top row).
+    ///
+    /// ```
+    /// use game2048::Moves;
+    ///
+    /// assert_eq!(Moves::rotate_90(0x1200_0000_0000_0000), 0x0001_0002_0000_0000);
+    /// ```
+    pub fn rotate_90(tiles: u64) -> u64 {
+        Self::flip_horizontal(Game::transpose(tiles))
+    }
+
+    /// Returns the smallest of the 8 rotations and reflections of `tiles`, so symmetric
+    /// positions share one entry in search tables. As packed values compare nibble by nibble
+    /// from the top-left cell, this is also the lexicographically smallest grid.
+    ///
+    /// ```
+    /// use game2048::Moves;
+    ///
+    /// // A tile in any corner is the same position
+    /// assert_eq!(Moves::canonical_form(0x1000_0000_0000_0000), 0x0000_0000_0000_0001);
+    /// assert_eq!(Moves::canonical_form(0x0001_0000_0000_0000), 0x0000_0000_0000_0001);
+    /// ```
+    pub fn canonical_form(tiles: u64) -> u64 {
+        let mut smallest = tiles;
+        for start in [tiles, Self::flip_horizontal(tiles)] {
+            let mut rotated = start;
+            for _ in 0..4 {
+                smallest = smallest.min(rotated);
+                rotated = Self::rotate_90(rotated);
+            }
+        }
+        smallest
+    }
+
+    /// Renders `tiles` as a 4x4 grid, top row first, with tile values right-justified in
+    /// 6-character columns and empty cells shown as `.`.
+    ///
+    /// ```
+    /// use game2048::Moves;
+    ///
+    /// let grid = Moves::to_ascii(0x1000_0200_0030_000B);
+    /// let rows: Vec<&str> = grid.lines().collect();
+    /// assert_eq!(rows[0], "     2     .     .     .");
+    /// assert_eq!(rows[3], "     .     .     .  2048");
+    /// ```
+    pub fn to_ascii(tiles: u64) -> String {
+        (0..4)
+            .map(|r| {
+                let row = tiles.row(r);
+                (0..4)
+                    .map(|c| match (row >> ((3 - c) * 4)) & 0xF {
+                        0 => format!("{:>6}", "."),
+                        tile => format!("{:>6}", 1_u32 << tile),
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Parses a grid in the format of [`Moves::to_ascii`]: 4 rows of 4 whitespace-separated
+    /// tiles, each a power of two from 2 to 32768, or `.` (or `0`) for an empty cell.
+    ///
+    /// ```
+    /// use game2048::Moves;
+    ///
+    /// let tiles = Moves::from_ascii(
+    ///     "2 . . .
+    ///      . 4 . .
+    ///      . . 8 .
+    ///      . . . 2048",
+    /// );
+    /// assert_eq!(tiles, Ok(0x1000_0200_0030_000B));
+    /// assert_eq!(Moves::from_ascii("2 . . 3\n. . . .").unwrap_err().col, 3);
+    /// ```
+    pub fn from_ascii(s: &str) -> Result<u64, ParseBoardError> {
+        let rows: Vec<&str> = s.lines().filter(|line| !line.trim().is_empty()).collect();
+        let mut tiles = 0_u64;
+        for r in 0..4 {
+            let Some(line) = rows.get(r) else {
+                return Err(ParseBoardError::new(r, 0, "missing row"));
+            };
+            let cells: Vec<&str> = line.split_whitespace().collect();
+            let mut row = 0_u16;
+            for c in 0..4 {
+                let Some(cell) = cells.get(c) else {
+                    return Err(ParseBoardError::new(r, c, "missing tile"));
+                };
+                let tile = match *cell {
+                    "." | "0" => 0,
+                    cell => match cell.parse::<u32>() {
+                        Ok(value) if value.is_power_of_two() && (2..=32768).contains(&value) => {
+                            value.trailing_zeros() as u16
+                        }
+                        _ => {
+                            return Err(ParseBoardError::new(
+                                r,
+                                c,
+                                &format!("{cell:?} is not a tile value"),
+                            ))
+                        }
+                    },
+                };
+                row |= tile << ((3 - c) * 4);
+            }
+            if cells.len() > 4 {
+                return Err(ParseBoardError::new(r, 4, "more than 4 tiles"));
+            }
+            tiles = tiles.set_row(r as u8, row);
+        }
+        if rows.len() > 4 {
+            return Err(ParseBoardError::new(4, 0, "more than 4 rows"));
+        }
+        Ok(tiles)
+    }
+}
+
+/// Where and why [`Moves::from_ascii`] rejected a grid (row 0 is the top row, column 0 the
+/// left column).
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+#[error("Invalid board at row {row}, column {col}: {reason}")]
+pub struct ParseBoardError {
+    pub row: usize,
+    pub col: usize,
+    pub reason: String,
+}
+
+impl ParseBoardError {
+    fn new(row: usize, col: usize, reason: &str) -> Self {
+        Self {
+            row,
+            col,
+            reason: reason.to_string(),
+        }
+    }
+}
+
+/// Row, column and tile accessors for packed boards (4 bits per tile exponent).
+///
+/// Rows and columns are numbered as in [`Game::convert_to_matrix`]: row 0 is the top row,
+/// column 0 the left column. A row or column is packed into a `u16` with its first tile in
+/// the highest nybble.
+///
+/// ```
+/// use game2048::BoardOps;
+///
+/// // | 1 | 2 | 3 | 4 |
+/// // | 0 | 0 | 0 | 0 |
+/// // | 0 | 0 | 0 | 0 |
+/// // | 5 | 0 | 0 | 0 |
+/// let board = 0x1234_0000_0000_5000_u64;
+/// assert_eq!(board.row(0), 0x1234);
+/// assert_eq!(board.col(0), 0x1005);
+/// assert_eq!(board.set_row(3, 0).row(3), 0);
+/// assert_eq!(board.highest_tile(), 32);
+/// assert_eq!(board.empty_cells(), 11);
+/// ```
+pub trait BoardOps {
+    /// Returns row `r` (0-3).
+    fn row(self, r: u8) -> u16;
+    /// Returns the board with row `r` (0-3) replaced by `row`.
+    fn set_row(self, r: u8, row: u16) -> u64;
+    /// Returns column `c` (0-3), top tile first.
+    fn col(self, c: u8) -> u16;
+    /// Returns the board with column `c` (0-3) replaced by `col`.
+    fn set_col(self, c: u8, col: u16) -> u64;
+    /// Returns the value of the highest tile (0 on an empty board).
+    fn highest_tile(self) -> u32;
+    /// Returns the number of empty cells.
+    fn empty_cells(self) -> u8;
+    /// Returns how many directions change the board (see [`Moves::count_available_moves`]).
+    fn count_available_moves(self) -> u8;
+}
+
+impl BoardOps for u64 {
+    fn row(self, r: u8) -> u16 {
+        assert!(r < 4, "Row out of range: {r}");
+        ((self >> ((3 - r) * 16)) & ROW_MASK) as u16
+    }
+
+    fn set_row(self, r: u8, row: u16) -> u64 {
+        assert!(r < 4, "Row out of range: {r}");
+        let shift = (3 - r) * 16;
+        (self & !(ROW_MASK << shift)) | ((row as u64) << shift)
+    }
+
+    fn col(self, c: u8) -> u16 {
+        assert!(c < 4, "Column out of range: {c}");
+        let column = (self >> ((3 - c) * 4)) & COL_MASK;
+        ((column | (column >> 12) | (column >> 24) | (column >> 36)) & ROW_MASK) as u16
+    }
+
+    fn set_col(self, c: u8, col: u16) -> u64 {
+        assert!(c < 4, "Column out of range: {c}");
+        let shift = (3 - c) * 4;
+        (self & !(COL_MASK << shift)) | (Moves::column_from(col as u64) << shift)
+    }
+
+    fn highest_tile(self) -> u32 {
+        Game::highest_tile(self) as u32
+    }
+
+    fn empty_cells(self) -> u8 {
+        Game::count_empty(self) as u8
+    }
+
+    fn count_available_moves(self) -> u8 {
+        Moves::count_available_moves(self)
+    }
+}
+
 impl Default for Moves {
     fn default() -> Self {
         Moves::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_has_any_valid_move_matches_valid_moves() {
+        let boards = [
+            0x0000_0000_0000_0000_u64,
+            0x0000_0000_0000_0001,
+            0x1234_2341_3412_4123, // Deadlocked checkerboard
+            0x1234_2341_3412_4113, // Merges available
+            0xFEDC_BA98_7654_3210,
+        ];
+        for board in boards {
+            assert_eq!(
+                Moves::has_any_valid_move(board),
+                !Moves::valid_moves(board).is_empty()
+            );
+        }
+        assert!(Moves::valid_moves(0x1234_2341_3412_4123).is_empty());
+    }
+
+    #[test]
+    fn test_board_ops_empty_board() {
+        let board = 0_u64;
+        for i in 0..4 {
+            assert_eq!(board.row(i), 0);
+            assert_eq!(board.col(i), 0);
+            assert_eq!(board.set_row(i, 0), board);
+            assert_eq!(board.set_col(i, 0), board);
+        }
+        assert_eq!(board.highest_tile(), 0);
+        assert_eq!(board.empty_cells(), 16);
+    }
+
+    #[test]
+    fn test_board_ops_full_board() {
+        let board = 0xFEDC_BA98_7654_3211_u64;
+        assert_eq!(board.row(0), 0xFEDC);
+        assert_eq!(board.row(3), 0x3211);
+        assert_eq!(board.col(0), 0xFB73);
+        assert_eq!(board.col(3), 0xC841);
+        for i in 0..4 {
+            assert_eq!(board.set_row(i, board.row(i)), board);
+            assert_eq!(board.set_col(i, board.col(i)), board);
+        }
+        assert_eq!(board.set_row(1, 0), 0xFEDC_0000_7654_3211);
+        assert_eq!(board.set_col(2, 0), 0xFE0C_BA08_7604_3201);
+        assert_eq!(board.set_row(2, 0xFFFF).row(2), 0xFFFF);
+        assert_eq!(board.highest_tile(), 32768);
+        assert_eq!(board.empty_cells(), 0);
+    }
+
+    #[test]
+    fn test_board_ops_single_tile() {
+        // Each position with a single 2: row and column see only that tile
+        for r in 0..4_u8 {
+            for c in 0..4_u8 {
+                let board = 0_u64.set_row(r, 0x1000 >> (c * 4));
+                assert_eq!(board.count_ones(), 1);
+                assert_eq!(board.col(c), 0x1000 >> (r * 4));
+                assert_eq!(0_u64.set_col(c, 0x1000 >> (r * 4)), board);
+                for i in 0..4_u8 {
+                    if i != r {
+                        assert_eq!(board.row(i), 0);
+                    }
+                    if i != c {
+                        assert_eq!(board.col(i), 0);
+                    }
+                }
+                assert_eq!(Game::convert_to_matrix(board)[r as usize][c as usize], 1);
+                assert_eq!(board.highest_tile(), 2);
+                assert_eq!(board.empty_cells(), 15);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Row out of range")]
+    fn test_board_ops_row_out_of_range() {
+        0_u64.row(4);
+    }
+
+    #[test]
+    #[should_panic(expected = "Column out of range")]
+    fn test_board_ops_col_out_of_range() {
+        0_u64.set_col(4, 0);
+    }
+
+    #[test]
+    fn test_count_available_moves_matches_each_direction() {
+        let fixed_boards = [
+            0x0000_0000_0000_0000_u64,
+            0x0000_0000_0000_0001,
+            0x1234_2341_3412_4123, // Deadlocked checkerboard
+            0x1234_2341_3412_4113, // Merges available
+            0x1234_5678_9ABC_DEF1, // Full board, no merges
+            0xFEDC_BA98_7654_3210,
+            0x1111_2222_3333_4444,
+        ];
+        let mut state = 0x2048_0000_CAFE_F00D_u64;
+        let fuzzed_boards = (0..10_000).map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        });
+        for board in fixed_boards.into_iter().chain(fuzzed_boards) {
+            let expected = Moves::DIRECTIONS
+                .into_iter()
+                .filter(|direction| Moves::apply_direction(board, *direction) != board)
+                .count() as u8;
+            assert_eq!(Moves::count_available_moves(board), expected);
+            assert_eq!(board.count_available_moves(), expected);
+            assert_eq!(expected == 0, !Moves::has_any_valid_move(board));
+        }
+        assert_eq!(Moves::count_available_moves(0x1234_2341_3412_4123), 0);
+        assert_eq!(Moves::count_available_moves(0), 0);
+    }
+
+    #[test]
+    fn test_score_move_matches_slide_and_merged_tiles() {
+        let fixed_boards = [
+            0x0000_0000_0000_0000_u64,
+            0x0000_0000_0000_1122,
+            0x1234_2341_3412_4123, // Deadlocked checkerboard
+            0xFF00_0000_0000_00FF, // Merging two 32768 tiles keeps one
+            0x1111_2222_3333_4444,
+        ];
+        let mut state = 0x0F0F_2048_1024_BEEF_u64;
+        let fuzzed_boards = (0..10_000).map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        });
+        for board in fixed_boards.into_iter().chain(fuzzed_boards) {
+            for direction in Moves::DIRECTIONS {
+                let (new_tiles, score_delta) = Moves::score_move(board, direction);
+                assert_eq!(new_tiles, Game::slide(board, direction));
+                assert_eq!(
+                    score_delta,
+                    Game::merged_tiles(board, direction).iter().sum::<u64>()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_score_move_merging_two_1024_tiles_scores_2048() {
+        // | 1024 | 1024 | 0 | 0 | on the bottom row
+        let board = 0x0000_0000_0000_AA00_u64;
+        assert_eq!(
+            Moves::score_move(board, Direction::Left),
+            (0x0000_0000_0000_B000, 2048)
+        );
+        assert_eq!(
+            Moves::score_move(board, Direction::Right),
+            (0x0000_0000_0000_000B, 2048)
+        );
+        // Stacked in the left column
+        let board = 0xA000_A000_0000_0000_u64;
+        assert_eq!(Moves::score_move(board, Direction::Up).1, 2048);
+        assert_eq!(Moves::score_move(board, Direction::Down).1, 2048);
+        // Sliding without merging scores nothing
+        assert_eq!(
+            Moves::score_move(0x0000_0000_0000_00A0, Direction::Left).1,
+            0
+        );
+    }
+
+    #[test]
+    fn test_ascii_round_trip() {
+        let mut state = 0x2048_0000_ACE5_F00D_u64;
+        for _ in 0..10_000 {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            assert_eq!(Moves::from_ascii(&Moves::to_ascii(state)), Ok(state));
+        }
+        assert_eq!(Moves::from_ascii(&Moves::to_ascii(0)), Ok(0));
+        assert_eq!(Moves::from_ascii(&Moves::to_ascii(u64::MAX)), Ok(u64::MAX));
+    }
+
+    #[test]
+    fn test_from_ascii_reports_row_and_column() {
+        let error = Moves::from_ascii("2 . . .\n. . 5 .\n. . . .\n. . . .").unwrap_err();
+        assert_eq!((error.row, error.col), (1, 2));
+        let error = Moves::from_ascii("2 . . .\n. . . .\n. .\n. . . .").unwrap_err();
+        assert_eq!((error.row, error.col), (2, 2));
+        let error = Moves::from_ascii("2 . . .\n. . . .").unwrap_err();
+        assert_eq!((error.row, error.col), (2, 0));
+        let error = Moves::from_ascii(". . . . 2\n. . . .\n. . . .\n. . . .").unwrap_err();
+        assert_eq!((error.row, error.col), (0, 4));
+        assert!(Moves::from_ascii("65536 . . .\n. . . .\n. . . .\n. . . .").is_err());
+    }
+
+    #[test]
+    fn test_zobrist_collision_rate() {
+        let mut state = 0x1357_9BDF_0246_8ACE_u64;
+        let mut boards = std::collections::HashSet::new();
+        while boards.len() < 10_000 {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            boards.insert(state);
+        }
+        let hashes: std::collections::HashSet<u64> =
+            boards.iter().map(|board| zobrist_hash(*board)).collect();
+        // Below 0.01% of 10 000 boards means no collision at all
+        let collisions = boards.len() - hashes.len();
+        assert!(collisions * 10_000 < boards.len());
+        assert!(Moves::is_duplicate_state(0x1234, 0x1234));
+        assert!(!Moves::is_duplicate_state(0x1234, 0x4321));
+    }
+
+    #[test]
+    fn test_apply_all_directions_matches_apply_direction() {
+        for board in [
+            0x0000_0000_0000_0001_u64,
+            0x1234_2341_3412_4113,
+            0xBA98_1234_0011_2211,
+        ] {
+            let results = Moves::apply_all_directions(board);
+            for (direction, (new_board, score_gained)) in Moves::DIRECTIONS.into_iter().zip(results)
+            {
+                let expected = Moves::apply_direction(board, direction);
+                assert_eq!(new_board, expected);
+                assert_eq!(score_gained, Game::score(expected) - Game::score(board));
+            }
+        }
+    }
+
+    #[test]
+    fn test_successors_and_placement_branching() {
+        // | 0 | 0 | 0 | 0 |
+        // | 0 | 2 | 0 | 0 |
+        // | 0 | 0 | 2 | 0 |
+        // | 0 | 0 | 0 | 0 |
+        let fresh = 0x0000_0100_0010_0000_u64;
+        let successors = Moves::gen_all_successors(fresh);
+        assert_eq!(successors.len(), 4);
+        for (direction, new_tiles) in &successors {
+            assert_eq!(*new_tiles, Moves::apply_direction(fresh, *direction));
+        }
+
+        // Each successor keeps both tiles, leaving 14 empty cells
+        let mut seed = 42;
+        let boards = Moves::gen_with_random_tile(fresh, &mut seed);
+        for (_, new_tiles) in &successors {
+            assert_eq!(new_tiles.empty_cells(), 14);
+        }
+        assert_eq!(boards.len(), successors.len() * 14 * 2);
+        assert!(boards.iter().all(|board| board.empty_cells() == 13));
+    }
+
+    /// Asymmetric boards (plus corner and edge tiles) and their images, computed cell by cell
+    const SYMMETRY_BOARDS: [u64; 8] = [
+        0x1234_5678_9ABC_DEF0,
+        0x1000_0000_0000_0000,
+        0x0000_0000_0000_0001,
+        0x1200_0000_0000_0000,
+        0x0000_0300_0000_0040,
+        0x1122_3344_5566_7788,
+        0xB000_0A00_0090_0008,
+        0x0000_0000_0000_1230,
+    ];
+
+    fn assert_symmetry_cases(transform: fn(u64) -> u64, expected: [u64; 8]) {
+        for (board, expected) in SYMMETRY_BOARDS.into_iter().zip(expected) {
+            assert_eq!(
+                transform(board),
+                expected,
+                "board {:016X}: got {:016X}, expected {:016X}",
+                board,
+                transform(board),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_flip_horizontal() {
+        assert_symmetry_cases(
+            Moves::flip_horizontal,
+            [
+                0x4321_8765_CBA9_0FED,
+                0x0001_0000_0000_0000,
+                0x0000_0000_0000_1000,
+                0x0021_0000_0000_0000,
+                0x0000_0030_0000_0400,
+                0x2211_4433_6655_8877,
+                0x000B_00A0_0900_8000,
+                0x0000_0000_0000_0321,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_flip_vertical() {
+        assert_symmetry_cases(
+            Moves::flip_vertical,
+            [
+                0xDEF0_9ABC_5678_1234,
+                0x0000_0000_0000_1000,
+                0x0001_0000_0000_0000,
+                0x0000_0000_0000_1200,
+                0x0040_0000_0300_0000,
+                0x7788_5566_3344_1122,
+                0x0008_0090_0A00_B000,
+                0x1230_0000_0000_0000,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_rotate_90() {
+        assert_symmetry_cases(
+            Moves::rotate_90,
+            [
+                0xD951_EA62_FB73_0C84,
+                0x0001_0000_0000_0000,
+                0x0000_0000_0000_1000,
+                0x0001_0002_0000_0000,
+                0x0000_0030_4000_0000,
+                0x7531_7531_8642_8642,
+                0x000B_00A0_0900_8000,
+                0x1000_2000_3000_0000,
+            ],
+        );
+        // Four quarter turns and two flips are the identity
+        for board in SYMMETRY_BOARDS {
+            let mut rotated = board;
+            for _ in 0..4 {
+                rotated = Moves::rotate_90(rotated);
+            }
+            assert_eq!(rotated, board);
+            assert_eq!(Moves::flip_horizontal(Moves::flip_horizontal(board)), board);
+            assert_eq!(Moves::flip_vertical(Moves::flip_vertical(board)), board);
+        }
+    }
+
+    #[test]
+    fn test_canonical_form() {
+        assert_symmetry_cases(
+            Moves::canonical_form,
+            [
+                0x0C84_FB73_EA62_D951,
+                0x0000_0000_0000_0001,
+                0x0000_0000_0000_0001,
+                0x0000_0000_0000_0021,
+                0x0000_0004_0300_0000,
+                0x1122_3344_5566_7788,
+                0x0008_0090_0A00_B000,
+                0x0000_0000_0000_0321,
+            ],
+        );
+        // Every image of a board has the same canonical form
+        for board in SYMMETRY_BOARDS {
+            let canonical = Moves::canonical_form(board);
+            assert_eq!(Moves::canonical_form(Moves::rotate_90(board)), canonical);
+            assert_eq!(
+                Moves::canonical_form(Moves::flip_vertical(board)),
+                canonical
+            );
+            assert_eq!(
+                Moves::canonical_form(Moves::flip_horizontal(board)),
+                canonical
+            );
+        }
+    }
+}
@@ -9,7 +9,159 @@ pub fn hash_seed(board_id: &str, username: &str, timestamp: u64) -> u32 {
     u32::from_le_bytes(result[0..4].try_into().unwrap()) // First 4 bytes as u32
 }
 
+/// Spawn draw of `SpawnGenerator::Hashed` boards, in `min..max`
 pub fn rnd_range(board_id: &str, username: &str, timestamp: u64, min: u32, max: u32) -> u32 {
     let seed = hash_seed(board_id, username, timestamp);
     (seed % (max - min)) + min
 }
+
+/// Multiplier of the 64-bit Lehmer generator behind tile spawns
+pub const LEHMER_MULTIPLIER: u64 = 6364136223846793005;
+
+/// Advances a 64-bit Lehmer (multiplicative congruential) generator and returns the high
+/// 32 bits of the new state, the better-mixed half. The state is kept odd so it cannot reach 0.
+pub fn lehmer_next(state: &mut u64) -> u32 {
+    *state = (*state | 1).wrapping_mul(LEHMER_MULTIPLIER);
+    (*state >> 32) as u32
+}
+
+/// Generator state for the tile spawned at `timestamp` on `board`: the game's seed
+/// (sha256 of board id and username) re-seeded with `board ^ timestamp`, so a leaked seed
+/// alone does not predict the spawns of a game.
+pub fn spawn_state(board_id: &str, username: &str, timestamp: u64, board: u64) -> u64 {
+    let mut hasher = Sha256::new();
+    hasher.update(board_id);
+    hasher.update(username);
+    let result = hasher.finalize();
+    let mut state = u64::from_le_bytes(result[0..8].try_into().unwrap());
+    state ^= board ^ timestamp;
+    state
+}
+
+/// A uniformly chosen empty cell of `board`, as a nybble index (0 = lowest 4 bits).
+///
+/// Scales one 32-bit draw by the number of empty cells instead of rejecting draws, then
+/// clears that many low bits of the vacancy mask. Panics if the board has no empty cell.
+pub fn next_empty_cell(state: &mut u64, board: u64) -> u8 {
+    let mut vacancy = (0..16)
+        .filter(|cell| (board >> (cell * 4)) & 0xF == 0)
+        .fold(0u16, |mask, cell| mask | (1 << cell));
+    let empty_cells = vacancy.count_ones();
+    assert!(empty_cells > 0, "Board has no empty cell");
+    let rank = (u64::from(lehmer_next(state)) * u64::from(empty_cells)) >> 32;
+    for _ in 0..rank {
+        vacancy &= vacancy - 1;
+    }
+    vacancy.trailing_zeros() as u8
+}
+
+/// Leading zero bits of sha256(player_chain_id || leaderboard_id || nonce), nonce as decimal text
+pub fn proof_of_work_bits(player_chain_id: &str, leaderboard_id: &str, nonce: u64) -> u32 {
+    let mut hasher = Sha256::new();
+    hasher.update(player_chain_id);
+    hasher.update(leaderboard_id);
+    hasher.update(nonce.to_string());
+    let result = hasher.finalize();
+
+    let mut bits = 0;
+    for byte in result {
+        bits += byte.leading_zeros();
+        if byte != 0 {
+            break;
+        }
+    }
+    bits
+}
+
+/// First 8 bytes of sha256(chain_id), mixed with the randomness beacon for board seeds
+pub fn chain_id_hash(chain_id: &str) -> u64 {
+    let result = Sha256::digest(chain_id);
+    u64::from_le_bytes(result[0..8].try_into().unwrap())
+}
+
+/// Beacon contribution the main chain derives when a shard's message arrives: sha256 of the
+/// shard's chain ID and the receiving block's time and height, none of which the shard picks
+pub fn beacon_contribution(chain_id: &str, received_at_micros: u64, block_height: u64) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(chain_id.as_bytes());
+    hasher.update(received_at_micros.to_le_bytes());
+    hasher.update(block_height.to_le_bytes());
+    hasher.finalize().into()
+}
+
+/// Next beacon value: sha256 of the previous value followed by each round contribution
+pub fn next_beacon_value(previous: u64, chain_hashes: &[[u8; 32]]) -> u64 {
+    let mut hasher = Sha256::new();
+    hasher.update(previous.to_le_bytes());
+    for chain_hash in chain_hashes {
+        hasher.update(chain_hash);
+    }
+    let result = hasher.finalize();
+    u64::from_le_bytes(result[0..8].try_into().unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Game, SpawnDistribution, SpawnGenerator};
+
+    #[test]
+    fn test_next_empty_cell_is_uniform() {
+        // Left half full, right half empty
+        let board = 0x1200_3400_5600_7800;
+        let mut counts = [0u32; 16];
+        let mut state = spawn_state("board", "alice", 1, board);
+        for _ in 0..10_000 {
+            counts[next_empty_cell(&mut state, board) as usize] += 1;
+        }
+
+        let expected = 10_000.0 / 8.0;
+        let mut chi_square = 0.0;
+        for (cell, count) in counts.into_iter().enumerate() {
+            if (board >> (cell * 4)) & 0xF != 0 {
+                assert_eq!(count, 0, "cell {} is not empty", cell);
+            } else {
+                chi_square += (count as f64 - expected).powi(2) / expected;
+            }
+        }
+        // 7 degrees of freedom: 24.32 is the 0.1% critical value
+        assert!(chi_square < 24.32, "chi-square {}", chi_square);
+    }
+
+    #[test]
+    fn test_beacon_contribution_depends_on_receipt() {
+        let contribution = beacon_contribution("shard", 1_000, 7);
+        assert_eq!(contribution, beacon_contribution("shard", 1_000, 7));
+        assert_ne!(contribution, beacon_contribution("other", 1_000, 7));
+        assert_ne!(contribution, beacon_contribution("shard", 1_001, 7));
+        assert_ne!(contribution, beacon_contribution("shard", 1_000, 8));
+    }
+
+    #[test]
+    fn test_spawn_state_depends_on_board() {
+        assert_ne!(
+            spawn_state("board", "alice", 1, 0x1000),
+            spawn_state("board", "alice", 1, 0x2000)
+        );
+        let mut state = 0;
+        lehmer_next(&mut state);
+        assert_ne!(state, 0);
+    }
+
+    #[test]
+    fn test_hashed_boards_keep_their_spawns() {
+        let hashed = SpawnDistribution::default().with_generator(SpawnGenerator::Hashed);
+        let lehmer = SpawnDistribution::default();
+        let mut generators_differ = false;
+        for timestamp in 0..100 {
+            // On an empty board the hashed seed's cell index is the nybble index
+            let seed = hash_seed("board", "alice", timestamp);
+            let tile = if seed % 10 == 9 { 2 } else { 1 };
+            let spawned = Game::spawn_tile("board", "alice", timestamp, 0, &hashed);
+            assert_eq!(spawned, tile << ((seed % 16) * 4));
+            generators_differ |=
+                spawned != Game::spawn_tile("board", "alice", timestamp, 0, &lehmer);
+        }
+        assert!(generators_differ);
+    }
+}
@@ -7,7 +7,8 @@ use std::sync::Arc;
 
 use self::service_handlers::{MutationHandler, QueryHandler, SubscriptionHandler};
 use self::state::Game2048;
-use async_graphql::{Request, Response, Schema};
+use async_graphql::{Response, Schema, ServerError};
+use game2048::{GraphQLQuery, GraphQLQueryResponse, MAX_BATCH_QUERIES};
 use linera_sdk::{linera_base_types::WithServiceAbi, views::View, Service, ServiceRuntime};
 
 pub struct Game2048Service {
@@ -34,7 +35,7 @@ impl Service for Game2048Service {
         }
     }
 
-    async fn handle_query(&self, request: Request) -> Response {
+    async fn handle_query(&self, query: GraphQLQuery) -> GraphQLQueryResponse {
         let schema = Schema::build(
             QueryHandler {
                 state: self.state.clone(),
@@ -50,6 +51,27 @@ impl Service for Game2048Service {
             },
         )
         .finish();
-        schema.execute(request).await
+
+        match query {
+            GraphQLQuery::Single(request) => {
+                GraphQLQueryResponse::Single(schema.execute(request).await)
+            }
+            // 📦 BATCH: Each query executes independently against the same state snapshot
+            GraphQLQuery::Batch(requests) => {
+                if requests.len() > MAX_BATCH_QUERIES {
+                    return GraphQLQueryResponse::Single(Response::from_errors(vec![
+                        ServerError::new(
+                            format!("Batch exceeds {} queries", MAX_BATCH_QUERIES),
+                            None,
+                        ),
+                    ]));
+                }
+                let mut responses = Vec::with_capacity(requests.len());
+                for request in requests {
+                    responses.push(schema.execute(request).await);
+                }
+                GraphQLQueryResponse::Batch(responses)
+            }
+        }
     }
 }
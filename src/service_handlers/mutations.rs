@@ -2,8 +2,11 @@ use crate::service_handlers::types::millis_to_micros;
 use crate::state::Game2048;
 use crate::Game2048Service;
 use async_graphql::Object;
-use game2048::{LeaderboardAction, LeaderboardSettings, Operation};
-use linera_sdk::ServiceRuntime;
+use game2048::{
+    BoardMoveEntry, BoardSize, BonusWindow, CriticalAdminAction, GameMetadataEntry,
+    LeaderboardAction, LeaderboardSettings, Operation, PlayerPreferences, ShardAuth,
+};
+use linera_sdk::{linera_base_types::Amount, ServiceRuntime};
 use std::sync::Arc;
 
 pub struct MutationHandler {
@@ -22,6 +25,7 @@ impl MutationHandler {
         []
     }
 
+    #[allow(clippy::too_many_arguments)]
     async fn new_board(
         &self,
         player: String,
@@ -30,6 +34,8 @@ impl MutationHandler {
         leaderboard_id: String, // Leaderboard ID parameter
         // 🎵 Rhythm mode: which music track was used (-1 = no rhythm/metronome, 0+ = track index)
         rhythm_track_index: Option<i32>,
+        // 🔲 Board dimensions (default 4x4)
+        board_size: Option<BoardSize>,
     ) -> [u8; 0] {
         // Validate player exists and password is correct
         self.validate_player_password(&player, &password_hash).await;
@@ -43,6 +49,34 @@ impl MutationHandler {
             password_hash,
             leaderboard_id, // Use provided leaderboard ID
             rhythm_track_index: rhythm_track_index.unwrap_or(-1) as i16,
+            pow_nonce: None,
+            board_size: board_size.unwrap_or_default(),
+        };
+        self.runtime.schedule_operation(&operation);
+        []
+    }
+
+    /// ⛏️ Create a board with a proof-of-work nonce (tournaments with powDifficulty)
+    async fn new_board_with_pow(
+        &self,
+        player: String,
+        password_hash: String,
+        timestamp: String,
+        leaderboard_id: String,
+        pow_nonce: u64,
+    ) -> [u8; 0] {
+        self.validate_player_password(&player, &password_hash).await;
+
+        let timestamp_micros = millis_to_micros(&timestamp).expect("Invalid timestamp format");
+
+        let operation = Operation::NewBoard {
+            player,
+            timestamp: timestamp_micros,
+            password_hash,
+            leaderboard_id,
+            rhythm_track_index: -1,
+            pow_nonce: Some(pow_nonce),
+            board_size: BoardSize::FourByFour,
         };
         self.runtime.schedule_operation(&operation);
         []
@@ -74,6 +108,24 @@ impl MutationHandler {
         []
     }
 
+    /// 📦 Moves for several boards in one operation
+    async fn batch_make_moves(
+        &self,
+        entries: Vec<BoardMoveEntry>,
+        player: String,
+        password_hash: String,
+    ) -> [u8; 0] {
+        self.validate_player_password(&player, &password_hash).await;
+
+        let operation = Operation::BatchMakeMoves {
+            entries,
+            player,
+            password_hash,
+        };
+        self.runtime.schedule_operation(&operation);
+        []
+    }
+
     async fn leaderboard_action(
         &self,
         leaderboard_id: String,
@@ -87,16 +139,13 @@ impl MutationHandler {
 
         // Convert timestamps from milliseconds to microseconds
         let converted_settings = LeaderboardSettings {
-            name: settings.name,
-            description: settings.description,
             start_time: millis_to_micros(&settings.start_time)
                 .expect("Invalid start_time")
                 .to_string(),
             end_time: millis_to_micros(&settings.end_time)
                 .expect("Invalid end_time")
                 .to_string(),
-            shard_number: settings.shard_number,
-            base_triggerer_count: settings.base_triggerer_count,
+            ..settings
         };
 
         let operation = Operation::LeaderboardAction {
@@ -110,6 +159,31 @@ impl MutationHandler {
         []
     }
 
+    /// 📋 Create a leaderboard with another's settings, a new name and new times (milliseconds)
+    #[allow(clippy::too_many_arguments)]
+    async fn clone_leaderboard(
+        &self,
+        source_leaderboard_id: String,
+        new_name: String,
+        new_start_time: String,
+        new_end_time: String,
+        player: String,
+        password_hash: String,
+    ) -> [u8; 0] {
+        self.validate_player_password(&player, &password_hash).await;
+
+        let operation = Operation::CloneLeaderboard {
+            source_leaderboard_id,
+            new_name,
+            new_start_time: millis_to_micros(&new_start_time).expect("Invalid start_time"),
+            new_end_time: millis_to_micros(&new_end_time).expect("Invalid end_time"),
+            player,
+            password_hash,
+        };
+        self.runtime.schedule_operation(&operation);
+        []
+    }
+
     async fn toggle_mod(&self, player: String, password_hash: String, username: String) -> [u8; 0] {
         // Validate player exists and password is correct
         self.validate_player_password(&player, &password_hash).await;
@@ -154,6 +228,13 @@ impl MutationHandler {
         []
     }
 
+    /// 🎟️ WAITLIST: Admit waitlisted players into free slots (for leaderboard chains)
+    async fn process_waitlist(&self, leaderboard_id: String) -> [u8; 0] {
+        let operation = Operation::ProcessWaitlist { leaderboard_id };
+        self.runtime.schedule_operation(&operation);
+        []
+    }
+
     /// 🚀 NEW: Emit current active tournaments (for leaderboard chains)
     async fn update_active_tournaments(&self) -> [u8; 0] {
         let operation = Operation::UpdateActiveTournaments;
@@ -234,6 +315,32 @@ impl MutationHandler {
         []
     }
 
+    /// 📊 Reset this chain's resource usage counters (admin only)
+    async fn reset_resource_usage(&self, admin: String, password_hash: String) -> [u8; 0] {
+        let operation = Operation::ResetResourceUsage {
+            admin,
+            password_hash,
+        };
+        self.runtime.schedule_operation(&operation);
+        []
+    }
+
+    /// 📮 Re-process a failed shard message (admin only, shard chain)
+    async fn retry_dead_letter(
+        &self,
+        key: String,
+        admin: String,
+        password_hash: String,
+    ) -> [u8; 0] {
+        let operation = Operation::RetryDeadLetter {
+            key,
+            admin,
+            password_hash,
+        };
+        self.runtime.schedule_operation(&operation);
+        []
+    }
+
     /// 🚀 NEW: Manual leaderboard refresh - player can trigger update when their score is higher
     /// This sends a TriggerUpdate message directly to the leaderboard chain
     async fn request_leaderboard_refresh(
@@ -275,7 +382,7 @@ impl MutationHandler {
         self.runtime.schedule_operation(&operation);
         []
     }
-    
+
     /// 🚀 MESSAGE-BASED: Claim player chain after registration
     /// This triggers block production which processes the inbox messages
     /// (RegisterPlayer, SubscribeToMainChain)
@@ -284,7 +391,49 @@ impl MutationHandler {
         self.runtime.schedule_operation(&operation);
         []
     }
-    
+
+    /// 🎲 Contribute this shard chain to the randomness beacon (only registered shards count)
+    async fn contribute_to_beacon(&self) -> [u8; 0] {
+        let operation = Operation::ContributeToBeacon {
+            shard_chain_id: self.runtime.chain_id(),
+        };
+        self.runtime.schedule_operation(&operation);
+        []
+    }
+
+    /// 🔑 ADMIN: Register a shard's new ed25519 public key (64 hex characters).
+    /// Generate the key pair off-chain; the secret key never goes on chain.
+    async fn rotate_shard_key(
+        &self,
+        admin: String,
+        password_hash: String,
+        leaderboard_id: String,
+        shard_chain_id: String,
+        public_key: String,
+    ) -> [u8; 0] {
+        let public_key = ShardAuth::parse_public_key(&public_key)
+            .expect("Public key must be 64 hex characters of an ed25519 key");
+        let operation = Operation::RotateShardKey {
+            admin,
+            password_hash,
+            leaderboard_id,
+            shard_chain_id,
+            public_key,
+        };
+        self.runtime.schedule_operation(&operation);
+        []
+    }
+
+    /// ♻️ ADMIN: Return chains claimed but never initialized to the chain pool
+    async fn recover_stale_chains(&self, admin: String, password_hash: String) -> [u8; 0] {
+        let operation = Operation::RecoverStaleChains {
+            admin,
+            password_hash,
+        };
+        self.runtime.schedule_operation(&operation);
+        []
+    }
+
     /// 🚀 MANUAL SCORE SUBMISSION: Submit current board score to leaderboard
     /// Called when user clicks "refresh leaderboard" button
     /// Only sends if: score > 0 AND score > player's tournament best
@@ -296,7 +445,7 @@ impl MutationHandler {
     ) -> [u8; 0] {
         // Validate player exists and password is correct
         self.validate_player_password(&player, &password_hash).await;
-        
+
         let operation = Operation::SubmitCurrentScore {
             board_id,
             player,
@@ -305,6 +454,799 @@ impl MutationHandler {
         self.runtime.schedule_operation(&operation);
         []
     }
+
+    /// ⚙️ Update the player's preferences (call on the player chain)
+    async fn update_preferences(
+        &self,
+        player: String,
+        password_hash: String,
+        preferences: PlayerPreferences,
+    ) -> [u8; 0] {
+        // Validate player exists and password is correct
+        self.validate_player_password(&player, &password_hash).await;
+
+        let operation = Operation::UpdatePreferences {
+            player,
+            password_hash,
+            preferences,
+        };
+        self.runtime.schedule_operation(&operation);
+        []
+    }
+
+    /// 👫 Follow another player's best scores (call on the player chain)
+    async fn add_friend(
+        &self,
+        friend_username: String,
+        player: String,
+        password_hash: String,
+    ) -> [u8; 0] {
+        self.validate_player_password(&player, &password_hash).await;
+
+        let operation = Operation::AddFriend {
+            friend_username,
+            player,
+            password_hash,
+        };
+        self.runtime.schedule_operation(&operation);
+        []
+    }
+
+    /// 👫 Stop following a player (call on the player chain)
+    async fn remove_friend(
+        &self,
+        friend_username: String,
+        player: String,
+        password_hash: String,
+    ) -> [u8; 0] {
+        self.validate_player_password(&player, &password_hash).await;
+
+        let operation = Operation::RemoveFriend {
+            friend_username,
+            player,
+            password_hash,
+        };
+        self.runtime.schedule_operation(&operation);
+        []
+    }
+
+    /// 🔑 Request a password change token (call on the main chain); the token is the
+    /// operation's response
+    async fn request_password_change(&self, player: String, old_password_hash: String) -> [u8; 0] {
+        self.validate_player_password(&player, &old_password_hash)
+            .await;
+
+        let operation = Operation::RequestPasswordChange {
+            player,
+            old_password_hash,
+        };
+        self.runtime.schedule_operation(&operation);
+        []
+    }
+
+    /// 🔑 Set a new password hash with a token from requestPasswordChange (call on the main chain)
+    async fn confirm_password_change(
+        &self,
+        player: String,
+        change_token: String,
+        new_password_hash: String,
+    ) -> [u8; 0] {
+        let operation = Operation::ConfirmPasswordChange {
+            player,
+            change_token,
+            new_password_hash,
+        };
+        self.runtime.schedule_operation(&operation);
+        []
+    }
+
+    /// 🧳 Move the player to another chain they own (call on the main chain)
+    async fn migrate_player(
+        &self,
+        old_player: String,
+        new_chain_id: String,
+        old_password_hash: String,
+        new_password_hash: String,
+    ) -> [u8; 0] {
+        self.validate_player_password(&old_player, &old_password_hash)
+            .await;
+
+        let operation = Operation::MigratePlayer {
+            old_player,
+            new_chain_id,
+            old_password_hash,
+            new_password_hash,
+        };
+        self.runtime.schedule_operation(&operation);
+        []
+    }
+
+    /// 📦 Export the player's data (call on the player chain); the JSON is the operation's response
+    async fn export_player_data(&self, player: String, password_hash: String) -> [u8; 0] {
+        self.validate_player_password(&player, &password_hash).await;
+
+        let operation = Operation::ExportPlayerData {
+            player,
+            password_hash,
+        };
+        self.runtime.schedule_operation(&operation);
+        []
+    }
+
+    /// 🏷️ Replace client-defined metadata on an active game (max 5 entries)
+    async fn set_game_metadata(
+        &self,
+        board_id: String,
+        player: String,
+        password_hash: String,
+        metadata: Vec<GameMetadataEntry>,
+    ) -> [u8; 0] {
+        // Validate player exists and password is correct
+        self.validate_player_password(&player, &password_hash).await;
+
+        let operation = Operation::SetGameMetadata {
+            board_id,
+            player,
+            password_hash,
+            metadata,
+        };
+        self.runtime.schedule_operation(&operation);
+        []
+    }
+
+    /// ⏱️ Convert a classic game to a speed game before the first move
+    async fn convert_to_timed_game(
+        &self,
+        board_id: String,
+        player: String,
+        password_hash: String,
+        time_limit_seconds: u32,
+    ) -> [u8; 0] {
+        // Validate player exists and password is correct
+        self.validate_player_password(&player, &password_hash).await;
+
+        let operation = Operation::ConvertToTimedGame {
+            board_id,
+            player,
+            password_hash,
+            time_limit_seconds,
+        };
+        self.runtime.schedule_operation(&operation);
+        []
+    }
+
+    /// 💡 Request a move suggestion (max 5 per board; read it with the `hint` query)
+    async fn request_hint(
+        &self,
+        board_id: String,
+        player: String,
+        password_hash: String,
+    ) -> [u8; 0] {
+        // Validate player exists and password is correct
+        self.validate_player_password(&player, &password_hash).await;
+
+        let operation = Operation::RequestHint {
+            board_id,
+            player,
+            password_hash,
+        };
+        self.runtime.schedule_operation(&operation);
+        []
+    }
+
+    /// 💾 Save a snapshot of the board to resume from later
+    async fn save_snapshot(
+        &self,
+        board_id: String,
+        player: String,
+        password_hash: String,
+    ) -> [u8; 0] {
+        self.validate_player_password(&player, &password_hash).await;
+
+        let operation = Operation::SaveSnapshot {
+            board_id,
+            player,
+            password_hash,
+        };
+        self.runtime.schedule_operation(&operation);
+        []
+    }
+
+    /// 💾 Restore the board from its saved snapshot
+    async fn load_snapshot(
+        &self,
+        board_id: String,
+        player: String,
+        password_hash: String,
+    ) -> [u8; 0] {
+        self.validate_player_password(&player, &password_hash).await;
+
+        let operation = Operation::LoadSnapshot {
+            board_id,
+            player,
+            password_hash,
+        };
+        self.runtime.schedule_operation(&operation);
+        []
+    }
+
+    /// 📅 Start today's daily challenge board
+    async fn get_daily_challenge(&self, player: String, password_hash: String) -> [u8; 0] {
+        self.validate_player_password(&player, &password_hash).await;
+
+        let operation = Operation::GetDailyChallenge {
+            player,
+            password_hash,
+        };
+        self.runtime.schedule_operation(&operation);
+        []
+    }
+
+    /// ↩️ Undo the last move (uses one of the board's undos)
+    async fn undo_move(&self, board_id: String, player: String, password_hash: String) -> [u8; 0] {
+        // Validate player exists and password is correct
+        self.validate_player_password(&player, &password_hash).await;
+
+        let operation = Operation::UndoMove {
+            board_id,
+            player,
+            password_hash,
+        };
+        self.runtime.schedule_operation(&operation);
+        []
+    }
+
+    /// ↪️ Continue a game on a new board at half score (within 5 minutes of creating it)
+    async fn continue_game(
+        &self,
+        old_board_id: String,
+        player: String,
+        password_hash: String,
+        new_board_size: Option<BoardSize>,
+    ) -> [u8; 0] {
+        // Validate player exists and password is correct
+        self.validate_player_password(&player, &password_hash).await;
+
+        let operation = Operation::ContinueGame {
+            old_board_id,
+            player,
+            password_hash,
+            new_board_size: new_board_size.unwrap_or_default(),
+        };
+        self.runtime.schedule_operation(&operation);
+        []
+    }
+
+    /// 👥 TEAMS: Start a shared board on this chain, with one invite code per invited player
+    async fn create_team_game(
+        &self,
+        team_name: String,
+        player: String,
+        password_hash: String,
+        invite_codes: Vec<String>,
+    ) -> [u8; 0] {
+        self.validate_player_password(&player, &password_hash).await;
+
+        let operation = Operation::CreateTeamGame {
+            team_name,
+            player,
+            password_hash,
+            invite_codes,
+        };
+        self.runtime.schedule_operation(&operation);
+        []
+    }
+
+    /// 👥 TEAMS: Join a team game hosted on another chain
+    async fn join_team_game(
+        &self,
+        team_board_id: String,
+        invite_code: String,
+        player: String,
+        password_hash: String,
+    ) -> [u8; 0] {
+        self.validate_player_password(&player, &password_hash).await;
+
+        let operation = Operation::JoinTeamGame {
+            team_board_id,
+            invite_code,
+            player,
+            password_hash,
+        };
+        self.runtime.schedule_operation(&operation);
+        []
+    }
+
+    /// 🤝 MATCHMAKING: Wait for a similarly rated opponent (main chain)
+    async fn enter_matchmaking(
+        &self,
+        player: String,
+        password_hash: String,
+        preferred_time_limit: u32,
+    ) -> [u8; 0] {
+        // Validate player exists and password is correct
+        self.validate_player_password(&player, &password_hash).await;
+
+        let operation = Operation::EnterMatchmaking {
+            player,
+            password_hash,
+            preferred_time_limit,
+        };
+        self.runtime.schedule_operation(&operation);
+        []
+    }
+
+    /// 🤝 MATCHMAKING: Pair waiting players (admin, main chain)
+    async fn process_matchmaking(&self, admin: String, password_hash: String) -> [u8; 0] {
+        let operation = Operation::ProcessMatchmaking {
+            admin,
+            password_hash,
+        };
+        self.runtime.schedule_operation(&operation);
+        []
+    }
+
+    /// 🔮 SPECTATOR: Predict the tournament winner and their final score
+    /// Must be called on the spectator's own chain before the tournament ends
+    async fn submit_prediction(
+        &self,
+        spectator_chain_id: String,
+        leaderboard_id: String,
+        predicted_winner: String,
+        predicted_score: u64,
+    ) -> [u8; 0] {
+        let operation = Operation::SubmitPrediction {
+            spectator_chain_id,
+            leaderboard_id,
+            predicted_winner,
+            predicted_score,
+        };
+        self.runtime.schedule_operation(&operation);
+        []
+    }
+
+    /// 📡 LIVE STREAM: Register a relay endpoint for a tournament (host/admin, main chain)
+    /// The auth token is hashed here, so only its hash is scheduled on chain
+    #[allow(clippy::too_many_arguments)]
+    async fn register_stream_endpoint(
+        &self,
+        admin: String,
+        password_hash: String,
+        leaderboard_id: String,
+        endpoint_url: String,
+        auth_token: String,
+        relay_owner: String,
+    ) -> [u8; 0] {
+        // Validate player exists and password is correct
+        self.validate_player_password(&admin, &password_hash).await;
+
+        let operation = Operation::RegisterStreamEndpoint {
+            admin,
+            password_hash,
+            leaderboard_id,
+            endpoint_url,
+            auth_token_hash: game2048::stream_auth_token_hash(&auth_token),
+            relay_owner,
+        };
+        self.runtime.schedule_operation(&operation);
+        []
+    }
+
+    /// 🔐 MULTI-SIG: Configure admin signers and threshold (super-admin, main chain)
+    async fn configure_multi_sig(
+        &self,
+        required_signatures: u8,
+        admin_list: Vec<String>,
+        player: String,
+        password_hash: String,
+    ) -> [u8; 0] {
+        let operation = Operation::ConfigureMultiSig {
+            required_signatures,
+            admin_list,
+            player,
+            password_hash,
+        };
+        self.runtime.schedule_operation(&operation);
+        []
+    }
+
+    /// 🔐 MULTI-SIG: Propose a critical admin action (main chain)
+    async fn propose_admin_action(
+        &self,
+        proposer: String,
+        password_hash: String,
+        action: CriticalAdminAction,
+        proposal_id: String,
+    ) -> [u8; 0] {
+        // Validate player exists and password is correct
+        self.validate_player_password(&proposer, &password_hash)
+            .await;
+
+        let operation = Operation::ProposeAdminAction {
+            proposer,
+            password_hash,
+            action,
+            proposal_id,
+        };
+        self.runtime.schedule_operation(&operation);
+        []
+    }
+
+    /// 🔐 MULTI-SIG: Approve a pending proposal (main chain)
+    async fn approve_admin_action(
+        &self,
+        approver: String,
+        password_hash: String,
+        proposal_id: String,
+    ) -> [u8; 0] {
+        // Validate player exists and password is correct
+        self.validate_player_password(&approver, &password_hash)
+            .await;
+
+        let operation = Operation::ApproveAdminAction {
+            approver,
+            password_hash,
+            proposal_id,
+        };
+        self.runtime.schedule_operation(&operation);
+        []
+    }
+
+    /// 🛡️ MODERATION: Replace the blocked-word list (super-admin, main chain)
+    async fn update_blocked_words(
+        &self,
+        blocked_words: Vec<String>,
+        player: String,
+        password_hash: String,
+    ) -> [u8; 0] {
+        let operation = Operation::UpdateBlockedWords {
+            blocked_words,
+            player,
+            password_hash,
+        };
+        self.runtime.schedule_operation(&operation);
+        []
+    }
+
+    /// 🚩 MODERATION: Confirm or clear a game flagged as a suspected cheat (admin, main chain)
+    async fn review_flagged_game(
+        &self,
+        board_id: String,
+        admin: String,
+        password_hash: String,
+        verdict: game2048::FlagVerdict,
+    ) -> [u8; 0] {
+        let operation = Operation::ReviewFlaggedGame {
+            board_id,
+            admin,
+            password_hash,
+            verdict,
+        };
+        self.runtime.schedule_operation(&operation);
+        []
+    }
+
+    /// ⏰ BONUS WINDOW: Schedule a score multiplier window (host/admin, main chain)
+    /// `window.start` / `window.end` are in milliseconds
+    async fn add_bonus_window(
+        &self,
+        leaderboard_id: String,
+        admin: String,
+        password_hash: String,
+        window: BonusWindow,
+    ) -> [u8; 0] {
+        let operation = Operation::AddBonusWindow {
+            leaderboard_id,
+            admin,
+            password_hash,
+            window: BonusWindow {
+                start: window
+                    .start
+                    .checked_mul(1000)
+                    .expect("Bonus window start is out of range"),
+                end: window
+                    .end
+                    .checked_mul(1000)
+                    .expect("Bonus window end is out of range"),
+                ..window
+            },
+        };
+        self.runtime.schedule_operation(&operation);
+        []
+    }
+
+    /// ⚖️ HANDICAP: Set a player's score multiplier (0.5 - 1.0) before the tournament starts
+    async fn set_player_handicap(
+        &self,
+        leaderboard_id: String,
+        admin: String,
+        password_hash: String,
+        player: String,
+        multiplier: f64,
+    ) -> [u8; 0] {
+        let operation = Operation::SetPlayerHandicap {
+            leaderboard_id,
+            admin,
+            password_hash,
+            player,
+            multiplier,
+        };
+        self.runtime.schedule_operation(&operation);
+        []
+    }
+
+    /// 🏅 PRIZE POOL: Send tokens into a tournament's prize pool ("native" or a token application ID)
+    async fn fund_prize_pool(
+        &self,
+        leaderboard_id: String,
+        application_id: String,
+        amount: Amount,
+    ) -> [u8; 0] {
+        let operation = Operation::FundPrizePool {
+            leaderboard_id,
+            application_id,
+            amount,
+        };
+        self.runtime.schedule_operation(&operation);
+        []
+    }
+
+    /// 🏅 PRIZE POOL: Pay out prizes after the tournament ends (host/admin)
+    async fn distribute_prizes(
+        &self,
+        leaderboard_id: String,
+        admin: String,
+        password_hash: String,
+    ) -> [u8; 0] {
+        let operation = Operation::DistributePrizes {
+            leaderboard_id,
+            admin,
+            password_hash,
+        };
+        self.runtime.schedule_operation(&operation);
+        []
+    }
+
+    /// 🏁 Finalise an ended tournament and pay its rewards (host/admin)
+    async fn finalise_leaderboard(
+        &self,
+        leaderboard_id: String,
+        player: String,
+        password_hash: String,
+    ) -> [u8; 0] {
+        self.validate_player_password(&player, &password_hash).await;
+
+        let operation = Operation::FinaliseLeaderboard {
+            leaderboard_id,
+            player,
+            password_hash,
+        };
+        self.runtime.schedule_operation(&operation);
+        []
+    }
+
+    /// 📥 Import off-chain tournament history (JSON array, max 500 records per call)
+    async fn import_historical_data(
+        &self,
+        leaderboard_id: String,
+        admin: String,
+        password_hash: String,
+        data_json: String,
+    ) -> [u8; 0] {
+        let operation = Operation::ImportHistoricalData {
+            leaderboard_id,
+            admin,
+            password_hash,
+            data_json,
+        };
+        self.runtime.schedule_operation(&operation);
+        []
+    }
+
+    /// 🥊 BRACKET: Seed a tournament's players into a single-elimination bracket (host/admin, main chain)
+    async fn bracket_tournament(
+        &self,
+        leaderboard_id: String,
+        admin: String,
+        password_hash: String,
+        bracket_size: u32,
+        round_duration_seconds: u64,
+    ) -> [u8; 0] {
+        let operation = Operation::BracketTournament {
+            leaderboard_id,
+            admin,
+            password_hash,
+            bracket_size,
+            round_duration_seconds,
+        };
+        self.runtime.schedule_operation(&operation);
+        []
+    }
+
+    /// 🔄 ROUND ROBIN: Pair a tournament's players for `rounds` rounds (host/admin, main chain)
+    async fn schedule_round_robin(
+        &self,
+        leaderboard_id: String,
+        rounds: u8,
+        round_duration_seconds: u64,
+        player: String,
+        password_hash: String,
+    ) -> [u8; 0] {
+        self.validate_player_password(&player, &password_hash).await;
+
+        let operation = Operation::ScheduleRoundRobin {
+            leaderboard_id,
+            rounds,
+            round_duration_seconds,
+            player,
+            password_hash,
+        };
+        self.runtime.schedule_operation(&operation);
+        []
+    }
+
+    /// 🔁 REPLAY: Put a tournament in replay mode (host/admin, main chain)
+    async fn initiate_tournament_replay(
+        &self,
+        leaderboard_id: String,
+        admin: String,
+        password_hash: String,
+    ) -> [u8; 0] {
+        let operation = Operation::InitiateTournamentReplay {
+            leaderboard_id,
+            admin,
+            password_hash,
+        };
+        self.runtime.schedule_operation(&operation);
+        []
+    }
+
+    /// 🔁 REPLAY: Replay a board's recorded moves (player chain)
+    async fn replay_moves(
+        &self,
+        board_id: String,
+        player: String,
+        password_hash: String,
+    ) -> [u8; 0] {
+        // Validate player exists and password is correct
+        self.validate_player_password(&player, &password_hash).await;
+
+        let operation = Operation::ReplayMoves {
+            board_id,
+            player,
+            password_hash,
+        };
+        self.runtime.schedule_operation(&operation);
+        []
+    }
+
+    /// 🔁 REPLAY: Verify a shared move log against a board (fails if the log was tampered with)
+    async fn replay_game(&self, board_id: String, move_log: Vec<u8>) -> [u8; 0] {
+        let operation = Operation::ReplayGame { board_id, move_log };
+        self.runtime.schedule_operation(&operation);
+        []
+    }
+
+    /// 🔁 REPLAY: Compare replayed scores and leave replay mode (host/admin, main chain)
+    async fn finalize_replay(
+        &self,
+        leaderboard_id: String,
+        admin: String,
+        password_hash: String,
+    ) -> [u8; 0] {
+        let operation = Operation::FinalizeReplay {
+            leaderboard_id,
+            admin,
+            password_hash,
+        };
+        self.runtime.schedule_operation(&operation);
+        []
+    }
+
+    /// ⏸️ PAUSE: Hold the tournament's score updates (host/admin, main chain)
+    async fn pause_leaderboard(
+        &self,
+        leaderboard_id: String,
+        reason: String,
+        player: String,
+        password_hash: String,
+    ) -> [u8; 0] {
+        self.validate_player_password(&player, &password_hash).await;
+
+        let operation = Operation::PauseLeaderboard {
+            leaderboard_id,
+            reason,
+            player,
+            password_hash,
+        };
+        self.runtime.schedule_operation(&operation);
+        []
+    }
+
+    /// ⏸️ PAUSE: Apply the held score updates and resume scoring (host/admin, main chain)
+    async fn resume_leaderboard(
+        &self,
+        leaderboard_id: String,
+        reason: String,
+        player: String,
+        password_hash: String,
+    ) -> [u8; 0] {
+        self.validate_player_password(&player, &password_hash).await;
+
+        let operation = Operation::ResumeLeaderboard {
+            leaderboard_id,
+            reason,
+            player,
+            password_hash,
+        };
+        self.runtime.schedule_operation(&operation);
+        []
+    }
+
+    /// 🧹 Remove players who never scored and were inactive for the threshold (host/admin)
+    async fn purge_inactive_players(
+        &self,
+        leaderboard_id: String,
+        inactive_threshold_seconds: u64,
+        admin: String,
+        password_hash: String,
+    ) -> [u8; 0] {
+        self.validate_player_password(&admin, &password_hash).await;
+
+        let operation = Operation::PurgeInactivePlayers {
+            leaderboard_id,
+            inactive_threshold_seconds,
+            admin,
+            password_hash,
+        };
+        self.runtime.schedule_operation(&operation);
+        []
+    }
+
+    /// 🪙 TOKEN GATE: Attest a player chain's balance (attestation service chain)
+    async fn attest_balance(
+        &self,
+        player_chain_id: String,
+        leaderboard_id: String,
+        held: Amount,
+        expires_at: String, // milliseconds
+    ) -> [u8; 0] {
+        let expires_at = millis_to_micros(&expires_at).expect("Invalid expires_at");
+        let operation = Operation::AttestBalance {
+            player_chain_id,
+            leaderboard_id,
+            held,
+            expires_at,
+        };
+        self.runtime.schedule_operation(&operation);
+        []
+    }
+
+    /// 📡 LIVE STREAM: Relay reports delivery result (leaderboard chain, signed by the relay owner)
+    async fn report_stream_delivery(&self, endpoint_url: String, success: bool) -> [u8; 0] {
+        let operation = Operation::ReportStreamDelivery {
+            endpoint_url,
+            success,
+        };
+        self.runtime.schedule_operation(&operation);
+        []
+    }
+
+    /// 🌟 Send platform highlights to a chain (call on the main chain)
+    async fn subscribe_platform_events(&self, subscriber_chain_id: String) -> [u8; 0] {
+        let operation = Operation::SubscribePlatformEvents {
+            subscriber_chain_id,
+        };
+        self.runtime.schedule_operation(&operation);
+        []
+    }
+
+    /// 🔕 Stop receiving a channel's events on this chain
+    async fn unsubscribe_from_channel(&self, channel: String) -> [u8; 0] {
+        let operation = Operation::UnsubscribeFromChannel { channel };
+        self.runtime.schedule_operation(&operation);
+        []
+    }
 }
 
 impl MutationHandler {
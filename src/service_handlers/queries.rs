@@ -1,8 +1,11 @@
 use crate::service_handlers::types::*;
 use crate::state::Game2048;
 use async_graphql::{Enum, Object};
-use game2048::Game;
-use linera_sdk::ServiceRuntime;
+use game2048::{
+    BoardStateResponse, Game, LeaderboardCursor, RankedScore, TileMergeCount, TournamentPrediction,
+    TournamentStatus, COMBO_CARD_MAX_TILE, MAX_HINTS_PER_BOARD, MAX_LEADERBOARD_PAGE_SIZE,
+};
+use linera_sdk::{linera_base_types::Amount, ServiceRuntime};
 use std::collections::HashMap;
 use std::sync::Arc;
 
@@ -110,6 +113,46 @@ impl QueryHandler {
         }
     }
 
+    /// ⚙️ Player preferences (player chain) - visible to the player or a moderator only
+    async fn player_preferences(
+        &self,
+        player: String,
+        requester: String,
+        password_hash: String,
+    ) -> Option<game2048::PlayerPreferences> {
+        let requester_entry = self.state.players.try_load_entry(&requester).await.ok()??;
+        if *requester_entry.password_hash.get() != password_hash {
+            return None;
+        }
+        if requester != player && !*requester_entry.is_mod.get() {
+            return None;
+        }
+
+        Some(
+            self.state
+                .player_preferences
+                .get(&player)
+                .await
+                .ok()
+                .flatten()
+                .unwrap_or_default(),
+        )
+    }
+
+    /// 🏅 A player's best ended games, best first (player chain)
+    async fn player_history(&self, player: String, limit: u8) -> Vec<game2048::BoardSummary> {
+        let Ok(Some(player_state)) = self.state.players.try_load_entry(&player).await else {
+            return Vec::new();
+        };
+        player_state
+            .top_boards
+            .get()
+            .iter()
+            .take(limit as usize)
+            .cloned()
+            .collect()
+    }
+
     /// 🎯 Get player's score from leaderboard (single source of truth)
     /// Direct key lookup - O(1), no loop needed
     /// Query this on the LEADERBOARD CHAIN (not player chain)
@@ -123,7 +166,12 @@ impl QueryHandler {
         }
     }
 
-    async fn board(&self, board_id: Option<String>, move_offset: Option<u32>, move_limit: Option<u32>) -> Option<BoardState> {
+    async fn board(
+        &self,
+        board_id: Option<String>,
+        move_offset: Option<u32>,
+        move_limit: Option<u32>,
+    ) -> Option<BoardState> {
         let board_id = board_id.unwrap_or(self.state.latest_board_id.get().to_string());
         if let Ok(Some(game)) = self.state.boards.try_load_entry(&board_id).await {
             // Load move history with pagination
@@ -159,11 +207,13 @@ impl QueryHandler {
             }
 
             let has_more_moves = end_index < total_moves;
-            
+            let merge_combinations = Self::load_merge_combinations(&game).await;
+
             let game_state = BoardState {
                 board_id: game.board_id.get().to_string(),
                 board: Game::convert_to_matrix(*game.board.get()),
                 is_ended: *game.is_ended.get(),
+                is_deadlocked: *game.is_deadlocked.get(),
                 score: *game.score.get(),
                 player: game.player.get().to_string(),
                 chain_id: game.chain_id.get().to_string(),
@@ -179,6 +229,29 @@ impl QueryHandler {
                 has_more_moves,
                 // 🎵 Rhythm mode: which music track was used
                 rhythm_track_index: *game.rhythm_track_index.get(),
+                spawn_generator: *game.spawn_generator.get(),
+                metadata: game.metadata.get().clone(),
+                combo_card_complete: TileMergeCount::is_combo_complete(
+                    &merge_combinations,
+                    COMBO_CARD_MAX_TILE,
+                ),
+                merge_combinations,
+                variant: game.variant.get().to_string(),
+                deadline: micros_to_millis(*game.deadline.get()),
+                continuation_score: *game.continuation_score.get(),
+                continued_from: game.continued_from.get().clone(),
+                continued_to: game.continued_to.get().clone(),
+                total_valid_moves_available: *game.total_valid_moves_available.get(),
+                moves_with_only_one_option: *game.moves_with_only_one_option.get(),
+                average_branching_factor: game.average_branching_factor(),
+                undo_budget: *game.undo_budget.get(),
+                lives: *game.lives.get(),
+                current_combo: *game.current_combo.get(),
+                last_combo_bonus: *game.last_combo_bonus.get(),
+                total_think_time_micros: *game.total_think_time_micros.get(),
+                min_move_time_micros: *game.min_move_time_micros.get(),
+                max_move_time_micros: *game.max_move_time_micros.get(),
+                avg_move_time_micros: game.avg_move_time_micros(),
             };
             Some(game_state)
         } else {
@@ -186,6 +259,79 @@ impl QueryHandler {
         }
     }
 
+    /// 🔎 A live board's current tiles and status (None if missing or not `player`'s board)
+    async fn board_state(&self, board_id: String, player: String) -> Option<BoardStateResponse> {
+        let game = self.state.boards.try_load_entry(&board_id).await.ok()??;
+        if *game.player.get() != player {
+            return None;
+        }
+        Some(BoardStateResponse::from_board(
+            *game.board.get(),
+            *game.score.get(),
+            *game.move_count.get(),
+            *game.is_ended.get(),
+            *game.created_at.get(),
+            self.runtime.system_time().micros(),
+        ))
+    }
+
+    /// 🛡️ Anti-cheat checks for a board with their margins (player chain, moderators only)
+    /// Thresholds come from the board's tournament (defaults if not cached)
+    async fn anti_cheat_report(
+        &self,
+        board_id: String,
+        requester: String,
+        password_hash: String,
+    ) -> Vec<game2048::AntiCheatCheck> {
+        let Ok(Some(requester_entry)) = self.state.players.try_load_entry(&requester).await else {
+            return Vec::new();
+        };
+        if *requester_entry.password_hash.get() != password_hash || !*requester_entry.is_mod.get() {
+            return Vec::new();
+        }
+        let Ok(Some(board)) = self.state.boards.try_load_entry(&board_id).await else {
+            return Vec::new();
+        };
+
+        let config = self
+            .state
+            .tournaments_cache_json
+            .get(board.leaderboard_id.get())
+            .await
+            .ok()
+            .flatten()
+            .and_then(|json| serde_json::from_str::<game2048::TournamentInfo>(&json).ok())
+            .and_then(|tournament| tournament.anti_cheat)
+            .unwrap_or_default();
+
+        let mut moves = Vec::new();
+        for index in 0..*board.move_count.get() {
+            if let Ok(Some(record)) = board.move_history.try_load_entry(&index).await {
+                let direction = match *record.direction.get() {
+                    0 => game2048::Direction::Up,
+                    1 => game2048::Direction::Down,
+                    2 => game2048::Direction::Left,
+                    _ => game2048::Direction::Right,
+                };
+                moves.push(game2048::MoveSample {
+                    direction,
+                    timestamp: *record.timestamp.get(),
+                    board_after: *record.board_after.get(),
+                    score_after: *record.score_after.get(),
+                });
+            }
+        }
+
+        game2048::AntiCheat::analyze(
+            &config,
+            &board_id,
+            board.player.get(),
+            &moves,
+            *board.null_move_count.get(),
+            &board.spawn_distribution(),
+        )
+    }
+
     async fn boards(&self, board_ids: Option<Vec<String>>, limit: Option<i32>) -> Vec<BoardState> {
         let mut board_ids = board_ids.unwrap_or_default();
         let mut boards: Vec<BoardState> = Vec::new();
@@ -200,11 +346,13 @@ impl QueryHandler {
 
         for board_id in board_ids_to_query {
             if let Ok(Some(board)) = self.state.boards.try_load_entry(&board_id).await {
+                let merge_combinations = Self::load_merge_combinations(&board).await;
                 // Don't load full move history for list queries (performance)
                 boards.push(BoardState {
                     board_id,
                     board: Game::convert_to_matrix(*board.board.get()),
                     is_ended: *board.is_ended.get(),
+                    is_deadlocked: *board.is_deadlocked.get(),
                     score: *board.score.get(),
                     player: board.player.get().to_string(),
                     chain_id: board.chain_id.get().to_string(),
@@ -220,6 +368,29 @@ impl QueryHandler {
                     has_more_moves: false,
                     // 🎵 Rhythm mode: which music track was used
                     rhythm_track_index: *board.rhythm_track_index.get(),
+                    spawn_generator: *board.spawn_generator.get(),
+                    metadata: board.metadata.get().clone(),
+                    combo_card_complete: TileMergeCount::is_combo_complete(
+                        &merge_combinations,
+                        COMBO_CARD_MAX_TILE,
+                    ),
+                    merge_combinations,
+                    variant: board.variant.get().to_string(),
+                    deadline: micros_to_millis(*board.deadline.get()),
+                    continuation_score: *board.continuation_score.get(),
+                    continued_from: board.continued_from.get().clone(),
+                    continued_to: board.continued_to.get().clone(),
+                    total_valid_moves_available: *board.total_valid_moves_available.get(),
+                    moves_with_only_one_option: *board.moves_with_only_one_option.get(),
+                    average_branching_factor: board.average_branching_factor(),
+                    undo_budget: *board.undo_budget.get(),
+                    lives: *board.lives.get(),
+                    current_combo: *board.current_combo.get(),
+                    last_combo_bonus: *board.last_combo_bonus.get(),
+                    total_think_time_micros: *board.total_think_time_micros.get(),
+                    min_move_time_micros: *board.min_move_time_micros.get(),
+                    max_move_time_micros: *board.max_move_time_micros.get(),
+                    avg_move_time_micros: board.avg_move_time_micros(),
                 });
             }
         }
@@ -259,6 +430,8 @@ impl QueryHandler {
                             score: *score,
                             board_id: leaderboard_id.clone(),
                             is_ended: false, // Will be updated later
+                            cap_reached_at: None,
+                            time_to_target_micros: None,
                         },
                     );
                     Ok(())
@@ -288,6 +461,18 @@ impl QueryHandler {
                 .await
                 .unwrap();
 
+            // 🎯 SCORE CAP: When the cap was reached (tiebreaker)
+            leaderboard
+                .cap_reached_at
+                .for_each_index_value(|username, cap_reached_at| {
+                    if let Some(ranker) = players.get_mut(&username) {
+                        ranker.cap_reached_at = Some(*cap_reached_at);
+                    }
+                    Ok(())
+                })
+                .await
+                .unwrap();
+
             // 🚀 Collect active boards currently tracked on the leaderboard
             let mut active_boards: Vec<ActiveBoard> = Vec::new();
             leaderboard
@@ -308,9 +493,21 @@ impl QueryHandler {
             // Sort active boards by score descending for deterministic output
             active_boards.sort_by(|a, b| b.score.cmp(&a.score));
 
-            // 🚀 SORT rankers by score descending (highest first)
-            let mut rankers: Vec<Ranker> = players.into_values().collect();
-            rankers.sort_by(|a, b| b.score.cmp(&a.score));
+            // 🚀 SORT rankers in leaderboard order (speed runs leave out players who
+            // never reached the target tile)
+            let is_speed_run = leaderboard.mode.get().target_tile().is_some();
+            let rankers: Vec<Ranker> = leaderboard
+                .ranked_scores()
+                .await
+                .into_iter()
+                .filter_map(|entry| {
+                    let mut ranker = players.remove(&entry.player)?;
+                    if is_speed_run {
+                        ranker.time_to_target_micros = Some(entry.time_to_target);
+                    }
+                    Some(ranker)
+                })
+                .collect();
 
             // 🚀 PAGINATION: Apply top/offset
             let top = top.unwrap_or(100) as usize; // Default: top 100
@@ -341,6 +538,1347 @@ impl QueryHandler {
         }
     }
 
+    /// 📄 Leaderboard page of up to `first` players (max 100) after the `after` cursor.
+    /// None when the leaderboard does not exist or the cursor is invalid
+    async fn leaderboard_page(
+        &self,
+        leaderboard_id: String,
+        after: Option<String>,
+        first: u32,
+    ) -> Option<LeaderboardPage> {
+        let ranked = self.ranked_scores(&leaderboard_id).await?;
+        let start = match after {
+            Some(after) => LeaderboardCursor::decode(&after)?.resume_index(&ranked),
+            None => 0,
+        };
+        let end = ranked
+            .len()
+            .min(start + first.min(MAX_LEADERBOARD_PAGE_SIZE) as usize);
+
+        let edges: Vec<LeaderboardEdge> = ranked[start..end]
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| {
+                let rank = (start + index) as u32 + 1;
+                LeaderboardEdge {
+                    cursor: LeaderboardCursor {
+                        offset: rank,
+                        last: entry.clone(),
+                    }
+                    .encode(),
+                    rank,
+                    node: LeaderboardEntry {
+                        username: entry.player.clone(),
+                        score: entry.score,
+                    },
+                }
+            })
+            .collect();
+        let page_info = PageInfo {
+            has_next_page: end < ranked.len(),
+            has_previous_page: start > 0,
+            start_cursor: edges.first().map(|edge| edge.cursor.clone()),
+            end_cursor: edges.last().map(|edge| edge.cursor.clone()),
+        };
+        Some(LeaderboardPage {
+            edges,
+            page_info,
+            total_count: ranked.len() as u32,
+        })
+    }
+
+    /// 📸 Score ranking kept at `epoch` (unix seconds / the leaderboard's snapshot interval)
+    async fn leaderboard_snapshot(
+        &self,
+        leaderboard_id: String,
+        epoch: u64,
+    ) -> async_graphql::Result<Vec<LeaderboardEntry>> {
+        let leaderboard = self
+            .state
+            .leaderboards
+            .try_load_entry(&leaderboard_id)
+            .await?
+            .ok_or("Leaderboard not found")?;
+        let snapshot = leaderboard
+            .score_snapshots
+            .get(&epoch)
+            .await?
+            .ok_or_else(|| format!("No leaderboard snapshot at epoch {}", epoch))?;
+        Ok(snapshot
+            .into_iter()
+            .map(|(username, score)| LeaderboardEntry { username, score })
+            .collect())
+    }
+
+    /// 📊 Player, score and activity statistics of a leaderboard (leaderboard chain)
+    async fn analytics_dashboard(&self, leaderboard_id: String) -> Option<AnalyticsDashboard> {
+        let leaderboard = self
+            .state
+            .leaderboards
+            .try_load_entry(&leaderboard_id)
+            .await
+            .ok()??;
+
+        let mut scores: Vec<u64> = Vec::new();
+        let _ = leaderboard
+            .score
+            .for_each_index_value(|_, score| {
+                scores.push(*score);
+                Ok(())
+            })
+            .await;
+        scores.sort_unstable();
+        let avg_score = match scores.len() {
+            0 => 0,
+            count => scores.iter().sum::<u64>() / count as u64,
+        };
+        let median_score = scores.get(scores.len() / 2).copied().unwrap_or(0);
+        let mut score_distribution: Vec<ScoreBucket> = Vec::new();
+        for score in &scores {
+            let min_score = match score {
+                0 => 0,
+                score => 1 << score.ilog2(),
+            };
+            match score_distribution.last_mut() {
+                Some(bucket) if bucket.min_score == min_score => bucket.players += 1,
+                _ => score_distribution.push(ScoreBucket {
+                    min_score,
+                    players: 1,
+                }),
+            }
+        }
+
+        let mut highest_tile_distribution = HashMap::new();
+        let _ = leaderboard
+            .tiles_index
+            .for_each_index_value(|milestone, players| {
+                highest_tile_distribution.insert(milestone, players.len() as u32);
+                Ok(())
+            })
+            .await;
+
+        let mut most_active_hours = leaderboard.games_by_hour.get().clone();
+        most_active_hours.resize(24, 0);
+        let today = self.runtime.system_time().micros() / 86_400_000_000;
+        let mut games_per_day = Vec::with_capacity(30);
+        for day in today.saturating_sub(29)..=today {
+            let games = leaderboard
+                .games_per_day
+                .get(&day)
+                .await
+                .ok()
+                .flatten()
+                .unwrap_or(0);
+            games_per_day.push(DailyGames { day, games });
+        }
+
+        Some(AnalyticsDashboard {
+            total_players: scores.len() as u32,
+            total_games_played: *leaderboard.total_boards.get() as u64,
+            avg_score,
+            median_score,
+            score_distribution,
+            highest_tile_distribution,
+            most_active_hours,
+            games_per_day,
+        })
+    }
+
+    /// 🗓️ Final ranking of an ended season (0-based)
+    async fn season_archive(
+        &self,
+        leaderboard_id: String,
+        season: u8,
+    ) -> async_graphql::Result<Vec<LeaderboardEntry>> {
+        let leaderboard = self
+            .state
+            .leaderboards
+            .try_load_entry(&leaderboard_id)
+            .await?
+            .ok_or("Leaderboard not found")?;
+        let archive = leaderboard
+            .season_archive
+            .get(&season)
+            .await?
+            .ok_or_else(|| format!("Season {} has not ended", season))?;
+        Ok(archive
+            .into_iter()
+            .map(|(username, score)| LeaderboardEntry { username, score })
+            .collect())
+    }
+
+    /// 🗓️ Current season of a leaderboard (None = no seasons, or none started yet)
+    async fn current_season(&self, leaderboard_id: String) -> Option<SeasonInfo> {
+        let leaderboard = self
+            .state
+            .leaderboards
+            .try_load_entry(&leaderboard_id)
+            .await
+            .ok()??;
+        let config = (*leaderboard.season_config.get())?;
+        let started_at = *leaderboard.season_started_at.get();
+        if started_at == 0 {
+            return None;
+        }
+        Some(SeasonInfo {
+            season: *leaderboard.current_season.get(),
+            started_at,
+            ends_at: started_at + config.season_duration_seconds * 1_000_000,
+            max_seasons: config.max_seasons,
+            carry_over_percent: config.carry_over_percent,
+        })
+    }
+
+    /// 📄 Rank of a player on a leaderboard (1 = first place)
+    async fn rank_of(&self, leaderboard_id: String, player: String) -> Option<u32> {
+        let ranked = self.ranked_scores(&leaderboard_id).await?;
+        ranked
+            .iter()
+            .position(|entry| entry.player == player)
+            .map(|index| index as u32 + 1)
+    }
+
+    /// 🏟️ Active tournaments the player has boards in, with their per-tournament stats (player chain)
+    async fn player_tournament_summary(&self, player: String) -> Vec<PlayerTournamentSummary> {
+        let Ok(Some(player_entry)) = self.state.players.try_load_entry(&player).await else {
+            return Vec::new();
+        };
+        let mut board_counts: Vec<(String, u32)> = Vec::new();
+        let _ = player_entry
+            .boards_per_tournament
+            .for_each_index_value(|leaderboard_id, count| {
+                board_counts.push((leaderboard_id, *count));
+                Ok(())
+            })
+            .await;
+
+        let current_time = self.runtime.system_time().micros();
+        let player_record = self
+            .state
+            .player_records
+            .try_load_entry(&player)
+            .await
+            .ok()
+            .flatten();
+        let mut summaries = Vec::new();
+        for (leaderboard_id, board_count) in board_counts {
+            let Some(tournament) = self
+                .state
+                .tournaments_cache_json
+                .get(&leaderboard_id)
+                .await
+                .ok()
+                .flatten()
+                .and_then(|json| serde_json::from_str::<game2048::TournamentInfo>(&json).ok())
+            else {
+                continue;
+            };
+            if !tournament.is_active(current_time) {
+                continue;
+            }
+            let best_score = match &player_record {
+                Some(record) => record
+                    .best_score
+                    .get(&leaderboard_id)
+                    .await
+                    .ok()
+                    .flatten()
+                    .unwrap_or(0),
+                None => 0,
+            };
+            summaries.push(PlayerTournamentSummary {
+                leaderboard_id,
+                name: tournament.name,
+                board_count,
+                best_score,
+                end_time: micros_to_millis(tournament.end_time.unwrap_or(0)),
+            });
+        }
+        summaries
+    }
+
+    /// ⚡ Players by best score per move, most efficient first (ties by name)
+    async fn ranking_by_efficiency(&self, leaderboard_id: String) -> Vec<RankedEntry> {
+        let Ok(Some(leaderboard)) = self
+            .state
+            .leaderboards
+            .try_load_entry(&leaderboard_id)
+            .await
+        else {
+            return Vec::new();
+        };
+        let mut efficiencies: Vec<(String, u64)> = Vec::new();
+        let _ = leaderboard
+            .efficiency
+            .for_each_index_value(|player, efficiency| {
+                efficiencies.push((player, *efficiency));
+                Ok(())
+            })
+            .await;
+        efficiencies.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+        let mut ranking = Vec::with_capacity(efficiencies.len());
+        for (player, efficiency) in efficiencies {
+            let board_id = leaderboard
+                .best_efficiency_board_ids
+                .get(&player)
+                .await
+                .ok()
+                .flatten()
+                .unwrap_or_default();
+            ranking.push(RankedEntry {
+                player,
+                efficiency,
+                board_id,
+            });
+        }
+        ranking
+    }
+
+    /// 🖼️ Leaderboard widget data with every field pre-formatted for display (max 25 rows)
+    /// Services are read-only, so request rate limiting is left to the node/proxy serving the widget
+    async fn leaderboard_embed(
+        &self,
+        leaderboard_id: Option<String>,
+        rows: u32,
+        theme: EmbedTheme,
+    ) -> Option<EmbedData> {
+        let leaderboard_id = leaderboard_id.unwrap_or("".to_string());
+        let leaderboard = self
+            .state
+            .leaderboards
+            .try_load_entry(&leaderboard_id)
+            .await
+            .ok()??;
+
+        let mut scores: Vec<(String, u64)> = Vec::new();
+        leaderboard
+            .score
+            .for_each_index_value(|username, score| {
+                scores.push((username, *score));
+                Ok(())
+            })
+            .await
+            .unwrap();
+        scores.sort_by(|a, b| b.1.cmp(&a.1));
+        scores.truncate(rows.min(25) as usize);
+
+        let mut embed_rows = Vec::new();
+        for (index, (username, score)) in scores.into_iter().enumerate() {
+            let highest_tile = leaderboard
+                .highest_tiles
+                .get(&username)
+                .await
+                .ok()
+                .flatten()
+                .unwrap_or(0);
+            let flag = (index == 0 && theme != EmbedTheme::Minimal).then(|| "⭐".to_string());
+            embed_rows.push(EmbedRow {
+                rank: index as u32 + 1,
+                username,
+                score: format_with_commas(score),
+                highest_tile: format_with_commas(highest_tile),
+                flag,
+            });
+        }
+
+        let current_time = self.runtime.system_time().micros();
+        let end_time = *leaderboard.end_time.get();
+        let ends_in = (end_time > current_time)
+            .then(|| format_duration_short((end_time - current_time) / 1_000_000));
+        let last_update = *leaderboard.leaderboard_last_update.get();
+        let last_updated = format!(
+            "{} ago",
+            format_duration_short(current_time.saturating_sub(last_update) / 1_000_000)
+        );
+
+        Some(EmbedData {
+            rows: embed_rows,
+            tournament_name: leaderboard.name.get().to_string(),
+            theme,
+            ends_in,
+            last_updated,
+        })
+    }
+
+    /// 👣 Player chain interactions (score submissions, predictions, triggers) on this leaderboard chain
+    async fn visitor_count(&self, leaderboard_id: Option<String>) -> Option<VisitorCount> {
+        let leaderboard_id = leaderboard_id.unwrap_or("".to_string());
+        let leaderboard = self
+            .state
+            .leaderboards
+            .try_load_entry(&leaderboard_id)
+            .await
+            .ok()??;
+
+        let mut registers: Vec<(u16, u8)> = Vec::new();
+        leaderboard
+            .visitor_registers
+            .for_each_index_value(|index, rank| {
+                registers.push((index, *rank));
+                Ok(())
+            })
+            .await
+            .unwrap();
+        let unique_visitors = game2048::HyperLogLog::estimate(&registers);
+
+        let today = self.runtime.system_time().micros() / 86_400_000_000;
+        let (day, count) = *leaderboard.daily_visitors.get();
+
+        Some(VisitorCount {
+            unique_visitors,
+            returning_visitors: leaderboard
+                .total_visits
+                .get()
+                .saturating_sub(unique_visitors),
+            today_visitors: if day == today { count } else { 0 },
+        })
+    }
+
+    /// 🔁 Replay leaderboard (leaderboard chain) - empty until the replay is finalized
+    async fn replay_leaderboard(&self, leaderboard_id: Option<String>) -> Vec<ReplayRanking> {
+        let leaderboard_id = leaderboard_id.unwrap_or("".to_string());
+        let Ok(Some(leaderboard)) = self
+            .state
+            .leaderboards
+            .try_load_entry(&leaderboard_id)
+            .await
+        else {
+            return Vec::new();
+        };
+
+        leaderboard
+            .replay_results
+            .get()
+            .iter()
+            .enumerate()
+            .map(|(index, result)| ReplayRanking {
+                rank: index as u32 + 1,
+                username: result.username.clone(),
+                original_score: result.original_score,
+                replay_score: result.replay_score,
+                flagged: result.flagged,
+            })
+            .collect()
+    }
+
+    /// 📊 Resource usage counters for this chain (admins only)
+    async fn resource_usage(
+        &self,
+        admin: String,
+        password_hash: String,
+    ) -> Option<crate::state::ResourceUsage> {
+        let Ok(Some(admin_entry)) = self.state.players.try_load_entry(&admin).await else {
+            return None;
+        };
+        if *admin_entry.password_hash.get() != password_hash || !*admin_entry.is_mod.get() {
+            return None;
+        }
+        Some(self.state.resource_usage.get().clone())
+    }
+
+    /// 🚩 Games flagged as suspected cheats, newest first (admins only, main chain)
+    async fn flagged_games(
+        &self,
+        admin: String,
+        password_hash: String,
+        limit: u32,
+    ) -> Vec<crate::state::FlaggedGameEntry> {
+        let Ok(Some(admin_entry)) = self.state.players.try_load_entry(&admin).await else {
+            return Vec::new();
+        };
+        if *admin_entry.password_hash.get() != password_hash || !*admin_entry.is_mod.get() {
+            return Vec::new();
+        }
+        let mut flagged = Vec::new();
+        let _ = self
+            .state
+            .admin_flags
+            .for_each_index_value(|_, entry| {
+                flagged.push(entry.into_owned());
+                Ok(())
+            })
+            .await;
+        flagged.sort_by(|a, b| b.flagged_at.cmp(&a.flagged_at));
+        flagged.truncate(limit as usize);
+        flagged
+    }
+
+    /// 📮 Shard messages that failed to process, oldest first (admins only, shard chain)
+    async fn dead_letters(
+        &self,
+        admin: String,
+        password_hash: String,
+        limit: u32,
+    ) -> Vec<DeadLetter> {
+        let Ok(Some(admin_entry)) = self.state.players.try_load_entry(&admin).await else {
+            return Vec::new();
+        };
+        if *admin_entry.password_hash.get() != password_hash || !*admin_entry.is_mod.get() {
+            return Vec::new();
+        }
+        let mut entries = Vec::new();
+        let _ = self
+            .state
+            .dead_letter_queue
+            .for_each_index_value(|key, entry| {
+                entries.push((key, entry.into_owned()));
+                Ok(())
+            })
+            .await;
+        entries.sort_by_key(|(_, entry)| entry.first_failure_at);
+        entries
+            .into_iter()
+            .take(limit as usize)
+            .map(|(key, entry)| DeadLetter {
+                key,
+                failure_reason: entry.failure_reason,
+                attempts: entry.attempts,
+                first_failure_at: micros_to_millis(entry.first_failure_at),
+                permanently_failed: entry.permanently_failed,
+            })
+            .collect()
+    }
+
+    /// 🎟️ 1-based place of a player on a full tournament's waitlist (leaderboard chain)
+    async fn waitlist_position(&self, leaderboard_id: String, player: String) -> Option<u32> {
+        let waitlist = self.state.waitlist.get(&leaderboard_id).await.ok()??;
+        waitlist
+            .iter()
+            .position(|(waiting, _)| *waiting == player)
+            .map(|index| index as u32 + 1)
+    }
+
+    /// 👫 The player's friends by their best score in any active tournament, best first
+    /// (player chain)
+    async fn friend_leaderboard(&self, player: String) -> Vec<FriendScore> {
+        if !matches!(
+            self.state.players.try_load_entry(&player).await,
+            Ok(Some(_))
+        ) {
+            return Vec::new();
+        }
+        let mut friends = Vec::new();
+        let _ = self
+            .state
+            .friends
+            .for_each_index(|username| {
+                friends.push(username);
+                Ok(())
+            })
+            .await;
+
+        let current_time = self.runtime.system_time().micros();
+        let mut entries = Vec::new();
+        for username in friends {
+            let scores = self
+                .state
+                .friend_scores
+                .get(&username)
+                .await
+                .ok()
+                .flatten()
+                .unwrap_or_default();
+            let mut entry = FriendScore {
+                username,
+                best_score: 0,
+                leaderboard_id: String::new(),
+            };
+            for (leaderboard_id, best_score) in scores {
+                if best_score <= entry.best_score {
+                    continue;
+                }
+                let is_active = self
+                    .state
+                    .tournaments_cache_json
+                    .get(&leaderboard_id)
+                    .await
+                    .ok()
+                    .flatten()
+                    .and_then(|json| serde_json::from_str::<game2048::TournamentInfo>(&json).ok())
+                    .is_some_and(|tournament| tournament.is_active(current_time));
+                if is_active {
+                    entry.best_score = best_score;
+                    entry.leaderboard_id = leaderboard_id;
+                }
+            }
+            entries.push(entry);
+        }
+        entries.sort_by(|a, b| {
+            b.best_score
+                .cmp(&a.best_score)
+                .then_with(|| a.username.cmp(&b.username))
+        });
+        entries
+    }
+
+    /// 🥊 Bracket matches of a tournament by round (leaderboard chain)
+    async fn bracket(&self, leaderboard_id: Option<String>) -> Vec<game2048::BracketMatchup> {
+        let Ok(Some(leaderboard)) = self
+            .state
+            .leaderboards
+            .try_load_entry(&leaderboard_id.unwrap_or_default())
+            .await
+        else {
+            return Vec::new();
+        };
+        let mut matchups = Vec::new();
+        let _ = leaderboard
+            .bracket_matchups
+            .for_each_index_value(|id, matchup| {
+                matchups.push((id, matchup.into_owned()));
+                Ok(())
+            })
+            .await;
+        matchups.sort_by_key(|(id, _)| game2048::BracketMatchup::parse_id(id));
+        matchups.into_iter().map(|(_, matchup)| matchup).collect()
+    }
+
+    /// 🔄 Pairings of one round of a tournament's round robin (leaderboard chain)
+    async fn round_schedule(&self, leaderboard_id: String, round: u8) -> Vec<game2048::Matchup> {
+        let Ok(Some(leaderboard)) = self.state.leaderboards.try_load_entry("").await else {
+            return Vec::new();
+        };
+        if *leaderboard.leaderboard_id.get() != leaderboard_id {
+            return Vec::new();
+        }
+        leaderboard
+            .rr_schedule
+            .get(&round)
+            .await
+            .ok()
+            .flatten()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(player_a, player_b)| game2048::Matchup {
+                round,
+                player_a,
+                player_b,
+            })
+            .collect()
+    }
+
+    /// 🥊 This chain's player's latest bracket result in a tournament (player chain)
+    async fn bracket_status(&self, leaderboard_id: String) -> Option<crate::state::BracketStatus> {
+        self.state
+            .bracket_status
+            .get(&leaderboard_id)
+            .await
+            .ok()
+            .flatten()
+    }
+
+    /// 👥 Team game hosted on this chain (host player chain)
+    async fn team_game(&self, team_board_id: String) -> Option<crate::state::TeamGame> {
+        self.state
+            .team_games
+            .get(&team_board_id)
+            .await
+            .ok()
+            .flatten()
+    }
+
+    /// 👥 Latest synced board of a team game joined from this chain (member player chain)
+    async fn team_membership(
+        &self,
+        team_board_id: String,
+    ) -> Option<crate::state::TeamBoardMirror> {
+        self.state
+            .team_memberships
+            .get(&team_board_id)
+            .await
+            .ok()
+            .flatten()
+    }
+
+    /// 🤝 Matchmaking queue position and estimated wait (main chain)
+    async fn matchmaking_status(&self, player: String) -> MatchmakingStatus {
+        let player_chain_id = match self.state.players.try_load_entry(&player).await {
+            Ok(Some(entry)) => entry.chain_id.get().clone(),
+            _ => String::new(),
+        };
+        let current_time = self.runtime.system_time().micros();
+
+        let mut waiting = Vec::new();
+        let _ = self
+            .state
+            .matchmaking_pool
+            .for_each_index_value(|player_chain_id, entry| {
+                if current_time < entry.expires_at() {
+                    waiting.push((entry.entered_at, player_chain_id));
+                }
+                Ok(())
+            })
+            .await;
+        waiting.sort();
+        let position = waiting
+            .iter()
+            .position(|(_, waiting_chain_id)| *waiting_chain_id == player_chain_id);
+
+        let average_wait = *self.state.matchmaking_average_wait.get();
+        let estimated_wait_seconds = match position {
+            Some(index) if average_wait > 0 => {
+                let waited = current_time.saturating_sub(waiting[index].0);
+                Some(average_wait.saturating_sub(waited) / 1_000_000)
+            }
+            _ => None,
+        };
+        let matched_with = self
+            .state
+            .matchmaking_matches
+            .get(&player_chain_id)
+            .await
+            .ok()
+            .flatten();
+
+        MatchmakingStatus {
+            in_pool: position.is_some(),
+            queue_position: position.map_or(0, |index| index as u32 + 1),
+            pool_size: waiting.len() as u32,
+            estimated_wait_seconds,
+            matched_with,
+        }
+    }
+
+    /// ⛏️ Proof-of-work difficulty for new boards in a tournament (0 = none)
+    async fn pow_difficulty(&self, leaderboard_id: String) -> u8 {
+        match self
+            .state
+            .leaderboards
+            .try_load_entry(&leaderboard_id)
+            .await
+        {
+            Ok(Some(leaderboard)) => *leaderboard.pow_difficulty.get(),
+            _ => 0,
+        }
+    }
+
+    /// 🎲 Current randomness beacon (main chain: latest round, player chains: cached copy)
+    async fn current_beacon(&self) -> game2048::RandomnessBeacon {
+        self.state.randomness_beacon.get().clone()
+    }
+
+    /// ⚔️ Head-to-head record of two players over archived tournaments
+    /// (one tournament if `leaderboard_id` is set, otherwise all of them)
+    async fn head_to_head_record(
+        &self,
+        player_a: String,
+        player_b: String,
+        leaderboard_id: Option<String>,
+    ) -> game2048::HeadToHeadRecord {
+        let tournaments = self.archived_records(leaderboard_id).await;
+        game2048::HeadToHeadRecord::compute(&tournaments, &player_a, &player_b)
+    }
+
+    /// ⚔️ Head-to-head records among the top N players of archived tournaments, each pair once
+    async fn head_to_head_matrix(
+        &self,
+        leaderboard_id: Option<String>,
+        top_n: u32,
+    ) -> Vec<game2048::HeadToHeadRecord> {
+        let tournaments = self.archived_records(leaderboard_id).await;
+        game2048::HeadToHeadRecord::matrix(&tournaments, top_n)
+    }
+
+    /// 📥 Imported off-chain history for a tournament
+    async fn archived_tournament(
+        &self,
+        leaderboard_id: String,
+    ) -> Option<crate::state::ArchivedTournament> {
+        self.state
+            .archived_tournaments
+            .get(&leaderboard_id)
+            .await
+            .ok()
+            .flatten()
+    }
+
+    /// 🎟️ Players who advanced to this tournament from qualifiers (leaderboard chain)
+    async fn qualified_players(&self, leaderboard_id: Option<String>) -> Vec<QualifiedPlayer> {
+        let Ok(Some(leaderboard)) = self
+            .state
+            .leaderboards
+            .try_load_entry(&leaderboard_id.unwrap_or_default())
+            .await
+        else {
+            return Vec::new();
+        };
+
+        let mut players = Vec::new();
+        let _ = leaderboard
+            .pre_qualified
+            .for_each_index_value(|username, source_leaderboard_id| {
+                players.push(QualifiedPlayer {
+                    username,
+                    source_leaderboard_id: source_leaderboard_id.into_owned(),
+                });
+                Ok(())
+            })
+            .await;
+        players
+    }
+
+    /// 🌿 Distribution of average branching factors across a tournament's boards (nearest-rank percentiles)
+    async fn branching_factor_distribution(
+        &self,
+        leaderboard_id: Option<String>,
+    ) -> Option<BranchingFactorDistribution> {
+        let Ok(Some(leaderboard)) = self
+            .state
+            .leaderboards
+            .try_load_entry(&leaderboard_id.unwrap_or_default())
+            .await
+        else {
+            return None;
+        };
+
+        let mut factors = Vec::new();
+        let _ = leaderboard
+            .branching_factors
+            .for_each_index_value(|_, factor| {
+                factors.push(*factor);
+                Ok(())
+            })
+            .await;
+        factors.sort_by(f64::total_cmp);
+
+        let percentile = |p: usize| match factors.len() {
+            0 => 0.0,
+            len => factors[(len * p).div_ceil(100).max(1) - 1],
+        };
+        Some(BranchingFactorDistribution {
+            games: factors.len() as u32,
+            p10: percentile(10),
+            p25: percentile(25),
+            p50: percentile(50),
+            p75: percentile(75),
+            p90: percentile(90),
+        })
+    }
+
+    /// 🏁 Warmup games submitted to the leaderboard chain (not ranked)
+    async fn warmup_stats(&self, leaderboard_id: Option<String>) -> Option<WarmupStats> {
+        let Ok(Some(leaderboard)) = self
+            .state
+            .leaderboards
+            .try_load_entry(&leaderboard_id.unwrap_or_default())
+            .await
+        else {
+            return None;
+        };
+
+        let mut games_played = 0;
+        let mut top_score = 0;
+        let _ = leaderboard
+            .warmup_scores
+            .for_each_index_value(|_, score| {
+                games_played += 1;
+                top_score = top_score.max(*score);
+                Ok(())
+            })
+            .await;
+
+        let warmup_duration_seconds = *leaderboard.warmup_duration_seconds.get();
+        let start_time = *leaderboard.start_time.get();
+        let current_time = self.runtime.system_time().micros();
+        let in_warmup = warmup_duration_seconds > 0
+            && current_time < start_time
+            && current_time
+                >= start_time.saturating_sub(warmup_duration_seconds as u64 * 1_000_000);
+
+        Some(WarmupStats {
+            warmup_duration_seconds,
+            in_warmup,
+            games_played,
+            players: leaderboard.warmup_players.count().await.unwrap_or(0) as u32,
+            top_score,
+        })
+    }
+
+    /// 🏅 Prize pool per token: configured amount, funded and paid out so far.
+    /// Funding and payouts are tracked on the leaderboard chain.
+    async fn prize_pool_composition(
+        &self,
+        leaderboard_id: Option<String>,
+    ) -> Vec<PrizePoolTokenStatus> {
+        let Ok(Some(leaderboard)) = self
+            .state
+            .leaderboards
+            .try_load_entry(&leaderboard_id.unwrap_or_default())
+            .await
+        else {
+            return Vec::new();
+        };
+        let mut composition = Vec::new();
+        for entry in leaderboard.prize_pool_entries.get().clone() {
+            let funded = leaderboard
+                .prize_pool_funded
+                .get(&entry.application_id)
+                .await
+                .ok()
+                .flatten()
+                .unwrap_or(Amount::ZERO);
+            let distributed = leaderboard
+                .prize_pool_distributed
+                .get(&entry.application_id)
+                .await
+                .ok()
+                .flatten()
+                .unwrap_or(Amount::ZERO);
+            composition.push(PrizePoolTokenStatus {
+                application_id: entry.application_id,
+                pool_amount: entry.amount,
+                payouts: entry.payouts,
+                funded,
+                distributed,
+            });
+        }
+        composition
+    }
+
+    /// 🏆 Players ranked by highest tile (last snapshot from UpdateLeaderboard).
+    /// `tier` keeps only players who reached that tile (e.g. 8192).
+    async fn highest_tile_leaderboard(
+        &self,
+        leaderboard_id: Option<String>,
+        page: Option<u32>,
+        page_size: Option<u32>,
+        tier: Option<u64>,
+    ) -> TileLeaderboardPage {
+        let ranked: Vec<TileRanking> = self
+            .tile_rankings(leaderboard_id.unwrap_or_default())
+            .await
+            .into_iter()
+            .filter(|ranking| tier.is_none_or(|tier| ranking.highest_tile >= tier))
+            .collect();
+
+        let message = if ranked.is_empty() {
+            Some(match tier {
+                Some(tier) => format!("No one has reached {} yet", tier),
+                None => "No one has reached a tile yet".to_string(),
+            })
+        } else {
+            None
+        };
+        let page_size = page_size.unwrap_or(50).clamp(1, 100) as usize;
+        let page = page.unwrap_or(0) as usize;
+        TileLeaderboardPage {
+            total_players: ranked.len() as u32,
+            entries: ranked
+                .into_iter()
+                .skip(page * page_size)
+                .take(page_size)
+                .collect(),
+            message,
+        }
+    }
+
+    /// 🏆 Leaderboard entries of players whose highest tile is at least `min_highest_tile`,
+    /// in score order
+    async fn leaderboard_filtered(
+        &self,
+        leaderboard_id: String,
+        min_highest_tile: u32,
+    ) -> Vec<LeaderboardEntry> {
+        let Ok(Some(leaderboard)) = self
+            .state
+            .leaderboards
+            .try_load_entry(&leaderboard_id)
+            .await
+        else {
+            return Vec::new();
+        };
+        let min_milestone = game2048::tile_milestone(min_highest_tile as u64);
+        let mut candidates = std::collections::HashSet::new();
+        let _ = leaderboard
+            .tiles_index
+            .for_each_index_value(|milestone, players| {
+                if milestone >= min_milestone {
+                    candidates.extend(players.into_owned());
+                }
+                Ok(())
+            })
+            .await;
+        // The lowest bucket can hold tiles below a minimum that is not a power of two
+        let mut qualified = std::collections::HashSet::new();
+        for player in candidates {
+            let highest_tile = leaderboard
+                .highest_tiles
+                .get(&player)
+                .await
+                .ok()
+                .flatten()
+                .unwrap_or(0);
+            if highest_tile >= min_highest_tile as u64 {
+                qualified.insert(player);
+            }
+        }
+        drop(leaderboard);
+
+        self.ranked_scores(&leaderboard_id)
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|entry| qualified.contains(&entry.player))
+            .map(|entry| LeaderboardEntry {
+                username: entry.player,
+                score: entry.score,
+            })
+            .collect()
+    }
+
+    /// 🏆 A player's place on the highest-tile leaderboard
+    async fn my_tile_rank(
+        &self,
+        player: String,
+        leaderboard_id: Option<String>,
+    ) -> Option<TileRanking> {
+        self.tile_rankings(leaderboard_id.unwrap_or_default())
+            .await
+            .into_iter()
+            .find(|ranking| ranking.username == player)
+    }
+
+    /// ⚖️ Leaderboard ranked by effective score (raw score * player handicap)
+    async fn leaderboard_with_handicaps(
+        &self,
+        leaderboard_id: Option<String>,
+    ) -> Vec<HandicapRanking> {
+        let leaderboard_id = leaderboard_id.unwrap_or("".to_string());
+        let Ok(Some(leaderboard)) = self
+            .state
+            .leaderboards
+            .try_load_entry(&leaderboard_id)
+            .await
+        else {
+            return Vec::new();
+        };
+
+        let mut scores: Vec<(String, u64)> = Vec::new();
+        leaderboard
+            .score
+            .for_each_index_value(|username, score| {
+                scores.push((username, *score));
+                Ok(())
+            })
+            .await
+            .unwrap();
+
+        let mut rankings = Vec::new();
+        for (username, raw_score) in scores {
+            let handicap = leaderboard.handicap(&username).await;
+            rankings.push(HandicapRanking {
+                rank: 0,
+                effective_score: game2048::handicapped_score(raw_score, handicap),
+                username,
+                raw_score,
+                handicap,
+            });
+        }
+        rankings.sort_by(|a, b| {
+            b.effective_score
+                .cmp(&a.effective_score)
+                .then_with(|| a.username.cmp(&b.username))
+        });
+        for (index, ranking) in rankings.iter_mut().enumerate() {
+            ranking.rank = index as u32 + 1;
+        }
+        rankings
+    }
+
+    /// 🔮 Spectator prediction leaderboard
+    /// Predictions stay hidden until the tournament ends, then are ranked by
+    /// correct winner first and proximity to the winning score second.
+    /// PredictionWinner is the award the contract settled when finalising the tournament
+    async fn spectator_leaderboard(&self, leaderboard_id: Option<String>) -> Vec<SpectatorRanking> {
+        let leaderboard_id = leaderboard_id.unwrap_or("".to_string());
+        let Ok(Some(leaderboard)) = self
+            .state
+            .leaderboards
+            .try_load_entry(&leaderboard_id)
+            .await
+        else {
+            return Vec::new();
+        };
+
+        let end_time = *leaderboard.end_time.get();
+        let current_time = self.runtime.system_time().micros();
+        if end_time == 0 || current_time < end_time {
+            return Vec::new();
+        }
+
+        // Winner = first of the final ranking once finalised, else the highest score
+        // (ties broken by username for determinism)
+        let winner = if *leaderboard.status.get() == TournamentStatus::Finalised {
+            leaderboard.final_ranking.get().first().cloned()
+        } else {
+            let mut winner: Option<(String, u64)> = None;
+            leaderboard
+                .score
+                .for_each_index_value(|username, score| {
+                    let is_better = match &winner {
+                        Some((best_name, best_score)) => {
+                            *score > *best_score || (*score == *best_score && username < *best_name)
+                        }
+                        None => true,
+                    };
+                    if is_better {
+                        winner = Some((username, *score));
+                    }
+                    Ok(())
+                })
+                .await
+                .unwrap();
+            winner
+        };
+        let Some((winner_name, winner_score)) = winner else {
+            return Vec::new();
+        };
+
+        let mut predictions = Vec::new();
+        leaderboard
+            .predictions
+            .for_each_index_value(|_, prediction| {
+                predictions.push(prediction.into_owned());
+                Ok(())
+            })
+            .await
+            .unwrap();
+
+        let prediction_winner = leaderboard.prediction_winner.get();
+        TournamentPrediction::rank_all(predictions, &winner_name, winner_score)
+            .into_iter()
+            .map(|prediction| SpectatorRanking {
+                correct_winner: prediction.predicted_winner == winner_name,
+                proximity: prediction.proximity(winner_score),
+                submitted_at: micros_to_millis(prediction.submitted_at),
+                achievement: (prediction_winner.as_ref() == Some(&prediction.spectator_chain_id))
+                    .then_some(SpectatorAchievement::PredictionWinner),
+                spectator_chain_id: prediction.spectator_chain_id,
+                predicted_winner: prediction.predicted_winner,
+                predicted_score: prediction.predicted_score,
+            })
+            .collect()
+    }
+
+    /// 📡 Registered live stream endpoints (auth tokens are stored as hashes and never shown)
+    async fn stream_endpoints(&self, leaderboard_id: Option<String>) -> Vec<StreamEndpointInfo> {
+        let leaderboard_id = leaderboard_id.unwrap_or("".to_string());
+        if let Ok(Some(leaderboard)) = self
+            .state
+            .leaderboards
+            .try_load_entry(&leaderboard_id)
+            .await
+        {
+            leaderboard
+                .stream_endpoints
+                .get()
+                .iter()
+                .map(|endpoint| StreamEndpointInfo {
+                    endpoint_url: endpoint.endpoint_url.clone(),
+                    relay_owner: endpoint.relay_owner.clone(),
+                    consecutive_failures: endpoint.consecutive_failures,
+                    registered_at: micros_to_millis(endpoint.registered_at),
+                })
+                .collect()
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// 🔥 Settings that were in effect for a tournament at `timestamp` (milliseconds, main chain)
+    async fn effective_settings(
+        &self,
+        leaderboard_id: String,
+        timestamp: String,
+    ) -> Option<EffectiveSettings> {
+        let timestamp = millis_to_micros(&timestamp).ok()?;
+        let leaderboard = self
+            .state
+            .leaderboards
+            .try_load_entry(&leaderboard_id)
+            .await
+            .ok()??;
+        let history = leaderboard.settings_history.elements().await.ok()?;
+        let version = game2048::effective_settings_at(&history, timestamp)?;
+        let settings = &version.settings;
+        let to_millis = |micros: &str| micros_to_millis(micros.parse::<u64>().unwrap_or(0));
+
+        Some(EffectiveSettings {
+            leaderboard_id,
+            effective_at: micros_to_millis(version.effective_at),
+            name: settings.name.clone(),
+            description: settings.description.clone(),
+            start_time: to_millis(&settings.start_time),
+            end_time: to_millis(&settings.end_time),
+            shard_number: settings.shard_number,
+            base_triggerer_count: settings.base_triggerer_count,
+            entry_min_balance: settings
+                .entry_token_requirement
+                .as_ref()
+                .map(|requirement| requirement.min_balance.to_string()),
+        })
+    }
+
+    /// 🔐 Unexpired multi-sig proposals (main chain, multi-sig admins only)
+    async fn pending_proposals(
+        &self,
+        admin: String,
+        password_hash: String,
+    ) -> Vec<PendingProposal> {
+        let Ok(Some(player)) = self.state.players.try_load_entry(&admin).await else {
+            return Vec::new();
+        };
+        let config = self.state.multisig_config.get();
+        if *player.password_hash.get() != password_hash || !config.admin_list.contains(&admin) {
+            return Vec::new();
+        }
+
+        let current_time = self.runtime.system_time().micros();
+        let mut proposals = Vec::new();
+        self.state
+            .pending_proposals
+            .for_each_index_value(|proposal_id, proposal| {
+                if !proposal.is_expired(current_time) {
+                    let action = match &proposal.action {
+                        game2048::CriticalAdminAction::ToggleAdmin(username) => {
+                            format!("ToggleAdmin({})", username)
+                        }
+                        game2048::CriticalAdminAction::DeleteLeaderboard(leaderboard_id) => {
+                            format!("DeleteLeaderboard({})", leaderboard_id)
+                        }
+                    };
+                    proposals.push(PendingProposal {
+                        proposal_id,
+                        proposer: proposal.proposer.clone(),
+                        action,
+                        approvals: proposal.approvals.clone(),
+                        required_signatures: config.required_signatures,
+                        created_at: micros_to_millis(proposal.created_at),
+                        expires_at: micros_to_millis(proposal.expires_at()),
+                    });
+                }
+                Ok(())
+            })
+            .await
+            .unwrap_or_default();
+        proposals
+    }
+
+    /// 📅 Daily challenge results of `date` (`YYYY-MM-DD`), best first (main chain).
+    /// Only the current day is kept.
+    async fn daily_leaderboard(&self, date: String) -> Vec<game2048::DailyChallengeRecord> {
+        let Ok(Some(leaderboard)) = self.state.leaderboards.try_load_entry("").await else {
+            return Vec::new();
+        };
+        if *leaderboard.daily_leaderboard_date.get() != date {
+            return Vec::new();
+        }
+        let mut records = Vec::new();
+        let _ = leaderboard
+            .daily_leaderboard
+            .for_each_index_value(|_, record| {
+                records.push(record.into_owned());
+                Ok(())
+            })
+            .await;
+        records.sort_by(|a, b| {
+            b.score
+                .cmp(&a.score)
+                .then(a.completed_at.cmp(&b.completed_at))
+        });
+        records
+    }
+
+    /// 🌟 Latest platform highlights received by this subscriber chain (oldest first)
+    async fn platform_highlights(&self) -> Vec<game2048::PlatformHighlight> {
+        self.state
+            .platform_highlights
+            .elements()
+            .await
+            .unwrap_or_default()
+    }
+
+    /// 🌟 Number of chains receiving platform highlights (main chain)
+    async fn platform_subscriber_count(&self) -> u32 {
+        self.state.platform_subscribers.count().await.unwrap_or(0) as u32
+    }
+
+    /// 🛡️ Blocked words for tournament names/descriptions (main chain)
+    async fn blocked_words(&self) -> Vec<String> {
+        self.state.moderation_config.get().blocked_words.clone()
+    }
+
+    /// 🛡️ Moderation override audit log (main chain, oldest first)
+    async fn moderation_audit_log(&self) -> Vec<ModerationAuditRecord> {
+        self.state
+            .moderation_audit_log
+            .elements()
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .map(|entry| ModerationAuditRecord {
+                admin: entry.admin,
+                leaderboard_id: entry.leaderboard_id,
+                violations: entry.violations,
+                timestamp: micros_to_millis(entry.timestamp),
+            })
+            .collect()
+    }
+
+    /// 💡 Latest hint requested for a board
+    async fn hint(&self, board_id: String) -> Option<HintResponse> {
+        let board = self.state.boards.try_load_entry(&board_id).await.ok()??;
+        let hint = board.last_hint.get().clone()?;
+        let hints_used = *board.hints_used.get();
+        Some(HintResponse {
+            direction: hint.direction,
+            hint_score: hint.hint_score,
+            is_current: hint.move_count == *board.move_count.get() && !*board.is_ended.get(),
+            hints_used,
+            hints_remaining: MAX_HINTS_PER_BOARD.saturating_sub(hints_used),
+        })
+    }
+
+    /// 🔁 Move log and replay hash of a board, for sharing and verifying replays
+    async fn board_replay(&self, board_id: String) -> Option<BoardReplay> {
+        let board = self.state.boards.try_load_entry(&board_id).await.ok()??;
+        let replay_hash = *board.replay_hash.get();
+        Some(BoardReplay {
+            board_id,
+            move_log: board.move_log.get().clone(),
+            replay_hash: if replay_hash == [0; 32] {
+                String::new()
+            } else {
+                replay_hash
+                    .iter()
+                    .map(|byte| format!("{:02x}", byte))
+                    .collect()
+            },
+        })
+    }
+
+    /// 🔑 Shard key rotation audit log (main chain, oldest first)
+    async fn shard_key_rotations(&self) -> Vec<ShardKeyRotationRecord> {
+        self.state
+            .shard_key_rotations
+            .elements()
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .map(|entry| ShardKeyRotationRecord {
+                shard_chain_id: entry.shard_chain_id,
+                admin: entry.admin,
+                public_key: entry
+                    .public_key
+                    .iter()
+                    .map(|byte| format!("{:02x}", byte))
+                    .collect(),
+                timestamp: micros_to_millis(entry.timestamp),
+            })
+            .collect()
+    }
+
     /// Query tournaments with optional filtering by time status (defaults to active)
     async fn leaderboards(&self, filter: Option<TournamentFilter>) -> Vec<LeaderboardState> {
         let filter = filter.unwrap_or(TournamentFilter::Active);
@@ -364,9 +1902,52 @@ impl QueryHandler {
             target_size,
             low_threshold,
             needs_replenish,
+            available: pool_size,
+            total_ever_created: *self.state.chain_pool_total_created.get(),
+            claimed: self.state.claimed_chains.count().await.unwrap_or(0) as u32,
+            last_refill_at: *self.state.chain_pool_last_refill_at.get(),
+            min_pool_size: *self.state.chain_pool_min_size.get(),
         }
     }
 
+    /// ♻️ ADMIN: Claimed chains not initialized for at least `older_than_hours`
+    async fn stale_chains(
+        &self,
+        admin: String,
+        password_hash: String,
+        older_than_hours: u32,
+    ) -> Vec<StaleChain> {
+        let Ok(Some(admin_entry)) = self.state.players.try_load_entry(&admin).await else {
+            return Vec::new();
+        };
+        if *admin_entry.password_hash.get() != password_hash || !*admin_entry.is_mod.get() {
+            return Vec::new();
+        }
+
+        let cutoff = self
+            .runtime
+            .system_time()
+            .micros()
+            .saturating_sub(older_than_hours as u64 * 3_600_000_000);
+        let mut stale = Vec::new();
+        let _ = self
+            .state
+            .claimed_chains
+            .for_each_index_value(|chain_id, claim| {
+                if !claim.initialized && claim.claim_timestamp < cutoff {
+                    stale.push(StaleChain {
+                        chain_id,
+                        username: claim.username.clone(),
+                        claim_timestamp: claim.claim_timestamp,
+                    });
+                }
+                Ok(())
+            })
+            .await;
+        stale.sort_by_key(|chain| chain.claim_timestamp);
+        stale
+    }
+
     async fn shards(&self) -> Shard {
         if let Some(shard) = self.state.shards.try_load_entry("").await.unwrap() {
             let mut scores: HashMap<String, u64> = HashMap::new();
@@ -413,6 +1994,90 @@ impl QueryHandler {
 }
 
 impl QueryHandler {
+    /// 📄 Scores of a leaderboard in `RankedScore::rank_all` order (None = no such leaderboard)
+    async fn ranked_scores(&self, leaderboard_id: &str) -> Option<Vec<RankedScore>> {
+        let leaderboard = self
+            .state
+            .leaderboards
+            .try_load_entry(leaderboard_id)
+            .await
+            .ok()??;
+        Some(leaderboard.ranked_scores().await)
+    }
+
+    /// ⚔️ Score records of archived tournaments, computed on demand for head-to-head queries
+    async fn archived_records(
+        &self,
+        leaderboard_id: Option<String>,
+    ) -> Vec<Vec<game2048::HistoricalRecord>> {
+        if let Some(leaderboard_id) = leaderboard_id {
+            return match self.state.archived_tournaments.get(&leaderboard_id).await {
+                Ok(Some(archive)) => vec![archive.records],
+                _ => Vec::new(),
+            };
+        }
+
+        let mut tournaments = Vec::new();
+        let _ = self
+            .state
+            .archived_tournaments
+            .for_each_index_value(|_, archive| {
+                tournaments.push(archive.into_owned().records);
+                Ok(())
+            })
+            .await;
+        tournaments
+    }
+
+    /// 🏆 Highest-tile ranking from the cached snapshot
+    async fn tile_rankings(&self, leaderboard_id: String) -> Vec<TileRanking> {
+        let Ok(Some(leaderboard)) = self
+            .state
+            .leaderboards
+            .try_load_entry(&leaderboard_id)
+            .await
+        else {
+            return Vec::new();
+        };
+        leaderboard
+            .tile_leaderboard_snapshot
+            .get()
+            .iter()
+            .enumerate()
+            .map(|(index, (username, highest_tile))| TileRanking {
+                rank: index as u32 + 1,
+                username: username.clone(),
+                highest_tile: *highest_tile,
+            })
+            .collect()
+    }
+
+    /// 🃏 Merge counts of a board, by tile value
+    async fn load_merge_combinations(board: &crate::state::BoardState) -> Vec<TileMergeCount> {
+        let mut merges = Vec::new();
+        board
+            .merge_combinations
+            .for_each_index_value(|tile, count| {
+                merges.push(TileMergeCount {
+                    tile,
+                    count: *count,
+                    first_achieved_at: 0,
+                });
+                Ok(())
+            })
+            .await
+            .unwrap();
+        for merge in &mut merges {
+            merge.first_achieved_at = board
+                .first_time_tiles
+                .get(&merge.tile)
+                .await
+                .unwrap()
+                .unwrap_or(0);
+        }
+        merges
+    }
+
     /// Helper method to get tournaments filtered by status
     async fn get_tournaments_by_filter(&self, filter: TournamentFilter) -> Vec<LeaderboardState> {
         let mut leaderboard_ids: Vec<String> = Vec::new();
@@ -1,4 +1,5 @@
-use async_graphql::SimpleObject;
+use async_graphql::{Enum, SimpleObject};
+use linera_sdk::linera_base_types::Amount;
 use std::collections::HashMap;
 
 /// Helper function to convert microseconds to milliseconds for GraphQL responses
@@ -24,11 +25,35 @@ pub fn millis_to_micros(millis_str: &str) -> Result<u64, String> {
     }
 }
 
+/// Format an integer with comma thousands separators (1234567 -> "1,234,567")
+pub fn format_with_commas(value: u64) -> String {
+    let digits = value.to_string();
+    let mut formatted = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            formatted.push(',');
+        }
+        formatted.push(digit);
+    }
+    formatted
+}
+
+/// Format a duration in seconds using its largest unit ("45s", "12m", "3h", "2d")
+pub fn format_duration_short(seconds: u64) -> String {
+    match seconds {
+        0..=59 => format!("{}s", seconds),
+        60..=3_599 => format!("{}m", seconds / 60),
+        3_600..=86_399 => format!("{}h", seconds / 3_600),
+        _ => format!("{}d", seconds / 86_400),
+    }
+}
+
 #[derive(SimpleObject)]
 pub struct BoardState {
     pub board_id: String,
     pub board: [[u16; 4]; 4],
     pub is_ended: bool,
+    pub is_deadlocked: bool,
     pub score: u64,
     pub player: String,
     pub chain_id: String,
@@ -45,6 +70,34 @@ pub struct BoardState {
     pub has_more_moves: bool,
     // 🎵 Rhythm mode: which music track was used (-1 = no rhythm/metronome, 0+ = track index)
     pub rhythm_track_index: i16,
+    // 🎲 Generator of the board's spawns, for clients predicting tiles
+    pub spawn_generator: game2048::SpawnGenerator,
+    // 🏷️ Client-defined metadata
+    pub metadata: Vec<game2048::GameMetadataEntry>,
+    // 🃏 Combo card: tiles produced by merges (every tile from 4 to 2048 completes the card)
+    pub merge_combinations: Vec<game2048::TileMergeCount>,
+    pub combo_card_complete: bool,
+    // ⏱️ Game variant ("Classic2048" / "Speed2048(60s)") and speed game deadline (millis, "0" = none)
+    pub variant: String,
+    pub deadline: String,
+    // ↪️ Continuation: carried-over score and linked boards ("" = none)
+    pub continuation_score: u64,
+    pub continued_from: String,
+    pub continued_to: String,
+    // 🌿 Branching factor: valid directions before each move (low = often forced)
+    pub total_valid_moves_available: u64,
+    pub moves_with_only_one_option: u32,
+    pub average_branching_factor: f64,
+    pub undo_budget: u32, // ↩️ Undos left (4294967295 = unlimited)
+    pub lives: u8,        // 💀 Elimination lives left (0 outside elimination games)
+    // 💥 Combo: consecutive moves with merges, and the combo bonus of the latest move
+    pub current_combo: u8,
+    pub last_combo_bonus: u64,
+    // ⏲️ Move timing: block time per move, measured between move batches (micros, min/max 0 = none yet)
+    pub total_think_time_micros: u64,
+    pub min_move_time_micros: u64,
+    pub max_move_time_micros: u64,
+    pub avg_move_time_micros: u64,
 }
 
 #[derive(SimpleObject)]
@@ -53,6 +106,47 @@ pub struct LeaderboardEntry {
     pub score: u64,
 }
 
+/// 🏟️ A player's standing in one active tournament (player chain)
+#[derive(SimpleObject)]
+pub struct PlayerTournamentSummary {
+    pub leaderboard_id: String,
+    pub name: String,
+    pub board_count: u32,
+    pub best_score: u64,
+    pub end_time: String, // milliseconds, "0" = unlimited
+}
+
+/// ⚡ Player's best score per move, and the board it came from
+#[derive(SimpleObject)]
+pub struct RankedEntry {
+    pub player: String,
+    pub efficiency: u64,
+    pub board_id: String,
+}
+
+/// 📄 One page of a leaderboard (`leaderboardPage`)
+#[derive(SimpleObject)]
+pub struct LeaderboardPage {
+    pub edges: Vec<LeaderboardEdge>,
+    pub page_info: PageInfo,
+    pub total_count: u32,
+}
+
+#[derive(SimpleObject)]
+pub struct LeaderboardEdge {
+    pub cursor: String,
+    pub rank: u32, // 1 = first place
+    pub node: LeaderboardEntry,
+}
+
+#[derive(SimpleObject)]
+pub struct PageInfo {
+    pub has_next_page: bool,
+    pub has_previous_page: bool,
+    pub start_cursor: Option<String>,
+    pub end_cursor: Option<String>,
+}
+
 #[derive(SimpleObject)]
 pub struct LeaderboardState {
     pub leaderboard_id: String,
@@ -84,6 +178,8 @@ pub struct Ranker {
     pub score: u64,
     pub board_id: String,
     pub is_ended: bool,
+    pub cap_reached_at: Option<u64>, // Tiebreaker: earlier cap times rank higher
+    pub time_to_target_micros: Option<u64>, // 🏎️ Speed runs: fastest time to the target tile
 }
 
 #[derive(SimpleObject)]
@@ -128,8 +224,287 @@ pub struct MoveHistoryRecord {
 /// 🚀 CHAIN POOL: Status information for monitoring
 #[derive(SimpleObject)]
 pub struct ChainPoolStatus {
-    pub pool_size: u32,      // Current number of unclaimed chains
-    pub target_size: u32,    // Target pool size
-    pub low_threshold: u32,  // Threshold to trigger replenish
-    pub needs_replenish: bool, // True if pool_size < low_threshold
+    pub pool_size: u32,          // Current number of unclaimed chains
+    pub target_size: u32,        // Target pool size
+    pub low_threshold: u32,      // Threshold to trigger replenish
+    pub needs_replenish: bool,   // True if pool_size < low_threshold
+    pub available: u32,          // Same as pool_size
+    pub total_ever_created: u32, // Chains opened by RefillChainPool
+    pub claimed: u32,            // Chains claimed by registered players
+    pub last_refill_at: u64,     // micros, 0 = never refilled
+    pub min_pool_size: u32,      // AggregateScores refills below this (0 = off)
+}
+
+/// 🎟️ Player who advanced to a tournament from a qualifier
+#[derive(SimpleObject)]
+pub struct QualifiedPlayer {
+    pub username: String,
+    pub source_leaderboard_id: String,
+}
+
+/// 🌿 Percentiles of the average branching factor over a tournament's ranked boards
+#[derive(SimpleObject)]
+pub struct BranchingFactorDistribution {
+    pub games: u32,
+    pub p10: f64,
+    pub p25: f64,
+    pub p50: f64,
+    pub p75: f64,
+    pub p90: f64,
+}
+
+/// 🏁 Games played during a tournament's warmup window (not ranked)
+#[derive(SimpleObject)]
+pub struct WarmupStats {
+    pub warmup_duration_seconds: u32,
+    pub in_warmup: bool,
+    pub games_played: u32, // Warmup boards that submitted a score
+    pub players: u32,
+    pub top_score: u64,
+}
+
+/// ♻️ CHAIN POOL: Claimed chain whose player chain never initialized
+#[derive(SimpleObject)]
+pub struct StaleChain {
+    pub chain_id: String,
+    pub username: String,
+    pub claim_timestamp: u64, // micros
+}
+
+/// 🔮 Achievements awarded to spectators
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+pub enum SpectatorAchievement {
+    /// Most accurate predictor of the tournament
+    PredictionWinner,
+}
+
+/// 🔮 Spectator prediction ranking entry (ranked by accuracy once the tournament ends)
+#[derive(SimpleObject)]
+pub struct SpectatorRanking {
+    pub spectator_chain_id: String,
+    pub predicted_winner: String,
+    pub predicted_score: u64,
+    pub correct_winner: bool,
+    pub proximity: f64,       // abs(predicted - actual) / actual (0.0 = exact)
+    pub submitted_at: String, // milliseconds
+    pub achievement: Option<SpectatorAchievement>,
+}
+
+/// 📡 Live stream endpoint (only the auth token's hash is on chain, so none of it is shown)
+#[derive(SimpleObject)]
+pub struct StreamEndpointInfo {
+    pub endpoint_url: String,
+    pub relay_owner: String,
+    pub consecutive_failures: u32,
+    pub registered_at: String, // milliseconds
+}
+
+/// 🔥 Settings version in effect at a given time (for dispute resolution)
+#[derive(SimpleObject)]
+pub struct EffectiveSettings {
+    pub leaderboard_id: String,
+    pub effective_at: String, // milliseconds
+    pub name: String,
+    pub description: Option<String>,
+    pub start_time: String, // milliseconds
+    pub end_time: String,   // milliseconds
+    pub shard_number: Option<u32>,
+    pub base_triggerer_count: Option<u32>,
+    pub entry_min_balance: Option<String>,
+}
+
+/// 🛡️ Moderator override of a failed content check
+#[derive(SimpleObject)]
+pub struct ModerationAuditRecord {
+    pub admin: String,
+    pub leaderboard_id: String,
+    pub violations: Vec<String>,
+    pub timestamp: String, // milliseconds
+}
+
+/// 💡 Latest hint for a board; `is_current` is false once a move was made after it
+#[derive(SimpleObject)]
+pub struct HintResponse {
+    pub direction: game2048::Direction,
+    pub hint_score: u64,
+    pub is_current: bool,
+    pub hints_used: u32,
+    pub hints_remaining: u32,
+}
+
+/// 🔁 Shareable move log of a board (replay hash as hex, empty until the game ends)
+#[derive(SimpleObject)]
+pub struct BoardReplay {
+    pub board_id: String,
+    pub move_log: Vec<u8>,
+    pub replay_hash: String,
+}
+
+/// 🔑 Shard signing key rotation (public key as hex)
+#[derive(SimpleObject)]
+pub struct ShardKeyRotationRecord {
+    pub shard_chain_id: String,
+    pub admin: String,
+    pub public_key: String,
+    pub timestamp: String, // milliseconds
+}
+
+/// 🔐 Pending multi-sig proposal
+#[derive(SimpleObject)]
+pub struct PendingProposal {
+    pub proposal_id: String,
+    pub proposer: String,
+    pub action: String,
+    pub approvals: Vec<String>,
+    pub required_signatures: u8,
+    pub created_at: String, // milliseconds
+    pub expires_at: String, // milliseconds
+}
+
+/// 🖼️ Embed widget theme
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+pub enum EmbedTheme {
+    Light,
+    Dark,
+    /// No decorations (first-place flag omitted)
+    Minimal,
+}
+
+/// 🖼️ Pre-formatted leaderboard row for embedding
+#[derive(SimpleObject)]
+pub struct EmbedRow {
+    pub rank: u32,
+    pub username: String,
+    pub score: String,        // "12,345"
+    pub highest_tile: String, // "2,048"
+    pub flag: Option<String>, // "⭐" for first place
+}
+
+/// 🖼️ Pre-formatted leaderboard widget data
+#[derive(SimpleObject)]
+pub struct EmbedData {
+    pub rows: Vec<EmbedRow>,
+    pub tournament_name: String,
+    pub theme: EmbedTheme,
+    pub ends_in: Option<String>, // "3h" (None = unlimited or ended)
+    pub last_updated: String,    // "45s ago"
+}
+
+/// 👣 Player chain interactions with a leaderboard
+#[derive(SimpleObject)]
+pub struct VisitorCount {
+    pub unique_visitors: u64,    // HyperLogLog estimate (~1% error)
+    pub returning_visitors: u64, // Visits from chains already counted
+    pub today_visitors: u32,     // Visits since 00:00 UTC
+}
+
+/// ⚖️ Leaderboard entry ranked by handicapped (effective) score
+#[derive(SimpleObject)]
+pub struct HandicapRanking {
+    pub rank: u32,
+    pub username: String,
+    pub raw_score: u64,
+    pub handicap: f64, // 1.0 = no handicap
+    pub effective_score: u64,
+}
+
+/// 🤝 A player's place in the matchmaking pool, or the opponent they were matched with
+#[derive(SimpleObject)]
+pub struct MatchmakingStatus {
+    pub in_pool: bool,
+    pub queue_position: u32, // 1 = waiting longest, 0 = not waiting
+    pub pool_size: u32,
+    pub estimated_wait_seconds: Option<u64>, // None until a match has been made
+    pub matched_with: Option<crate::state::MatchmakingMatch>,
+}
+
+/// 🏅 One token of a tournament's prize pool
+#[derive(SimpleObject)]
+pub struct PrizePoolTokenStatus {
+    pub application_id: String,
+    pub pool_amount: Amount,
+    pub payouts: Vec<Amount>, // By rank, starting at 1st
+    pub funded: Amount,
+    pub distributed: Amount,
+}
+
+/// 🏆 Player ranked by highest tile reached
+#[derive(SimpleObject)]
+pub struct TileRanking {
+    pub rank: u32,
+    pub username: String,
+    pub highest_tile: u64,
+}
+
+/// 🏆 One page of the highest-tile leaderboard
+#[derive(SimpleObject)]
+pub struct TileLeaderboardPage {
+    pub entries: Vec<TileRanking>,
+    pub total_players: u32,      // Players at or above the requested tier
+    pub message: Option<String>, // Shown when no one reached the tier
+}
+
+/// 📊 Players whose best score is at least `min_score` and below the next bucket
+#[derive(SimpleObject)]
+pub struct ScoreBucket {
+    pub min_score: u64,
+    pub players: u32,
+}
+
+/// 📊 New boards counted on one day
+#[derive(SimpleObject)]
+pub struct DailyGames {
+    pub day: u64, // unix seconds / 86400
+    pub games: u32,
+}
+
+/// 📊 Aggregate statistics of a leaderboard
+#[derive(SimpleObject)]
+pub struct AnalyticsDashboard {
+    pub total_players: u32,
+    pub total_games_played: u64,
+    pub avg_score: u64,
+    pub median_score: u64,
+    pub score_distribution: Vec<ScoreBucket>, // power-of-two buckets, lowest first
+    pub highest_tile_distribution: HashMap<u32, u32>, // tile milestone -> players
+    pub most_active_hours: Vec<u32>,          // new boards per UTC hour (24 entries)
+    pub games_per_day: Vec<DailyGames>,       // last 30 days, oldest first
+}
+
+/// 🗓️ The season a tournament is in
+#[derive(SimpleObject)]
+pub struct SeasonInfo {
+    pub season: u8, // 0-based
+    pub started_at: u64,
+    pub ends_at: u64,
+    pub max_seasons: u8, // 0 = no limit
+    pub carry_over_percent: u8,
+}
+
+/// 🔁 Replay leaderboard entry (original vs replayed best score)
+#[derive(SimpleObject)]
+pub struct ReplayRanking {
+    pub rank: u32,
+    pub username: String,
+    pub original_score: u64,
+    pub replay_score: u64,
+    pub flagged: bool, // Differs from the original by more than 1%
+}
+
+/// 📮 Failed shard message waiting for an admin retry
+#[derive(SimpleObject)]
+pub struct DeadLetter {
+    pub key: String, // "{chain_id}:{message_nonce}", passed to retryDeadLetter
+    pub failure_reason: String,
+    pub attempts: u8,
+    pub first_failure_at: String, // milliseconds
+    pub permanently_failed: bool,
+}
+
+/// 👫 A friend's best score across the active tournaments
+#[derive(SimpleObject)]
+pub struct FriendScore {
+    pub username: String,
+    pub best_score: u64,
+    pub leaderboard_id: String, // Tournament of the best score (empty = no score yet)
 }
@@ -0,0 +1,46 @@
+use ed25519_dalek::VerifyingKey;
+use linera_sdk::linera_base_types::{AccountOwner, Ed25519PublicKey};
+
+/// Ed25519 keys that authenticate shard score messages.
+///
+/// Key pairs are generated off-chain: chains only ever see the public key. A shard chain signs
+/// its aggregation blocks with the secret key, so the `BulkScoreUpdate` messages it sends carry
+/// the matching owner as authenticated signer, which its leaderboard chain checks against the
+/// registered public key.
+pub struct ShardAuth;
+
+impl ShardAuth {
+    /// Public key from 64 hex characters (None = malformed or not a curve point)
+    pub fn parse_public_key(hex: &str) -> Option<[u8; 32]> {
+        crate::decode_hex_32(hex).filter(|bytes| VerifyingKey::from_bytes(bytes).is_ok())
+    }
+
+    /// Account owner that signs blocks with a shard's key
+    pub fn owner(public_key: &[u8; 32]) -> AccountOwner {
+        AccountOwner::from(Ed25519PublicKey(*public_key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::SigningKey;
+
+    #[test]
+    fn test_public_key_parsing_and_owner() {
+        let public_key = SigningKey::from_bytes(&[7u8; 32])
+            .verifying_key()
+            .to_bytes();
+        let hex: String = public_key.iter().map(|b| format!("{:02x}", b)).collect();
+        assert_eq!(ShardAuth::parse_public_key(&hex), Some(public_key));
+        assert_eq!(ShardAuth::parse_public_key(&hex[..62]), None);
+        assert_eq!(ShardAuth::parse_public_key(&"zz".repeat(32)), None);
+
+        // Each key maps to its own signer
+        let other_key = SigningKey::from_bytes(&[8u8; 32])
+            .verifying_key()
+            .to_bytes();
+        assert_eq!(ShardAuth::owner(&public_key), ShardAuth::owner(&public_key));
+        assert_ne!(ShardAuth::owner(&public_key), ShardAuth::owner(&other_key));
+    }
+}
@@ -0,0 +1,618 @@
+use crate::{lehmer_next, Direction, PowerUpConfig};
+
+/// 🧱 Obstacle tile: never moves or merges, and tiles cannot slide through it
+pub const OBSTACLE_TILE: u8 = 0xFF;
+/// 💣 Bomb tile: slides but never merges; a merge next to it clears its 3x3 neighbourhood
+pub const BOMB_TILE: u8 = 0xFE;
+
+/// Smallest and largest board dimensions `SizedBoard` plays
+pub const MIN_BOARD_DIMENSION: usize = 3;
+pub const MAX_BOARD_DIMENSION: usize = 6;
+
+/// Square board from 3x3 to 6x6, stored as tile exponents row by row from the top-left.
+///
+/// 4x4 boards are packed into a `u64` (16 tiles of 4 bits) and played with the move tables.
+/// The contract plays the other sizes with `SizedBoard`, which runs the same slide and merge
+/// rules and converts to and from the packed form for 4x4.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SizedBoard {
+    pub dimension: usize,
+    pub tiles: Vec<u8>,
+}
+
+impl SizedBoard {
+    /// Creates an empty `dimension` x `dimension` board.
+    /// Panics unless `dimension` is within `MIN_BOARD_DIMENSION..=MAX_BOARD_DIMENSION`.
+    pub fn empty(dimension: usize) -> SizedBoard {
+        assert!(
+            (MIN_BOARD_DIMENSION..=MAX_BOARD_DIMENSION).contains(&dimension),
+            "Unsupported board dimension {dimension}"
+        );
+        SizedBoard {
+            dimension,
+            tiles: vec![0; dimension * dimension],
+        }
+    }
+
+    /// Unpacks a 4x4 `u64` board (top-left tile in the highest nybble).
+    pub fn from_u64(board: u64) -> SizedBoard {
+        let tiles = (0..16)
+            .map(|i| ((board >> (60 - 4 * i)) & 0xF) as u8)
+            .collect();
+        SizedBoard {
+            dimension: 4,
+            tiles,
+        }
+    }
+
+    /// Packs the board into a `u64`, or `None` if it is not 4x4.
+    pub fn to_u64(&self) -> Option<u64> {
+        if self.dimension != 4 {
+            return None;
+        }
+        Some(self.tiles.iter().enumerate().fold(0, |board, (i, &tile)| {
+            board | (u64::from(tile & 0xF) << (60 - 4 * i))
+        }))
+    }
+
+    /// Slides a line of tile exponents towards index 0, merging each pair once.
+    /// Returns the score gained (`2 << merged_exponent` per merge; two 0xF tiles
+    /// stay 0xF and score nothing, matching the move tables).
+    ///
+    /// ```
+    /// use game2048::SizedBoard;
+    ///
+    /// let mut line = [0, 1, 1, 2, 2];
+    /// assert_eq!(SizedBoard::slide_row(&mut line), 8 + 16);
+    /// assert_eq!(line, [2, 3, 0, 0, 0]);
+    /// ```
+    ///
+    /// `OBSTACLE_TILE`s stay in place and split the line: tiles slide within each segment.
+    /// `BOMB_TILE`s slide like tiles but never merge.
+    pub fn slide_row(line: &mut [u8]) -> u64 {
+        Self::slide_line(line).0
+    }
+
+    /// `slide_row`, also returning the line index of each merged tile
+    fn slide_line(line: &mut [u8]) -> (u64, Vec<usize>) {
+        let mut score = 0;
+        let mut merges = Vec::new();
+        let mut start = 0;
+        while start < line.len() {
+            if line[start] == OBSTACLE_TILE {
+                start += 1;
+                continue;
+            }
+            let end = line[start..]
+                .iter()
+                .position(|&tile| tile == OBSTACLE_TILE)
+                .map_or(line.len(), |offset| start + offset);
+            score += Self::slide_segment(&mut line[start..end], start, &mut merges);
+            start = end;
+        }
+        (score, merges)
+    }
+
+    fn slide_segment(segment: &mut [u8], offset: usize, merges: &mut Vec<usize>) -> u64 {
+        let tiles: Vec<u8> = segment.iter().copied().filter(|&tile| tile != 0).collect();
+        let mut result = Vec::with_capacity(segment.len());
+        let mut score = 0;
+        let mut i = 0;
+        while i < tiles.len() {
+            if i + 1 < tiles.len() && tiles[i] == tiles[i + 1] && tiles[i] != BOMB_TILE {
+                if tiles[i] < 0xF {
+                    score += 2 << (tiles[i] + 1);
+                }
+                merges.push(offset + result.len());
+                result.push((tiles[i] + 1).min(0xF));
+                i += 2;
+            } else {
+                result.push(tiles[i]);
+                i += 1;
+            }
+        }
+        result.resize(segment.len(), 0);
+        segment.copy_from_slice(&result);
+        score
+    }
+
+    /// Slides one column towards the top (`towards_top`) or bottom. Returns the score gained.
+    pub fn slide_col(&mut self, col: usize, towards_top: bool) -> u64 {
+        let mut merges = Vec::new();
+        let score = self.slide_col_merges(col, towards_top, &mut merges);
+        self.detonate_bombs(&merges);
+        score
+    }
+
+    fn slide_col_merges(&mut self, col: usize, towards_top: bool, merges: &mut Vec<usize>) -> u64 {
+        let dimension = self.dimension;
+        let mut indices: Vec<usize> = (0..dimension).map(|row| row * dimension + col).collect();
+        if !towards_top {
+            indices.reverse();
+        }
+        self.slide_indices(&indices, merges)
+    }
+
+    /// Applies a move without spawning a tile. Returns the score gained.
+    pub fn slide(&mut self, direction: Direction) -> u64 {
+        let dimension = self.dimension;
+        let mut merges = Vec::new();
+        let score = (0..dimension)
+            .map(|line| match direction {
+                Direction::Up => self.slide_col_merges(line, true, &mut merges),
+                Direction::Down => self.slide_col_merges(line, false, &mut merges),
+                Direction::Left | Direction::Right => {
+                    let mut indices: Vec<usize> =
+                        (0..dimension).map(|col| line * dimension + col).collect();
+                    if direction == Direction::Right {
+                        indices.reverse();
+                    }
+                    self.slide_indices(&indices, &mut merges)
+                }
+            })
+            .sum();
+        self.detonate_bombs(&merges);
+        score
+    }
+
+    fn slide_indices(&mut self, indices: &[usize], merges: &mut Vec<usize>) -> u64 {
+        let mut line: Vec<u8> = indices.iter().map(|&i| self.tiles[i]).collect();
+        let (score, line_merges) = Self::slide_line(&mut line);
+        merges.extend(line_merges.into_iter().map(|position| indices[position]));
+        for (&i, tile) in indices.iter().zip(line) {
+            self.tiles[i] = tile;
+        }
+        score
+    }
+
+    /// Indices of the cells at most one row and one column away from `index`, itself included
+    fn neighbourhood(&self, index: usize) -> impl Iterator<Item = usize> {
+        let dimension = self.dimension;
+        let (row, col) = (index / dimension, index % dimension);
+        (row.saturating_sub(1)..(row + 2).min(dimension)).flat_map(move |r| {
+            (col.saturating_sub(1)..(col + 2).min(dimension)).map(move |c| r * dimension + c)
+        })
+    }
+
+    /// 💣 Bombs next to a merged tile clear their 3x3 neighbourhood (obstacles remain)
+    fn detonate_bombs(&mut self, merges: &[usize]) {
+        if merges.is_empty() {
+            return;
+        }
+        let detonated: Vec<usize> = (0..self.tiles.len())
+            .filter(|&index| self.tiles[index] == BOMB_TILE)
+            .filter(|&index| {
+                self.neighbourhood(index)
+                    .any(|neighbour| merges.contains(&neighbour))
+            })
+            .collect();
+        for bomb in detonated {
+            let cleared: Vec<usize> = self.neighbourhood(bomb).collect();
+            for index in cleared {
+                if self.tiles[index] != OBSTACLE_TILE {
+                    self.tiles[index] = 0;
+                }
+            }
+        }
+    }
+
+    /// Rolls `config`'s chances and places an obstacle or a bomb on a random empty cell.
+    /// Returns the cell, or `None` when nothing was placed.
+    pub fn place_power_up(&mut self, config: &PowerUpConfig, state: &mut u64) -> Option<usize> {
+        let roll = (lehmer_next(state) % 100) as u8;
+        let tile = if roll < config.obstacle_tile_chance_percent {
+            OBSTACLE_TILE
+        } else if roll < config.obstacle_tile_chance_percent + config.bomb_tile_chance_percent {
+            BOMB_TILE
+        } else {
+            return None;
+        };
+        let empty: Vec<usize> = (0..self.tiles.len())
+            .filter(|&index| self.tiles[index] == 0)
+            .collect();
+        if empty.is_empty() {
+            return None;
+        }
+        let cell = empty[lehmer_next(state) as usize % empty.len()];
+        self.tiles[cell] = tile;
+        Some(cell)
+    }
+
+    /// Places a 2 (90%) or a 4 (10%) on a random empty cell, like the 4x4 spawns.
+    /// Returns the cell, or `None` when the board is full.
+    pub fn spawn_tile(&mut self, state: &mut u64) -> Option<usize> {
+        let empty: Vec<usize> = (0..self.tiles.len())
+            .filter(|&index| self.tiles[index] == 0)
+            .collect();
+        if empty.is_empty() {
+            return None;
+        }
+        let cell = empty[lehmer_next(state) as usize % empty.len()];
+        self.tiles[cell] = if lehmer_next(state) % 10 == 0 { 2 } else { 1 };
+        Some(cell)
+    }
+
+    /// Value of the highest numbered tile (0 on an empty board)
+    pub fn highest_tile(&self) -> u64 {
+        self.tiles
+            .iter()
+            .filter(|&&tile| tile > 0 && tile <= 0xF)
+            .map(|&tile| 1u64 << tile)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Returns true when no direction changes the board.
+    pub fn is_ended(&self) -> bool {
+        [
+            Direction::Up,
+            Direction::Down,
+            Direction::Left,
+            Direction::Right,
+        ]
+        .into_iter()
+        .all(|direction| {
+            let mut board = self.clone();
+            board.slide(direction);
+            board == *self
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Game, Moves};
+
+    fn xorshift(state: &mut u64) -> u64 {
+        *state ^= *state << 13;
+        *state ^= *state >> 7;
+        *state ^= *state << 17;
+        *state
+    }
+
+    /// Random board with exponents up to 10, so merges never saturate at 0xF.
+    fn random_board(dimension: usize, state: &mut u64) -> SizedBoard {
+        let mut board = SizedBoard::empty(dimension);
+        for tile in board.tiles.iter_mut() {
+            let roll = xorshift(state) % 16;
+            // Mostly small exponents so that merges are common
+            *tile = if roll < 4 {
+                0
+            } else {
+                (roll % 4 + xorshift(state) % 8 / 7 * 7) as u8
+            };
+        }
+        board
+    }
+
+    fn total_value(board: &SizedBoard) -> u64 {
+        board
+            .tiles
+            .iter()
+            .filter(|&&tile| tile > 0)
+            .map(|&tile| 1u64 << tile)
+            .sum()
+    }
+
+    fn mirrored(board: &SizedBoard) -> SizedBoard {
+        let dimension = board.dimension;
+        let mut result = board.clone();
+        for row in 0..dimension {
+            for col in 0..dimension {
+                result.tiles[row * dimension + col] =
+                    board.tiles[row * dimension + dimension - 1 - col];
+            }
+        }
+        result
+    }
+
+    fn transposed(board: &SizedBoard) -> SizedBoard {
+        let dimension = board.dimension;
+        let mut result = board.clone();
+        for row in 0..dimension {
+            for col in 0..dimension {
+                result.tiles[row * dimension + col] = board.tiles[col * dimension + row];
+            }
+        }
+        result
+    }
+
+    #[test]
+    fn test_four_by_four_matches_move_tables() {
+        let mut state = 0x2048_2048_2048_2048;
+        for _ in 0..500 {
+            let board = random_board(4, &mut state);
+            let packed = board.to_u64().unwrap();
+            assert_eq!(SizedBoard::from_u64(packed), board);
+            for (direction, (new_board, score_gained)) in Moves::DIRECTIONS
+                .into_iter()
+                .zip(Moves::apply_all_directions(packed))
+            {
+                let mut sized = board.clone();
+                assert_eq!(sized.slide(direction), score_gained);
+                assert_eq!(sized.to_u64(), Some(new_board));
+            }
+            assert_eq!(board.is_ended(), Game::is_ended(packed));
+        }
+    }
+
+    #[test]
+    fn test_slide_properties_for_all_sizes() {
+        let mut state = 0x1357_9BDF_0246_8ACE;
+        for dimension in MIN_BOARD_DIMENSION..=MAX_BOARD_DIMENSION {
+            for _ in 0..200 {
+                let board = random_board(dimension, &mut state);
+                for direction in Moves::DIRECTIONS {
+                    let mut moved = board.clone();
+                    moved.slide(direction);
+                    assert_eq!(total_value(&moved), total_value(&board));
+                }
+
+                // After sliding left, no empty cell sits before a tile in any row
+                let mut left = board.clone();
+                left.slide(Direction::Left);
+                for row in left.tiles.chunks(dimension) {
+                    let first_empty = row.iter().position(|&tile| tile == 0).unwrap_or(row.len());
+                    assert!(row[first_empty..].iter().all(|&tile| tile == 0));
+                }
+
+                let mut right = board.clone();
+                let right_score = right.slide(Direction::Right);
+                let mut left = mirrored(&board);
+                assert_eq!(left.slide(Direction::Left), right_score);
+                assert_eq!(mirrored(&left), right);
+
+                let mut up = board.clone();
+                let up_score = up.slide(Direction::Up);
+                let mut left = transposed(&board);
+                assert_eq!(left.slide(Direction::Left), up_score);
+                assert_eq!(transposed(&left), up);
+            }
+        }
+    }
+
+    const O: u8 = OBSTACLE_TILE;
+    const B: u8 = BOMB_TILE;
+
+    fn four_by_four(tiles: [u8; 16]) -> SizedBoard {
+        SizedBoard {
+            dimension: 4,
+            tiles: tiles.to_vec(),
+        }
+    }
+
+    fn slid(line: &[u8]) -> (Vec<u8>, u64) {
+        let mut line = line.to_vec();
+        let score = SizedBoard::slide_row(&mut line);
+        (line, score)
+    }
+
+    #[test]
+    fn test_obstacle_stops_sliding_tiles() {
+        assert_eq!(slid(&[0, O, 0, 1]), (vec![0, O, 1, 0], 0));
+    }
+
+    #[test]
+    fn test_obstacle_blocks_merges_through_it() {
+        assert_eq!(slid(&[1, O, 1, 0]), (vec![1, O, 1, 0], 0));
+    }
+
+    #[test]
+    fn test_tiles_merge_within_a_segment() {
+        assert_eq!(slid(&[0, 1, 1, O]), (vec![2, 0, 0, O], 8));
+        assert_eq!(slid(&[O, 0, 1, 1]), (vec![O, 2, 0, 0], 8));
+    }
+
+    #[test]
+    fn test_adjacent_obstacles_never_move() {
+        assert_eq!(slid(&[O, O, 1, 0]), (vec![O, O, 1, 0], 0));
+        assert_eq!(slid(&[O, O, O, O]), (vec![O, O, O, O], 0));
+    }
+
+    #[test]
+    fn test_obstacles_never_merge_with_each_other() {
+        let mut board = four_by_four([O; 16]);
+        assert_eq!(board.slide(Direction::Left), 0);
+        assert_eq!(board.tiles, vec![O; 16]);
+        assert!(board.is_ended());
+    }
+
+    #[test]
+    fn test_obstacle_blocks_sliding_right() {
+        let mut board = four_by_four([0, O, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        board.slide(Direction::Right);
+        assert_eq!(&board.tiles[0..4], &[0, O, 0, 1]);
+    }
+
+    #[test]
+    fn test_obstacle_blocks_sliding_up() {
+        let mut board = four_by_four([0, 0, 0, 0, O, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0]);
+        board.slide(Direction::Up);
+        assert_eq!(board.tiles[0], 0);
+        assert_eq!(board.tiles[4], O);
+        assert_eq!(board.tiles[8], 1);
+    }
+
+    #[test]
+    fn test_obstacle_in_column_keeps_segments_apart() {
+        let mut board = four_by_four([1, 0, 0, 0, 0, 0, 0, 0, O, 0, 0, 0, 1, 0, 0, 0]);
+        assert_eq!(board.slide_col(0, false), 0);
+        assert_eq!(
+            [
+                board.tiles[0],
+                board.tiles[4],
+                board.tiles[8],
+                board.tiles[12]
+            ],
+            [0, 1, O, 1]
+        );
+    }
+
+    #[test]
+    fn test_board_with_obstacles_is_not_ended_while_a_tile_can_move() {
+        let mut tiles = [O; 16];
+        tiles[0] = 1;
+        tiles[1] = 0;
+        assert!(!four_by_four(tiles).is_ended());
+        tiles[1] = 2;
+        assert!(four_by_four(tiles).is_ended());
+    }
+
+    #[test]
+    fn test_bomb_slides_like_a_tile() {
+        assert_eq!(slid(&[0, 0, B, 0]), (vec![B, 0, 0, 0], 0));
+    }
+
+    #[test]
+    fn test_bombs_never_merge() {
+        assert_eq!(slid(&[B, B, 0, 0]), (vec![B, B, 0, 0], 0));
+        assert_eq!(slid(&[1, B, 1, 0]), (vec![1, B, 1, 0], 0));
+    }
+
+    #[test]
+    fn test_bomb_stops_at_an_obstacle() {
+        assert_eq!(slid(&[0, O, 0, B]), (vec![0, O, B, 0], 0));
+    }
+
+    #[test]
+    fn test_merge_next_to_bomb_clears_its_neighbourhood() {
+        let mut board = four_by_four([1, 1, B, 0, 0, 0, 3, 0, 5, 0, 0, 0, 0, 0, 0, 0]);
+        // The merge still scores before the bomb clears it
+        assert_eq!(board.slide(Direction::Left), 8);
+        assert_eq!(&board.tiles[0..8], &[0; 8]);
+        assert_eq!(board.tiles[8], 5);
+    }
+
+    #[test]
+    fn test_bomb_ignores_distant_merges() {
+        let mut board = four_by_four([B, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 0, 0]);
+        board.slide(Direction::Left);
+        assert_eq!(board.tiles[0], B);
+        assert_eq!(board.tiles[12], 2);
+    }
+
+    #[test]
+    fn test_diagonal_merge_detonates_bomb() {
+        let mut board = four_by_four([0, 1, 1, 0, 2, B, 0, 0, 0, 0, 0, 0, 4, 0, 0, 0]);
+        board.slide(Direction::Left);
+        // The merge lands in row 0, column 0; the bomb stays in row 1, column 1
+        assert_eq!(&board.tiles[0..2], &[0, 0]);
+        assert_eq!(&board.tiles[4..6], &[0, 0]);
+        assert_eq!(board.tiles[12], 4);
+    }
+
+    #[test]
+    fn test_detonation_keeps_obstacles() {
+        let mut board = four_by_four([1, 1, B, O, 0, 0, O, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        board.slide(Direction::Left);
+        assert_eq!(&board.tiles[0..4], &[0, 0, 0, O]);
+        assert_eq!(board.tiles[6], O);
+    }
+
+    #[test]
+    fn test_corner_bomb_clears_only_cells_on_the_board() {
+        let mut board = four_by_four([B, 1, 1, 0, 2, 0, 0, 0, 3, 0, 0, 0, 0, 0, 0, 0]);
+        board.slide(Direction::Left);
+        assert_eq!(&board.tiles[0..3], &[0, 0, 0]);
+        assert_eq!(&board.tiles[4..6], &[0, 0]);
+        assert_eq!(board.tiles[8], 3);
+    }
+
+    #[test]
+    fn test_every_bomb_next_to_a_merge_detonates() {
+        let mut board = four_by_four([1, 1, B, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2, 2, B, 0]);
+        board.slide(Direction::Left);
+        assert!(board.tiles.iter().all(|&tile| tile == 0));
+    }
+
+    #[test]
+    fn test_bomb_without_merge_survives_a_move() {
+        let mut board = four_by_four([0, 1, 0, B, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        board.slide(Direction::Left);
+        assert_eq!(&board.tiles[0..4], &[1, B, 0, 0]);
+    }
+
+    #[test]
+    fn test_column_merge_detonates_bomb() {
+        let mut board = four_by_four([B, 0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(board.slide_col(0, true), 8);
+        assert!(board.tiles.iter().all(|&tile| tile == 0));
+    }
+
+    #[test]
+    fn test_no_power_up_without_chances() {
+        let config = PowerUpConfig {
+            obstacle_tile_chance_percent: 0,
+            bomb_tile_chance_percent: 0,
+        };
+        let mut board = SizedBoard::empty(4);
+        let mut state = 42;
+        for _ in 0..100 {
+            assert_eq!(board.place_power_up(&config, &mut state), None);
+        }
+        assert!(board.tiles.iter().all(|&tile| tile == 0));
+    }
+
+    #[test]
+    fn test_power_ups_land_on_empty_cells_only() {
+        let config = PowerUpConfig {
+            obstacle_tile_chance_percent: 100,
+            bomb_tile_chance_percent: 0,
+        };
+        let mut board = four_by_four([1; 16]);
+        board.tiles[9] = 0;
+        let mut state = 7;
+        assert_eq!(board.place_power_up(&config, &mut state), Some(9));
+        assert_eq!(board.tiles[9], O);
+        assert_eq!(board.place_power_up(&config, &mut state), None);
+    }
+
+    #[test]
+    fn test_bomb_chance_places_bombs() {
+        let config = PowerUpConfig {
+            obstacle_tile_chance_percent: 0,
+            bomb_tile_chance_percent: 100,
+        };
+        let mut board = SizedBoard::empty(3);
+        let mut state = 11;
+        let cell = board.place_power_up(&config, &mut state).unwrap();
+        assert_eq!(board.tiles[cell], B);
+        assert_eq!(board.tiles.iter().filter(|&&tile| tile != 0).count(), 1);
+    }
+
+    #[test]
+    fn test_power_up_placement_is_deterministic() {
+        let config = PowerUpConfig {
+            obstacle_tile_chance_percent: 10,
+            bomb_tile_chance_percent: 10,
+        };
+        let place_all = |mut state: u64| {
+            let mut board = SizedBoard::empty(6);
+            for _ in 0..50 {
+                board.place_power_up(&config, &mut state);
+            }
+            board
+        };
+        assert_eq!(place_all(2048), place_all(2048));
+        let placed = place_all(2048)
+            .tiles
+            .iter()
+            .filter(|&&tile| tile != 0)
+            .count();
+        assert!(placed > 0 && placed < 36);
+    }
+
+    #[test]
+    fn test_spawned_tiles_fill_every_empty_cell() {
+        let mut board = SizedBoard::empty(5);
+        let mut state = 2048;
+        for _ in 0..25 {
+            assert!(board.spawn_tile(&mut state).is_some());
+        }
+        assert!(board.tiles.iter().all(|&tile| tile == 1 || tile == 2));
+        assert_eq!(board.spawn_tile(&mut state), None);
+        assert!(board.highest_tile() == 2 || board.highest_tile() == 4);
+        assert_eq!(SizedBoard::empty(3).highest_tile(), 0);
+    }
+}
@@ -1,9 +1,11 @@
 use async_graphql::{scalar, SimpleObject};
+use linera_sdk::linera_base_types::{Amount, ChainId};
 use linera_sdk::views::{
-    linera_views, CollectionView, MapView, QueueView, RegisterView, RootView, View,
+    linera_views, CollectionView, MapView, QueueView, RegisterView, RootView, SetView, View,
     ViewStorageContext,
 };
 use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
 
 #[derive(Debug, Default, Deserialize, Serialize, Clone)]
 pub enum GameStatus {
@@ -40,6 +42,10 @@ pub struct Player {
     pub chain_id: RegisterView<String>,
     pub is_mod: RegisterView<bool>,
     pub boards_per_tournament: MapView<String, u32>, // tournament_id -> board_count
+    pub tournament_streak: RegisterView<u32>, // 🔥 Consecutive ended tournaments with a scoring game
+    pub skill_rating: RegisterView<u32>, // 🤝 Matchmaking rating (0 = unrated, uses DEFAULT_SKILL_RATING)
+    pub top_boards: RegisterView<Vec<game2048::BoardSummary>>, // 🏅 Best ended games, best first
+    pub last_board_creation: MapView<String, u64>, // 🕒 tournament_id -> last NewBoard time (micros)
 }
 
 #[derive(View, SimpleObject)]
@@ -59,19 +65,101 @@ pub struct BoardState {
     pub move_history: CollectionView<u32, MoveRecord>, // move_index -> MoveRecord
     pub move_count: RegisterView<u32>,                 // Total number of moves made
     // 🔒 DUPLICATE PREVENTION: Track last processed move timestamp
-    pub last_processed_timestamp: RegisterView<u64>,  // Last move timestamp processed (for duplicate detection)
+    pub last_processed_timestamp: RegisterView<u64>, // Last move timestamp processed (for duplicate detection)
+    pub is_deadlocked: RegisterView<bool>,           // Cached: board ended with no valid moves
+    pub null_move_count: RegisterView<u32>, // Submitted moves that did not change the board (anti-cheat)
+    pub spawn_2_count: RegisterView<u32>,   // Tiles spawned by moves (anti-cheat spawn ratio)
+    pub spawn_4_count: RegisterView<u32>,
+    // 🌿 Branching factor: valid directions on the board before each move
+    pub total_valid_moves_available: RegisterView<u64>,
+    pub moves_with_only_one_option: RegisterView<u32>, // Forced moves
+    // ⏲️ Move timing: block time per move, measured between move batches (micros, min/max 0 = none yet)
+    pub total_think_time_micros: RegisterView<u64>,
+    pub min_move_time_micros: RegisterView<u64>,
+    pub max_move_time_micros: RegisterView<u64>,
+    #[graphql(skip)]
+    pub last_move_batch_time: RegisterView<u64>, // Block time of the latest move batch (0 = unknown)
     // 🎵 Rhythm mode: which music track was used (-1 = no rhythm/metronome, 0+ = track index)
     pub rhythm_track_index: RegisterView<i16>,
+    pub bonus_score: RegisterView<u64>, // ⏰ Extra points earned inside bonus windows
+    // ↪️ Continuation: score carried over from a previous board, and the boards on either side
+    pub continuation_score: RegisterView<u64>,
+    pub is_warmup: RegisterView<bool>, // 🏁 Created during the warmup window (not ranked)
+    pub continued_from: RegisterView<String>,
+    pub continued_to: RegisterView<String>,
+    pub board_size: RegisterView<game2048::BoardSize>,
+    // 🔲 Tiles of 3x3, 5x5 and 6x6 boards as `SizedBoard` exponents (empty for 4x4, which uses `board`)
+    pub sized_tiles: RegisterView<Vec<u8>>,
+    // 🔁 Shareable replay: encoded directions, sealed with a hash when the game ends
+    #[graphql(skip)]
+    pub move_log: RegisterView<Vec<u8>>,
+    #[graphql(skip)]
+    pub replay_hash: RegisterView<[u8; 32]>, // all zero until the game ends
+    // ↩️ Undo: remaining undos (UNLIMITED_UNDOS outside tournaments) and recent states, oldest first
+    pub undo_budget: RegisterView<u32>,
+    #[graphql(skip)]
+    pub undo_history: RegisterView<VecDeque<UndoSnapshot>>,
+    #[graphql(skip)]
+    pub undo_history_size: RegisterView<u32>,
+    // 💡 Hints: requests so far (max MAX_HINTS_PER_BOARD) and the latest suggestion
+    pub hints_used: RegisterView<u32>,
+    pub last_hint: RegisterView<Option<game2048::MoveHint>>,
+    // 🃏 Combo card: tiles produced by merges
+    #[graphql(skip)]
+    pub merge_combinations: MapView<u32, u32>, // tile value -> times produced by a merge
+    #[graphql(skip)]
+    pub first_time_tiles: MapView<u32, u64>, // tile value -> move timestamp first produced (ms)
+    // ⏱️ Game variant; speed games reject moves after `deadline` (micros, 0 = none)
+    #[graphql(skip)]
+    pub variant: RegisterView<game2048::GameVariant>,
+    pub deadline: RegisterView<u64>,
+    // 💀 Elimination: lives left; a deadlock with lives left restarts the board
+    pub lives: RegisterView<u8>,
+    // 🎲 Tile spawn weights from the tournament (empty = classic)
+    #[graphql(skip)]
+    pub tile_weights: RegisterView<Vec<game2048::TileWeight>>,
+    // 🎲 Generator of the board's spawns (boards from before it was recorded read `Hashed`)
+    pub spawn_generator: RegisterView<game2048::SpawnGenerator>,
+    // 💥 Combo: consecutive moves with merges, and the combo bonus of the latest move
+    pub current_combo: RegisterView<u8>,
+    pub last_combo_bonus: RegisterView<u64>,
+    // 🏷️ Client-defined metadata (max 5 entries)
+    #[graphql(skip)]
+    pub metadata: RegisterView<Vec<game2048::GameMetadataEntry>>,
     // 🚀 Hybrid score submission optimization
-    pub highest_tile_sent: RegisterView<u64>,    // Highest tile we've sent to leaderboard
+    pub highest_tile_sent: RegisterView<u64>, // Highest tile we've sent to leaderboard
     pub last_score_sent_time: RegisterView<u64>, // Timestamp of last score submission (micros)
     // 🚀 Debounced score submission - reduce message spam
-    pub tile_milestone_send_after: RegisterView<u64>,    // Send tile milestone after this time (micros)
+    pub tile_milestone_send_after: RegisterView<u64>, // Send tile milestone after this time (micros)
     pub score_improvement_send_after: RegisterView<u64>, // Send score improvement after this time (micros)
     pub pending_tile_milestone: RegisterView<u64>,       // Pending highest tile to send (0 = none)
     pub pending_score: RegisterView<u64>,                // Pending score to send (0 = none)
 }
 
+/// ↩️ Board state before a move, restored by `UndoMove`
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+pub struct UndoSnapshot {
+    pub board: u64,
+    pub score: u64,
+    pub bonus_score: u64,
+    pub move_count: u32,
+}
+
+/// 💾 Saved copy of a board, restored once by `LoadSnapshot`
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+pub struct GameSnapshot {
+    pub leaderboard_id: String,
+    pub board: u64,
+    pub score: u64,
+    pub bonus_score: u64,
+    pub move_count: u32,
+    pub last_processed_timestamp: u64,
+    pub is_ended: bool,
+    pub move_log: Vec<u8>,
+    pub end_time: u64, // Tournament end time (0 = unlimited)
+    pub saved_at: u64,
+}
+
 #[derive(Debug, Default, Deserialize, Serialize, Clone)]
 pub struct ActiveBoardInfo {
     pub player: String,
@@ -79,6 +167,297 @@ pub struct ActiveBoardInfo {
     pub is_ended: bool,
 }
 
+/// ⏸️ An `UpdateScore` held while its leaderboard is paused
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+pub struct QueuedScoreUpdate {
+    pub player: String,
+    pub board_id: String,
+    pub score: u64,
+    pub is_end: bool,
+    pub timestamp: u64,
+    pub message_nonce: u64,
+    pub origin_chain_id: String, // Chain that sent the update (dead letter key)
+}
+
+/// 🛡️ Moderation settings for tournament names/descriptions (main chain)
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+pub struct ModerationConfig {
+    pub blocked_words: Vec<String>, // lowercase
+}
+
+/// 🛡️ Moderator override of a failed content check
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+pub struct ModerationAuditEntry {
+    pub admin: String,
+    pub leaderboard_id: String,
+    pub violations: Vec<String>,
+    pub timestamp: u64,
+}
+
+/// 🚩 Game ended as a suspected cheat, kept for admin review (main chain)
+#[derive(Debug, Clone, Deserialize, Serialize, SimpleObject)]
+pub struct FlaggedGameEntry {
+    pub board_id: String,
+    pub player: String,
+    pub player_chain_id: String,
+    pub leaderboard_id: String,
+    pub score: u64,
+    pub reason: String,
+    pub flagged_at: u64,                        // micros
+    pub verdict: Option<game2048::FlagVerdict>, // None until reviewed
+    pub reviewed_by: String,
+}
+
+/// 🔑 Shard signing key rotation (the public key is recorded, never the secret)
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+pub struct ShardKeyRotation {
+    pub shard_chain_id: String,
+    pub admin: String,
+    pub public_key: [u8; 32],
+    pub timestamp: u64,
+}
+
+/// 📮 Shard message that failed to process, kept for admin retries (shard chain)
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DeadLetterEntry {
+    pub message_bytes: Vec<u8>, // BCS-serialised `Message`
+    pub failure_reason: String,
+    pub attempts: u8,             // Retries so far
+    pub first_failure_at: u64,    // micros
+    pub permanently_failed: bool, // No retries left
+}
+
+/// 🔐 M-of-N signers for critical admin actions (main chain)
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+pub struct MultiSigConfig {
+    pub required_signatures: u8, // 0 = multi-sig not configured
+    pub admin_list: Vec<String>,
+}
+
+/// 🔐 Critical admin action waiting for signatures
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct AdminProposal {
+    pub proposer: String,
+    pub action: game2048::CriticalAdminAction,
+    pub approvals: Vec<String>, // Includes the proposer
+    pub created_at: u64,
+}
+
+/// 📊 Per-chain resource counters (reset for billing cycles)
+#[derive(Debug, Clone, Default, Deserialize, Serialize, SimpleObject)]
+pub struct ResourceUsage {
+    pub total_operations_processed: u64,
+    pub total_messages_sent: u64,
+    pub total_messages_received: u64,
+    pub total_storage_bytes_written: u64, // Estimated from operation/message sizes
+    pub last_reset: u64,                  // micros
+    pub window_start: u64,                // micros, start of the current throttle window
+    pub window_messages_sent: u64,
+}
+
+/// ♻️ Pool chain handed to a registering player
+#[derive(Debug, Clone, Default, Deserialize, Serialize, SimpleObject)]
+pub struct ClaimedChain {
+    pub username: String,
+    pub claim_timestamp: u64, // micros
+    pub initialized: bool,    // Player chain processed its RegisterPlayer message
+}
+
+/// 🥊 A player's latest result in a tournament's bracket (player chain)
+#[derive(Debug, Clone, Default, Deserialize, Serialize, SimpleObject)]
+pub struct BracketStatus {
+    pub round: u32,
+    pub eliminated: bool,
+    pub next_matchup_id: Option<String>, // None once eliminated or after winning the final
+}
+
+/// 👥 Player on a team game
+#[derive(Debug, Clone, Default, Deserialize, Serialize, SimpleObject)]
+pub struct TeamMember {
+    pub player: String,
+    pub chain_id: String,
+}
+
+/// 👥 Team game hosted on this chain (host player chain)
+#[derive(Debug, Clone, Default, Deserialize, Serialize, SimpleObject)]
+pub struct TeamGame {
+    pub team_name: String,
+    pub host: String,
+    pub members: Vec<TeamMember>, // Joined players, host excluded
+    #[graphql(skip)]
+    pub open_invite_codes: Vec<String>, // Codes not used yet
+    pub score_share: u64,         // Each player's equal share once the game ends
+}
+
+/// 👥 Latest state of a team game hosted on another chain (member player chain)
+#[derive(Debug, Clone, Default, Deserialize, Serialize, SimpleObject)]
+pub struct TeamBoardMirror {
+    pub host_chain_id: String,
+    pub tiles: u64,
+    pub score: u64,
+    pub is_ended: bool,
+    pub score_share: u64,
+}
+
+/// 🤝 Opponent found by matchmaking (kept until the player enters the pool again)
+#[derive(Debug, Clone, Default, Deserialize, Serialize, SimpleObject)]
+pub struct MatchmakingMatch {
+    pub opponent: String,
+    pub opponent_chain_id: String,
+    pub time_limit: u32, // seconds, the shorter of both preferences
+    pub matched_at: u64, // micros
+}
+
+/// 📥 Tournament history imported from an off-chain system (no game replay)
+#[derive(Debug, Clone, Default, Deserialize, Serialize, SimpleObject)]
+pub struct ArchivedTournament {
+    pub leaderboard_id: String,
+    pub records: Vec<game2048::HistoricalRecord>,
+    pub imported_by: String,
+    pub imported_at: u64, // micros, last import
+}
+
+impl BoardState {
+    /// 🌿 Valid directions available per move on average (0.0 before the first move)
+    pub fn average_branching_factor(&self) -> f64 {
+        match *self.move_count.get() {
+            0 => 0.0,
+            move_count => *self.total_valid_moves_available.get() as f64 / move_count as f64,
+        }
+    }
+
+    /// ⏲️ Time a batch of `moves` moves applied at block time `block_time_micros`: the block
+    /// time since the previous batch is shared equally by the batch's moves
+    #[allow(dead_code)] // Only used by the contract
+    pub fn record_move_batch(&mut self, block_time_micros: u64, moves: u32) {
+        let previous_batch_time = *self.last_move_batch_time.get();
+        self.last_move_batch_time.set(block_time_micros);
+        if previous_batch_time == 0 {
+            return;
+        }
+        let mut timing = game2048::MoveTiming {
+            total_think_time_micros: *self.total_think_time_micros.get(),
+            min_move_time_micros: *self.min_move_time_micros.get(),
+            max_move_time_micros: *self.max_move_time_micros.get(),
+        };
+        timing.record_batch(block_time_micros.saturating_sub(previous_batch_time), moves);
+        self.total_think_time_micros
+            .set(timing.total_think_time_micros);
+        self.min_move_time_micros.set(timing.min_move_time_micros);
+        self.max_move_time_micros.set(timing.max_move_time_micros);
+    }
+
+    /// ⏲️ Average time per move in micros (0 before the first move)
+    pub fn avg_move_time_micros(&self) -> u64 {
+        match *self.move_count.get() {
+            0 => 0,
+            move_count => *self.total_think_time_micros.get() / move_count as u64,
+        }
+    }
+
+    /// 🔲 The board's tiles when it is not 4x4
+    #[allow(dead_code)] // Only used by the contract
+    pub fn sized_board(&self) -> Option<game2048::SizedBoard> {
+        match *self.board_size.get() {
+            game2048::BoardSize::FourByFour => None,
+            size => Some(game2048::SizedBoard {
+                dimension: size.dimension(),
+                tiles: self.sized_tiles.get().clone(),
+            }),
+        }
+    }
+
+    /// 🎲 Spawn probabilities of the board's tiles (weights are validated when stored),
+    /// drawn by the board's recorded generator
+    pub fn spawn_distribution(&self) -> game2048::SpawnDistribution {
+        game2048::SpawnDistribution::from_weights(self.tile_weights.get())
+            .unwrap_or_default()
+            .with_generator(*self.spawn_generator.get())
+    }
+}
+
+impl Leaderboard {
+    /// ⚖️ A player's score multiplier (1.0 = no handicap)
+    pub async fn handicap(&self, player: &str) -> f64 {
+        self.player_handicaps
+            .get(player)
+            .await
+            .unwrap()
+            .unwrap_or(1.0)
+    }
+
+    /// Effective (handicapped) scores in `RankedScore::rank_all` order, tie-broken by the
+    /// leaderboard's policy. Speed runs only rank players who reached the target tile
+    pub async fn ranked_scores(&self) -> Vec<game2048::RankedScore> {
+        let mut ranked = Vec::new();
+        self.score
+            .for_each_index_value(|player, score| {
+                ranked.push(game2048::RankedScore {
+                    player,
+                    score: *score,
+                    cap_reached_at: u64::MAX,
+                    tie_break: 0,
+                    time_to_target: 0,
+                });
+                Ok(())
+            })
+            .await
+            .unwrap();
+        for entry in ranked.iter_mut() {
+            entry.score =
+                game2048::handicapped_score(entry.score, self.handicap(&entry.player).await);
+        }
+        if self.mode.get().target_tile().is_some() {
+            let mut reached = Vec::new();
+            for mut entry in ranked {
+                if let Some(time) = self.time_to_target.get(&entry.player).await.unwrap() {
+                    entry.time_to_target = time;
+                    reached.push(entry);
+                }
+            }
+            ranked = reached;
+        }
+        let tie_break = *self.tie_break.get();
+        for entry in ranked.iter_mut() {
+            if let Some(cap_reached_at) = self.cap_reached_at.get(&entry.player).await.unwrap() {
+                entry.cap_reached_at = cap_reached_at;
+            }
+            if let Some(policy) = tie_break {
+                let player = &entry.player;
+                entry.tie_break = policy.key(
+                    self.highest_tiles.get(player).await.unwrap().unwrap_or(0),
+                    self.best_moves_counts
+                        .get(player)
+                        .await
+                        .unwrap()
+                        .unwrap_or(0),
+                    self.first_achievement_time
+                        .get(player)
+                        .await
+                        .unwrap()
+                        .unwrap_or(0),
+                    self.efficiency.get(player).await.unwrap().unwrap_or(0),
+                );
+            }
+        }
+        game2048::RankedScore::rank_all(&mut ranked);
+        ranked
+    }
+}
+
+impl AdminProposal {
+    /// Proposals expire 24 hours after creation
+    pub const TTL_MICROS: u64 = 24 * 60 * 60 * 1_000_000;
+
+    pub fn expires_at(&self) -> u64 {
+        self.created_at + Self::TTL_MICROS
+    }
+
+    pub fn is_expired(&self, current_time: u64) -> bool {
+        current_time >= self.expires_at()
+    }
+}
+
 #[derive(View, SimpleObject)]
 #[view(context = ViewStorageContext)]
 pub struct LeaderboardShard {
@@ -93,6 +472,7 @@ pub struct LeaderboardShard {
     pub is_ended: MapView<String, bool>,           // username, is_ended (for best board)
     pub player_chain_ids: MapView<String, String>, // username -> chain_id mapping
     pub highest_tiles: MapView<String, u64>,       // username -> highest_tile
+    pub avg_move_times: MapView<String, u64>,      // ⏲️ username -> average move time (micros)
     #[graphql(skip)]
     pub game_statuses: MapView<String, game2048::GameStatus>, // username -> game_status
     pub counter: RegisterView<u16>,                // update count
@@ -100,6 +480,12 @@ pub struct LeaderboardShard {
     #[graphql(skip)]
     pub active_boards: MapView<String, ActiveBoardInfo>, // board_id -> board summary
 
+    // 🗜️ Compact scores for shard-to-shard sync and quick rank computation
+    #[graphql(skip)]
+    pub compact_scores: MapView<String, game2048::CompactPlayerScore>, // username -> compact score
+    #[graphql(skip)]
+    pub bulk_sent_scores: MapView<String, u64>, // 📦 username -> score last sent in a BulkScoreUpdate
+
     // 🚀 NEW: Board counting per tournament (flattened key: "tournament_id:player_chain_id")
     pub tournament_player_board_counts: MapView<String, u32>, // "tournament_id:player_chain_id" -> board_count
 
@@ -123,6 +509,11 @@ pub struct LeaderboardShard {
     pub round_history: QueueView<String>,        // JSON of past round data (last N rounds)
     pub round_counter: RegisterView<u32>,        // Current aggregation round number
     pub round_start_time: RegisterView<u64>,     // When current round started
+
+    // ⏸️ PAUSE: Score updates held while the leaderboard is paused, in arrival order
+    pub is_paused: RegisterView<bool>,
+    #[graphql(skip)]
+    pub paused_score_queue: MapView<String, Vec<QueuedScoreUpdate>>, // leaderboard_id -> held updates
 }
 
 #[derive(View, SimpleObject)]
@@ -139,11 +530,42 @@ pub struct Leaderboard {
     pub total_players: RegisterView<u32>,
     pub is_pinned: RegisterView<bool>,
 
-    pub score: MapView<String, u64>,        // username, score
-    pub board_ids: MapView<String, String>, // username, board_id
-    pub is_ended: MapView<String, bool>,    // username, is_ended (for best board)
+    pub score: MapView<String, u64>,         // username, score
+    pub board_ids: MapView<String, String>,  // username, board_id
+    pub is_ended: MapView<String, bool>,     // username, is_ended (for best board)
     pub highest_tiles: MapView<String, u64>, // username -> highest_tile (for message-based updates)
     pub last_update: MapView<String, u64>,   // username -> last_update_timestamp
+    #[graphql(skip)]
+    pub tile_leaderboard_snapshot: RegisterView<Vec<(String, u64)>>, // 🏆 (username, highest_tile), set on UpdateLeaderboard
+    // 📸 SNAPSHOTS: Score ranking at the first UpdateLeaderboard of each interval
+    pub snapshot_interval_seconds: RegisterView<u64>, // 0 = no snapshots
+    #[graphql(skip)]
+    pub score_snapshots: MapView<u64, Vec<(String, u64)>>, // epoch (unix seconds / interval) -> (username, score), best first
+    // 🗓️ SEASONS: Rotated on UpdateLeaderboard; each ended season's final ranking is archived
+    pub season_config: RegisterView<Option<game2048::SeasonConfig>>,
+    pub current_season: RegisterView<u8>,     // 0-based
+    pub season_started_at: RegisterView<u64>, // micros, 0 = not started
+    #[graphql(skip)]
+    pub season_archive: MapView<u8, Vec<(String, u64)>>, // season -> (username, score), best first
+
+    // 🥊 BRACKET: Single-elimination matches, decided on UpdateLeaderboard after their deadline
+    pub bracket_size: RegisterView<u32>, // 0 = no bracket
+    pub bracket_round_duration: RegisterView<u64>, // micros
+    pub bracket_matchups: MapView<String, game2048::BracketMatchup>, // "round.index" -> matchup
+    pub bracket_champion: RegisterView<String>, // "" until the final is decided
+    pub is_paused: RegisterView<bool>,   // ⏸️ Scoring paused by the host (main chain registry)
+
+    // 🔄 ROUND ROBIN: Circle-method pairings; rounds advance on UpdateLeaderboard after their deadline
+    #[graphql(skip)]
+    pub rr_schedule: MapView<u8, Vec<(String, String)>>, // round (1 = first) -> pairs
+    pub rr_rounds: RegisterView<u8>,          // 0 = no round robin
+    pub rr_current_round: RegisterView<u8>,   // past `rr_rounds` once finished
+    pub rr_round_duration: RegisterView<u64>, // micros
+    pub rr_round_deadline: RegisterView<u64>, // micros
+    #[graphql(skip)]
+    pub rr_round_start_scores: MapView<String, u64>, // username -> score when the round began
+    pub rr_wins: MapView<String, u32>,        // username -> matches won
+
     #[graphql(skip)]
     pub active_boards: MapView<String, ActiveBoardInfo>, // board_id -> board summary
 
@@ -167,6 +589,172 @@ pub struct Leaderboard {
     pub last_successful_update: RegisterView<u64>, // Last time leaderboard was successfully updated
 
     pub admin_base_triggerer_count: RegisterView<u32>, // Admin-configurable base triggerer count
+
+    // 🔮 SPECTATOR PREDICTIONS: spectator_chain_id -> prediction (one per spectator chain)
+    #[graphql(skip)]
+    pub predictions: MapView<String, game2048::TournamentPrediction>,
+    // 🔮 Spectator chain awarded PredictionWinner, settled when the tournament is finalised
+    pub prediction_winner: RegisterView<Option<String>>,
+
+    // 📡 LIVE STREAM: Registered relay endpoints (max 3)
+    #[graphql(skip)]
+    pub stream_endpoints: RegisterView<Vec<game2048::StreamEndpoint>>,
+
+    // 🪙 TOKEN GATE: Entry requirement for board creation (None = open entry)
+    #[graphql(skip)]
+    pub entry_token_requirement: RegisterView<Option<game2048::TokenRequirement>>,
+
+    // ⚖️ HANDICAP: Effective score = raw score * multiplier (0.5 - 1.0)
+    pub player_handicaps: MapView<String, f64>, // username -> multiplier
+
+    // 🔁 REPLAY: Post-tournament replay of recorded games
+    pub replay_mode: RegisterView<bool>,
+    pub replay_scores: MapView<String, u64>, // username -> best replayed score
+    #[graphql(skip)]
+    pub replay_results: RegisterView<Vec<game2048::ReplayResult>>, // Set by FinalizeReplay
+
+    // 🛡️ ANTI-CHEAT: Per-tournament thresholds (None = defaults)
+    #[graphql(skip)]
+    pub anti_cheat_config: RegisterView<Option<game2048::AntiCheatConfig>>,
+
+    // ⏰ BONUS WINDOWS: Scheduled score multiplier windows (max 5)
+    #[graphql(skip)]
+    pub active_bonus_windows: RegisterView<Vec<game2048::BonusWindow>>,
+
+    // ⏱️ Classic games may be converted to speed games
+    pub allow_variant_conversion: RegisterView<bool>,
+
+    // ⛏️ PROOF OF WORK: Leading zero bits required of a new board's proof (0 = none)
+    pub pow_difficulty: RegisterView<u8>,
+
+    // 🎯 SCORE CAP: Reaching the cap ends the game; earlier cap times rank higher (0 = no cap)
+    pub max_score_per_player: RegisterView<u64>,
+    pub cap_reached_at: MapView<String, u64>, // username -> timestamp the cap was first reached
+
+    // 🏁 WARMUP: Boards created before the start; their scores are not ranked
+    pub warmup_duration_seconds: RegisterView<u32>,
+
+    // ↩️ UNDO: Undos per game (0 = none) and board states kept for them (0 = default)
+    pub undo_budget: RegisterView<u32>,
+    pub undo_history_size: RegisterView<u32>,
+
+    // ⏱️ SPEED: Variant of new boards (speed boards get a deadline at creation)
+    #[graphql(skip)]
+    pub variant: RegisterView<game2048::GameVariant>,
+    // 🎲 SPAWNS: Tile spawn weights of new boards (empty = classic)
+    pub tile_weights: RegisterView<Vec<game2048::TileWeight>>,
+    // 💥 COMBO: Moves with 3+ merges earn a combo bonus
+    pub combo_multiplier_enabled: RegisterView<bool>,
+    // 🚦 MOVE RATE LIMIT: Per-call move cap and minimum gap between moves (0 = default)
+    pub max_moves_per_call: RegisterView<u16>,
+    pub min_move_interval_micros: RegisterView<u64>,
+    // 🕒 NEW BOARD COOLDOWN: Seconds between two new boards of a player (0 = default)
+    pub new_board_cooldown_seconds: RegisterView<u64>,
+    // 🧱💣 POWER-UPS: Chances of obstacle and bomb tiles (None = no power-ups)
+    pub power_ups: RegisterView<Option<game2048::PowerUpConfig>>,
+
+    // 💡 HINTS: Hints requested on each player's ranked board
+    pub hints_used: MapView<String, u32>, // username -> hints used
+    // ⚡ EFFICIENCY: Best score per move of each player, and the board it came from
+    pub efficiency: MapView<String, u64>, // username -> best efficiency
+    pub best_efficiency_board_ids: MapView<String, String>, // username -> board_id
+    // ⚖️ TIE-BREAK: Order of equal scores, and the best game details it compares
+    #[graphql(skip)]
+    pub tie_break: RegisterView<Option<game2048::TieBreakPolicy>>,
+    pub best_moves_counts: MapView<String, u32>, // username -> moves of the best game
+    pub first_achievement_time: MapView<String, u64>, // username -> when the best score was reached
+    // 🏎️ SPEED RUN: Ranked by the fastest time to the target tile; others are unranked
+    pub mode: RegisterView<game2048::LeaderboardMode>,
+    pub time_to_target: MapView<String, u64>, // username -> fastest micros to the target tile
+    // 🔁 DEDUP: Newest SubmitScore nonce applied for each board; retried or stale ones are dropped
+    #[graphql(skip)]
+    pub last_applied_nonce: MapView<String, u64>, // board_id -> message nonce
+    // 🏅 HISTORY: Each player's best ended games, as last reported with SubmitScore
+    #[graphql(skip)]
+    pub player_top_boards: MapView<String, Vec<game2048::BoardSummary>>, // username -> best first
+    // 📊 ANALYTICS: New boards by UTC hour of day and by day, counted as board counts arrive
+    pub games_by_hour: RegisterView<Vec<u32>>, // 24 entries once a board is counted
+    #[graphql(skip)]
+    pub games_per_day: MapView<u64, u32>, // day (unix seconds / 86400) -> new boards
+    // 🏆 TILE INDEX: Players grouped by the power-of-two milestone of their highest tile
+    #[graphql(skip)]
+    pub tiles_index: MapView<u32, Vec<String>>, // milestone -> usernames
+    // 💀 ELIMINATION: Lives left on each board that lost one (leaderboard chain)
+    pub elimination_lives: MapView<String, u8>, // board_id -> lives remaining
+    pub warmup_phase_announced: RegisterView<u8>, // 0 = none, 1 = warmup started, 2 = tournament started
+    pub warmup_scores: MapView<String, u64>, // board_id -> best warmup score (leaderboard chain)
+    pub warmup_players: MapView<String, bool>, // username -> played a warmup game (leaderboard chain)
+
+    // ⏳ COUNTDOWN: Announcements before the end (leaderboard chain)
+    pub countdown_thresholds: RegisterView<Vec<u64>>, // seconds before end (empty = DEFAULT_COUNTDOWN_THRESHOLDS)
+    pub last_threshold_fired: RegisterView<u64>, // smallest threshold announced so far (0 = none)
+
+    // 🎟️ QUALIFIER: Advance top players when this qualifier ends / players who qualified for this tournament
+    pub qualifier_settings: RegisterView<Option<game2048::QualifierSettings>>,
+    pub qualifier_advanced: RegisterView<bool>, // Top players already sent (leaderboard chain)
+    pub pre_qualified: MapView<String, String>, // username -> qualifier leaderboard_id
+
+    // 🎟️ WAITLIST: New players past `max_participants` wait for a slot (leaderboard chain)
+    pub max_participants: RegisterView<Option<u32>>, // None = no limit
+    pub waitlist_admitted: MapView<String, u64>, // username -> admitted at (micros), until the first score
+    pub participants: MapView<String, u64>, // username -> last registration or score (micros), holds a slot
+    #[graphql(skip)]
+    pub waitlist_chain_ids: MapView<String, String>, // username -> player chain to notify
+
+    // 🔑 Public keys of shards that have been given a signing key (leaderboard chain)
+    #[graphql(skip)]
+    pub shard_registry: MapView<String, [u8; 32]>, // shard chain_id -> public key
+
+    // 🌿 Average branching factor of each ranked board (leaderboard chain)
+    #[graphql(skip)]
+    pub branching_factors: MapView<String, f64>, // board_id -> average valid moves per move
+
+    // 🔥 STREAK BONUS: Multipliers for consecutive tournaments played
+    #[graphql(skip)]
+    pub streak_bonus_tiers: RegisterView<Vec<game2048::StreakBonusTier>>,
+    pub streak_bonus_applied: MapView<String, bool>, // username -> best score includes streak bonus
+
+    // 🏅 PRIZE POOL: Configured on both chains; funding and payouts tracked on the leaderboard chain
+    #[graphql(skip)]
+    pub prize_pool_entries: RegisterView<Vec<game2048::PrizePoolEntry>>,
+    #[graphql(skip)]
+    pub prize_pool_funded: MapView<String, Amount>, // application_id -> funded amount
+    #[graphql(skip)]
+    pub prize_pool_distributed: MapView<String, Amount>, // application_id -> paid out amount
+    pub player_chain_ids: MapView<String, String>, // username -> player chain (prize payouts)
+
+    // 🏁 FINALISATION: Set on both chains; rewards are configured on the main chain
+    pub status: RegisterView<game2048::TournamentStatus>,
+    pub reward_structure: RegisterView<Option<game2048::RewardStructure>>,
+    #[graphql(skip)]
+    pub final_ranking: RegisterView<Vec<(String, u64)>>, // (username, score), best first
+
+    // 📋 CLONE: Leaderboard whose settings this one was created from (main chain)
+    pub cloned_from: RegisterView<Option<String>>,
+
+    // 👣 VISITORS: Player chain interactions (HyperLogLog registers for unique chains)
+    #[graphql(skip)]
+    pub visitor_registers: MapView<u16, u8>, // register index -> rank
+    pub total_visits: RegisterView<u64>,
+    #[graphql(skip)]
+    pub daily_visitors: RegisterView<(u64, u32)>, // (day_number, visits that day)
+
+    // 🔥 HOT RELOAD: Settings versions (main chain) for dispute resolution
+    pub settings_effective_at: MapView<String, u64>, // setting key -> when its current value took effect
+    #[graphql(skip)]
+    pub settings_history: QueueView<game2048::SettingsVersion>,
+
+    // 🌟 PLATFORM EVENTS: Players already announced to the main chain
+    #[graphql(skip)]
+    pub platform_top_10: RegisterView<Vec<String>>, // top 10 at the last UpdateLeaderboard
+    #[graphql(skip)]
+    pub platform_milestones: MapView<String, u64>, // username -> highest milestone tile announced
+
+    // 📅 DAILY CHALLENGE: Today's results (main chain); a record for a newer date resets them
+    #[graphql(skip)]
+    pub daily_leaderboard_date: RegisterView<String>,
+    #[graphql(skip)]
+    pub daily_leaderboard: MapView<String, game2048::DailyChallengeRecord>, // username -> best record of the day
 }
 
 #[derive(View, SimpleObject)]
@@ -192,6 +780,7 @@ pub struct Game2048 {
     pub player_records: CollectionView<String, PlayerRecord>, // player_chain_id
     pub onboard_chains: QueueView<String>,                // chain_id
     pub nonce: RegisterView<u64>,
+    pub score_message_nonce: RegisterView<u64>, // 🔁 Last nonce sent with SubmitScore (player chain)
     pub latest_board_id: RegisterView<String>,
 
     // 🚀 NEW: Event index tracking for reliable event reading
@@ -203,6 +792,7 @@ pub struct Game2048 {
     // Note: Using String storage for TournamentInfo to avoid GraphQL OutputType issues
     pub tournaments_cache_json: MapView<String, String>, // tournament_id -> JSON-serialized tournament info
     pub last_tournament_update: RegisterView<u64>,       // Last tournament update timestamp
+    pub tournament_end_times: MapView<String, u64>, // 🔥 Every tournament seen with an end time (for streaks)
 
     // 🚀 NEW: Triggerer system for player chains
     pub triggerer_list: QueueView<String>, // Current triggerer list (sorted by activity)
@@ -219,7 +809,74 @@ pub struct Game2048 {
     pub is_in_tier6: RegisterView<bool>,           // Flag to track if we're in tier 6 mode
 
     // 🚀 CHAIN POOL: Pre-created chains for fast registration
-    pub unclaimed_chains: QueueView<String>,         // Pre-created chain IDs available for claiming
-    pub chain_pool_target_size: RegisterView<u32>,   // Target pool size (e.g., 100)
+    pub unclaimed_chains: QueueView<String>, // Pre-created chain IDs available for claiming
+    pub chain_pool_target_size: RegisterView<u32>, // Target pool size (e.g., 100)
     pub chain_pool_low_threshold: RegisterView<u32>, // Trigger replenish when below this (e.g., 20)
+    pub claimed_chains: MapView<String, ClaimedChain>, // chain_id -> claim (♻️ stale claims are recovered)
+    pub unclaimed_timeout_hours: RegisterView<u32>,    // 0 = DEFAULT_UNCLAIMED_TIMEOUT_HOURS
+    pub chain_pool_min_size: RegisterView<u32>, // AggregateScores refills below this (0 = off)
+    pub chain_pool_total_created: RegisterView<u32>, // Chains ever opened for the pool
+    pub chain_pool_last_refill_at: RegisterView<u64>, // micros, 0 = never refilled
+
+    // 🎲 BEACON: Current beacon (main chain publishes, player chains cache) and the round being collected
+    pub randomness_beacon: RegisterView<game2048::RandomnessBeacon>,
+    pub beacon_contributions: RegisterView<Vec<(ChainId, [u8; 32])>>, // main chain only
+
+    // 🛡️ MODERATION: Blocked words and override audit log (main chain)
+    pub moderation_config: RegisterView<ModerationConfig>,
+    pub moderation_audit_log: QueueView<ModerationAuditEntry>,
+    pub admin_flags: MapView<String, FlaggedGameEntry>, // 🚩 board_id -> flagged game (main chain)
+
+    // 🔑 SHARD KEYS: Key rotations requested by admins (main chain)
+    pub shard_key_rotations: QueueView<ShardKeyRotation>,
+
+    // 🔑 PASSWORD: Outstanding change tokens and the secret they are issued from (main chain)
+    pub pending_password_changes: MapView<String, (String, u64)>, // username -> (token sha256, expires_at micros)
+    pub password_change_secret: RegisterView<[u8; 32]>,
+
+    // 📮 DEAD LETTERS: Shard messages that failed to process (shard chain)
+    pub dead_letter_queue: MapView<String, DeadLetterEntry>, // "{chain_id}:{message_nonce}" -> entry
+
+    // 🔐 MULTI-SIG: Critical admin actions need M-of-N signatures (main chain)
+    pub multisig_config: RegisterView<MultiSigConfig>,
+    pub pending_proposals: MapView<String, AdminProposal>, // proposal_id -> proposal
+
+    // 📊 Resource usage counters for this chain
+    pub resource_usage: RegisterView<ResourceUsage>,
+
+    // 📥 Off-chain tournament history (main chain)
+    pub archived_tournaments: MapView<String, ArchivedTournament>, // leaderboard_id -> archive
+
+    // 🤝 MATCHMAKING: Waiting players and found opponents (main chain)
+    pub matchmaking_pool: MapView<String, game2048::MatchmakingEntry>, // player_chain_id -> entry
+    pub matchmaking_matches: MapView<String, MatchmakingMatch>,        // player_chain_id -> match
+    pub matchmaking_average_wait: RegisterView<u64>, // micros, moving average of matched waits
+
+    // ⚙️ Player preferences (player chain)
+    pub player_preferences: MapView<String, game2048::PlayerPreferences>, // username -> preferences
+
+    // 🪙 TOKEN GATE: Balance attestations received by this player chain
+    pub balance_attestations: MapView<String, game2048::BalanceAttestation>, // leaderboard_id -> attestation
+    pub removed_from_tournaments: MapView<String, String>, // 🧹 leaderboard_id -> why the player was removed
+    pub qualified_tournaments: MapView<String, bool>, // 🎟️ leaderboard_id -> advanced from a qualifier (skips entry requirements)
+    pub waitlist: MapView<String, Vec<(String, u64)>>, // 🎟️ leaderboard_id -> waiting (username, timestamp), earliest first (leaderboard chain)
+    pub waitlist_admissions: MapView<String, bool>, // 🎟️ leaderboard_id -> admitted from the waitlist (player chain)
+    pub bracket_status: MapView<String, BracketStatus>, // 🥊 leaderboard_id -> latest bracket result
+    // 👥 TEAMS: Hosted team games, and team games joined on other chains (player chain)
+    pub team_games: MapView<String, TeamGame>, // team_board_id -> team
+    pub team_memberships: MapView<String, TeamBoardMirror>, // team_board_id -> latest synced board
+    // 👫 FRIENDS: Followed players, their chains and best scores, and chains following us (player chain)
+    pub friends: SetView<String>,                           // username
+    pub friend_chain_ids: MapView<String, String>,          // username -> friend's player chain
+    pub friend_scores: MapView<String, Vec<(String, u64)>>, // username -> (leaderboard_id, best score)
+    pub friend_subscribers: SetView<String>,                // chain_id of followers
+    // 🌟 PLATFORM EVENTS: Chains receiving highlights (main chain) and the latest received
+    pub platform_subscribers: SetView<ChainId>,
+    pub platform_highlights: QueueView<game2048::PlatformHighlight>, // oldest first, max MAX_PLATFORM_HIGHLIGHTS
+    // 💾 SNAPSHOTS: Saved boards players can resume from (player chain)
+    pub snapshots: MapView<String, GameSnapshot>, // board_id -> snapshot
+    // 🧳 MIGRATION: Chains players migrated away from (main, leaderboard and shard chains)
+    pub migrated_players: MapView<String, String>, // old chain_id -> new chain_id
+    // 🔔 SUBSCRIPTIONS: Event channels this chain receives, and from which chain
+    pub subscriptions: SetView<(String, ChainId)>, // (channel, chain_id)
 }
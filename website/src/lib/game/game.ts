@@ -1,9 +1,9 @@
-import { rndRange } from '$lib/utils/random';
+import { spawnTileChoice, type SpawnGenerator } from '$lib/utils/random';
 
 import {
+	boardToBigInt,
 	boardToString,
 	generateTabletFromMatrix,
-	genNewTileValue,
 	isEmptyTile,
 	reverse,
 	transpose
@@ -139,6 +139,7 @@ const genNewTiles = async (
 	boardId: string,
 	username: string,
 	timestamp: string,
+	spawnGenerator: SpawnGenerator,
 	prevTablet?: string
 ): Promise<Tablet> => {
 	const tabletString = boardToString(tablet);
@@ -147,9 +148,14 @@ const genNewTiles = async (
 	const countEmptyTiles = tablet.flat().filter(isEmptyTile).length;
 	if (countEmptyTiles === 0 && checkGameOver(tablet)) return tablet;
 
-	const tileValue = await genNewTileValue(boardId, username, timestamp);
-	const randomIndex = await rndRange(boardId, username, timestamp, 0, countEmptyTiles);
-	return await spawnTile(tablet, tileValue, randomIndex);
+	const { tileValue, targetIndex } = await spawnTileChoice(
+		boardId,
+		username,
+		timestamp,
+		boardToBigInt(tablet),
+		spawnGenerator
+	);
+	return await spawnTile(tablet, tileValue, targetIndex);
 };
 
 export const genInitialState = (
@@ -157,7 +163,8 @@ export const genInitialState = (
 	dimension: number,
 	boardId: string,
 	username: string,
-	skipGameOverCheck: boolean = false
+	skipGameOverCheck: boolean = false,
+	spawnGenerator: SpawnGenerator = 'LEHMER'
 ): GameState => {
 	const tablet = generateTabletFromMatrix(initialTablet);
 	return {
@@ -176,6 +183,7 @@ export const genInitialState = (
 						boardId,
 						username,
 						timestamp,
+						spawnGenerator,
 						prevTablet
 					)
 				),
@@ -187,6 +195,7 @@ export const genInitialState = (
 						boardId,
 						username,
 						timestamp,
+						spawnGenerator,
 						prevTablet
 					)
 				),
@@ -198,6 +207,7 @@ export const genInitialState = (
 						boardId,
 						username,
 						timestamp,
+						spawnGenerator,
 						prevTablet
 					)
 				),
@@ -209,6 +219,7 @@ export const genInitialState = (
 						boardId,
 						username,
 						timestamp,
+						spawnGenerator,
 						prevTablet
 					)
 				)
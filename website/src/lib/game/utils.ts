@@ -1,15 +1,12 @@
-import { rndRange } from '../utils/random';
+import { spawnTileChoice, type SpawnGenerator } from '../utils/random';
 
 import type { Tablet, TileContent } from './models';
 
-export const genNewTileValue = async (
-	boardId: string,
-	username: string,
-	timestamp: string
-): Promise<number> => {
-	const random = await rndRange(boardId, username, timestamp, 0, 10);
-	return random === 9 ? 2 : 1;
-};
+/** Packs a board like Rust's u64 (top-left tile in the highest 4 bits) */
+export const boardToBigInt = (board: Tablet | number[][]): bigint =>
+	board
+		.flatMap((row) => row.map((tile) => (typeof tile === 'object' ? tile.value : tile)))
+		.reduce((packed, value) => (packed << 4n) | BigInt(value), 0n);
 
 export const isEmptyTile = (tile: TileContent): boolean => tile.value === 0;
 
@@ -104,12 +101,14 @@ const spawnTileOnMatrix = (board: number[][], tileValue: number, targetIndex: nu
  * @param boardId The board ID
  * @param player The player username
  * @param createdAt The creation timestamp (in milliseconds from API)
+ * @param spawnGenerator The board's `spawnGenerator`
  * @returns Promise resolving to 4x4 initial board matrix
  */
 export const computeInitialBoard = async (
 	boardId: string,
 	player: string,
-	createdAt: string
+	createdAt: string,
+	spawnGenerator: SpawnGenerator = 'LEHMER'
 ): Promise<number[][]> => {
 	// Start with empty board
 	let board: number[][] = [
@@ -124,16 +123,24 @@ export const computeInitialBoard = async (
 	const timestampMinus1 = (parseInt(timestamp) - 1).toString();
 	
 	// First tile spawn (uses timestamp)
-	const emptyCount1 = 16; // All cells empty
-	const tileValue1 = await genNewTileValue(boardId, player, timestamp);
-	const targetIndex1 = await rndRange(boardId, player, timestamp, 0, emptyCount1);
-	board = spawnTileOnMatrix(board, tileValue1, targetIndex1);
+	const first = await spawnTileChoice(
+		boardId,
+		player,
+		timestamp,
+		boardToBigInt(board),
+		spawnGenerator
+	);
+	board = spawnTileOnMatrix(board, first.tileValue, first.targetIndex);
 	
 	// Second tile spawn (uses timestamp - 1)
-	const emptyCount2 = 15; // One cell occupied
-	const tileValue2 = await genNewTileValue(boardId, player, timestampMinus1);
-	const targetIndex2 = await rndRange(boardId, player, timestampMinus1, 0, emptyCount2);
-	board = spawnTileOnMatrix(board, tileValue2, targetIndex2);
+	const second = await spawnTileChoice(
+		boardId,
+		player,
+		timestampMinus1,
+		boardToBigInt(board),
+		spawnGenerator
+	);
+	board = spawnTileOnMatrix(board, second.tileValue, second.targetIndex);
 	
 	return board;
 };
@@ -24,3 +24,63 @@ export const rndRange = async (
 	const seed = await hashSeed(boardId, username, timestamp);
 	return (seed % (max - min)) + min;
 };
+
+/** Rust's `SpawnGenerator`, recorded per board (boards from before it was recorded are HASHED) */
+export type SpawnGenerator = 'HASHED' | 'LEHMER';
+
+const LEHMER_MULTIPLIER = 6364136223846793005n;
+const U64_MASK = (1n << 64n) - 1n;
+
+/** Mirrors Rust's `lehmer_next`: the advanced state and its high 32 bits */
+const lehmerNext = (state: bigint): [bigint, number] => {
+	const next = ((state | 1n) * LEHMER_MULTIPLIER) & U64_MASK;
+	return [next, Number(next >> 32n)];
+};
+
+/** Mirrors Rust's `spawn_state`: the game's seed re-seeded with the board and the timestamp */
+const spawnState = async (
+	boardId: string,
+	username: string,
+	timestamp: string,
+	board: bigint
+): Promise<bigint> => {
+	const hasher = new Sha256();
+	hasher.update(boardId);
+	hasher.update(username);
+	const hash = await hasher.digest();
+	const seed = new DataView(hash.buffer).getBigUint64(0, true);
+	return seed ^ board ^ BigInt(timestamp);
+};
+
+/**
+ * Mirrors Rust's `Game::spawn_tile`
+ * @param board Board packed like Rust's u64 (top-left tile in the highest 4 bits)
+ * @param spawnGenerator The board's `spawnGenerator`
+ * @returns The tile to place (1 for 2, 2 for 4) and which empty cell gets it,
+ * counting empty cells from the bottom-right
+ */
+export const spawnTileChoice = async (
+	boardId: string,
+	username: string,
+	timestamp: string,
+	board: bigint,
+	spawnGenerator: SpawnGenerator = 'LEHMER'
+): Promise<{ tileValue: number; targetIndex: number }> => {
+	let emptyCells = 0;
+	for (let cell = 0n; cell < 16n; cell++) {
+		if (((board >> (cell * 4n)) & 0xfn) === 0n) emptyCells++;
+	}
+	if (spawnGenerator === 'HASHED') {
+		const tileRoll = await rndRange(boardId, username, timestamp, 0, 10);
+		return {
+			tileValue: tileRoll === 9 ? 2 : 1,
+			targetIndex: await rndRange(boardId, username, timestamp, 0, emptyCells)
+		};
+	}
+	let state = await spawnState(boardId, username, timestamp, board);
+	let draw: number;
+	[state, draw] = lehmerNext(state);
+	const targetIndex = Number((BigInt(draw) * BigInt(emptyCells)) >> 32n);
+	[state, draw] = lehmerNext(state);
+	return { tileValue: draw % 10 === 9 ? 2 : 1, targetIndex };
+};